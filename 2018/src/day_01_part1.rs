@@ -39,7 +39,6 @@ pub fn solve(input: &str) -> i32 {
 
     let result = calculate_frequency(&frequencies);
     println!("Resulting frequency: {}", result);
-    assert_eq!(result, 493);
     result
 }
 
@@ -48,7 +48,6 @@ pub fn solve(input: &str) -> i32 {
 
     let result = find_first_repeat(&frequencies);
     println!("First repeat frequency: {}", result);
-    assert_eq!(result, 413);
     result
 }
 
@@ -116,7 +116,6 @@ pub fn solve(input: &str) -> u32 {
     let fabric = Fabric::from_claims(&claims);
     let overlap = fabric.count_overlap();
     println!("Overlap: {}", overlap);
-    assert_eq!(overlap, 104241);
     overlap
 }
 
@@ -207,7 +207,6 @@ pub fn solve(input: &str) -> u32 {
     let answer = schedule.strategy_1();
 
     println!("Strategy 1: {}", answer);
-    assert_eq!(answer, 102688);
     answer
 }
 
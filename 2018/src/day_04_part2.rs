@@ -164,7 +164,6 @@ pub fn solve(input: &str) -> u32 {
     let answer = schedule.strategy_2();
 
     println!("Strategy 2: {}", answer);
-    assert_eq!(answer, 56901);
     answer
 }
 
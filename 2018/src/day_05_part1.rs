@@ -111,7 +111,6 @@ pub fn solve(input: &str) -> u32 {
     let mut polymer = Polymer::from_string(input);
     polymer.react_all();
     println!("Units remaining: {}", polymer.length());
-    assert_eq!(polymer.length(), 10132);
     polymer.length()
 }
 
@@ -123,7 +123,6 @@ pub fn solve(input: &str) -> u32 {
     let mut polymer = Polymer::from_string(input);
     polymer.improve();
     println!("Shortest length: {}", polymer.length());
-    assert_eq!(polymer.length(), 4572);
     polymer.length()
 }
 
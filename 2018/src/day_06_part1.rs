@@ -163,7 +163,6 @@ pub fn solve(input: &str) -> u32 {
     landing_zone.scan();
     let largest = landing_zone.get_largest_finite();
     println!("Largest finite area: {}", largest);
-    assert_eq!(largest, 3882);
     largest
 }
 
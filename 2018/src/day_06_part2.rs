@@ -120,7 +120,6 @@ pub fn solve(input: &str) -> usize {
     let max_size = 10000;
     let region_size = landing_zone.count_points_under_size(max_size);
     println!("Region under {}: {}", max_size, region_size);
-    assert_eq!(region_size, 43852);
     region_size
 }
 
@@ -89,7 +89,6 @@ pub fn solve(input: &str) -> u32 {
     let tree = Node::from_string(input);
     let meta_sum = tree.sum_metadata();
     println!("Sum of metadata: {}", meta_sum);
-    assert_eq!(meta_sum, 44838);
     meta_sum
 }
 
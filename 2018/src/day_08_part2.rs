@@ -89,7 +89,6 @@ pub fn solve(input: &str) -> u32 {
     let tree = Node::from_string(input);
     let value = tree.value();
     println!("Root value: {}", value);
-    assert_eq!(value, 22198);
     value
 }
 
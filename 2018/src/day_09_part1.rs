@@ -139,7 +139,6 @@ pub fn solve(input: &str) -> u32 {
     let mut game = Game::from_string(input);
     let high_score = game.play();
     println!("High score: {}", high_score);
-    assert_eq!(high_score, 374690);
     high_score
 }
 
@@ -89,7 +89,6 @@ pub fn solve(input: &str) -> u32 {
     game.max_marble *= 100;
     let high_score = game.play();
     println!("High score: {}", high_score);
-    assert_eq!(high_score, 3009951158);
     high_score
 }
 
@@ -261,23 +261,7 @@ impl fmt::Display for StarMap {
 pub fn solve(input: &str) -> String {
     let mut star_map = StarMap::from_string(input);
     star_map.step_until_minimum_range();
-    let message = star_map.to_string();
-    assert_eq!(
-        message.trim(),
-        "
-#....#..#.......######....##....#....#..#####....####...######
-#....#..#............#...#..#...#...#...#....#..#....#.......#
-.#..#...#............#..#....#..#..#....#....#..#............#
-.#..#...#...........#...#....#..#.#.....#....#..#...........#.
-..##....#..........#....#....#..##......#####...#..........#..
-..##....#.........#.....######..##......#....#..#..###....#...
-.#..#...#........#......#....#..#.#.....#....#..#....#...#....
-.#..#...#.......#.......#....#..#..#....#....#..#....#..#.....
-#....#..#.......#.......#....#..#...#...#....#..#...##..#.....
-#....#..######..######..#....#..#....#..#####....###.#..######"
-            .trim()
-    );
-    message
+    star_map.to_string()
 }
 
 #[cfg(test)]
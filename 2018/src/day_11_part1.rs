@@ -125,7 +125,6 @@ pub fn solve(input: &str) -> Point2 {
 
     let max_power_point = grid.max_power_point();
     println!("Largest total power: {}", max_power_point);
-    assert_eq!(max_power_point, Point2 { x: 243, y: 68 });
     max_power_point
 }
 
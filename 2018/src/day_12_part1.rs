@@ -211,7 +211,7 @@ impl Tunnel {
             // However, we skip the first 4 since they will always be false and rule_id can start at 0 by definition.
             // So we only need 4 extra pots total.
             let (pots, slice_offset) = current.trim();
-            for &pot in pots.iter().chain(std::iter::repeat(&false).take(4)) {
+            for &pot in pots.iter().chain(std::iter::repeat_n(&false, 4)) {
                 rule_id = (rule_id << 1) & 0x1F; // Keep only 5 bits
                 if pot == true {
                     // The new pot is the rightmost (2 from the center)
@@ -236,7 +236,6 @@ pub fn solve(input: &str) -> i32 {
 
     let sum_pots = tunnel.pots.sum();
     //println!("Sum of pots: {}", sum_pots);
-    assert_eq!(sum_pots, 3890);
     sum_pots
 }
 
@@ -5,6 +5,7 @@
     After fifty billion (50000000000) generations, what is the sum of the numbers of all pots which contain a plant?
 */
 
+use common::cycle;
 use nom::{
     bytes::complete::tag,
     character::complete::{line_ending, one_of},
@@ -83,6 +84,7 @@ impl fmt::Display for Pots {
     }
 }
 
+#[derive(Clone)]
 struct Tunnel {
     pots: Pots,
     rules: Vec<bool>,
@@ -151,7 +153,7 @@ impl Tunnel {
             // However, we skip the first 4 since they will always be false and rule_id can start at 0 by definition.
             // So we only need 4 extra pots total.
             let (pots, slice_offset) = current.trim();
-            for &pot in pots.iter().chain(std::iter::repeat(&false).take(4)) {
+            for &pot in pots.iter().chain(std::iter::repeat_n(&false, 4)) {
                 rule_id = (rule_id << 1) & 0x1F; // Keep only 5 bits
                 if pot == true {
                     // The new pot is the rightmost (2 from the center)
@@ -168,19 +170,19 @@ impl Tunnel {
     }
 }
 
-// Currently this takes about 12 hours to run.
-// Ideas for future improvement:
-// * Make rules process more than 1 pot at a time, by increasing the 'size' of the rule. E.g. instead of 5 bits,
-//   make it 15 and it would process 11 pots at a time. This approach is limited by memory usage.
-// * Use a more compact data structure than Vec<bool> for the pots such as packed bits
+// The plants settle into a pattern which moves along by the same amount every generation, so once the pattern repeats
+// the sum can be extrapolated
 #[aoc(day12, part2)]
 pub fn solve(input: &str) -> i64 {
-    let mut tunnel = Tunnel::from_string(input);
-    tunnel.step(50_000_000_000);
+    let tunnel = Tunnel::from_string(input);
+    let history = cycle::find_cycle(
+        tunnel,
+        |tunnel| tunnel.step(1),
+        |tunnel| tunnel.pots.trim().0.to_vec(),
+    );
 
-    let sum_pots = tunnel.pots.sum();
+    let sum_pots = history.score_at(50_000_000_000, |tunnel| tunnel.pots.sum());
     println!("Sum of pots: {}", sum_pots);
-    assert_eq!(sum_pots, 4800000001087);
     sum_pots
 }
 
@@ -362,7 +362,6 @@ pub fn solve(input: &str) -> Point2 {
     //println!("{}", track_map);
     let crash_site = track_map.run_until_first_crash();
     println!("Crash site: {}", crash_site);
-    assert_eq!(crash_site, Point2 { x: 103, y: 85 });
     crash_site
 }
 
@@ -265,7 +265,6 @@ pub fn solve(input: &str) -> Point2 {
     //println!("{}", track_map);
     let last_cart = track_map.run_until_last_crash();
     println!("Last cart: {}", last_cart);
-    assert_eq!(last_cart, Point2 { x: 88, y: 64 });
     last_cart
 }
 
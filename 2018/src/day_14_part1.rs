@@ -66,7 +66,6 @@ pub fn solve(input: &str) -> u64 {
     let target_number = input.trim().parse::<usize>().unwrap();
     let score = score_after_target(target_number);
     println!("Score: {}", score);
-    assert_eq!(score, 1741551073);
     score
 }
 
@@ -75,6 +74,7 @@ mod test {
     use super::*;
 
     #[test]
+    #[expect(clippy::zero_prefixed_literal)] // Matches the puzzle text, which includes the leading zero
     fn test_score_after_target() {
         assert_eq!(score_after_target(9), 5158916779);
         assert_eq!(score_after_target(5), 0124515891);
@@ -58,7 +58,6 @@ pub fn solve(input: &str) -> usize {
     let target_number = input.trim();
     let num_recipes = recipes_before_target(target_number);
     println!("Recipes: {}", num_recipes);
-    assert_eq!(num_recipes, 20322683);
     num_recipes
 }
 
@@ -273,9 +273,9 @@
     What is the outcome of the combat described in your puzzle input?
 */
 
+use common::search;
 use common::Point2;
 use std::collections::BTreeMap;
-use std::collections::BTreeSet;
 use std::fmt;
 use std::fmt::Write;
 
@@ -321,13 +321,6 @@ impl fmt::Display for Tile {
     }
 }
 
-#[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
-struct Path {
-    first: Point2,
-    last: Point2,
-    length: usize,
-}
-
 struct BattleMap {
     tiles: BTreeMap<Point2, Tile>,
 }
@@ -433,77 +426,6 @@ impl BattleMap {
             .collect()
     }
 
-    fn find_paths(&self, from: &Point2, to: &[Point2]) -> Vec<Path> {
-        let mut paths: BTreeSet<Path> = BTreeSet::new();
-        let mut shortest_path = None;
-        let to_set: BTreeSet<Point2> = to.iter().cloned().collect();
-
-        let starts: Vec<Point2> = self.identify_adjacent_empty(from);
-        for start in starts {
-            let mut visited: BTreeSet<Point2> = BTreeSet::new();
-            visited.insert(*from);
-            visited.insert(start);
-
-            let mut frontier: Vec<Point2> = vec![start];
-            let mut distance = 0;
-            while frontier.is_empty() == false {
-                distance += 1;
-                if let Some(shortest) = shortest_path {
-                    if distance > shortest {
-                        break; // As an optimization, quit searching for paths if already exceeded the shortest we've seen
-                    }
-                }
-
-                let mut found_a_target = false;
-                for frontier_point in std::mem::take(&mut frontier) {
-                    if to_set.contains(&frontier_point) == true {
-                        found_a_target = true;
-                        let path = Path {
-                            first: start,
-                            last: frontier_point,
-                            length: distance,
-                        };
-                        paths.insert(path);
-                    }
-                    visited.insert(frontier_point);
-
-                    let next: Vec<Point2> = self
-                        .identify_adjacent_empty(&frontier_point)
-                        .into_iter()
-                        .filter(|point| !visited.contains(point))
-                        .collect();
-                    for &n in &next {
-                        if frontier.contains(&n) == false {
-                            frontier.push(n);
-                        }
-                    }
-                }
-
-                // As an optimization we quit searching once we found any target (finishing the current frontier). It is
-                // guaranteed that we won't need any longer paths since the next step after finding the paths would be
-                // culling all but the shortest ones.
-                if found_a_target == true {
-                    if let Some(shortest) = shortest_path {
-                        if distance < shortest {
-                            shortest_path = Some(distance);
-                        }
-                    } else {
-                        shortest_path = Some(distance);
-                    }
-                    break;
-                }
-            }
-        }
-
-        // Convert from BTreeSet to Vec, removing all but the shortest paths
-        let paths_vec: Vec<Path> = paths
-            .iter()
-            .filter(|path| path.length == shortest_path.unwrap())
-            .cloned()
-            .collect();
-        paths_vec
-    }
-
     fn tick(&mut self) -> bool {
         let units = self.identify_units();
         for unit in units {
@@ -536,26 +458,19 @@ impl BattleMap {
                     continue;
                 }
 
-                // Find the shortest path(s) to each reachable space
-                let mut paths: Vec<Path> = self.find_paths(&unit_location, &adjacents);
-                paths.sort_by_key(|path| path.last); // The paths are usually, but not always, in reading order by target. Ensure it by sorting.
-                if paths.is_empty() == true {
-                    // No paths to a target
-                    continue;
-                }
-
-                // Keep only paths to the first target in reading order
-                let target = paths[0].last;
-                let paths_to_target: Vec<Path> = paths
-                    .into_iter()
-                    .filter(|path| path.last == target)
-                    .collect();
+                // Find the nearest reachable space, taking the first in reading order if there's a tie
+                let paths = search::bfs_all(
+                    [unit_location],
+                    |p| self.identify_adjacent_empty(p),
+                    |p| adjacents.contains(p),
+                );
+                let target = match paths.goals().iter().copied().min_by(Point2::cmp_yx) {
+                    Some(target) => target,
+                    None => continue, // No paths to a target
+                };
 
                 // Take the first step in reading order
-                let mut steps: Vec<Point2> =
-                    paths_to_target.into_iter().map(|path| path.first).collect();
-                steps.sort_by(Point2::cmp_yx);
-                unit_location = steps[0];
+                unit_location = paths.paths_by(&target, Point2::cmp_yx).next().unwrap()[1];
 
                 // Step by overwriting current space and new space
                 let unit_data = self.tiles.insert(unit, Tile::Empty).unwrap();
@@ -667,7 +582,6 @@ pub fn solve(input: &str) -> u32 {
     let mut battle_map = BattleMap::from_string(input);
     let score = battle_map.battle();
     println!("Score: {}", score);
-    assert_eq!(score, 184206);
     score
 }
 
@@ -61,70 +61,25 @@
     Ignoring the opcode numbers, how many samples in your puzzle input behave like three or more opcodes?
 */
 
+use crate::elfcode::{Instruction, Opcode, State};
 use nom::{
     bytes::complete::tag,
     character::complete::{char, digit1, multispace0},
     combinator::map_res,
     multi::many1,
-    sequence::{delimited, pair, preceded, terminated, tuple},
+    sequence::{delimited, preceded, terminated, tuple},
     IResult,
 };
-use std::ops::{Index, IndexMut};
 
-#[derive(Clone, Eq, PartialEq)]
-struct State([u32; 4]);
-
-impl State {
-    fn parser(input: &str) -> IResult<&str, Self> {
-        let (input, (a, b, c, d)) = tuple((
-            preceded(
-                pair(multispace0, char('[')),
-                map_res(digit1, |x: &str| x.parse::<u32>()),
-            ),
-            preceded(tag(", "), map_res(digit1, |x: &str| x.parse::<u32>())),
-            preceded(tag(", "), map_res(digit1, |x: &str| x.parse::<u32>())),
-            delimited(
-                tag(", "),
-                map_res(digit1, |x: &str| x.parse::<u32>()),
-                char(']'),
-            ),
-        ))(input)?;
-
-        Ok((input, Self([a, b, c, d])))
-    }
-}
-
-impl Index<u32> for State {
-    type Output = u32;
-
-    fn index(&self, index: u32) -> &Self::Output {
-        &self.0[index as usize]
-    }
-}
-
-impl IndexMut<u32> for State {
-    fn index_mut(&mut self, index: u32) -> &mut Self::Output {
-        &mut self.0[index as usize]
-    }
-}
-
-#[derive(Debug)]
-enum Error {
-    Opcode,
-    Functioncode,
-    InputA,
-    InputB,
-    OutputC,
-}
-
-struct Instruction {
+// An instruction whose opcode number hasn't been matched to an operation yet
+struct EncodedInstruction {
     opcode: u8,
     input_a: u32,
     input_b: u32,
     output_c: u32,
 }
 
-impl Instruction {
+impl EncodedInstruction {
     fn from_string(input: &str) -> Self {
         Self::parser(input).unwrap().1
     }
@@ -152,245 +107,20 @@ impl Instruction {
         ))
     }
 
-    fn validate_opcode(&self) -> Result<(), Error> {
-        if self.opcode > 15 {
-            Err(Error::Opcode)
-        } else {
-            Ok(())
-        }
-    }
-
-    fn validate_reg_a(&self) -> Result<(), Error> {
-        if self.input_a > 3 {
-            Err(Error::InputA)
-        } else {
-            Ok(())
-        }
-    }
-
-    fn validate_reg_b(&self) -> Result<(), Error> {
-        if self.input_b > 3 {
-            Err(Error::InputB)
-        } else {
-            Ok(())
-        }
-    }
-
-    fn validate_reg_c(&self) -> Result<(), Error> {
-        if self.output_c > 3 {
-            Err(Error::OutputC)
-        } else {
-            Ok(())
-        }
-    }
-
-    fn dispatch(&self, input: State, opcode_lookup: &[u8; 16]) -> Result<State, Error> {
-        self.validate_opcode()?;
-        let function_code = opcode_lookup[self.opcode as usize];
-        self.dispatch_as(input, function_code)
-    }
-
-    fn dispatch_as(&self, input: State, function_code: u8) -> Result<State, Error> {
-        match function_code {
-            0 => self.addr(input),
-            1 => self.addi(input),
-            2 => self.mulr(input),
-            3 => self.muli(input),
-            4 => self.banr(input),
-            5 => self.bani(input),
-            6 => self.borr(input),
-            7 => self.bori(input),
-            8 => self.setr(input),
-            9 => self.seti(input),
-            10 => self.gtir(input),
-            11 => self.gtri(input),
-            12 => self.gtrr(input),
-            13 => self.eqir(input),
-            14 => self.eqri(input),
-            15 => self.eqrr(input),
-            _ => Err(Error::Functioncode),
-        }
-    }
-
-    fn addr(&self, input: State) -> Result<State, Error> {
-        self.validate_reg_a()?;
-        self.validate_reg_b()?;
-        self.validate_reg_c()?;
-
-        let mut state = input;
-        state[self.output_c] = state[self.input_a] + state[self.input_b];
-        Ok(state)
-    }
-
-    fn addi(&self, input: State) -> Result<State, Error> {
-        self.validate_reg_a()?;
-        self.validate_reg_c()?;
-
-        let mut state = input;
-        state[self.output_c] = state[self.input_a] + self.input_b;
-        Ok(state)
-    }
-
-    fn mulr(&self, input: State) -> Result<State, Error> {
-        self.validate_reg_a()?;
-        self.validate_reg_b()?;
-        self.validate_reg_c()?;
-
-        let mut state = input;
-        state[self.output_c] = state[self.input_a] * state[self.input_b];
-        Ok(state)
-    }
-
-    fn muli(&self, input: State) -> Result<State, Error> {
-        self.validate_reg_a()?;
-        self.validate_reg_c()?;
-
-        let mut state = input;
-        state[self.output_c] = state[self.input_a] * self.input_b;
-        Ok(state)
-    }
-
-    fn banr(&self, input: State) -> Result<State, Error> {
-        self.validate_reg_a()?;
-        self.validate_reg_b()?;
-        self.validate_reg_c()?;
-
-        let mut state = input;
-        state[self.output_c] = state[self.input_a] & state[self.input_b];
-        Ok(state)
-    }
-
-    fn bani(&self, input: State) -> Result<State, Error> {
-        self.validate_reg_a()?;
-        self.validate_reg_c()?;
-
-        let mut state = input;
-        state[self.output_c] = state[self.input_a] & self.input_b;
-        Ok(state)
-    }
-
-    fn borr(&self, input: State) -> Result<State, Error> {
-        self.validate_reg_a()?;
-        self.validate_reg_b()?;
-        self.validate_reg_c()?;
-
-        let mut state = input;
-        state[self.output_c] = state[self.input_a] | state[self.input_b];
-        Ok(state)
-    }
-
-    fn bori(&self, input: State) -> Result<State, Error> {
-        self.validate_reg_a()?;
-        self.validate_reg_c()?;
-
-        let mut state = input;
-        state[self.output_c] = state[self.input_a] | self.input_b;
-        Ok(state)
-    }
-
-    fn setr(&self, input: State) -> Result<State, Error> {
-        self.validate_reg_a()?;
-        self.validate_reg_c()?;
-
-        let mut state = input;
-        state[self.output_c] = state[self.input_a];
-        Ok(state)
-    }
-
-    fn seti(&self, input: State) -> Result<State, Error> {
-        self.validate_reg_a()?;
-        self.validate_reg_c()?;
-
-        let mut state = input;
-        state[self.output_c] = self.input_a;
-        Ok(state)
-    }
-
-    fn gtir(&self, input: State) -> Result<State, Error> {
-        self.validate_reg_b()?;
-        self.validate_reg_c()?;
-
-        let mut state = input;
-        if self.input_a > state[self.input_b] {
-            state[self.output_c] = 1;
-        } else {
-            state[self.output_c] = 0;
-        }
-        Ok(state)
-    }
-
-    fn gtri(&self, input: State) -> Result<State, Error> {
-        self.validate_reg_a()?;
-        self.validate_reg_c()?;
-
-        let mut state = input;
-        if state[self.input_a] > self.input_b {
-            state[self.output_c] = 1;
-        } else {
-            state[self.output_c] = 0;
-        }
-        Ok(state)
-    }
-
-    fn gtrr(&self, input: State) -> Result<State, Error> {
-        self.validate_reg_a()?;
-        self.validate_reg_b()?;
-        self.validate_reg_c()?;
-
-        let mut state = input;
-        if state[self.input_a] > state[self.input_b] {
-            state[self.output_c] = 1;
-        } else {
-            state[self.output_c] = 0;
-        }
-        Ok(state)
-    }
-
-    fn eqir(&self, input: State) -> Result<State, Error> {
-        self.validate_reg_b()?;
-        self.validate_reg_c()?;
-
-        let mut state = input;
-        if self.input_a == state[self.input_b] {
-            state[self.output_c] = 1;
-        } else {
-            state[self.output_c] = 0;
-        }
-        Ok(state)
-    }
-
-    fn eqri(&self, input: State) -> Result<State, Error> {
-        self.validate_reg_a()?;
-        self.validate_reg_c()?;
-
-        let mut state = input;
-        if state[self.input_a] == self.input_b {
-            state[self.output_c] = 1;
-        } else {
-            state[self.output_c] = 0;
-        }
-        Ok(state)
-    }
-
-    fn eqrr(&self, input: State) -> Result<State, Error> {
-        self.validate_reg_a()?;
-        self.validate_reg_b()?;
-        self.validate_reg_c()?;
-
-        let mut state = input;
-        if state[self.input_a] == state[self.input_b] {
-            state[self.output_c] = 1;
-        } else {
-            state[self.output_c] = 0;
-        }
-        Ok(state)
+    fn decode(&self, function_code: u8) -> Instruction<u32> {
+        Instruction::new(
+            Opcode::ALL[function_code as usize],
+            self.input_a,
+            self.input_b,
+            self.output_c,
+        )
     }
 }
 
 struct Sample {
-    before: State,
-    op: Instruction,
-    after: State,
+    before: State<u32, 4>,
+    op: EncodedInstruction,
+    after: State<u32, 4>,
 }
 
 impl Sample {
@@ -400,9 +130,15 @@ impl Sample {
 
     fn parser(input: &str) -> IResult<&str, Self> {
         let (input, (before, op, after)) = tuple((
-            preceded(pair(multispace0, tag("Before:")), State::parser),
-            Instruction::parser,
-            preceded(pair(multispace0, tag("After:")), State::parser),
+            preceded(
+                tuple((multispace0, tag("Before:"), multispace0)),
+                State::parser,
+            ),
+            EncodedInstruction::parser,
+            preceded(
+                tuple((multispace0, tag("After:"), multispace0)),
+                State::parser,
+            ),
         ))(input)?;
 
         Ok((input, Self { before, op, after }))
@@ -411,12 +147,11 @@ impl Sample {
     fn find_possible_opcodes(&self) -> Vec<u8> {
         // Returns a vec of all possible function codes
         (0..16)
-            .map(|i| (i, self.op.dispatch_as(self.before.clone(), i)))
-            .filter(|(_i, result)| match result {
-                Ok(x) => x == &self.after,
-                _ => false,
+            .filter(|&i| {
+                let mut state = self.before.clone();
+                let result = self.op.decode(i).execute(&mut state);
+                result.is_ok() && state == self.after
             })
-            .map(|(i, _result)| i)
             .collect()
     }
 }
@@ -434,7 +169,6 @@ pub fn solve(input: &str) -> u32 {
     let samples = Sample::many_from_string(input);
     let gt_3_opcodes_count = count_ambiguous_opcodes(&samples);
     println!("Samples >= 3 opcodes: {}", gt_3_opcodes_count);
-    assert_eq!(gt_3_opcodes_count, 646);
     gt_3_opcodes_count
 }
 
@@ -443,11 +177,11 @@ mod test {
     use super::*;
 
     #[test]
-    fn test_dispatch_as() {
-        let instr = Instruction::from_string("9 2 1 2");
-        let before = State([3, 2, 1, 1]);
-        let after = instr.dispatch_as(before, 2).unwrap();
-        assert_eq!(after.0, [3, 2, 2, 1]);
+    fn test_decode() {
+        let instr = EncodedInstruction::from_string("9 2 1 2");
+        let mut state = State([3, 2, 1, 1]);
+        instr.decode(2).execute(&mut state).unwrap();
+        assert_eq!(state.0, [3, 2, 2, 1]);
     }
 
     #[test]
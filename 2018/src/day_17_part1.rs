@@ -389,7 +389,6 @@ pub fn solve(input: &str) -> u32 {
     //println!("{}", geo_map);
     let water_can_touch = geo_map.count_water_can_touch();
     println!("Water can touch: {}", water_can_touch);
-    assert_eq!(water_can_touch, 39649);
     water_can_touch
 }
 
@@ -390,7 +390,6 @@ pub fn solve(input: &str) -> u32 {
     //println!("{}", geo_map);
     let water_tiles = geo_map.count_water_tiles();
     println!("Water tiles: {}", water_tiles);
-    assert_eq!(water_tiles, 28872);
     water_tiles
 }
 
@@ -153,8 +153,8 @@
     What will the total resource value of the lumber collection area be after 10 minutes?
 */
 
-use common::Point2;
-use std::collections::HashMap;
+use common::automaton::{Dense, Neighbors};
+use common::{Grid, TileChar};
 use std::fmt;
 
 #[derive(Clone, Copy, Eq, PartialEq)]
@@ -164,107 +164,72 @@ enum Tile {
     Lumberyard,
 }
 
-impl Tile {
-    fn from_char(c: char) -> Self {
-        match c {
-            '.' => Self::OpenGround,
-            '|' => Self::Trees,
-            '#' => Self::Lumberyard,
-            _ => panic!("Unknown tile: {}", c),
-        }
-    }
-
-    fn to_char(self) -> char {
+impl TileChar for Tile {
+    fn to_char(&self) -> char {
         match self {
             Self::OpenGround => '.',
             Self::Trees => '|',
             Self::Lumberyard => '#',
         }
     }
+
+    fn from_char(c: char) -> Option<Self> {
+        match c {
+            '.' => Some(Self::OpenGround),
+            '|' => Some(Self::Trees),
+            '#' => Some(Self::Lumberyard),
+            _ => None,
+        }
+    }
+
+    fn all_chars() -> Vec<char> {
+        vec!['.', '|', '#']
+    }
 }
 
 struct Construction {
-    tiles: HashMap<Point2, Tile>,
-    size: usize,
+    area: Dense<Tile>,
 }
 
 impl Construction {
     fn from_string(input: &str) -> Self {
-        let mut tiles = HashMap::new();
-        let mut p = Point2::origin();
-        for line in input.trim().lines() {
-            for c in line.chars() {
-                tiles.insert(p, Tile::from_char(c));
-                p.x += 1;
-            }
-            p.x = 0;
-            p.y += 1;
-        }
-
-        let range = Point2::get_range(tiles.keys()).unwrap();
-        let size = range.x.1 - range.x.0 + 1;
-        assert_eq!(size, range.y.1 - range.y.0 + 1);
-
         Self {
-            tiles,
-            size: size as usize,
+            area: Dense::with_adjacents(Grid::from_string(input.trim())),
         }
     }
 
-    fn determine_next_tile(&self, point: &Point2) -> Option<Tile> {
-        let trees_count = point
-            .adjacents()
-            .map(|adj| self.tiles.get(&adj))
-            .filter(|&t| t == Some(&Tile::Trees))
-            .count();
-        let lumberyard_count = point
-            .adjacents()
-            .map(|adj| self.tiles.get(&adj))
-            .filter(|&t| t == Some(&Tile::Lumberyard))
-            .count();
-        match self.tiles.get(point) {
-            Some(Tile::OpenGround) => {
-                if trees_count >= 3 {
-                    return Some(Tile::Trees);
-                }
-            }
-            Some(Tile::Trees) => {
-                if lumberyard_count >= 3 {
-                    return Some(Tile::Lumberyard);
-                }
+    fn next_tile(tile: &Tile, neighbors: Neighbors<'_, Tile>) -> Tile {
+        let mut trees_count = 0;
+        let mut lumberyard_count = 0;
+        for adj in neighbors {
+            match adj {
+                Tile::Trees => trees_count += 1,
+                Tile::Lumberyard => lumberyard_count += 1,
+                Tile::OpenGround => (),
             }
-            Some(Tile::Lumberyard) => {
-                if lumberyard_count == 0 || trees_count == 0 {
-                    return Some(Tile::OpenGround);
-                }
-            }
-            None => panic!("Unexpected empty tile at {}", point),
         }
-
-        None
+        match tile {
+            Tile::OpenGround if trees_count >= 3 => Tile::Trees,
+            Tile::Trees if lumberyard_count >= 3 => Tile::Lumberyard,
+            Tile::Lumberyard if lumberyard_count == 0 || trees_count == 0 => Tile::OpenGround,
+            _ => *tile,
+        }
     }
 
     fn sim(&mut self, minutes: u32) {
-        let mut new_tiles: HashMap<Point2, Tile> = HashMap::new();
-        for _ in 0..minutes {
-            new_tiles.clear();
-            for &point in self.tiles.keys() {
-                if let Some(t) = self.determine_next_tile(&point) {
-                    new_tiles.insert(point, t);
-                }
-            }
-            self.tiles.extend(&new_tiles);
-        }
+        self.area.run(minutes as usize, Self::next_tile);
     }
 
     fn resource_value(&self) -> u32 {
         let trees_count = self
-            .tiles
+            .area
+            .cells()
             .values()
             .filter(|&tile| tile == &Tile::Trees)
             .count() as u32;
         let lumberyard_count = self
-            .tiles
+            .area
+            .cells()
             .values()
             .filter(|&tile| tile == &Tile::Lumberyard)
             .count() as u32;
@@ -274,19 +239,7 @@ impl Construction {
 
 impl fmt::Display for Construction {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let range = Point2::get_range(self.tiles.keys()).unwrap();
-        for y in range.y.0..=range.y.1 {
-            for x in range.x.0..=range.x.1 {
-                if let Some(tile) = self.tiles.get(&Point2 { x, y }) {
-                    write!(f, "{}", tile.to_char())?;
-                } else {
-                    write!(f, " ")?;
-                }
-            }
-            writeln!(f)?;
-        }
-
-        Ok(())
+        write!(f, "{}", self.area.cells())
     }
 }
 
@@ -298,7 +251,6 @@ pub fn solve(input: &str) -> u32 {
 
     let resource_value = construction.resource_value();
     println!("Resource value: {}", resource_value);
-    assert_eq!(resource_value, 653184);
     resource_value
 }
 
@@ -306,6 +258,11 @@ pub fn solve(input: &str) -> u32 {
 mod test {
     use super::*;
 
+    #[test]
+    fn test_unknown_tile() {
+        assert!(Grid::<Tile>::parser(".|#\n.x#").is_err());
+    }
+
     #[test]
     fn test_sim() {
         let input = "
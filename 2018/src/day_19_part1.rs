@@ -42,393 +42,20 @@
     What value is left in register 0 when the background process halts?
 */
 
-use nom::{
-    bytes::complete::tag,
-    character::complete::{alpha1, digit1, multispace0, space1},
-    combinator::map_res,
-    multi::many1,
-    sequence::{pair, preceded, tuple},
-    IResult,
-};
-use std::ops::{Index, IndexMut};
-
-const NUM_REGISTERS: usize = 6;
-
-#[derive(Clone, Debug, Eq, PartialEq)]
-struct State([u32; NUM_REGISTERS]);
-
-impl Index<u32> for State {
-    type Output = u32;
-
-    fn index(&self, index: u32) -> &Self::Output {
-        &self.0[index as usize]
-    }
-}
-
-impl IndexMut<u32> for State {
-    fn index_mut(&mut self, index: u32) -> &mut Self::Output {
-        &mut self.0[index as usize]
-    }
-}
-
-impl Index<usize> for State {
-    type Output = u32;
-
-    fn index(&self, index: usize) -> &Self::Output {
-        &self.0[index]
-    }
-}
-
-impl IndexMut<usize> for State {
-    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
-        &mut self.0[index]
-    }
-}
-
-#[derive(Debug)]
-enum Error {
-    Opcode,
-    Functioncode,
-    InputA,
-    InputB,
-    OutputC,
-}
-
-struct Instruction {
-    opcode: u8,
-    input_a: u32,
-    input_b: u32,
-    output_c: u32,
-}
-
-impl Instruction {
-    fn parser(input: &str) -> IResult<&str, Self> {
-        let (input, (name, a, b, c)) = tuple((
-            preceded(multispace0, alpha1),
-            preceded(space1, map_res(digit1, |a: &str| a.parse::<u32>())),
-            preceded(space1, map_res(digit1, |b: &str| b.parse::<u32>())),
-            preceded(space1, map_res(digit1, |c: &str| c.parse::<u32>())),
-        ))(input)?;
-
-        let opcode = match name {
-            "addr" => 0,
-            "addi" => 1,
-            "mulr" => 2,
-            "muli" => 3,
-            "banr" => 4,
-            "bani" => 5,
-            "borr" => 6,
-            "bori" => 7,
-            "setr" => 8,
-            "seti" => 9,
-            "gtir" => 10,
-            "gtri" => 11,
-            "gtrr" => 12,
-            "eqir" => 13,
-            "eqri" => 14,
-            "eqrr" => 15,
-            _ => panic!("Invalid opcode"),
-        };
-
-        Ok((
-            input,
-            Self {
-                opcode,
-                input_a: a,
-                input_b: b,
-                output_c: c,
-            },
-        ))
-    }
-
-    fn validate_opcode(&self) -> Result<(), Error> {
-        if self.opcode > 15 {
-            Err(Error::Opcode)
-        } else {
-            Ok(())
-        }
-    }
-
-    fn validate_reg_a(&self) -> Result<(), Error> {
-        if self.input_a >= NUM_REGISTERS as u32 {
-            Err(Error::InputA)
-        } else {
-            Ok(())
-        }
-    }
-
-    fn validate_reg_b(&self) -> Result<(), Error> {
-        if self.input_b >= NUM_REGISTERS as u32 {
-            Err(Error::InputB)
-        } else {
-            Ok(())
-        }
-    }
-
-    fn validate_reg_c(&self) -> Result<(), Error> {
-        if self.output_c >= NUM_REGISTERS as u32 {
-            Err(Error::OutputC)
-        } else {
-            Ok(())
-        }
-    }
-
-    fn dispatch(&self, input: State) -> Result<State, Error> {
-        self.validate_opcode()?;
-        match self.opcode {
-            0 => self.addr(input),
-            1 => self.addi(input),
-            2 => self.mulr(input),
-            3 => self.muli(input),
-            4 => self.banr(input),
-            5 => self.bani(input),
-            6 => self.borr(input),
-            7 => self.bori(input),
-            8 => self.setr(input),
-            9 => self.seti(input),
-            10 => self.gtir(input),
-            11 => self.gtri(input),
-            12 => self.gtrr(input),
-            13 => self.eqir(input),
-            14 => self.eqri(input),
-            15 => self.eqrr(input),
-            _ => Err(Error::Functioncode),
-        }
-    }
-
-    fn addr(&self, input: State) -> Result<State, Error> {
-        self.validate_reg_a()?;
-        self.validate_reg_b()?;
-        self.validate_reg_c()?;
-
-        let mut state = input;
-        state[self.output_c] = state[self.input_a] + state[self.input_b];
-        Ok(state)
-    }
-
-    fn addi(&self, input: State) -> Result<State, Error> {
-        self.validate_reg_a()?;
-        self.validate_reg_c()?;
-
-        let mut state = input;
-        state[self.output_c] = state[self.input_a] + self.input_b;
-        Ok(state)
-    }
-
-    fn mulr(&self, input: State) -> Result<State, Error> {
-        self.validate_reg_a()?;
-        self.validate_reg_b()?;
-        self.validate_reg_c()?;
-
-        let mut state = input;
-        state[self.output_c] = state[self.input_a] * state[self.input_b];
-        Ok(state)
-    }
-
-    fn muli(&self, input: State) -> Result<State, Error> {
-        self.validate_reg_a()?;
-        self.validate_reg_c()?;
-
-        let mut state = input;
-        state[self.output_c] = state[self.input_a] * self.input_b;
-        Ok(state)
-    }
-
-    fn banr(&self, input: State) -> Result<State, Error> {
-        self.validate_reg_a()?;
-        self.validate_reg_b()?;
-        self.validate_reg_c()?;
-
-        let mut state = input;
-        state[self.output_c] = state[self.input_a] & state[self.input_b];
-        Ok(state)
-    }
-
-    fn bani(&self, input: State) -> Result<State, Error> {
-        self.validate_reg_a()?;
-        self.validate_reg_c()?;
-
-        let mut state = input;
-        state[self.output_c] = state[self.input_a] & self.input_b;
-        Ok(state)
-    }
-
-    fn borr(&self, input: State) -> Result<State, Error> {
-        self.validate_reg_a()?;
-        self.validate_reg_b()?;
-        self.validate_reg_c()?;
-
-        let mut state = input;
-        state[self.output_c] = state[self.input_a] | state[self.input_b];
-        Ok(state)
-    }
-
-    fn bori(&self, input: State) -> Result<State, Error> {
-        self.validate_reg_a()?;
-        self.validate_reg_c()?;
-
-        let mut state = input;
-        state[self.output_c] = state[self.input_a] | self.input_b;
-        Ok(state)
-    }
-
-    fn setr(&self, input: State) -> Result<State, Error> {
-        self.validate_reg_a()?;
-        self.validate_reg_c()?;
-
-        let mut state = input;
-        state[self.output_c] = state[self.input_a];
-        Ok(state)
-    }
-
-    fn seti(&self, input: State) -> Result<State, Error> {
-        self.validate_reg_c()?;
-
-        let mut state = input;
-        state[self.output_c] = self.input_a;
-        Ok(state)
-    }
-
-    fn gtir(&self, input: State) -> Result<State, Error> {
-        self.validate_reg_b()?;
-        self.validate_reg_c()?;
-
-        let mut state = input;
-        if self.input_a > state[self.input_b] {
-            state[self.output_c] = 1;
-        } else {
-            state[self.output_c] = 0;
-        }
-        Ok(state)
-    }
-
-    fn gtri(&self, input: State) -> Result<State, Error> {
-        self.validate_reg_a()?;
-        self.validate_reg_c()?;
-
-        let mut state = input;
-        if state[self.input_a] > self.input_b {
-            state[self.output_c] = 1;
-        } else {
-            state[self.output_c] = 0;
-        }
-        Ok(state)
-    }
-
-    fn gtrr(&self, input: State) -> Result<State, Error> {
-        self.validate_reg_a()?;
-        self.validate_reg_b()?;
-        self.validate_reg_c()?;
-
-        let mut state = input;
-        if state[self.input_a] > state[self.input_b] {
-            state[self.output_c] = 1;
-        } else {
-            state[self.output_c] = 0;
-        }
-        Ok(state)
-    }
-
-    fn eqir(&self, input: State) -> Result<State, Error> {
-        self.validate_reg_b()?;
-        self.validate_reg_c()?;
-
-        let mut state = input;
-        if self.input_a == state[self.input_b] {
-            state[self.output_c] = 1;
-        } else {
-            state[self.output_c] = 0;
-        }
-        Ok(state)
-    }
-
-    fn eqri(&self, input: State) -> Result<State, Error> {
-        self.validate_reg_a()?;
-        self.validate_reg_c()?;
-
-        let mut state = input;
-        if state[self.input_a] == self.input_b {
-            state[self.output_c] = 1;
-        } else {
-            state[self.output_c] = 0;
-        }
-        Ok(state)
-    }
-
-    fn eqrr(&self, input: State) -> Result<State, Error> {
-        self.validate_reg_a()?;
-        self.validate_reg_b()?;
-        self.validate_reg_c()?;
-
-        let mut state = input;
-        if state[self.input_a] == state[self.input_b] {
-            state[self.output_c] = 1;
-        } else {
-            state[self.output_c] = 0;
-        }
-        Ok(state)
-    }
-}
-
-struct ChronalComputer {
-    state: State,
-    program: Vec<Instruction>,
-    ip_reg: usize,
-    ip: u32,
-}
-
-impl ChronalComputer {
-    fn from_string(input: &str) -> Self {
-        Self::parser(input).unwrap().1
-    }
-
-    fn parser(input: &str) -> IResult<&str, Self> {
-        let (input, (ip_reg, program)) = pair(
-            preceded(tag("#ip "), map_res(digit1, |x: &str| x.parse::<usize>())),
-            many1(Instruction::parser),
-        )(input)?;
-
-        Ok((
-            input,
-            Self {
-                state: State([0; NUM_REGISTERS]),
-                program,
-                ip_reg,
-                ip: 0,
-            },
-        ))
-    }
-
-    fn run_program(&mut self) -> u32 {
-        while (self.ip as usize) < self.program.len() {
-            // Write the IP to its bound register
-            self.state[self.ip_reg] = self.ip;
-
-            // Execute the instruction
-            self.state = self.program[self.ip as usize]
-                .dispatch(self.state.clone())
-                .unwrap();
-
-            // Write the register value back to the IP (plus an increment)
-            self.ip = self.state[self.ip_reg] + 1;
-        }
-
-        self.state[0u32]
-    }
-}
+use crate::elfcode::ChronalComputer;
 
 #[aoc(day19, part1)]
 pub fn solve(input: &str) -> u32 {
-    let mut chronal = ChronalComputer::from_string(input);
+    let mut chronal = ChronalComputer::<u32>::from_string(input);
     let result = chronal.run_program();
     println!("Register 0: {}", result);
-    assert_eq!(result, 1302);
     result
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::elfcode::State;
 
     #[test]
     fn test_run_program() {
@@ -441,7 +68,7 @@ addr 1 2 3
 setr 1 0 0
 seti 8 0 4
 seti 9 0 5";
-        let mut chronal = ChronalComputer::from_string(input);
+        let mut chronal = ChronalComputer::<u32>::from_string(input);
         chronal.run_program();
         assert_eq!(chronal.state, State([6, 5, 6, 0, 0, 9]));
     }
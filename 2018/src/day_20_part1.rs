@@ -387,7 +387,6 @@ pub fn solve(input: &str) -> u32 {
 
     let result = map.find_furthest_room();
     println!("Furthest room: {}", result);
-    assert_eq!(result, 3699);
     result
 }
 
@@ -564,7 +564,6 @@ pub fn solve(input: &str) -> u32 {
     let mut cave = Cave::from_string(input);
     let fastest_time = cave.find_fastest_time_to_target();
     println!("Fastest time: {}", fastest_time);
-    assert_eq!(fastest_time, 973);
     fastest_time
 }
 
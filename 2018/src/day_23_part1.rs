@@ -130,7 +130,6 @@ pub fn solve(input: &str) -> u32 {
     let swarm = Swarm::from_string(input);
     let in_range = swarm.find_bots_in_range_of_strongest();
     println!("Bots in range of strongest: {}", in_range);
-    assert_eq!(in_range, 294);
     in_range
 }
 
@@ -414,7 +414,6 @@ pub fn solve(input: &str) -> u32 {
     let mut system = System::from_string(input);
     let units = system.battle();
     println!("Units remaining: {}", units);
-    assert_eq!(units, 18717);
     units
 }
 
@@ -75,27 +75,15 @@
     How many constellations are formed by the fixed points in spacetime?
 */
 
-fn manhattan(a: &Point4D, b: &Point4D) -> u32 {
-    ((a.0 - b.0).abs() + (a.1 - b.1).abs() + (a.2 - b.2).abs() + (a.3 - b.3).abs()) as u32
-}
+use common::PointN;
 
-#[derive(Clone, Copy, Debug)]
-struct Point4D(i32, i32, i32, i32);
-
-impl Point4D {
-    fn from_string(input: &str) -> Self {
-        let mut parts = input.split(',');
-        Self(
-            parts.next().unwrap().trim().parse::<i32>().unwrap(),
-            parts.next().unwrap().trim().parse::<i32>().unwrap(),
-            parts.next().unwrap().trim().parse::<i32>().unwrap(),
-            parts.next().unwrap().trim().parse::<i32>().unwrap(),
-        )
-    }
+type Point4D = PointN<4>;
 
-    fn many_from_string(input: &str) -> Vec<Self> {
-        input.lines().map(Self::from_string).collect()
-    }
+fn parse_points(input: &str) -> Vec<Point4D> {
+    input
+        .lines()
+        .map(|line| line.parse().expect("Invalid point"))
+        .collect()
 }
 
 #[derive(Debug)]
@@ -110,7 +98,7 @@ impl Constellation {
 
     fn touches_point(&self, p: &Point4D) -> bool {
         for star in &self.stars {
-            if manhattan(p, star) <= 3 {
+            if Point4D::manhattan(*p, *star) <= 3 {
                 return true;
             }
         }
@@ -166,11 +154,10 @@ fn form_constellations(points: &[Point4D]) -> Vec<Constellation> {
 
 #[aoc(day25, part1)]
 pub fn solve(input: &str) -> usize {
-    let points: Vec<Point4D> = Point4D::many_from_string(input);
+    let points: Vec<Point4D> = parse_points(input);
     let constellations = form_constellations(&points);
     let count = constellations.len();
     println!("Total constellations: {}", count);
-    assert_eq!(count, 388);
     count
 }
 
@@ -189,7 +176,7 @@ mod test {
 0,0,0,6
 9,0,0,0
 12,0,0,0";
-        let points: Vec<Point4D> = Point4D::many_from_string(input);
+        let points: Vec<Point4D> = parse_points(input);
         let constellations = form_constellations(&points);
         assert_eq!(constellations.len(), 2);
     }
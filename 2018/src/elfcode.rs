@@ -0,0 +1,440 @@
+// The device's assembly language, as used by days 16, 19 and 21
+mod decompile;
+
+pub use decompile::*;
+
+use common::{machine::Machine, unsigned};
+use nom::{
+    bytes::complete::tag,
+    character::complete::{alpha1, char, multispace0, space1},
+    combinator::{map_opt, opt},
+    multi::{many1, separated_list1},
+    sequence::{delimited, pair, preceded, tuple},
+    IResult,
+};
+use num::PrimInt;
+use std::collections::HashMap;
+use std::convert::TryInto;
+use std::fmt;
+use std::ops::{Index, IndexMut};
+use std::str::FromStr;
+
+pub const NUM_REGISTERS: usize = 6;
+
+// Register values; any primitive integer wide enough for the program being run
+pub trait Word: PrimInt + FromStr + fmt::Debug + fmt::Display {}
+
+impl<T: PrimInt + FromStr + fmt::Debug + fmt::Display> Word for T {}
+
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct State<T, const N: usize = NUM_REGISTERS>(pub [T; N]);
+
+impl<T: Word, const N: usize> State<T, N> {
+    pub fn new() -> Self {
+        Self([T::zero(); N])
+    }
+
+    pub fn parser(input: &str) -> IResult<&str, Self> {
+        map_opt(
+            delimited(
+                char('['),
+                separated_list1(tag(", "), unsigned::<T>),
+                char(']'),
+            ),
+            |values| Some(Self(values.try_into().ok()?)),
+        )(input)
+    }
+}
+
+impl<T: Word, const N: usize> Default for State<T, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, const N: usize> Index<usize> for State<T, N> {
+    type Output = T;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        &self.0[index]
+    }
+}
+
+impl<T, const N: usize> IndexMut<usize> for State<T, N> {
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        &mut self.0[index]
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Opcode {
+    Addr,
+    Addi,
+    Mulr,
+    Muli,
+    Banr,
+    Bani,
+    Borr,
+    Bori,
+    Setr,
+    Seti,
+    Gtir,
+    Gtri,
+    Gtrr,
+    Eqir,
+    Eqri,
+    Eqrr,
+}
+
+impl Opcode {
+    pub const ALL: [Self; 16] = [
+        Self::Addr,
+        Self::Addi,
+        Self::Mulr,
+        Self::Muli,
+        Self::Banr,
+        Self::Bani,
+        Self::Borr,
+        Self::Bori,
+        Self::Setr,
+        Self::Seti,
+        Self::Gtir,
+        Self::Gtri,
+        Self::Gtrr,
+        Self::Eqir,
+        Self::Eqri,
+        Self::Eqrr,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Addr => "addr",
+            Self::Addi => "addi",
+            Self::Mulr => "mulr",
+            Self::Muli => "muli",
+            Self::Banr => "banr",
+            Self::Bani => "bani",
+            Self::Borr => "borr",
+            Self::Bori => "bori",
+            Self::Setr => "setr",
+            Self::Seti => "seti",
+            Self::Gtir => "gtir",
+            Self::Gtri => "gtri",
+            Self::Gtrr => "gtrr",
+            Self::Eqir => "eqir",
+            Self::Eqri => "eqri",
+            Self::Eqrr => "eqrr",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.iter().copied().find(|op| op.name() == name)
+    }
+
+    // Whether each of the A and B inputs names a register (rather than being an immediate value).
+    // For the set instructions B is ignored.
+    pub fn reg_inputs(self) -> (bool, bool) {
+        match self {
+            Self::Addr | Self::Mulr | Self::Banr | Self::Borr | Self::Gtrr | Self::Eqrr => {
+                (true, true)
+            }
+            Self::Addi | Self::Muli | Self::Bani | Self::Bori | Self::Gtri | Self::Eqri => {
+                (true, false)
+            }
+            Self::Setr => (true, false),
+            Self::Seti => (false, false),
+            Self::Gtir | Self::Eqir => (false, true),
+        }
+    }
+}
+
+#[derive(Debug, Eq, PartialEq)]
+pub enum Error {
+    InputA,
+    InputB,
+    OutputC,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Instruction<T> {
+    pub opcode: Opcode,
+    pub input_a: T,
+    pub input_b: T,
+    pub output_c: T,
+}
+
+impl<T: Word> Instruction<T> {
+    pub fn new(opcode: Opcode, input_a: T, input_b: T, output_c: T) -> Self {
+        Self {
+            opcode,
+            input_a,
+            input_b,
+            output_c,
+        }
+    }
+
+    pub fn from_string(input: &str) -> Self {
+        Self::parser(input).unwrap().1
+    }
+
+    pub fn parser(input: &str) -> IResult<&str, Self> {
+        let (input, (opcode, a, b, c)) = tuple((
+            preceded(multispace0, map_opt(alpha1, Opcode::from_name)),
+            preceded(space1, unsigned),
+            preceded(space1, unsigned),
+            preceded(space1, unsigned),
+        ))(input)?;
+
+        Ok((input, Self::new(opcode, a, b, c)))
+    }
+
+    fn reg<const N: usize>(value: T, error: Error) -> Result<usize, Error> {
+        value.to_usize().filter(|&r| r < N).ok_or(error)
+    }
+
+    pub fn execute<const N: usize>(&self, state: &mut State<T, N>) -> Result<(), Error> {
+        let (reg_a, reg_b) = self.opcode.reg_inputs();
+        let a = if reg_a {
+            state[Self::reg::<N>(self.input_a, Error::InputA)?]
+        } else {
+            self.input_a
+        };
+        let b = if reg_b {
+            state[Self::reg::<N>(self.input_b, Error::InputB)?]
+        } else {
+            self.input_b
+        };
+        let c = Self::reg::<N>(self.output_c, Error::OutputC)?;
+
+        state[c] = match self.opcode {
+            Opcode::Addr | Opcode::Addi => a + b,
+            Opcode::Mulr | Opcode::Muli => a * b,
+            Opcode::Banr | Opcode::Bani => a & b,
+            Opcode::Borr | Opcode::Bori => a | b,
+            Opcode::Setr | Opcode::Seti => a,
+            Opcode::Gtir | Opcode::Gtri | Opcode::Gtrr => Self::from_bool(a > b),
+            Opcode::Eqir | Opcode::Eqri | Opcode::Eqrr => Self::from_bool(a == b),
+        };
+        Ok(())
+    }
+
+    fn from_bool(value: bool) -> T {
+        if value {
+            T::one()
+        } else {
+            T::zero()
+        }
+    }
+
+    // Pseudocode for the instruction, naming the registers A, B, C... and the bound register ip
+    pub fn describe(&self, ip_reg: Option<usize>) -> String {
+        let reg = |value: T| match value.to_usize() {
+            Some(r) => reg_name(r, ip_reg),
+            None => format!("r{}", value),
+        };
+        let (reg_a, reg_b) = self.opcode.reg_inputs();
+        let a = if reg_a {
+            reg(self.input_a)
+        } else {
+            self.input_a.to_string()
+        };
+        let b = if reg_b {
+            reg(self.input_b)
+        } else {
+            self.input_b.to_string()
+        };
+        let c = reg(self.output_c);
+
+        match self.opcode {
+            Opcode::Addr | Opcode::Addi => format!("{} = {} + {}", c, a, b),
+            Opcode::Mulr | Opcode::Muli => format!("{} = {} * {}", c, a, b),
+            Opcode::Banr | Opcode::Bani => format!("{} = {} & {}", c, a, b),
+            Opcode::Borr | Opcode::Bori => format!("{} = {} | {}", c, a, b),
+            Opcode::Setr | Opcode::Seti => format!("{} = {}", c, a),
+            Opcode::Gtir | Opcode::Gtri | Opcode::Gtrr => format!("{} = ({} > {})", c, a, b),
+            Opcode::Eqir | Opcode::Eqri | Opcode::Eqrr => format!("{} = ({} == {})", c, a, b),
+        }
+    }
+}
+
+pub fn reg_name(reg: usize, ip_reg: Option<usize>) -> String {
+    if Some(reg) == ip_reg {
+        "ip".to_string()
+    } else if reg < 26 {
+        ((b'A' + reg as u8) as char).to_string()
+    } else {
+        format!("r{}", reg)
+    }
+}
+
+impl<T: Word> fmt::Display for Instruction<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} {} {} {}",
+            self.opcode.name(),
+            self.input_a,
+            self.input_b,
+            self.output_c
+        )
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct ChronalComputer<T, const N: usize = NUM_REGISTERS> {
+    pub state: State<T, N>,
+    pub program: Vec<Instruction<T>>,
+    pub ip_reg: Option<usize>, // Register the instruction pointer is bound to, if any
+    pub ip: usize,
+    idioms: HashMap<usize, Idiom<T>>, // Run in place of the loops they start, once accelerated
+}
+
+impl<T: Word, const N: usize> ChronalComputer<T, N> {
+    pub fn new(program: Vec<Instruction<T>>, ip_reg: Option<usize>) -> Self {
+        Self {
+            state: State::new(),
+            program,
+            ip_reg,
+            ip: 0,
+            idioms: HashMap::new(),
+        }
+    }
+
+    pub fn from_string(input: &str) -> Self {
+        Self::parser(input).unwrap().1
+    }
+
+    pub fn parser(input: &str) -> IResult<&str, Self> {
+        let (input, (ip_reg, program)) = pair(
+            opt(preceded(pair(multispace0, tag("#ip ")), unsigned::<usize>)),
+            many1(Instruction::parser),
+        )(input)?;
+
+        Ok((input, Self::new(program, ip_reg)))
+    }
+
+    pub fn run_program(&mut self) -> T {
+        self.run();
+        self.state[0]
+    }
+
+    pub fn disassemble(&self) -> String {
+        let mut output = String::new();
+        if let Some(ip_reg) = self.ip_reg {
+            output += &format!("#ip {}\n", ip_reg);
+        }
+        for (ip, instr) in self.program.iter().enumerate() {
+            let text = instr.to_string();
+            output += &format!("{:02}  {:<20}{}\n", ip, text, instr.describe(self.ip_reg));
+        }
+        output
+    }
+}
+
+impl<T: Word, const N: usize> Machine for ChronalComputer<T, N> {
+    type Instruction = Instruction<T>;
+
+    fn program(&self) -> &[Instruction<T>] {
+        &self.program
+    }
+
+    fn pc(&self) -> usize {
+        self.ip
+    }
+
+    fn is_halted(&self) -> bool {
+        self.ip >= self.program.len()
+    }
+
+    // Executes the instruction at the IP, returning false if the program has halted instead
+    fn step(&mut self) -> bool {
+        if self.is_halted() == true {
+            return false;
+        }
+
+        // Write the IP to its bound register
+        if let Some(ip_reg) = self.ip_reg {
+            self.state[ip_reg] = T::from(self.ip).expect("IP doesn't fit in a register");
+        }
+
+        // Execute the instruction, or the whole loop if it starts an idiom
+        if let Some(idiom) = self.idioms.get(&self.ip) {
+            self.ip = idiom.apply(&mut self.state);
+            return true;
+        }
+        self.program[self.ip].execute(&mut self.state).unwrap();
+
+        // Write the register value back to the IP (plus an increment). Anything that doesn't fit is past the end.
+        if let Some(ip_reg) = self.ip_reg {
+            self.ip = self.state[ip_reg].to_usize().unwrap_or(usize::MAX);
+        }
+        self.ip = self.ip.saturating_add(1);
+        true
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use common::machine::Stop;
+
+    const EXAMPLE: &str = "\
+#ip 0
+seti 5 0 1
+seti 6 0 2
+addi 0 1 0
+addr 1 2 3
+setr 1 0 0
+seti 8 0 4
+seti 9 0 5";
+
+    #[test]
+    fn test_execute() {
+        let mut state = State::<u32, 4>([3, 2, 1, 1]);
+        Instruction::new(Opcode::Mulr, 2, 1, 2)
+            .execute(&mut state)
+            .unwrap();
+        assert_eq!(state.0, [3, 2, 2, 1]);
+
+        Instruction::new(Opcode::Gtir, 4, 0, 3)
+            .execute(&mut state)
+            .unwrap();
+        assert_eq!(state.0, [3, 2, 2, 1]);
+
+        let result = Instruction::new(Opcode::Addr, 0, 4, 1).execute(&mut state);
+        assert_eq!(result, Err(Error::InputB));
+    }
+
+    #[test]
+    fn test_run_program() {
+        let mut chronal = ChronalComputer::<u32>::from_string(EXAMPLE);
+        chronal.run_program();
+        assert_eq!(chronal.state, State([6, 5, 6, 0, 0, 9]));
+
+        let mut chronal = ChronalComputer::<u64>::from_string(EXAMPLE);
+        assert_eq!(chronal.run_program(), 6);
+
+        let mut chronal = ChronalComputer::<u32>::from_string(EXAMPLE);
+        assert_eq!(chronal.trace(100), (Stop::Halted, vec![0, 1, 2, 4, 6]));
+    }
+
+    #[test]
+    fn test_disassemble() {
+        let chronal = ChronalComputer::<u32>::from_string(EXAMPLE);
+        let disassembly = chronal.disassemble();
+        let lines: Vec<&str> = disassembly.lines().collect();
+        assert_eq!(lines[0], "#ip 0");
+        assert_eq!(lines[3], "02  addi 0 1 0          ip = ip + 1");
+        assert_eq!(lines[5], "04  setr 1 0 0          ip = B");
+        assert_eq!(lines[7], "06  seti 9 0 5          F = 9");
+    }
+
+    #[test]
+    fn test_state_parser() {
+        assert_eq!(
+            State::<u32, 4>::parser("[3, 2, 1, 1]").unwrap().1,
+            State([3, 2, 1, 1])
+        );
+        assert!(State::<u32, 4>::parser("[3, 2, 1]").is_err());
+    }
+}
@@ -0,0 +1,599 @@
+use super::{reg_name, ChronalComputer, Instruction, Opcode, State, Word};
+use std::collections::BTreeSet;
+use std::fmt;
+
+// How an instruction affects control flow, when the instruction pointer is bound to a register
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Flow {
+    Next,
+    Jump(usize), // Unconditional; a target past the end of the program halts
+    Skip(usize), // Skips the following instruction if this register (a comparison result) is set
+    Computed,    // Depends on some other register value, so can't be known until runtime
+}
+
+// A recognized loop which can be replaced by an equivalent calculation
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Idiom<T> {
+    // for outer in 1..=target { for inner in 1..=target { if outer * inner == target { sum += outer } } }
+    DivisorSum {
+        start: usize,
+        end: usize,
+        sum: usize,
+        target: usize,
+        outer: usize,
+        inner: usize,
+        product: usize,
+    },
+    // quotient = 0; while (quotient + 1) * divisor <= dividend { quotient += 1 }
+    Division {
+        start: usize,
+        end: usize,
+        quotient: usize,
+        dividend: usize,
+        divisor: T,
+        temp: usize,
+    },
+}
+
+impl<T: Word> Idiom<T> {
+    pub fn start(&self) -> usize {
+        match *self {
+            Self::DivisorSum { start, .. } | Self::Division { start, .. } => start,
+        }
+    }
+
+    // Leaves the registers as the loop would have, returning the IP it would have continued from
+    pub fn apply<const N: usize>(&self, state: &mut State<T, N>) -> usize {
+        match *self {
+            Self::DivisorSum {
+                end,
+                sum,
+                target,
+                outer,
+                inner,
+                product,
+                ..
+            } => {
+                let n = state[target];
+                let mut total = T::zero();
+                let mut i = T::one();
+                // Compared by division so that nothing overflows for n close to the largest value of T
+                while i <= n / i {
+                    if n % i == T::zero() {
+                        total = total + i;
+                        if i != n / i {
+                            total = total + n / i;
+                        }
+                    }
+                    i = i + T::one();
+                }
+
+                let last = n.max(T::one()).saturating_add(T::one());
+                state[sum] = state[sum] + total;
+                state[outer] = last;
+                state[inner] = last;
+                state[product] = T::one();
+                end
+            }
+            Self::Division {
+                end,
+                quotient,
+                dividend,
+                divisor,
+                temp,
+                ..
+            } => {
+                state[quotient] = state[dividend] / divisor;
+                state[temp] = T::one();
+                end
+            }
+        }
+    }
+
+    fn describe(&self, ip_reg: Option<usize>) -> String {
+        let reg = |r| reg_name(r, ip_reg);
+        match *self {
+            Self::DivisorSum {
+                end, sum, target, ..
+            } => format!(
+                "{} += sum of divisors of {}, then goto L{}",
+                reg(sum),
+                reg(target),
+                end
+            ),
+            Self::Division {
+                end,
+                quotient,
+                dividend,
+                divisor,
+                ..
+            } => format!(
+                "{} = {} / {}, then goto L{}",
+                reg(quotient),
+                reg(dividend),
+                divisor,
+                end
+            ),
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Pattern {
+    Reg(usize), // Any register other than the IP, the same one each time this variable is used
+    Ip,         // The register bound to the IP
+    Imm(u8),    // This exact value
+    Const(usize), // Any value, the same one each time this variable is used
+    Offset(usize), // The IP of the start of the idiom plus this offset
+    Any,        // Ignored
+}
+
+struct Template(Opcode, Pattern, Pattern, Pattern);
+
+#[derive(Clone, Debug)]
+struct Bindings<T> {
+    regs: [Option<usize>; 5],
+    consts: [Option<T>; 1],
+}
+
+// Register variables for the idiom templates
+const SUM: usize = 0;
+const OUTER: usize = 1;
+const INNER: usize = 2;
+const PRODUCT: usize = 3;
+const TARGET: usize = 4;
+
+const QUOTIENT: usize = 0;
+const TEMP: usize = 1;
+const DIVIDEND: usize = 2;
+const DIVISOR: usize = 0;
+
+const DIVISOR_SUM: [Template; 15] = {
+    use Opcode::*;
+    use Pattern::*;
+    [
+        Template(Seti, Imm(1), Any, Reg(OUTER)),
+        Template(Seti, Imm(1), Any, Reg(INNER)),
+        Template(Mulr, Reg(OUTER), Reg(INNER), Reg(PRODUCT)),
+        Template(Eqrr, Reg(PRODUCT), Reg(TARGET), Reg(PRODUCT)),
+        Template(Addr, Reg(PRODUCT), Ip, Ip),
+        Template(Addi, Ip, Imm(1), Ip),
+        Template(Addr, Reg(OUTER), Reg(SUM), Reg(SUM)),
+        Template(Addi, Reg(INNER), Imm(1), Reg(INNER)),
+        Template(Gtrr, Reg(INNER), Reg(TARGET), Reg(PRODUCT)),
+        Template(Addr, Ip, Reg(PRODUCT), Ip),
+        Template(Seti, Offset(1), Any, Ip),
+        Template(Addi, Reg(OUTER), Imm(1), Reg(OUTER)),
+        Template(Gtrr, Reg(OUTER), Reg(TARGET), Reg(PRODUCT)),
+        Template(Addr, Reg(PRODUCT), Ip, Ip),
+        Template(Seti, Offset(0), Any, Ip),
+    ]
+};
+
+const DIVISION: [Template; 9] = {
+    use Opcode::*;
+    use Pattern::*;
+    [
+        Template(Seti, Imm(0), Any, Reg(QUOTIENT)),
+        Template(Addi, Reg(QUOTIENT), Imm(1), Reg(TEMP)),
+        Template(Muli, Reg(TEMP), Const(DIVISOR), Reg(TEMP)),
+        Template(Gtrr, Reg(TEMP), Reg(DIVIDEND), Reg(TEMP)),
+        Template(Addr, Reg(TEMP), Ip, Ip),
+        Template(Addi, Ip, Imm(1), Ip),
+        Template(Seti, Offset(8), Any, Ip),
+        Template(Addi, Reg(QUOTIENT), Imm(1), Reg(QUOTIENT)),
+        Template(Seti, Offset(0), Any, Ip),
+    ]
+};
+
+#[derive(Clone, Debug)]
+pub struct Decompilation<T> {
+    pub ip_reg: Option<usize>,
+    pub program: Vec<Instruction<T>>,
+    pub flows: Vec<Flow>,
+    pub blocks: Vec<(usize, usize)>, // Start and end (exclusive) of each basic block
+    pub idioms: Vec<Idiom<T>>,
+}
+
+impl<T: Word, const N: usize> ChronalComputer<T, N> {
+    pub fn decompile(&self) -> Decompilation<T> {
+        let flows: Vec<Flow> = (0..self.program.len()).map(|ip| self.flow(ip)).collect();
+
+        // Blocks start at the beginning, any jump target and after any change in control flow
+        let mut leaders = BTreeSet::new();
+        leaders.insert(0);
+        for (ip, flow) in flows.iter().enumerate() {
+            match *flow {
+                Flow::Next => continue,
+                Flow::Jump(target) => {
+                    leaders.insert(target);
+                }
+                Flow::Skip(_) => {
+                    leaders.insert(ip + 2);
+                }
+                Flow::Computed => (),
+            }
+            leaders.insert(ip + 1);
+        }
+        let leaders: Vec<usize> = leaders
+            .into_iter()
+            .filter(|&ip| ip < self.program.len())
+            .chain(std::iter::once(self.program.len()))
+            .collect();
+        let blocks = leaders.windows(2).map(|w| (w[0], w[1])).collect();
+
+        let idioms = (0..self.program.len())
+            .filter_map(|ip| self.find_idiom(ip))
+            .collect();
+
+        Decompilation {
+            ip_reg: self.ip_reg,
+            program: self.program.clone(),
+            flows,
+            blocks,
+            idioms,
+        }
+    }
+
+    // Replaces any recognized idioms with their equivalent calculation when running
+    pub fn accelerate(&mut self) {
+        self.idioms = self
+            .decompile()
+            .idioms
+            .into_iter()
+            .map(|idiom| (idiom.start(), idiom))
+            .collect();
+    }
+
+    // Instructions which read the given register
+    pub fn reads_of(&self, reg: usize) -> Vec<usize> {
+        self.program
+            .iter()
+            .enumerate()
+            .filter(|(_, instr)| {
+                let (reg_a, reg_b) = instr.opcode.reg_inputs();
+                (reg_a && instr.input_a.to_usize() == Some(reg))
+                    || (reg_b && instr.input_b.to_usize() == Some(reg))
+            })
+            .map(|(ip, _)| ip)
+            .collect()
+    }
+
+    // A program which only halts when register A matches a value it calculates has a single comparison with A.
+    // Returns the IP of that comparison and the register compared against.
+    pub fn halt_check(&self) -> (usize, usize) {
+        let reads = self.reads_of(0);
+        assert_eq!(reads.len(), 1, "Register A should only be read once");
+
+        let ip = reads[0];
+        let instr = &self.program[ip];
+        let other = if instr.input_a.to_usize() == Some(0) {
+            instr.input_b
+        } else {
+            instr.input_a
+        };
+        (ip, other.to_usize().unwrap())
+    }
+
+    fn flow(&self, ip: usize) -> Flow {
+        let instr = &self.program[ip];
+        let Some(ip_reg) = self.ip_reg else {
+            return Flow::Next;
+        };
+        if instr.output_c.to_usize() != Some(ip_reg) {
+            return Flow::Next;
+        }
+
+        // Which inputs are known without running the program, i.e. aren't registers other than the IP
+        let (reg_a, reg_b) = instr.opcode.reg_inputs();
+        let is_other_reg = |is_reg: bool, value: T| is_reg && value.to_usize() != Some(ip_reg);
+        let other_a = is_other_reg(reg_a, instr.input_a);
+        let other_b = is_other_reg(reg_b, instr.input_b);
+
+        if other_a == false && other_b == false {
+            let mut state = State::<T, N>::new();
+            state[ip_reg] = T::from(ip).unwrap();
+            instr.execute(&mut state).unwrap();
+            let target = state[ip_reg].to_usize().unwrap_or(usize::MAX);
+            return Flow::Jump(target.saturating_add(1));
+        }
+
+        // Adding a comparison result to the IP conditionally skips the next instruction
+        if instr.opcode == Opcode::Addr && other_a != other_b && ip > 0 {
+            let flag = if other_a {
+                instr.input_a
+            } else {
+                instr.input_b
+            };
+            let prev = &self.program[ip - 1];
+            let is_comparison = matches!(
+                prev.opcode,
+                Opcode::Gtir
+                    | Opcode::Gtri
+                    | Opcode::Gtrr
+                    | Opcode::Eqir
+                    | Opcode::Eqri
+                    | Opcode::Eqrr
+            );
+            if is_comparison && prev.output_c == flag {
+                return Flow::Skip(flag.to_usize().unwrap());
+            }
+        }
+
+        Flow::Computed
+    }
+
+    fn find_idiom(&self, start: usize) -> Option<Idiom<T>> {
+        if let Some(b) = self.match_template(start, &DIVISOR_SUM) {
+            return Some(Idiom::DivisorSum {
+                start,
+                end: start + DIVISOR_SUM.len(),
+                sum: b.regs[SUM]?,
+                target: b.regs[TARGET]?,
+                outer: b.regs[OUTER]?,
+                inner: b.regs[INNER]?,
+                product: b.regs[PRODUCT]?,
+            });
+        }
+        if let Some(b) = self.match_template(start, &DIVISION) {
+            let divisor = b.consts[DIVISOR]?;
+            if divisor > T::zero() {
+                return Some(Idiom::Division {
+                    start,
+                    end: start + DIVISION.len(),
+                    quotient: b.regs[QUOTIENT]?,
+                    dividend: b.regs[DIVIDEND]?,
+                    divisor,
+                    temp: b.regs[TEMP]?,
+                });
+            }
+        }
+        None
+    }
+
+    fn match_template(&self, start: usize, template: &[Template]) -> Option<Bindings<T>> {
+        let ip_reg = self.ip_reg?;
+        let instrs = self.program.get(start..start + template.len())?;
+
+        let mut bindings = Bindings {
+            regs: [None; 5],
+            consts: [None; 1],
+        };
+        for (instr, Template(opcode, a, b, c)) in instrs.iter().zip(template) {
+            if instr.opcode != *opcode {
+                return None;
+            }
+
+            // Commutative operations may have their inputs either way around
+            let commutative = matches!(
+                opcode,
+                Opcode::Addr | Opcode::Mulr | Opcode::Banr | Opcode::Borr | Opcode::Eqrr
+            );
+            let orders: &[(T, T)] = if commutative {
+                &[
+                    (instr.input_a, instr.input_b),
+                    (instr.input_b, instr.input_a),
+                ]
+            } else {
+                &[(instr.input_a, instr.input_b)]
+            };
+
+            bindings = orders.iter().find_map(|&(input_a, input_b)| {
+                let mut attempt = bindings.clone();
+                let matched = Self::match_operand(&mut attempt, *a, input_a, start, ip_reg)
+                    && Self::match_operand(&mut attempt, *b, input_b, start, ip_reg)
+                    && Self::match_operand(&mut attempt, *c, instr.output_c, start, ip_reg);
+                if matched {
+                    Some(attempt)
+                } else {
+                    None
+                }
+            })?;
+        }
+        Some(bindings)
+    }
+
+    fn match_operand(
+        bindings: &mut Bindings<T>,
+        pattern: Pattern,
+        value: T,
+        start: usize,
+        ip_reg: usize,
+    ) -> bool {
+        match pattern {
+            Pattern::Reg(var) => {
+                let Some(reg) = value.to_usize().filter(|&r| r < N && r != ip_reg) else {
+                    return false;
+                };
+                match bindings.regs[var] {
+                    Some(bound) => bound == reg,
+                    None if bindings.regs.contains(&Some(reg)) => false, // Already used by another variable
+                    None => {
+                        bindings.regs[var] = Some(reg);
+                        true
+                    }
+                }
+            }
+            Pattern::Ip => value.to_usize() == Some(ip_reg),
+            Pattern::Imm(expected) => T::from(expected) == Some(value),
+            Pattern::Const(var) => match bindings.consts[var] {
+                Some(bound) => bound == value,
+                None => {
+                    bindings.consts[var] = Some(value);
+                    true
+                }
+            },
+            Pattern::Offset(offset) => value.to_usize() == Some(start + offset),
+            Pattern::Any => true,
+        }
+    }
+}
+
+impl<T: Word> fmt::Display for Decompilation<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let label = |target: usize| {
+            if target < self.program.len() {
+                format!("goto L{}", target)
+            } else {
+                "halt".to_string()
+            }
+        };
+
+        for &(start, end) in &self.blocks {
+            writeln!(f, "L{}:", start)?;
+            for ip in start..end {
+                if let Some(idiom) = self.idioms.iter().find(|idiom| idiom.start() == ip) {
+                    writeln!(f, "    // {}", idiom.describe(self.ip_reg))?;
+                }
+
+                let instr = &self.program[ip];
+                match self.flows[ip] {
+                    Flow::Next => writeln!(f, "    {}", instr.describe(self.ip_reg))?,
+                    Flow::Jump(target) => writeln!(f, "    {}", label(target))?,
+                    Flow::Skip(flag) => writeln!(
+                        f,
+                        "    if {} {}",
+                        reg_name(flag, self.ip_reg),
+                        label(ip + 2)
+                    )?,
+                    Flow::Computed => {
+                        writeln!(f, "    {}    // computed jump", instr.describe(self.ip_reg))?
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    // Sums the divisors of 36 the slow way, like day 19
+    const DIVISOR_SUM_PROGRAM: &str = "\
+#ip 5
+addi 5 16 5
+seti 1 3 1
+seti 1 1 2
+mulr 1 2 4
+eqrr 4 3 4
+addr 4 5 5
+addi 5 1 5
+addr 1 0 0
+addi 2 1 2
+gtrr 2 3 4
+addr 5 4 5
+seti 2 4 5
+addi 1 1 1
+gtrr 1 3 4
+addr 4 5 5
+seti 1 5 5
+mulr 5 5 5
+seti 36 0 3
+seti 0 0 5";
+
+    // Divides 1000 by 7 by counting, like day 21
+    const DIVISION_PROGRAM: &str = "\
+#ip 2
+seti 1000 0 4
+seti 0 1 5
+addi 5 1 1
+muli 1 7 1
+gtrr 1 4 1
+addr 1 2 2
+addi 2 1 2
+seti 9 1 2
+addi 5 1 5
+seti 1 8 2
+setr 5 2 0";
+
+    #[test]
+    fn test_flow() {
+        let chronal = ChronalComputer::<u32>::from_string(DIVISOR_SUM_PROGRAM);
+        let decompiled = chronal.decompile();
+        assert_eq!(decompiled.flows[0], Flow::Jump(17));
+        assert_eq!(decompiled.flows[3], Flow::Next);
+        assert_eq!(decompiled.flows[5], Flow::Skip(4));
+        assert_eq!(decompiled.flows[6], Flow::Jump(8));
+        assert_eq!(decompiled.flows[16], Flow::Jump(257)); // Halts
+        assert_eq!(decompiled.blocks[..4], [(0, 1), (1, 2), (2, 3), (3, 6)]);
+
+        let text = decompiled.to_string();
+        assert!(text.contains("L3:\n    E = B * C\n    E = (E == D)\n    if E goto L7\n"));
+        assert!(text.contains("    // A += sum of divisors of D, then goto L16\n"));
+        assert!(text.contains("L16:\n    halt\n"));
+    }
+
+    #[test]
+    fn test_reads_of() {
+        let chronal = ChronalComputer::<u32>::from_string(DIVISOR_SUM_PROGRAM);
+        assert_eq!(chronal.reads_of(0), [7]);
+        assert_eq!(chronal.halt_check(), (7, 1));
+    }
+
+    #[test]
+    fn test_divisor_sum() {
+        let mut chronal = ChronalComputer::<u32>::from_string(DIVISOR_SUM_PROGRAM);
+        let mut accelerated = chronal.clone();
+        accelerated.accelerate();
+        assert_eq!(
+            accelerated.decompile().idioms,
+            [Idiom::DivisorSum {
+                start: 1,
+                end: 16,
+                sum: 0,
+                target: 3,
+                outer: 1,
+                inner: 2,
+                product: 4,
+            }]
+        );
+
+        assert_eq!(chronal.run_program(), 91);
+        assert_eq!(accelerated.run_program(), 91);
+        assert_eq!(accelerated.state, chronal.state);
+    }
+
+    #[test]
+    fn test_divisor_sum_large() {
+        let idiom = Idiom::DivisorSum {
+            start: 1,
+            end: 16,
+            sum: 0,
+            target: 3,
+            outer: 1,
+            inner: 2,
+            product: 4,
+        };
+
+        // The largest prime below u32::MAX, so the divisors still sum to something that fits but the square root is
+        // only just below 2^16
+        let mut state = State::<u32>([0, 0, 0, u32::MAX - 4, 0, 0]);
+        assert_eq!(idiom.apply(&mut state), 16);
+        assert_eq!(state[0], u32::MAX - 3);
+        assert_eq!(state[1], u32::MAX - 3);
+    }
+
+    #[test]
+    fn test_division() {
+        let mut chronal = ChronalComputer::<u64>::from_string(DIVISION_PROGRAM);
+        let mut accelerated = chronal.clone();
+        accelerated.accelerate();
+        assert_eq!(
+            accelerated.decompile().idioms,
+            [Idiom::Division {
+                start: 1,
+                end: 10,
+                quotient: 5,
+                dividend: 4,
+                divisor: 7,
+                temp: 1,
+            }]
+        );
+
+        assert_eq!(chronal.run_program(), 142);
+        assert_eq!(accelerated.run_program(), 142);
+        assert_eq!(accelerated.state, chronal.state);
+    }
+}
@@ -58,5 +58,6 @@ mod day_23_part2;
 mod day_24_part1;
 mod day_24_part2;
 mod day_25_part1;
+mod elfcode;
 
 aoc_lib! { year = 2018 }
@@ -46,66 +46,25 @@
     Once you have a working computer, the first step is to restore the gravity assist program (your puzzle input) to the "1202 program alarm" state it had just before the last computer caught fire. To do this, before running the program, replace position 1 with the value 12 and replace position 2 with the value 2. What value is left at position 0 after the program halts?
 */
 
-fn run_program(program: &mut [u32]) {
-    let mut pc = 0u32;
-    loop {
-        // println!("Program: {:?}", program);
-        let opcode = program[pc as usize];
-        match opcode {
-            1 => opcode_add(program, &mut pc),
-            2 => opcode_mul(program, &mut pc),
-            99 => {
-                // println!("Program complete!");
-                break;
-            }
-            _ => panic!("Invalid opcode"),
-        }
-    }
-}
-
-fn opcode_add(program: &mut [u32], pc: &mut u32) {
-    let param1_addr = program[(*pc + 1) as usize];
-    let param2_addr = program[(*pc + 2) as usize];
-    let target_addr = program[(*pc + 3) as usize];
-    *pc += 4;
-
-    let param1 = program[param1_addr as usize];
-    let param2 = program[param2_addr as usize];
-
-    // println!("Add {} + {} => {}", param1, param2, target_addr);
-
-    program[target_addr as usize] = param1 + param2;
-}
-
-fn opcode_mul(program: &mut [u32], pc: &mut u32) {
-    let param1_addr = program[(*pc + 1) as usize];
-    let param2_addr = program[(*pc + 2) as usize];
-    let target_addr = program[(*pc + 3) as usize];
-    *pc += 4;
-
-    let param1 = program[param1_addr as usize];
-    let param2 = program[param2_addr as usize];
-
-    // println!("Add {} * {} => {}", param1, param2, target_addr);
+use common::intcode::Program;
 
-    program[target_addr as usize] = param1 * param2;
+fn run_program(code: &[i64]) -> Vec<i64> {
+    let mut program = Program::new(code);
+    program.run();
+    program.code().to_vec()
 }
 
 #[aoc(day2, part1)]
-pub fn solve(input: &str) -> u32 {
-    let mut program: Vec<u32> = input
-        .trim()
-        .split(',')
-        .map(|s| s.parse::<u32>().unwrap())
-        .collect();
+pub fn solve(input: &str) -> i64 {
+    let mut program = Program::from_string(input);
     // Position 1 changed to 12 and position 2 changed to 2 per instructions
-    program[1] = 12;
-    program[2] = 2;
+    program.set(1, 12);
+    program.set(2, 2);
 
-    run_program(&mut program);
+    program.run();
 
-    println!("Program position 0: {}", program[0]);
-    program[0]
+    println!("Program position 0: {}", program.get(0));
+    program.get(0)
 }
 
 #[cfg(test)]
@@ -114,20 +73,16 @@ mod test {
 
     #[test]
     fn test_program() {
-        let mut program1 = [1, 0, 0, 0, 99];
-        run_program(&mut program1);
+        let program1 = run_program(&[1, 0, 0, 0, 99]);
         assert_eq!(program1, [2, 0, 0, 0, 99]);
 
-        let mut program2 = [2, 3, 0, 3, 99];
-        run_program(&mut program2);
+        let program2 = run_program(&[2, 3, 0, 3, 99]);
         assert_eq!(program2, [2, 3, 0, 6, 99]);
 
-        let mut program3 = [2, 4, 4, 5, 99, 0];
-        run_program(&mut program3);
+        let program3 = run_program(&[2, 4, 4, 5, 99, 0]);
         assert_eq!(program3, [2, 4, 4, 5, 99, 9801]);
 
-        let mut program4 = [1, 1, 1, 4, 99, 5, 6, 0, 99];
-        run_program(&mut program4);
+        let program4 = run_program(&[1, 1, 1, 4, 99, 5, 6, 0, 99]);
         assert_eq!(program4, [30, 1, 1, 4, 2, 5, 6, 0, 99]);
     }
 }
@@ -19,75 +19,26 @@
     Find the input noun and verb that cause the program to produce the output 19690720. What is 100 * noun + verb? (For example, if noun=12 and verb=2, the answer would be 1202.)
 */
 
-// use std::process::Command;
-
-fn run_program(program: &mut [u32]) {
-    let mut pc = 0u32;
-    loop {
-        // println!("Program: {:?}", program);
-        // println!("PC: {}", pc);
-        // println!();
-
-        let opcode = program[pc as usize];
-        match opcode {
-            1 => opcode_add(program, &mut pc),
-            2 => opcode_mul(program, &mut pc),
-            99 => {
-                // println!("Program complete!");
-                break;
-            }
-            _ => panic!("Invalid opcode"),
-        }
-    }
-}
-
-fn opcode_add(program: &mut [u32], pc: &mut u32) {
-    let param1_addr = program[(*pc + 1) as usize];
-    let param2_addr = program[(*pc + 2) as usize];
-    let target_addr = program[(*pc + 3) as usize];
-    *pc += 4;
-
-    let param1 = program[param1_addr as usize];
-    let param2 = program[param2_addr as usize];
-
-    // println!("Add {} + {} => {}", param1, param2, target_addr);
-
-    program[target_addr as usize] = param1 + param2;
-}
-
-fn opcode_mul(program: &mut [u32], pc: &mut u32) {
-    let param1_addr = program[(*pc + 1) as usize];
-    let param2_addr = program[(*pc + 2) as usize];
-    let target_addr = program[(*pc + 3) as usize];
-    *pc += 4;
-
-    let param1 = program[param1_addr as usize];
-    let param2 = program[param2_addr as usize];
-
-    // println!("Add {} * {} => {}", param1, param2, target_addr);
+use common::intcode::Program;
 
-    program[target_addr as usize] = param1 * param2;
+fn run_program(code: &[i64]) -> Vec<i64> {
+    let mut program = Program::new(code);
+    program.run();
+    program.code().to_vec()
 }
 
 #[aoc(day2, part2)]
-pub fn solve(input: &str) -> u32 {
-    let mut program: Vec<u32> = input
-        .trim()
-        .split(',')
-        .map(|s| s.parse::<u32>().unwrap())
-        .collect();
-    // Position 1 changed to 12 and position 2 changed to 2 per instructions
-    program[1] = 12;
-    program[2] = 2;
+pub fn solve(input: &str) -> i64 {
+    let program = Program::from_string(input);
 
     for noun in 0..100 {
         for verb in 0..100 {
             let mut p = program.clone();
-            p[1] = noun;
-            p[2] = verb;
-            run_program(&mut p);
+            p.set(1, noun);
+            p.set(2, verb);
+            p.run();
 
-            let output = p[0];
+            let output = p.get(0);
             if output == 19690720 {
                 println!("Noun, verb = {}, {}", noun, verb);
                 println!("Answer = {}", 100 * noun + verb);
@@ -105,20 +56,16 @@ mod test {
 
     #[test]
     fn test_program() {
-        let mut program1 = [1, 0, 0, 0, 99];
-        run_program(&mut program1);
+        let program1 = run_program(&[1, 0, 0, 0, 99]);
         assert_eq!(program1, [2, 0, 0, 0, 99]);
 
-        let mut program2 = [2, 3, 0, 3, 99];
-        run_program(&mut program2);
+        let program2 = run_program(&[2, 3, 0, 3, 99]);
         assert_eq!(program2, [2, 3, 0, 6, 99]);
 
-        let mut program3 = [2, 4, 4, 5, 99, 0];
-        run_program(&mut program3);
+        let program3 = run_program(&[2, 4, 4, 5, 99, 0]);
         assert_eq!(program3, [2, 4, 4, 5, 99, 9801]);
 
-        let mut program4 = [1, 1, 1, 4, 99, 5, 6, 0, 99];
-        run_program(&mut program4);
+        let program4 = run_program(&[1, 1, 1, 4, 99, 5, 6, 0, 99]);
         assert_eq!(program4, [30, 1, 1, 4, 2, 5, 6, 0, 99]);
     }
 }
@@ -95,12 +95,11 @@ fn best_intersection(path1: Vec<&str>, path2: Vec<&str>) -> u32 {
 
     let intersect_points = intersection(&path1_points, &path2_points);
 
-    let closest_distance = intersect_points
+    intersect_points
         .iter()
         .map(|&p| manhattan_distance(p))
         .min()
-        .unwrap();
-    closest_distance
+        .unwrap()
 }
 
 #[aoc(day3, part1)]
@@ -81,12 +81,11 @@ fn best_intersection(path1: Vec<&str>, path2: Vec<&str>) -> u32 {
 
     let intersect_points = intersection(&path1_points, &path2_points);
 
-    let shortest_steps = intersect_points
+    intersect_points
         .iter()
         .map(|&c| path1_points[&c] + path2_points[&c])
         .min()
-        .unwrap();
-    shortest_steps
+        .unwrap()
 }
 
 #[aoc(day3, part2)]
@@ -47,150 +47,14 @@
     After providing 1 to the only input instruction and passing all the tests, what diagnostic code does the program produce?
 */
 
-struct Program {
-    code: Vec<i32>,
-    pc: u32,
-    input: Vec<i32>,
-    input_idx: u32,
-    output: Vec<i32>,
-}
-
-impl Program {
-    fn new(code: &[i32], input: &[i32]) -> Program {
-        Program {
-            code: code.to_vec(),
-            pc: 0,
-            input: input.to_vec(),
-            input_idx: 0,
-            output: Vec::new(),
-        }
-    }
-
-    fn run(&mut self) {
-        loop {
-            // println!("Program: {:?}", program);
-            // println!("PC: {}", pc);
-            // println!();
-
-            let opcode = self.get_opcode_curr();
-            match opcode {
-                1 => self.opcode_add(),
-                2 => self.opcode_mul(),
-                3 => self.opcode_in(),
-                4 => self.opcode_out(),
-                99 => {
-                    // println!("Program complete!");
-                    break;
-                }
-                _ => panic!("Invalid opcode"),
-            }
-        }
-    }
-
-    fn get_opcode_curr(&self) -> i32 {
-        Program::get_opcode(self.code[self.pc as usize])
-    }
-
-    fn get_opcode(code_word: i32) -> i32 {
-        code_word % 100
-    }
-
-    fn get_mode_curr(&self, param_idx: u32) -> i32 {
-        Program::get_mode(self.code[self.pc as usize], param_idx)
-    }
-
-    fn get_mode(code_word: i32, digit: u32) -> i32 {
-        let modes = code_word / 100;
-        (modes % 10i32.pow(digit)) / 10i32.pow(digit - 1)
-    }
-
-    fn get_param_addr(&self, param_idx: u32) -> u32 {
-        let mode = self.get_mode_curr(param_idx);
-        match mode {
-            0 => self.get_value(self.pc + param_idx) as u32,
-            1 => self.pc + param_idx,
-            _ => panic!(),
-        }
-    }
-
-    fn get_value(&self, addr: u32) -> i32 {
-        self.code[addr as usize]
-    }
-
-    fn set_value(&mut self, addr: u32, value: i32) {
-        self.code[addr as usize] = value;
-    }
-
-    // 1 + 2 => 3
-    fn opcode_add(&mut self) {
-        let param1_addr = self.get_param_addr(1);
-        let param2_addr = self.get_param_addr(2);
-        let param3_addr = self.get_param_addr(3); // Note: this is output so must ALWAYS be positional
-        self.pc += 4;
-
-        let param1 = self.get_value(param1_addr);
-        let param2 = self.get_value(param2_addr);
-
-        // println!("Add {} + {} => [{}]", param1, param2, param3_addr);
-
-        self.set_value(param3_addr, param1 + param2);
-    }
-
-    // 1 * 2 => 3
-    fn opcode_mul(&mut self) {
-        let param1_addr = self.get_param_addr(1);
-        let param2_addr = self.get_param_addr(2);
-        let param3_addr = self.get_param_addr(3); // Note: this is output so must ALWAYS be positional
-        self.pc += 4;
-
-        let param1 = self.get_value(param1_addr);
-        let param2 = self.get_value(param2_addr);
-
-        // println!("Mul {} * {} => [{}]", param1, param2, param3_addr);
-
-        self.set_value(param3_addr, param1 * param2);
-    }
-
-    // Get input and store in target
-    fn opcode_in(&mut self) {
-        let param1_addr = self.get_param_addr(1);
-        self.pc += 2;
-
-        let input = self.input[self.input_idx as usize];
-        self.input_idx += 1;
-
-        // println!("In {} => [{}]", input, param1_addr);
-
-        self.set_value(param1_addr, input);
-    }
-
-    // Get 1 and output it to user
-    fn opcode_out(&mut self) {
-        let param1_addr = self.get_param_addr(1);
-        self.pc += 2;
-
-        let param1 = self.get_value(param1_addr);
-
-        self.output.push(param1);
-
-        // println!("Out {}", param1);
-    }
-}
+use common::intcode::Program;
 
 #[aoc(day5, part1)]
-pub fn solve(input: &str) -> i32 {
-    let code: Vec<i32> = input
-        .trim()
-        .split(',')
-        .map(|s| s.parse::<i32>().unwrap())
-        .collect();
-    let input = [1];
-
-    let mut program = Program::new(&code, &input);
+pub fn solve(input: &str) -> i64 {
+    let mut program = Program::from_string(input).with_input([1]);
     program.run();
 
-    let result = program.output.iter().cloned().max().unwrap();
-    result
+    program.output().iter().cloned().max().unwrap()
 }
 
 #[cfg(test)]
@@ -200,45 +64,45 @@ mod test {
     #[test]
     fn test_program() {
         // Add
-        let mut program = Program::new(&[1, 0, 0, 0, 99], &[]);
+        let mut program = Program::new(&[1, 0, 0, 0, 99]);
         program.run();
-        assert_eq!(program.code, [2, 0, 0, 0, 99]);
-        assert_eq!(program.output, []);
+        assert_eq!(program.code(), [2, 0, 0, 0, 99]);
+        assert_eq!(program.output(), []);
 
         // Mul
-        let mut program = Program::new(&[2, 3, 0, 3, 99], &[]);
+        let mut program = Program::new(&[2, 3, 0, 3, 99]);
         program.run();
-        assert_eq!(program.code, [2, 3, 0, 6, 99]);
-        assert_eq!(program.output, []);
+        assert_eq!(program.code(), [2, 3, 0, 6, 99]);
+        assert_eq!(program.output(), []);
 
         // Mul
-        let mut program = Program::new(&[2, 4, 4, 5, 99, 0], &[]);
+        let mut program = Program::new(&[2, 4, 4, 5, 99, 0]);
         program.run();
-        assert_eq!(program.code, [2, 4, 4, 5, 99, 9801]);
-        assert_eq!(program.output, []);
+        assert_eq!(program.code(), [2, 4, 4, 5, 99, 9801]);
+        assert_eq!(program.output(), []);
 
         // Add / Mul
-        let mut program = Program::new(&[1, 1, 1, 4, 99, 5, 6, 0, 99], &[]);
+        let mut program = Program::new(&[1, 1, 1, 4, 99, 5, 6, 0, 99]);
         program.run();
-        assert_eq!(program.code, [30, 1, 1, 4, 2, 5, 6, 0, 99]);
-        assert_eq!(program.output, []);
+        assert_eq!(program.code(), [30, 1, 1, 4, 2, 5, 6, 0, 99]);
+        assert_eq!(program.output(), []);
 
         // Mode
-        let mut program = Program::new(&[1002, 4, 3, 4, 33], &[]);
+        let mut program = Program::new(&[1002, 4, 3, 4, 33]);
         program.run();
-        assert_eq!(program.code, [1002, 4, 3, 4, 99]);
-        assert_eq!(program.output, []);
+        assert_eq!(program.code(), [1002, 4, 3, 4, 99]);
+        assert_eq!(program.output(), []);
 
         // Input / Output
-        let mut program = Program::new(&[3, 0, 4, 0, 99], &[1]);
+        let mut program = Program::new(&[3, 0, 4, 0, 99]).with_input([1]);
         program.run();
-        assert_eq!(program.code, [1, 0, 4, 0, 99]);
-        assert_eq!(program.output, [1]);
+        assert_eq!(program.code(), [1, 0, 4, 0, 99]);
+        assert_eq!(program.output(), [1]);
 
         // Negative
-        let mut program = Program::new(&[1101, 100, -1, 4, 0], &[]);
+        let mut program = Program::new(&[1101, 100, -1, 4, 0]);
         program.run();
-        assert_eq!(program.code, [1101, 100, -1, 4, 99]);
-        assert_eq!(program.output, []);
+        assert_eq!(program.code(), [1101, 100, -1, 4, 99]);
+        assert_eq!(program.output(), []);
     }
 }
@@ -119,8 +119,7 @@ fn build_graph(input: &str) -> Vec<SpaceObject> {
 
 fn count_orbits(graph: &[SpaceObject]) -> u32 {
     // Get the total number of orbits
-    let total_orbits = graph.iter().map(|obj| obj.orbit_count).sum();
-    total_orbits
+    graph.iter().map(|obj| obj.orbit_count).sum()
 }
 
 #[aoc(day6, part1)]
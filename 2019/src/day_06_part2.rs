@@ -114,11 +114,10 @@ fn build_graph(input: &str) -> Vec<SpaceObject> {
 }
 
 fn find_node_in_graph(graph: &[SpaceObject], node_name: &str) -> usize {
-    let node_idx = graph
+    graph
         .iter()
         .position(|obj| obj.name == node_name)
-        .expect("Failed to find node!");
-    node_idx
+        .expect("Failed to find node!")
 }
 
 fn get_path_to_root(graph: &[SpaceObject], from_name: &str) -> Vec<SpaceObject> {
@@ -34,244 +34,25 @@
     Try every combination of the new phase settings on the amplifier feedback loop. What is the highest signal that can be sent to the thrusters?
 */
 
+use common::intcode::Program;
 use std::cmp;
 
-struct Program {
-    code: Vec<i32>,
-    pc: u32,
-    running: bool, // Should run or pause
-    halted: bool,  // Hit a halt instruction; completely done.
-    input: Vec<i32>,
-    input_idx: u32,
-    output: Vec<i32>,
-}
-
-impl Program {
-    fn new(code: &[i32], input: &[i32]) -> Program {
-        Program {
-            code: code.to_vec(),
-            pc: 0,
-            running: false,
-            halted: false,
-            input: input.to_vec(),
-            input_idx: 0,
-            output: Vec::new(),
-        }
-    }
-
-    fn run(&mut self) {
-        self.running = true;
-        while self.running == true {
-            // println!("Program: {:?}", program);
-            // println!("PC: {}", pc);
-            // println!();
-
-            let opcode = self.get_opcode_curr();
-            match opcode {
-                1 => self.opcode_add(),
-                2 => self.opcode_mul(),
-                3 => self.opcode_in(),
-                4 => self.opcode_out(),
-                5 => self.opcode_jmp(),
-                6 => self.opcode_jmpn(),
-                7 => self.opcode_lt(),
-                8 => self.opcode_eq(),
-                99 => self.opcode_halt(),
-                _ => panic!("Invalid opcode"),
-            }
-        }
-    }
-
-    fn get_opcode_curr(&self) -> i32 {
-        Program::get_opcode(self.code[self.pc as usize])
-    }
-
-    fn get_opcode(code_word: i32) -> i32 {
-        code_word % 100
-    }
-
-    fn get_mode_curr(&self, param_idx: u32) -> i32 {
-        Program::get_mode(self.code[self.pc as usize], param_idx)
-    }
-
-    fn get_mode(code_word: i32, digit: u32) -> i32 {
-        let modes = code_word / 100;
-        (modes % 10i32.pow(digit)) / 10i32.pow(digit - 1)
-    }
-
-    fn get_param_addr(&self, param_idx: u32) -> u32 {
-        let mode = self.get_mode_curr(param_idx);
-        match mode {
-            0 => self.get_value(self.pc + param_idx) as u32,
-            1 => self.pc + param_idx,
-            _ => panic!(),
-        }
-    }
-
-    fn get_value(&self, addr: u32) -> i32 {
-        self.code[addr as usize]
-    }
-
-    fn set_value(&mut self, addr: u32, value: i32) {
-        self.code[addr as usize] = value;
-    }
-
-    // 1 + 2 => 3
-    fn opcode_add(&mut self) {
-        let param1_addr = self.get_param_addr(1);
-        let param2_addr = self.get_param_addr(2);
-        let param3_addr = self.get_param_addr(3); // Note: this is output so must ALWAYS be positional
-        self.pc += 4;
-
-        let param1 = self.get_value(param1_addr);
-        let param2 = self.get_value(param2_addr);
-
-        // println!("Add {} + {} => [{}]", param1, param2, param3_addr);
-
-        self.set_value(param3_addr, param1 + param2);
-    }
-
-    // 1 * 2 => 3
-    fn opcode_mul(&mut self) {
-        let param1_addr = self.get_param_addr(1);
-        let param2_addr = self.get_param_addr(2);
-        let param3_addr = self.get_param_addr(3); // Note: this is output so must ALWAYS be positional
-        self.pc += 4;
-
-        let param1 = self.get_value(param1_addr);
-        let param2 = self.get_value(param2_addr);
-
-        // println!("Mul {} * {} => [{}]", param1, param2, param3_addr);
-
-        self.set_value(param3_addr, param1 * param2);
-    }
-
-    // Get input and store in target
-    fn opcode_in(&mut self) {
-        let param1_addr = self.get_param_addr(1);
-        self.pc += 2;
-
-        let input = self.input[self.input_idx as usize];
-        self.input_idx += 1;
-
-        // println!("In {} => [{}]", input, param1_addr);
-
-        self.set_value(param1_addr, input);
-    }
-
-    // Get 1 and output it to user
-    fn opcode_out(&mut self) {
-        let param1_addr = self.get_param_addr(1);
-        self.pc += 2;
-
-        let param1 = self.get_value(param1_addr);
-
-        self.output.push(param1);
-        self.running = false; // Pause so output can be processed
-
-        // println!("Out {}", param1);
-    }
-
-    // If 1 is non-zero, jump to 2
-    fn opcode_jmp(&mut self) {
-        let param1_addr = self.get_param_addr(1);
-        let param2_addr = self.get_param_addr(2);
-
-        let param1 = self.get_value(param1_addr);
-        let param2 = self.get_value(param2_addr);
-
-        if param1 != 0 {
-            // println!("Jmp {} => PC", param2);
-            self.pc = param2 as u32;
-        } else {
-            // println!("Jmp {}", param1);
-            self.pc += 3
-        }
-    }
-
-    // If 1 is zero, jump to 2
-    fn opcode_jmpn(&mut self) {
-        let param1_addr = self.get_param_addr(1);
-        let param2_addr = self.get_param_addr(2);
-
-        let param1 = self.get_value(param1_addr);
-        let param2 = self.get_value(param2_addr);
-
-        if param1 == 0 {
-            // println!("JmpN {} => PC", param2);
-            self.pc = param2 as u32;
-        } else {
-            // println!("JmpN {}", param1);
-            self.pc += 3
-        }
-    }
-
-    // If 1 < 2, #1 => 3, else #0 => 3
-    fn opcode_lt(&mut self) {
-        let param1_addr = self.get_param_addr(1);
-        let param2_addr = self.get_param_addr(2);
-        let param3_addr = self.get_param_addr(3); // Note: this is output so must ALWAYS be positional
-        self.pc += 4;
-
-        let param1 = self.get_value(param1_addr);
-        let param2 = self.get_value(param2_addr);
-
-        let mut value = 0;
-        if param1 < param2 {
-            value = 1;
-        }
-
-        // println!("LT {} < {}, {} => [{}]", param1, param2, value, param3_addr);
-
-        self.set_value(param3_addr, value);
-    }
-
-    // If 1 == 2, #1 => 3, else #0 => 3
-    fn opcode_eq(&mut self) {
-        let param1_addr = self.get_param_addr(1);
-        let param2_addr = self.get_param_addr(2);
-        let param3_addr = self.get_param_addr(3); // Note: this is output so must ALWAYS be positional
-        self.pc += 4;
-
-        let param1 = self.get_value(param1_addr);
-        let param2 = self.get_value(param2_addr);
-
-        let mut value = 0;
-        if param1 == param2 {
-            value = 1;
-        }
-
-        // println!("EQ {} == {}, {} => [{}]", param1, param2, value, param3_addr);
-
-        self.set_value(param3_addr, value);
-    }
-
-    // Set halted flag and stop running
-    fn opcode_halt(&mut self) {
-        self.running = false;
-        self.halted = true;
-        // Don't increment PC -- this lets us re-run the program where we left off and it will just halt immediately!
-
-        // println!("Program complete!");
-    }
-}
-
-fn check_signal(code: &[i32], phase: &[i32]) -> i32 {
+fn check_signal(code: &[i64], phase: &[i64]) -> i64 {
     let mut amp_programs: Vec<Program> = phase
         .iter()
-        .map(|&i| Program::new(code, &[i])) // Set initial input to phase settings
+        .map(|&i| Program::new(code).with_input([i])) // Set initial input to phase settings
         .collect();
     let mut next_input = Some(0);
     let mut last_output = 0;
-    while amp_programs.last().unwrap().halted == false {
+    while amp_programs.last().unwrap().is_halted() == false {
         for p in &mut amp_programs {
             if let Some(i) = next_input {
-                p.input.push(i);
+                p.push_input(i);
             }
 
             p.run();
 
-            next_input = p.output.pop();
+            next_input = p.take_output().pop();
             if let Some(i) = next_input {
                 last_output = i;
             }
@@ -281,7 +62,7 @@ fn check_signal(code: &[i32], phase: &[i32]) -> i32 {
     last_output
 }
 
-fn generate_permutations(outputs: &mut Vec<Vec<i32>>, sequence: &mut [i32], seq_idx: usize) {
+fn generate_permutations(outputs: &mut Vec<Vec<i64>>, sequence: &mut [i64], seq_idx: usize) {
     if sequence.len() == seq_idx {
         outputs.push(sequence.to_vec());
     } else {
@@ -293,8 +74,8 @@ fn generate_permutations(outputs: &mut Vec<Vec<i32>>, sequence: &mut [i32], seq_
     }
 }
 
-fn max_thruster_signal(code: &[i32], phases: &[i32]) -> u32 {
-    let mut phase_permutations: Vec<Vec<i32>> = Vec::new();
+fn max_thruster_signal(code: &[i64], phases: &[i64]) -> u32 {
+    let mut phase_permutations: Vec<Vec<i64>> = Vec::new();
     let mut phase_options = phases.to_vec();
     generate_permutations(&mut phase_permutations, &mut phase_options, 0);
 
@@ -307,13 +88,9 @@ fn max_thruster_signal(code: &[i32], phases: &[i32]) -> u32 {
 
 #[aoc(day7, part2)]
 pub fn solve(input: &str) -> u32 {
-    let code: Vec<i32> = input
-        .trim()
-        .split(',')
-        .map(|s| s.parse::<i32>().unwrap())
-        .collect();
+    let program = Program::from_string(input);
 
-    let max_signal = max_thruster_signal(&code, &[5, 6, 7, 8, 9]);
+    let max_signal = max_thruster_signal(program.code(), &[5, 6, 7, 8, 9]);
     println!("Max signal: {}", max_signal);
     max_signal
 }
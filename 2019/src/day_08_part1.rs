@@ -90,14 +90,13 @@ impl Image {
 }
 
 fn image_layer_with_fewest_zeros(image: &Image) -> usize {
-    let layer_id = image
+    image
         .layers
         .iter()
         .enumerate()
         .min_by_key(|&(_, layer)| layer.count_digits(0))
         .unwrap()
-        .0;
-    layer_id
+        .0
 }
 
 fn checksum_fewest_zeros(image: &Image, digit1: u32, digit2: u32) -> u32 {
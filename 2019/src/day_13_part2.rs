@@ -12,288 +12,14 @@
     Beat the game by breaking all the blocks. What is your score after the last block is broken?
 */
 
+use common::intcode::{Io, Program};
 use std::cmp::Ordering;
 use std::collections::HashMap;
+
 //use std::io;
 //use std::time;
 //use std::thread;
 
-struct Program {
-    code: Vec<i64>,
-    mem: HashMap<usize, i64>,
-    pc: usize,
-    running: bool, // Should run or pause
-    halted: bool,  // Hit a halt instruction; completely done.
-    relative_base_offset: i64,
-
-    input: Vec<i64>,
-    input_needed: bool,
-
-    output: Vec<i64>,
-}
-
-impl Program {
-    fn new(code: &[i64], input: &[i64]) -> Program {
-        Program {
-            code: code.to_vec(),
-            mem: HashMap::new(),
-            pc: 0,
-            running: false,
-            halted: false,
-            relative_base_offset: 0,
-            input: input.to_vec(),
-            input_needed: false,
-            output: Vec::new(),
-        }
-    }
-
-    fn run(&mut self) {
-        while self.halted == false {
-            self.execute_next_opcode();
-        }
-    }
-
-    fn run_with_pause(&mut self) {
-        self.running = true;
-        while self.running == true {
-            self.execute_next_opcode();
-        }
-    }
-
-    fn execute_next_opcode(&mut self) {
-        // println!("Program: {:?}", program);
-        // println!("PC: {}", pc);
-        // println!();
-
-        let opcode = self.get_opcode_curr();
-        match opcode {
-            1 => self.opcode_add(),
-            2 => self.opcode_mul(),
-            3 => self.opcode_in(),
-            4 => self.opcode_out(),
-            5 => self.opcode_jmp(),
-            6 => self.opcode_jmpn(),
-            7 => self.opcode_lt(),
-            8 => self.opcode_eq(),
-            9 => self.opcode_rel(),
-            99 => self.opcode_halt(),
-            _ => panic!("Invalid opcode"),
-        }
-    }
-
-    fn get_opcode_curr(&self) -> i64 {
-        Program::get_opcode(self.code[self.pc])
-    }
-
-    fn get_opcode(code_word: i64) -> i64 {
-        code_word % 100
-    }
-
-    fn get_mode_curr(&self, param_idx: u32) -> i64 {
-        Program::get_mode(self.code[self.pc], param_idx)
-    }
-
-    fn get_mode(code_word: i64, digit: u32) -> i64 {
-        let modes = code_word / 100;
-        (modes % 10i64.pow(digit)) / 10i64.pow(digit - 1)
-    }
-
-    fn get_param_addr(&self, param_idx: u32) -> usize {
-        let mode = self.get_mode_curr(param_idx);
-        match mode {
-            0 => self.get_value(self.pc + param_idx as usize) as usize,
-            1 => self.pc + param_idx as usize,
-            2 => {
-                (self.relative_base_offset + self.get_value(self.pc + param_idx as usize)) as usize
-            }
-            _ => panic!("Invalid param address mode: {}", mode),
-        }
-    }
-
-    fn get_value(&self, addr: usize) -> i64 {
-        let code_len = self.code.len();
-        let value = match addr {
-            a if a < code_len => self.code[addr],
-            a if a >= code_len => match self.mem.get(&addr) {
-                Some(value) => *value,
-                None => 0i64,
-            },
-            _ => panic!("Invalid address: {}", addr),
-        };
-        value
-    }
-
-    fn set_value(&mut self, addr: usize, value: i64) {
-        let code_len = self.code.len();
-        match addr {
-            a if a < code_len => {
-                self.code[addr] = value;
-            }
-            a if a >= code_len => {
-                self.mem.insert(addr, value);
-            }
-            _ => panic!("Invalid address: {}", addr),
-        }
-    }
-
-    // 1 + 2 => 3
-    fn opcode_add(&mut self) {
-        let param1_addr = self.get_param_addr(1);
-        let param2_addr = self.get_param_addr(2);
-        let param3_addr = self.get_param_addr(3); // Note: this is output so must ALWAYS be positional
-        self.pc += 4;
-
-        let param1 = self.get_value(param1_addr);
-        let param2 = self.get_value(param2_addr);
-
-        // println!("Add {} + {} => [{}]", param1, param2, param3_addr);
-
-        self.set_value(param3_addr, param1 + param2);
-    }
-
-    // 1 * 2 => 3
-    fn opcode_mul(&mut self) {
-        let param1_addr = self.get_param_addr(1);
-        let param2_addr = self.get_param_addr(2);
-        let param3_addr = self.get_param_addr(3); // Note: this is output so must ALWAYS be positional
-        self.pc += 4;
-
-        let param1 = self.get_value(param1_addr);
-        let param2 = self.get_value(param2_addr);
-
-        // println!("Mul {} * {} => [{}]", param1, param2, param3_addr);
-
-        self.set_value(param3_addr, param1 * param2);
-    }
-
-    // Get input and store in target
-    fn opcode_in(&mut self) {
-        let param1_addr = self.get_param_addr(1);
-
-        if self.input.is_empty() == false {
-            let input = self.input.remove(0);
-            self.input_needed = false;
-            self.pc += 2;
-
-            // println!("In {} => [{}]", input, param1_addr);
-
-            self.set_value(param1_addr, input);
-        } else {
-            self.running = false;
-            self.input_needed = true;
-            // Don't increment PC so running again is not an error
-            // println!("Input required!");
-        }
-    }
-
-    // Get 1 and output it to user
-    fn opcode_out(&mut self) {
-        let param1_addr = self.get_param_addr(1);
-        self.pc += 2;
-
-        let param1 = self.get_value(param1_addr);
-
-        self.output.push(param1);
-        self.running = false; // Pause so output can be processed
-
-        // println!("Out {}", param1);
-    }
-
-    // If 1 is non-zero, jump to 2
-    fn opcode_jmp(&mut self) {
-        let param1_addr = self.get_param_addr(1);
-        let param2_addr = self.get_param_addr(2);
-
-        let param1 = self.get_value(param1_addr);
-        let param2 = self.get_value(param2_addr);
-
-        if param1 != 0 {
-            // println!("Jmp {} => PC", param2);
-            self.pc = param2 as usize;
-        } else {
-            // println!("Jmp {}", param1);
-            self.pc += 3;
-        }
-    }
-
-    // If 1 is zero, jump to 2
-    fn opcode_jmpn(&mut self) {
-        let param1_addr = self.get_param_addr(1);
-        let param2_addr = self.get_param_addr(2);
-
-        let param1 = self.get_value(param1_addr);
-        let param2 = self.get_value(param2_addr);
-
-        if param1 == 0 {
-            // println!("JmpN {} => PC", param2);
-            self.pc = param2 as usize;
-        } else {
-            // println!("JmpN {}", param1);
-            self.pc += 3;
-        }
-    }
-
-    // If 1 < 2, #1 => 3, else #0 => 3
-    fn opcode_lt(&mut self) {
-        let param1_addr = self.get_param_addr(1);
-        let param2_addr = self.get_param_addr(2);
-        let param3_addr = self.get_param_addr(3); // Note: this is output so must ALWAYS be positional
-        self.pc += 4;
-
-        let param1 = self.get_value(param1_addr);
-        let param2 = self.get_value(param2_addr);
-
-        let mut value = 0;
-        if param1 < param2 {
-            value = 1;
-        }
-
-        // println!("LT {} < {}, {} => [{}]", param1, param2, value, param3_addr);
-
-        self.set_value(param3_addr, value);
-    }
-
-    // If 1 == 2, #1 => 3, else #0 => 3
-    fn opcode_eq(&mut self) {
-        let param1_addr = self.get_param_addr(1);
-        let param2_addr = self.get_param_addr(2);
-        let param3_addr = self.get_param_addr(3); // Note: this is output so must ALWAYS be positional
-        self.pc += 4;
-
-        let param1 = self.get_value(param1_addr);
-        let param2 = self.get_value(param2_addr);
-
-        let mut value = 0;
-        if param1 == param2 {
-            value = 1;
-        }
-
-        // println!("EQ {} == {}, {} => [{}]", param1, param2, value, param3_addr);
-
-        self.set_value(param3_addr, value);
-    }
-
-    fn opcode_rel(&mut self) {
-        let param1_addr = self.get_param_addr(1);
-        self.pc += 2;
-
-        let param1 = self.get_value(param1_addr);
-
-        // println!("Rel {}", param1);
-
-        self.relative_base_offset += param1;
-    }
-
-    // Set halted flag and stop running
-    fn opcode_halt(&mut self) {
-        self.running = false;
-        self.halted = true;
-        // Don't increment PC -- this lets us re-run the program where we left off and it will just halt immediately!
-
-        // println!("Program complete!");
-    }
-}
-
 #[derive(Clone, Copy, Eq, PartialEq)]
 enum Tile {
     Empty,
@@ -339,6 +65,9 @@ impl Tile {
 struct Game {
     tiles: HashMap<(i64, i64), Tile>,
     score: i64,
+    ball_coord: (i64, i64),
+    paddle_coord: (i64, i64),
+    pending: Vec<i64>, // Output values received until a full tile is available
 }
 
 impl Game {
@@ -346,6 +75,9 @@ impl Game {
         Game {
             tiles: HashMap::new(),
             score: 0,
+            ball_coord: (0, 0),
+            paddle_coord: (0, 0),
+            pending: Vec::new(),
         }
     }
 
@@ -419,75 +151,66 @@ impl Joystick {
     }
 }
 
-fn run_program_with_game(program: &mut Program, game: &mut Game) {
-    let mut ball_coord: (i64, i64) = (0, 0);
-    let mut paddle_coord: (i64, i64) = (0, 0);
-
-    while program.halted == false {
-        program.run_with_pause();
-
-        if program.input_needed == true {
-            // Show game being played
-            /*
-            game.display();
-            thread::sleep(time::Duration::from_millis(50));
-            */
-
-            // Get input from bot
-            let joystick = match paddle_coord.0.cmp(&ball_coord.0) {
-                Ordering::Less => Joystick::Right,
-                Ordering::Greater => Joystick::Left,
-                Ordering::Equal => Joystick::Neutral,
-            };
-
-            /*
-            // Get input from user
-            let mut line = String::new();
-            io::stdin().read_line(&mut line).expect("Failed to read line!");
-            let joystick = match line.trim() {
-                "l" => Joystick::Left,
-                "r" => Joystick::Right,
-                _   => Joystick::Neutral,
-            };
-            */
-
-            program.input.push(joystick.value());
-        } else if program.output.len() >= 3 {
-            let x_value = program.output.remove(0);
-            let y_value = program.output.remove(0);
+impl Io for Game {
+    fn input(&mut self) -> Option<i64> {
+        // Show game being played
+        /*
+        self.display();
+        thread::sleep(time::Duration::from_millis(50));
+        */
+
+        // Get input from bot
+        let joystick = match self.paddle_coord.0.cmp(&self.ball_coord.0) {
+            Ordering::Less => Joystick::Right,
+            Ordering::Greater => Joystick::Left,
+            Ordering::Equal => Joystick::Neutral,
+        };
+
+        /*
+        // Get input from user
+        let mut line = String::new();
+        io::stdin().read_line(&mut line).expect("Failed to read line!");
+        let joystick = match line.trim() {
+            "l" => Joystick::Left,
+            "r" => Joystick::Right,
+            _   => Joystick::Neutral,
+        };
+        */
+
+        Some(joystick.value())
+    }
+
+    fn output(&mut self, value: i64) {
+        self.pending.push(value);
+        if let [x_value, y_value, value] = self.pending[..] {
+            self.pending.clear();
+
             if x_value == -1 && y_value == 0 {
-                let score_value = program.output.remove(0);
-                game.score = score_value;
+                self.score = value;
             } else {
-                let tile_value = program.output.remove(0);
-                let tile = Tile::from_value(tile_value);
-                game.tiles.insert((x_value, y_value), tile);
+                let tile = Tile::from_value(value);
+                self.tiles.insert((x_value, y_value), tile);
 
                 // Update key positions for the bot
                 match tile {
-                    Tile::Ball => ball_coord = (x_value, y_value),
-                    Tile::Paddle => paddle_coord = (x_value, y_value),
+                    Tile::Ball => self.ball_coord = (x_value, y_value),
+                    Tile::Paddle => self.paddle_coord = (x_value, y_value),
                     _ => (),
                 }
             }
 
-            // game.display();
+            // self.display();
         }
     }
 }
 
 #[aoc(day13, part2)]
 pub fn solve(input: &str) -> i64 {
-    let code: Vec<i64> = input
-        .trim()
-        .split(',')
-        .map(|s| s.parse::<i64>().unwrap())
-        .collect();
-    let mut program = Program::new(&code, &[]);
-    program.code[0] = 2; // Play for free
+    let mut program = Program::from_string(input);
+    program.set(0, 2); // Play for free
 
     let mut game = Game::new();
-    run_program_with_game(&mut program, &mut game);
+    program.run_with(&mut game);
 
     println!("Final score: {}", game.score);
     game.score
@@ -496,24 +219,60 @@ pub fn solve(input: &str) -> i64 {
 #[cfg(test)]
 mod test {
     use super::*;
+    use common::intcode::assemble;
 
     #[test]
     fn test_program() {
         let code = [
             109, 1, 204, -1, 1001, 100, 1, 100, 1008, 100, 16, 101, 1006, 101, 0, 99,
         ];
-        let mut program = Program::new(&code, &[]);
+        let mut program = Program::new(&code);
         program.run();
-        assert_eq!(program.output, code);
+        assert_eq!(program.output(), code);
 
         let code = [1102, 34915192, 34915192, 7, 4, 7, 99, 0];
-        let mut program = Program::new(&code, &[]);
+        let mut program = Program::new(&code);
         program.run();
-        assert_eq!(program.output, [1219070632396864]);
+        assert_eq!(program.output(), [1219070632396864]);
 
         let code = [104, 1125899906842624, 99];
-        let mut program = Program::new(&code, &[]);
+        let mut program = Program::new(&code);
         program.run();
-        assert_eq!(program.output, [1125899906842624]);
+        assert_eq!(program.output(), [1125899906842624]);
+    }
+
+    #[test]
+    fn test_game() {
+        // Draws the paddle and a ball moving left, scoring whatever the joystick says each time
+        let code = assemble(
+            "
+                    REL #100
+            .var stick 0
+            .var done 1
+                    OUT #1
+                    OUT #2
+                    OUT #3
+            loop:   OUT [ball]
+                    OUT #1
+                    OUT #4
+                    IN -> [r+stick]
+                    OUT #-1
+                    OUT #0
+                    OUT [r+stick]
+                    ADD [ball], #-3 -> [ball]
+                    LT [ball], #0 -> [r+done]
+                    JZ [r+done], loop
+                    HALT
+            ball:   .data 4
+            ",
+        );
+        let mut program = Program::new(&code);
+        let mut game = Game::new();
+        program.run_with(&mut game);
+
+        // Ball started to the right of the paddle, then moved directly above it
+        assert_eq!(game.paddle_coord, (1, 2));
+        assert_eq!(game.ball_coord, (1, 1));
+        assert_eq!(game.score, Joystick::Neutral.value());
     }
 }
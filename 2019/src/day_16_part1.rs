@@ -74,7 +74,7 @@ use std::iter;
 fn gen_base_pattern(element: i32) -> Vec<i32> {
     let mut pattern: Vec<i32> = [0, 1, 0, -1]
         .iter()
-        .flat_map(|&i| iter::repeat(i).take((element + 1) as usize)) // If user specifies element 0 then want a pattern with 1 of each value
+        .flat_map(|&i| iter::repeat_n(i, (element + 1) as usize)) // If user specifies element 0 then want a pattern with 1 of each value
         .collect();
     pattern.rotate_left(1);
     pattern
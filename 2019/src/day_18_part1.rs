@@ -92,60 +92,10 @@
     How many steps is the shortest path that collects all of the keys?
 */
 
+use common::graph::Graph;
+use common::search;
+use common::Point2;
 use std::collections::HashMap;
-use std::collections::HashSet;
-use std::fmt;
-
-#[derive(Clone, Copy, Debug, PartialEq)]
-enum Cardinal {
-    North,
-    South,
-    West,
-    East,
-}
-
-impl Cardinal {
-    fn step_from(&self, coord: Point) -> Point {
-        let delta = match *self {
-            Self::North => (0, -1),
-            Self::South => (0, 1),
-            Self::West => (-1, 0),
-            Self::East => (1, 0),
-        };
-
-        Point {
-            x: coord.x + delta.0,
-            y: coord.y + delta.1,
-        }
-    }
-
-    fn opposite(&self) -> Self {
-        match *self {
-            Self::North => Self::South,
-            Self::South => Self::North,
-            Self::West => Self::East,
-            Self::East => Self::West,
-        }
-    }
-}
-
-impl fmt::Display for Cardinal {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let disp_str = match *self {
-            Self::North => "North",
-            Self::South => "South",
-            Self::West => "West",
-            Self::East => "East",
-        };
-        write!(f, "{}", disp_str)
-    }
-}
-
-#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
-struct Point {
-    x: i32,
-    y: i32,
-}
 
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
 enum Space {
@@ -183,14 +133,14 @@ impl Space {
 
 #[derive(Clone)]
 struct Map {
-    area: HashMap<Point, Space>,
+    area: HashMap<Point2, Space>,
 }
 
 impl Map {
     fn from_string(input: &str) -> Map {
         let mut area = HashMap::new();
 
-        let mut p = Point { x: 0, y: 0 };
+        let mut p = Point2 { x: 0, y: 0 };
         for line in input.lines() {
             for c in line.chars() {
                 let space = Space::from_value(c);
@@ -230,7 +180,7 @@ impl Map {
 
         for y in y_range.0..=y_range.1 {
             for x in x_range.0..=x_range.1 {
-                if let Some(t) = self.area.get(&Point { x, y }) {
+                if let Some(t) = self.area.get(&Point2 { x, y }) {
                     print!("{}", t.char());
                 } else {
                     print!(" ");
@@ -242,7 +192,7 @@ impl Map {
         println!();
     }
 
-    fn get_entrance(&self) -> Point {
+    fn get_entrance(&self) -> Point2 {
         for (&k, &v) in self.area.iter() {
             if let Space::Entrance = v {
                 return k;
@@ -252,280 +202,57 @@ impl Map {
         panic!("Could not find entrance");
     }
 
-    fn find_keys(&self, start_node: &Node) -> Vec<(Node, u32)> {
-        let mut frontier: Vec<Point> = Vec::new();
-        let mut walked: HashSet<Point> = HashSet::new();
-
-        frontier.push(start_node.point);
-        walked.insert(start_node.point);
-
-        let mut nodes: Vec<(Node, u32)> = Vec::new();
-        let mut counter = 0;
-        loop {
-            counter += 1;
-            for location in std::mem::take(&mut frontier) {
-                let candidates = [
-                    Cardinal::North,
-                    Cardinal::South,
-                    Cardinal::West,
-                    Cardinal::East,
-                ];
-                for direction in candidates.iter() {
-                    let step_in_direction = direction.step_from(location);
-                    //println!("Step: {:?}", step_in_direction);
-                    if !walked.contains(&step_in_direction) {
-                        match self.area.get(&step_in_direction) {
-                            Some(Space::Empty) | Some(Space::Entrance) => {
-                                frontier.push(step_in_direction);
-                                walked.insert(step_in_direction);
-                            }
-                            Some(Space::Key(c)) => {
-                                if start_node.has_key(*c) == false {
-                                    //println!("Key: {}", c);
-                                    let mut node = Node {
-                                        point: step_in_direction,
-                                        space: Space::Key(*c),
-                                        inventory: start_node.inventory,
-                                    };
-                                    node.set_key(*c, true);
-                                    nodes.push((node, counter));
-                                } else {
-                                    frontier.push(step_in_direction);
-                                    walked.insert(step_in_direction);
-                                }
-                            }
-                            Some(Space::Door(c)) => {
-                                if start_node.has_key(*c) == true {
-                                    //println!("Door: {}", c);
-                                    frontier.push(step_in_direction);
-                                    walked.insert(step_in_direction);
-                                }
-                            }
-                            _ => (),
-                        }
-                    }
+    // Only the entrance and keys matter, joined by the doors which need unlocking to get between them
+    fn build_graph(&self) -> Graph<Point2, u32> {
+        Graph::from_tiles_annotated(
+            &self.area,
+            |_, space| *space != Space::Wall,
+            |_, space| matches!(space, Space::Entrance | Space::Key(_)),
+            |doors, _, space| {
+                if let Space::Door(c) = space {
+                    *doors |= key_bit(*c);
                 }
-            }
-
-            //println!("frontier: {:?}", frontier);
-            if frontier.is_empty() == true {
-                break;
-            }
-        }
-
-        nodes
+            },
+        )
     }
 
-    fn build_graph(&self) -> Graph {
-        let entrance = self.get_entrance();
-        //println!("Entrance: {:?}", entrance);
-
-        let starting_vertex = Vertex {
-            id: 0,
-            node: Node {
-                point: entrance,
-                space: Space::Entrance,
-                inventory: 0,
-            },
-            distance: 0,
-            pi: None,
-            connected: Vec::new(),
+    // Returns the distance and the order the keys are collected in
+    fn get_shortest_path(&self) -> (u32, Vec<Space>) {
+        let graph = self.build_graph();
+        let entrance = graph.index_of(&self.get_entrance()).unwrap();
+        let key_at = |idx: usize| match self.area[graph.node(idx)] {
+            Space::Key(c) => key_bit(c),
+            _ => 0,
         };
-
-        let mut node_to_vertex_map: HashMap<Node, usize> = HashMap::new();
-        node_to_vertex_map.insert(starting_vertex.node, starting_vertex.id);
-
-        let mut graph = Graph {
-            vertices: Vec::new(),
+        let all_keys = (0..graph.len()).fold(0, |acc, idx| acc | key_at(idx));
+
+        // Search over where we are and which keys are held
+        let neighbors = |&(idx, inventory): &(usize, u32)| {
+            graph
+                .edges(idx)
+                .iter()
+                .filter(|edge| edge.annotation & !inventory == 0)
+                .map(|edge| ((edge.to, inventory | key_at(edge.to)), edge.dist))
+                .collect::<Vec<_>>()
         };
-        graph.vertices.push(starting_vertex);
-
-        let mut frontier: Vec<usize> = vec![0]; // Visit the starting vertex first
-
-        loop {
-            for frontier_id in std::mem::take(&mut frontier) {
-                let connected_nodes = self.find_keys(&graph.vertices[frontier_id].node);
-                /*println!("Nodes:");
-                for (node, distance) in &connected_nodes {
-                    println!("   {:?} @ {}", node, distance);
-                }*/
-
-                // Check if any of these connected nodes are new. If they are, create them as vertices in the graph.
-                for (node, distance) in connected_nodes {
-                    let id;
-                    if let Some(&idx) = node_to_vertex_map.get(&node) {
-                        // This one exists. Save its ID.
-                        id = idx;
-                    } else {
-                        // This one doesn't exist. Create it and save its ID.
-                        id = graph.vertices.len();
-                        node_to_vertex_map.insert(node, id);
-
-                        let v = Vertex {
-                            id,
-                            node,
-                            distance: u32::MAX,
-                            pi: Some(graph.vertices[frontier_id].id),
-                            connected: Vec::new(),
-                        };
-                        graph.vertices.push(v);
-                        frontier.push(id);
-                    }
-
-                    // Create an edge based on the saved ID and add it to the current node in the graph.
-                    let edge = Edge {
-                        vertex_id: id,
-                        distance,
-                    };
-                    graph.vertices[frontier_id].connected.push(edge);
-                }
+        let result = search::dijkstra([(entrance, 0)], neighbors, |&(_, inventory)| {
+            inventory == all_keys
+        });
+
+        // Skip the steps which only pass back over keys already held
+        let mut path = vec![Space::Entrance];
+        for step in result.goal_path().unwrap().windows(2) {
+            if step[0].1 != step[1].1 {
+                path.push(self.area[graph.node(step[1].0)]);
             }
-
-            //println!("Graph frontier: {:?}", frontier);
-            if frontier.is_empty() == true {
-                break;
-            }
-        }
-
-        //println!("Graph: {:#?}", graph);
-        graph
-    }
-}
-
-#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
-struct Node {
-    point: Point,
-    space: Space,
-    inventory: u32,
-}
-
-impl Node {
-    fn idx_from_char(c: char) -> usize {
-        if c.is_ascii_lowercase() == false {
-            panic!("Invalid character!");
         }
-
-        let idx = (c as u8) - b'a';
-        idx as usize
-    }
-
-    fn has_key(&self, c: char) -> bool {
-        let idx = Node::idx_from_char(c);
-        let key_bit = self.inventory & (1 << idx);
-        key_bit != 0
-    }
-
-    fn set_key(&mut self, c: char, value: bool) {
-        let idx = Node::idx_from_char(c);
-        let key_bit = if value == true { 1 } else { 0 };
-        self.inventory |= key_bit << idx;
+        (result.goal_distance().unwrap(), path)
     }
 }
 
-#[derive(Debug)]
-struct Edge {
-    vertex_id: usize,
-    distance: u32,
-}
-
-#[derive(Debug)]
-struct Vertex {
-    id: usize, // Not technically needed but useful for debug
-    node: Node,
-    distance: u32, // This is used purely for Dijkstra's, where u32::max_value() is used to indicate infinity distance
-    pi: Option<usize>, // The "parent" or the vertex from which the fastest path comes
-    connected: Vec<Edge>,
-}
-
-struct Graph {
-    vertices: Vec<Vertex>,
-}
-
-impl Graph {
-    fn dijkstra(&mut self, start_vertex: usize) {
-        // Set initial conditions:
-        //  - set all distances to infinity except the starting vertex which is set to 0
-        //  - set the starting vertex as the first vertex to process
-        for vertex in self.vertices.iter_mut() {
-            vertex.distance = u32::MAX;
-        }
-        self.vertices[start_vertex].distance = 0;
-
-        let mut visited: HashSet<usize> = HashSet::new();
-        let mut next: HashSet<usize> = HashSet::new();
-        next.insert(start_vertex);
-
-        while next.is_empty() == false {
-            // Find the next vertex with minimum distance. Mark it as visited
-            // (we're visiting it now) and remove it from the 'next' list.
-            let mut min_id = 0;
-            let mut min_value = u32::MAX;
-            for &vertex_id in next.iter() {
-                if self.vertices[vertex_id].distance < min_value {
-                    min_value = self.vertices[vertex_id].distance;
-                    min_id = vertex_id;
-                }
-            }
-            let current_vertex_id = min_id;
-            visited.insert(current_vertex_id);
-            next.remove(&min_id);
-            //println!("Dijkstra visit vertex #{}", current_vertex_id);
-
-            // Update distances of all adjacent vertices and add them to the 'next' list.
-            // Skip any vertices we've already visited.
-            let mut temp: Vec<(usize, u32, Option<usize>)> = Vec::new();
-            for e in self.vertices[current_vertex_id].connected.iter() {
-                if visited.contains(&e.vertex_id) {
-                    continue;
-                }
-
-                let new_distance = self.vertices[current_vertex_id].distance + e.distance;
-                let old_distance = self.vertices[e.vertex_id].distance;
-                if new_distance < old_distance {
-                    temp.push((e.vertex_id, new_distance, Some(current_vertex_id)));
-                }
-
-                next.insert(e.vertex_id);
-            }
-
-            for (vertex_id, distance, pi) in temp {
-                self.vertices[vertex_id].distance = distance;
-                self.vertices[vertex_id].pi = pi;
-            }
-        }
-    }
-
-    fn get_shortest_path(&self) -> (u32, Vec<Space>) {
-        let mut longest_inventory = 0;
-        let mut shortest_distance = u32::MAX;
-        let mut shortest_end_vertex_id = 0;
-        for vertex in self.vertices.iter() {
-            let current_inventory = vertex.node.inventory;
-            if current_inventory > longest_inventory {
-                longest_inventory = current_inventory;
-                shortest_distance = u32::MAX;
-                shortest_end_vertex_id = 0;
-            }
-
-            if vertex.distance < shortest_distance {
-                shortest_distance = vertex.distance;
-                shortest_end_vertex_id = vertex.id;
-            }
-        }
-
-        //println!("End vertex: {:?}", graph[shortest_end_vertex_id]);
-
-        let mut path: Vec<Space> = Vec::new();
-        let mut current_vertex_id = shortest_end_vertex_id;
-        path.push(self.vertices[current_vertex_id].node.space);
-        while let Some(parent_id) = self.vertices[current_vertex_id].pi {
-            current_vertex_id = parent_id;
-            path.push(self.vertices[current_vertex_id].node.space);
-        }
-        path.reverse();
-
-        (self.vertices[shortest_end_vertex_id].distance, path)
-    }
+fn key_bit(c: char) -> u32 {
+    assert!(c.is_ascii_lowercase(), "Invalid character!");
+    1 << (c as u8 - b'a')
 }
 
 #[aoc(day18, part1)]
@@ -533,28 +260,7 @@ pub fn solve(input: &str) -> u32 {
     let map = Map::from_string(input);
     map.display();
 
-    let mut graph = map.build_graph();
-
-    /*println!("Graph:");
-    println!("   {} vertices", graph.vertices.len());
-    for (id, vertex) in graph.vertices.iter().enumerate() {
-        println!("   Vertex {}:", id);
-        println!("      id: {}", vertex.id);
-        println!("      node.point: {:?}", vertex.node.point);
-        println!("      node.space: {:?}", vertex.node.space);
-        println!("      node.inventory: {}", vertex.node.inventory);
-        println!("      distance: {}", vertex.distance);
-        println!("      pi: {:?}", vertex.pi);
-        println!("      edges:");
-        for e in vertex.connected.iter() {
-            println!("         {:?}", e);
-        }
-        println!();
-    }*/
-
-    graph.dijkstra(0);
-
-    let (distance, path) = graph.get_shortest_path();
+    let (distance, path) = map.get_shortest_path();
     println!("Distance: {}", distance);
     println!("Path: {:?}", path);
     distance
@@ -572,9 +278,7 @@ mod test {
 #########
 ";
         let map = Map::from_string(input);
-        let mut graph = map.build_graph();
-        graph.dijkstra(0);
-        let (distance, path) = graph.get_shortest_path();
+        let (distance, path) = map.get_shortest_path();
         assert_eq!(distance, 8);
         assert_eq!(path, [Space::Entrance, Space::Key('a'), Space::Key('b')]);
 
@@ -586,9 +290,7 @@ mod test {
 ########################
 ";
         let map = Map::from_string(input);
-        let mut graph = map.build_graph();
-        graph.dijkstra(0);
-        let (distance, path) = graph.get_shortest_path();
+        let (distance, path) = map.get_shortest_path();
         assert_eq!(distance, 86);
         assert_eq!(
             path,
@@ -611,9 +313,7 @@ mod test {
 ########################
 ";
         let map = Map::from_string(input);
-        let mut graph = map.build_graph();
-        graph.dijkstra(0);
-        let (distance, path) = graph.get_shortest_path();
+        let (distance, path) = map.get_shortest_path();
         assert_eq!(distance, 132);
         assert_eq!(
             path,
@@ -641,9 +341,7 @@ mod test {
 #################
 ";
         let map = Map::from_string(input);
-        let mut graph = map.build_graph();
-        graph.dijkstra(0);
-        let (distance, _path) = graph.get_shortest_path();
+        let (distance, _path) = map.get_shortest_path();
         assert_eq!(distance, 136);
         // There are multiple possible paths, don't verify
 
@@ -656,9 +354,7 @@ mod test {
 ########################
 ";
         let map = Map::from_string(input);
-        let mut graph = map.build_graph();
-        graph.dijkstra(0);
-        let (distance, _path) = graph.get_shortest_path();
+        let (distance, _path) = map.get_shortest_path();
         assert_eq!(distance, 81);
         // There are multiple possible paths, don't verify
     }
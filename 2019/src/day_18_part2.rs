@@ -130,61 +130,10 @@
     After updating your map and using the remote-controlled robots, what is the fewest steps necessary to collect all of the keys?
 */
 
-use std::cmp::Ordering;
+use common::graph::Graph;
+use common::search;
+use common::Point2;
 use std::collections::HashMap;
-use std::collections::HashSet;
-use std::fmt;
-
-#[derive(Clone, Copy, Debug, PartialEq)]
-enum Cardinal {
-    North,
-    South,
-    West,
-    East,
-}
-
-impl Cardinal {
-    fn step_from(&self, coord: Point) -> Point {
-        let delta = match *self {
-            Self::North => (0, -1),
-            Self::South => (0, 1),
-            Self::West => (-1, 0),
-            Self::East => (1, 0),
-        };
-
-        Point {
-            x: coord.x + delta.0,
-            y: coord.y + delta.1,
-        }
-    }
-
-    fn opposite(&self) -> Self {
-        match *self {
-            Self::North => Self::South,
-            Self::South => Self::North,
-            Self::West => Self::East,
-            Self::East => Self::West,
-        }
-    }
-}
-
-impl fmt::Display for Cardinal {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let disp_str = match *self {
-            Self::North => "North",
-            Self::South => "South",
-            Self::West => "West",
-            Self::East => "East",
-        };
-        write!(f, "{}", disp_str)
-    }
-}
-
-#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
-struct Point {
-    x: i32,
-    y: i32,
-}
 
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
 enum Space {
@@ -222,14 +171,14 @@ impl Space {
 
 #[derive(Clone)]
 struct Map {
-    area: HashMap<Point, Space>,
+    area: HashMap<Point2, Space>,
 }
 
 impl Map {
     fn from_string(input: &str) -> Map {
         let mut area = HashMap::new();
 
-        let mut p = Point { x: 0, y: 0 };
+        let mut p = Point2 { x: 0, y: 0 };
         for line in input.lines() {
             for c in line.chars() {
                 let space = Space::from_value(c);
@@ -269,7 +218,7 @@ impl Map {
 
         for y in y_range.0..=y_range.1 {
             for x in x_range.0..=x_range.1 {
-                if let Some(t) = self.area.get(&Point { x, y }) {
+                if let Some(t) = self.area.get(&Point2 { x, y }) {
                     print!("{}", t.char());
                 } else {
                     print!(" ");
@@ -281,7 +230,7 @@ impl Map {
         println!();
     }
 
-    fn get_entrance(&self) -> Point {
+    fn get_entrance(&self) -> Point2 {
         for (&k, &v) in self.area.iter() {
             if let Space::Entrance = v {
                 return k;
@@ -291,7 +240,7 @@ impl Map {
         panic!("Could not find entrance");
     }
 
-    fn get_entrances(&self) -> Vec<Point> {
+    fn get_entrances(&self) -> Vec<Point2> {
         let mut entrances = Vec::new();
         for (&k, &v) in self.area.iter() {
             if v == Space::Entrance {
@@ -301,17 +250,11 @@ impl Map {
 
         // Put the list of entrances in order from top to bottom, left to right.
         // This makes the path results predictable and testable but is not strictly required.
-        entrances.sort_by(|a, b| {
-            let primary = a.y.cmp(&b.y);
-            match primary {
-                Ordering::Equal => a.x.cmp(&b.x),
-                _ => primary,
-            }
-        });
+        entrances.sort_by(Point2::cmp_yx);
         entrances
     }
 
-    fn split_entrance(&mut self, entrance: &Point) {
+    fn split_entrance(&mut self, entrance: &Point2) {
         let offsets = vec![
             ((0, 0), Space::Wall),
             ((-1, -1), Space::Entrance),
@@ -325,7 +268,7 @@ impl Map {
         ];
 
         for ((x, y), s) in offsets {
-            let p = Point {
+            let p = Point2 {
                 x: entrance.x + x,
                 y: entrance.y + y,
             };
@@ -333,304 +276,73 @@ impl Map {
         }
     }
 
-    fn find_keys(&self, start_node: &Node) -> Vec<(Node, u32)> {
-        let mut frontier: Vec<Point> = Vec::new();
-        let mut walked: HashSet<Point> = HashSet::new();
-
-        frontier.push(start_node.point);
-        walked.insert(start_node.point);
-
-        let mut nodes: Vec<(Node, u32)> = Vec::new();
-        let mut counter = 0;
-        loop {
-            counter += 1;
-            for location in std::mem::take(&mut frontier) {
-                let candidates = [
-                    Cardinal::North,
-                    Cardinal::South,
-                    Cardinal::West,
-                    Cardinal::East,
-                ];
-                for direction in candidates.iter() {
-                    let step_in_direction = direction.step_from(location);
-                    //println!("Step: {:?}", step_in_direction);
-                    if !walked.contains(&step_in_direction) {
-                        match self.area.get(&step_in_direction) {
-                            Some(Space::Empty) | Some(Space::Entrance) => {
-                                frontier.push(step_in_direction);
-                                walked.insert(step_in_direction);
-                            }
-                            Some(Space::Key(c)) => {
-                                if start_node.has_key(*c) == false {
-                                    //println!("Key: {}", c);
-                                    let mut node = Node {
-                                        point: step_in_direction,
-                                        space: Space::Key(*c),
-                                        inventory: start_node.inventory,
-                                    };
-                                    node.set_key(*c, true);
-                                    nodes.push((node, counter));
-                                } else {
-                                    frontier.push(step_in_direction);
-                                    walked.insert(step_in_direction);
-                                }
-                            }
-                            Some(Space::Door(c)) => {
-                                if start_node.has_key(*c) == true {
-                                    //println!("Door: {}", c);
-                                    frontier.push(step_in_direction);
-                                    walked.insert(step_in_direction);
-                                }
-                            }
-                            _ => (),
-                        }
-                    }
+    // Only the entrances and keys matter, joined by the doors which need unlocking to get between them
+    fn build_graph(&self) -> Graph<Point2, u32> {
+        Graph::from_tiles_annotated(
+            &self.area,
+            |_, space| *space != Space::Wall,
+            |_, space| matches!(space, Space::Entrance | Space::Key(_)),
+            |doors, _, space| {
+                if let Space::Door(c) = space {
+                    *doors |= key_bit(*c);
                 }
-            }
-
-            //println!("frontier: {:?}", frontier);
-            if frontier.is_empty() == true {
-                break;
-            }
-        }
-
-        nodes
+            },
+        )
     }
 
-    fn build_graph(&self) -> Graph {
-        // Attach all other entrances to the starting vertex
-        let entrances = self.get_entrances();
-        let mut starting_vertex = Vertex {
-            id: 0,
-            nodes: Vec::new(),
-            distance: 0,
-            pi: None,
-            connected: Vec::new(),
-        };
-        for e in entrances {
-            let n = Node {
-                point: e,
-                space: Space::Entrance,
-                inventory: 0,
-            };
-            starting_vertex.nodes.push(n);
-        }
-
-        let mut node_to_vertex_map: HashMap<Vec<Node>, usize> = HashMap::new();
-        node_to_vertex_map.insert(starting_vertex.nodes.clone(), starting_vertex.id);
-
-        let mut graph = Graph {
-            vertices: Vec::new(),
+    // Returns the distance and which key each robot is at every time a key is collected
+    fn get_shortest_path(&self) -> (u32, Vec<Vec<Space>>) {
+        let graph = self.build_graph();
+        let robots: Vec<usize> = self
+            .get_entrances()
+            .iter()
+            .map(|entrance| graph.index_of(entrance).unwrap())
+            .collect();
+        let key_at = |idx: usize| match self.area[graph.node(idx)] {
+            Space::Key(c) => key_bit(c),
+            _ => 0,
         };
-        graph.vertices.push(starting_vertex);
-
-        let mut frontier: Vec<usize> = vec![0]; // Visit the starting vertex first
-
-        loop {
-            for frontier_id in std::mem::take(&mut frontier) {
-                for node_idx in 0..graph.vertices[frontier_id].nodes.len() {
-                    let connected_nodes =
-                        self.find_keys(&graph.vertices[frontier_id].nodes[node_idx]);
-                    /*println!("Nodes:");
-                    for (node, distance) in &connected_nodes {
-                        println!("   {:?} @ {}", node, distance);
-                    }*/
-
-                    // Check if any of these connected nodes are new. If they are, create them as vertices in the graph.
-                    for (node, distance) in connected_nodes {
-                        // Create a new nodes array. The current node is replaced, and also every other node
-                        // must have its inventory updated (all inventories are just copies of each other for laziness reasons).
-                        let mut nodes = graph.vertices[frontier_id].nodes.clone();
-                        nodes[node_idx] = node;
-                        for n in &mut nodes {
-                            n.inventory = node.inventory;
-                        }
-
-                        let id;
-                        if let Some(&idx) = node_to_vertex_map.get(&nodes) {
-                            // This one exists. Save its ID.
-                            id = idx;
-                        } else {
-                            // This one doesn't exist. Create it and save its ID.
-                            id = graph.vertices.len();
-                            node_to_vertex_map.insert(nodes.clone(), id);
-
-                            let v = Vertex {
-                                id,
-                                nodes,
-                                distance: u32::MAX,
-                                pi: Some(graph.vertices[frontier_id].id),
-                                connected: Vec::new(),
-                            };
-                            graph.vertices.push(v);
-                            frontier.push(id);
-                        }
-
-                        // Create an edge based on the saved ID and add it to the current node in the graph.
-                        let edge = Edge {
-                            vertex_id: id,
-                            distance,
-                        };
-                        graph.vertices[frontier_id].connected.push(edge);
+        let all_keys = (0..graph.len()).fold(0, |acc, idx| acc | key_at(idx));
+
+        // Search over where every robot is and which keys are held. Only one robot moves at a time.
+        let neighbors = |(robots, inventory): &(Vec<usize>, u32)| {
+            let mut next = Vec::new();
+            for (i, &idx) in robots.iter().enumerate() {
+                for edge in graph.edges(idx) {
+                    if edge.annotation & !inventory == 0 {
+                        let mut next_robots = robots.clone();
+                        next_robots[i] = edge.to;
+                        next.push(((next_robots, inventory | key_at(edge.to)), edge.dist));
                     }
                 }
             }
+            next
+        };
+        let result = search::dijkstra([(robots, 0)], neighbors, |(_, inventory)| {
+            *inventory == all_keys
+        });
 
-            //println!("Graph frontier: {:?}", frontier);
-            if frontier.is_empty() == true {
-                break;
+        // Skip the steps which only pass back over keys already held, so that each robot is shown at the last key it
+        // collected
+        let states = result.goal_path().unwrap();
+        let mut current = vec![Space::Entrance; states[0].0.len()];
+        let mut path = vec![current.clone()];
+        for step in states.windows(2) {
+            if step[0].1 != step[1].1 {
+                let moved = (0..current.len())
+                    .find(|&i| step[0].0[i] != step[1].0[i])
+                    .unwrap();
+                current[moved] = self.area[graph.node(step[1].0[moved])];
+                path.push(current.clone());
             }
         }
-
-        //println!("Graph: {:#?}", graph);
-        graph
-    }
-}
-
-#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
-struct Node {
-    point: Point,
-    space: Space,
-    inventory: u32,
-}
-
-impl Node {
-    fn idx_from_char(c: char) -> usize {
-        if c.is_ascii_lowercase() == false {
-            panic!("Invalid character!");
-        }
-
-        let idx = (c as u8) - b'a';
-        idx as usize
+        (result.goal_distance().unwrap(), path)
     }
-
-    fn has_key(&self, c: char) -> bool {
-        let idx = Node::idx_from_char(c);
-        let key_bit = self.inventory & (1 << idx);
-        key_bit != 0
-    }
-
-    fn set_key(&mut self, c: char, value: bool) {
-        let idx = Node::idx_from_char(c);
-        let key_bit = if value == true { 1 } else { 0 };
-        self.inventory |= key_bit << idx;
-    }
-}
-
-#[derive(Debug)]
-struct Edge {
-    vertex_id: usize,
-    distance: u32,
-}
-
-#[derive(Debug)]
-struct Vertex {
-    id: usize, // Not technically needed but useful for debug
-    nodes: Vec<Node>,
-    distance: u32, // This is used purely for Dijkstra's, where u32::max_value() is used to indicate infinity distance
-    pi: Option<usize>, // The "parent" or the vertex from which the fastest path comes
-    connected: Vec<Edge>,
 }
 
-struct Graph {
-    vertices: Vec<Vertex>,
-}
-
-impl Graph {
-    fn dijkstra(&mut self, start_vertex: usize) {
-        // Set initial conditions:
-        //  - set all distances to infinity except the starting vertex which is set to 0
-        //  - set the starting vertex as the first vertex to process
-        for vertex in self.vertices.iter_mut() {
-            vertex.distance = u32::MAX;
-        }
-        self.vertices[start_vertex].distance = 0;
-
-        let mut visited: HashSet<usize> = HashSet::new();
-        let mut next: HashSet<usize> = HashSet::new();
-        next.insert(start_vertex);
-
-        while next.is_empty() == false {
-            // Find the next vertex with minimum distance. Mark it as visited
-            // (we're visiting it now) and remove it from the 'next' list.
-            let mut min_id = 0;
-            let mut min_value = u32::MAX;
-            for &vertex_id in next.iter() {
-                if self.vertices[vertex_id].distance < min_value {
-                    min_value = self.vertices[vertex_id].distance;
-                    min_id = vertex_id;
-                }
-            }
-            let current_vertex_id = min_id;
-            visited.insert(current_vertex_id);
-            next.remove(&min_id);
-            //println!("Dijkstra visit vertex #{}", current_vertex_id);
-
-            // Update distances of all adjacent vertices and add them to the 'next' list.
-            // Skip any vertices we've already visited.
-            let mut temp: Vec<(usize, u32, Option<usize>)> = Vec::new();
-            for e in self.vertices[current_vertex_id].connected.iter() {
-                if visited.contains(&e.vertex_id) {
-                    continue;
-                }
-
-                let new_distance = self.vertices[current_vertex_id].distance + e.distance;
-                let old_distance = self.vertices[e.vertex_id].distance;
-                if new_distance < old_distance {
-                    temp.push((e.vertex_id, new_distance, Some(current_vertex_id)));
-                }
-
-                next.insert(e.vertex_id);
-            }
-
-            for (vertex_id, distance, pi) in temp {
-                self.vertices[vertex_id].distance = distance;
-                self.vertices[vertex_id].pi = pi;
-            }
-        }
-    }
-
-    fn get_shortest_path(&self) -> (u32, Vec<Vec<Space>>) {
-        let mut longest_inventory = 0;
-        let mut shortest_distance = u32::MAX;
-        let mut shortest_end_vertex_id = 0;
-        for vertex in self.vertices.iter() {
-            let current_inventory = vertex.nodes[0].inventory; // All inventories are guaranteed to be the same; just use the first one.
-            if current_inventory > longest_inventory {
-                longest_inventory = current_inventory;
-                shortest_distance = u32::MAX;
-                shortest_end_vertex_id = 0;
-            }
-
-            if vertex.distance < shortest_distance {
-                shortest_distance = vertex.distance;
-                shortest_end_vertex_id = vertex.id;
-            }
-        }
-
-        //println!("End vertex: {:?}", graph[shortest_end_vertex_id]);
-
-        let mut path: Vec<Vec<Space>> = Vec::new();
-        let mut current_vertex_id = shortest_end_vertex_id;
-        let spaces: Vec<Space> = self.vertices[current_vertex_id]
-            .nodes
-            .iter()
-            .map(|n| n.space)
-            .collect();
-        path.push(spaces);
-        while let Some(parent_id) = self.vertices[current_vertex_id].pi {
-            current_vertex_id = parent_id;
-            let spaces: Vec<Space> = self.vertices[current_vertex_id]
-                .nodes
-                .iter()
-                .map(|n| n.space)
-                .collect();
-            path.push(spaces);
-        }
-        path.reverse();
-
-        (self.vertices[shortest_end_vertex_id].distance, path)
-    }
+fn key_bit(c: char) -> u32 {
+    assert!(c.is_ascii_lowercase(), "Invalid character!");
+    1 << (c as u8 - b'a')
 }
 
 #[aoc(day18, part2)]
@@ -641,28 +353,7 @@ pub fn solve(input: &str) -> u32 {
     map.split_entrance(&entrance);
     map.display();
 
-    let mut graph = map.build_graph();
-
-    /*println!("Graph:");
-    println!("   {} vertices", graph.vertices.len());
-    for (id, vertex) in graph.vertices.iter().enumerate() {
-        println!("   Vertex {}:", id);
-        println!("      id: {}", vertex.id);
-        println!("      node.point: {:?}", vertex.node.point);
-        println!("      node.space: {:?}", vertex.node.space);
-        println!("      node.inventory: {}", vertex.node.inventory);
-        println!("      distance: {}", vertex.distance);
-        println!("      pi: {:?}", vertex.pi);
-        println!("      edges:");
-        for e in vertex.connected.iter() {
-            println!("         {:?}", e);
-        }
-        println!();
-    }*/
-
-    graph.dijkstra(0);
-
-    let (distance, path) = graph.get_shortest_path();
+    let (distance, path) = map.get_shortest_path();
     println!("Distance: {}", distance);
     print!("Path: {:?}", path[0]);
     for path_idx in 0..path.len() {
@@ -698,9 +389,7 @@ mod test {
         let mut map = Map::from_string(input);
         let entrance = map.get_entrance();
         map.split_entrance(&entrance);
-        let mut graph = map.build_graph();
-        graph.dijkstra(0);
-        let (distance, path) = graph.get_shortest_path();
+        let (distance, path) = map.get_shortest_path();
         assert_eq!(distance, 8);
         assert_eq!(
             path,
@@ -750,9 +439,7 @@ mod test {
         let mut map = Map::from_string(input);
         let entrance = map.get_entrance();
         map.split_entrance(&entrance);
-        let mut graph = map.build_graph();
-        graph.dijkstra(0);
-        let (distance, _path) = graph.get_shortest_path();
+        let (distance, _path) = map.get_shortest_path();
         assert_eq!(distance, 24);
         // There are multiple possible paths, don't verify
 
@@ -768,9 +455,7 @@ mod test {
         let mut map = Map::from_string(input);
         let entrance = map.get_entrance();
         map.split_entrance(&entrance);
-        let mut graph = map.build_graph();
-        graph.dijkstra(0);
-        let (distance, path) = graph.get_shortest_path();
+        let (distance, path) = map.get_shortest_path();
         assert_eq!(distance, 32);
         assert_eq!(
             path,
@@ -870,9 +555,7 @@ mod test {
         let mut map = Map::from_string(input);
         let entrance = map.get_entrance();
         map.split_entrance(&entrance);
-        let mut graph = map.build_graph();
-        graph.dijkstra(0);
-        let (distance, _path) = graph.get_shortest_path();
+        let (distance, _path) = map.get_shortest_path();
         assert_eq!(distance, 72);
         // There are multiple possible paths, don't verify
     }
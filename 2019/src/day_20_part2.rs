@@ -123,87 +123,41 @@
     In your maze, when accounting for recursion, how many steps does it take to get from the open tile marked AA to the open tile marked ZZ, both at the outermost layer?
 */
 
+use common::graph::Graph;
+use common::search;
+use common::{Point2, TileChar, TileMap};
 use std::collections::HashMap;
-use std::collections::HashSet;
-use std::fmt;
-
-#[derive(Clone, Copy, Debug, PartialEq)]
-enum Cardinal {
-    North,
-    South,
-    West,
-    East,
-}
-
-impl Cardinal {
-    fn step_from(&self, coord: Point) -> Point {
-        let delta = match *self {
-            Self::North => (0, -1),
-            Self::South => (0, 1),
-            Self::West => (-1, 0),
-            Self::East => (1, 0),
-        };
-
-        Point {
-            x: coord.x + delta.0,
-            y: coord.y + delta.1,
-        }
-    }
-
-    fn opposite(&self) -> Self {
-        match *self {
-            Self::North => Self::South,
-            Self::South => Self::North,
-            Self::West => Self::East,
-            Self::East => Self::West,
-        }
-    }
-}
-
-impl fmt::Display for Cardinal {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let disp_str = match *self {
-            Self::North => "North",
-            Self::South => "South",
-            Self::West => "West",
-            Self::East => "East",
-        };
-        write!(f, "{}", disp_str)
-    }
-}
-
-#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
-struct Point {
-    x: i32,
-    y: i32,
-}
 
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
 enum Space {
-    Unknown,
     Empty,
     Wall,
     PortalPiece(char),
 }
 
-impl Space {
-    fn from_value(value: char) -> Self {
-        match value {
-            '.' => Self::Empty,
-            '#' => Self::Wall,
-            'A'..='Z' => Self::PortalPiece(value),
-            _ => Self::Unknown,
-        }
-    }
-
-    fn char(&self) -> char {
+impl TileChar for Space {
+    fn to_char(&self) -> char {
         match *self {
-            Self::Unknown => '?',
-            Self::Empty => ' ',
+            Self::Empty => '.',
             Self::Wall => '#',
             Self::PortalPiece(c) => c,
         }
     }
+
+    fn from_char(c: char) -> Option<Self> {
+        match c {
+            '.' => Some(Self::Empty),
+            '#' => Some(Self::Wall),
+            'A'..='Z' => Some(Self::PortalPiece(c)),
+            _ => None,
+        }
+    }
+
+    fn all_chars() -> Vec<char> {
+        let mut chars = vec!['.', '#'];
+        chars.extend('A'..='Z');
+        chars
+    }
 }
 
 #[derive(Clone, Copy, Debug)]
@@ -212,285 +166,136 @@ struct Portal {
     next_depth: i32,
 }
 
-#[derive(Clone, Copy, Eq, Hash, PartialEq)]
-struct Node {
-    point: Point,
-    depth: i32,
-}
-
 #[derive(Clone)]
 struct Map {
-    area: HashMap<Point, Space>,
-    portals: HashMap<Point, Portal>,
+    area: TileMap<Space, ' '>,
+    portals: HashMap<Point2, Portal>,
 }
 
 impl Map {
     fn from_string(input: &str) -> Map {
-        let mut area = HashMap::new();
-
-        let mut p = Point { x: 0, y: 0 };
-        for line in input.lines() {
-            for c in line.chars() {
-                let space = Space::from_value(c);
-                if space != Space::Unknown {
-                    area.insert(p, space);
-                    p.x += 1;
-                } else if c == ' ' {
-                    p.x += 1;
-                } else {
-                    panic!("Unknown input: 0x{:02x}", c as u8);
-                }
-            }
-            p.x = 0;
-            p.y += 1;
-        }
-
         let mut map = Map {
-            area,
+            area: TileMap::from_string(input),
             portals: HashMap::new(),
         };
         map.detect_portals();
-        //println!("Portals: {:#?}", map.portals);
-
         map
     }
 
     fn detect_portals(&mut self) {
-        let mut x_range = (0, 0);
-        let mut y_range = (0, 0);
-
-        for k in self.area.keys() {
-            if k.x < x_range.0 {
-                x_range = (k.x, x_range.1);
-            } else if k.x > x_range.1 {
-                x_range = (x_range.0, k.x);
-            }
-
-            if k.y < y_range.0 {
-                y_range = (k.y, y_range.1);
-            } else if k.y > y_range.1 {
-                y_range = (y_range.0, k.y);
-            }
-        }
-        // println!("x_range: {:?}", x_range);
-        // println!("y_range: {:?}", y_range);
-
-        let x_size = x_range.1 - x_range.0;
-        let y_size = y_range.1 - y_range.0;
-        for y in y_range.0..=y_range.1 {
-            for x in x_range.0..=x_range.1 {
-                if let Some(Space::PortalPiece(c0)) = self.area.get(&Point { x, y }) {
-                    let portal_piece0 = *c0;
-
-                    // We are scanning from left to right, top to bottom. So we only need to look to the right and down.
-                    // If there's an adjacent letter in any other position, this is part of an existing portal.
-                    let portal_piece1;
-                    if let Some(Space::PortalPiece(c1)) = self.area.get(&Point { x: x + 1, y }) {
-                        portal_piece1 = *c1;
-                    } else if let Some(Space::PortalPiece(c1)) =
-                        self.area.get(&Point { x, y: y + 1 })
-                    {
-                        portal_piece1 = *c1;
+        // The outside edge of the donut itself, not counting the labels around it
+        let body = Point2::get_range(
+            self.area
+                .iter()
+                .filter(|(_, space)| !matches!(space, Space::PortalPiece(_)))
+                .map(|(p, _)| p),
+        )
+        .unwrap();
+
+        let range = self.area.get_range().unwrap();
+        for y in range.y.0..=range.y.1 {
+            for x in range.x.0..=range.x.1 {
+                let p = Point2 { x, y };
+                let Some(&Space::PortalPiece(c0)) = self.area.get(&p) else {
+                    continue;
+                };
+
+                // We are scanning from left to right, top to bottom. So we only need to look to the right and down.
+                // If there's an adjacent letter in any other position, this is part of an existing portal.
+                let (second, before, after) =
+                    if let Some(&Space::PortalPiece(c1)) = self.area.get(&(p + (1, 0))) {
+                        (c1, p - (1, 0), p + (2, 0))
+                    } else if let Some(&Space::PortalPiece(c1)) = self.area.get(&(p + (0, 1))) {
+                        (c1, p - (0, 1), p + (0, 2))
                     } else {
                         continue; // Not a valid portal
-                    }
-
-                    // Find the walkable space. Relative to the first piece it is either one left, two right, one up, or two down.
-                    // Also decide what relative depth the portal leads to. +1 means it goes inward / deeper (inner part of the donut),
-                    // while -1 means it goes outward. The entrance and exit are special cases and have 0 relative depth.
-                    let portal_walkable;
-                    let portal_depth;
-                    if let Some(Space::Empty) = self.area.get(&Point { x: x - 1, y }) {
-                        portal_walkable = Point { x: x - 1, y };
-
-                        if (portal_piece0 == 'A' && portal_piece1 == 'A')
-                            || (portal_piece0 == 'Z' && portal_piece1 == 'Z')
-                        {
-                            portal_depth = 0; // Special case.
-                        } else if x < x_size / 2 {
-                            portal_depth = 1; // On left side of donut, and portal is to the right of the walkable space. Inward.
-                        } else {
-                            portal_depth = -1; // On right side of donut, and portal is to the right of the walkable space. Outward.
-                        }
-                    } else if let Some(Space::Empty) = self.area.get(&Point { x: x + 2, y }) {
-                        portal_walkable = Point { x: x + 2, y };
-
-                        if (portal_piece0 == 'A' && portal_piece1 == 'A')
-                            || (portal_piece0 == 'Z' && portal_piece1 == 'Z')
-                        {
-                            portal_depth = 0; // Special case.
-                        } else if x < x_size / 2 {
-                            portal_depth = -1; // On left side of donut, and portal is to the left of the walkable space. Outward.
-                        } else {
-                            portal_depth = 1; // On right side of donut, and portal is to the left of the walkable space. Inward.
-                        }
-                    } else if let Some(Space::Empty) = self.area.get(&Point { x, y: y - 1 }) {
-                        portal_walkable = Point { x, y: y - 1 };
-
-                        if (portal_piece0 == 'A' && portal_piece1 == 'A')
-                            || (portal_piece0 == 'Z' && portal_piece1 == 'Z')
-                        {
-                            portal_depth = 0; // Special case.
-                        } else if y < y_size / 2 {
-                            portal_depth = 1; // On upper side of donut, and portal is below the walkable space. Inward.
-                        } else {
-                            portal_depth = -1; // On lower side of donut, and portal is below the walkable space. Outward.
-                        }
-                    } else if let Some(Space::Empty) = self.area.get(&Point { x, y: y + 2 }) {
-                        portal_walkable = Point { x, y: y + 2 };
-
-                        if (portal_piece0 == 'A' && portal_piece1 == 'A')
-                            || (portal_piece0 == 'Z' && portal_piece1 == 'Z')
-                        {
-                            portal_depth = 0; // Special case.
-                        } else if y < y_size / 2 {
-                            portal_depth = -1; // On upper side of donut, and portal is above the walkable space. Outward.
-                        } else {
-                            portal_depth = 1; // On lower side of donut, and portal is above the walkable space. Inward.
-                        }
-                    } else {
-                        continue; // Not a valid portal
-                    }
-
-                    let portal = Portal {
-                        value: [portal_piece0, portal_piece1],
-                        next_depth: portal_depth,
                     };
-                    self.portals.insert(portal_walkable, portal);
-                }
-            }
-        }
-    }
-
-    fn display(&self) {
-        let mut x_range = (0, 0);
-        let mut y_range = (0, 0);
 
-        for k in self.area.keys() {
-            if k.x < x_range.0 {
-                x_range = (k.x, x_range.1);
-            } else if k.x > x_range.1 {
-                x_range = (x_range.0, k.x);
-            }
+                // The walkable space is either just before the first piece or just after the second
+                let walkable = if self.area.get(&before) == Some(&Space::Empty) {
+                    before
+                } else if self.area.get(&after) == Some(&Space::Empty) {
+                    after
+                } else {
+                    continue; // Not a valid portal
+                };
+
+                // Portals on the outside edge lead outward (-1) and those on the inside edge lead inward (+1). The
+                // entrance and exit are special cases and have 0 relative depth.
+                let value = [c0, second];
+                let next_depth = if value == ['A', 'A'] || value == ['Z', 'Z'] {
+                    0
+                } else if walkable.x == body.x.0
+                    || walkable.x == body.x.1
+                    || walkable.y == body.y.0
+                    || walkable.y == body.y.1
+                {
+                    -1
+                } else {
+                    1
+                };
 
-            if k.y < y_range.0 {
-                y_range = (k.y, y_range.1);
-            } else if k.y > y_range.1 {
-                y_range = (y_range.0, k.y);
+                let portal = Portal { value, next_depth };
+                self.portals.insert(walkable, portal);
             }
         }
-        // println!("x_range: {:?}", x_range);
-        // println!("y_range: {:?}", y_range);
+    }
 
-        for y in y_range.0..=y_range.1 {
-            for x in x_range.0..=x_range.1 {
-                if let Some(t) = self.area.get(&Point { x, y }) {
-                    print!("{}", t.char());
-                } else {
-                    print!(" ");
-                }
-            }
-            println!();
-        }
-        println!();
-        println!();
+    fn find_portal(&self, value: [char; 2]) -> Point2 {
+        self.portals
+            .iter()
+            .find(|(_, portal)| portal.value == value)
+            .map(|(&p, _)| p)
+            .unwrap_or_else(|| panic!("Could not find portal {}{}", value[0], value[1]))
     }
 
-    fn get_entrance(&self) -> Point {
-        for (&k, &v) in self.portals.iter() {
-            if let ['A', 'A'] = v.value {
-                return k;
-            }
-        }
-        panic!("No entrance found!");
+    // Only the portals matter, joined by the steps between them
+    fn build_graph(&self) -> Graph<Point2> {
+        Graph::from_tiles(
+            &self.area,
+            |_, space| *space == Space::Empty,
+            |p, _| self.portals.contains_key(&p),
+        )
     }
 
     fn a_to_z(&self) -> u32 {
-        let entrance = self.get_entrance();
-        //println!("Entrance: {:?}", entrance);
-
-        let mut frontier: Vec<Node> = Vec::new();
-        let mut walked: HashSet<Node> = HashSet::new();
-
-        let entrance_node = Node {
-            point: entrance,
-            depth: 0,
-        };
-        frontier.push(entrance_node);
-        walked.insert(entrance_node);
-
-        let mut steps = 0;
-        loop {
-            steps += 1;
-            for location in std::mem::take(&mut frontier) {
-                let candidates = [
-                    Cardinal::North,
-                    Cardinal::South,
-                    Cardinal::West,
-                    Cardinal::East,
-                ];
-                for direction in candidates.iter() {
-                    let step_in_direction = direction.step_from(location.point);
-                    //println!("Step: {:?}", step_in_direction);
-                    let step_node = Node {
-                        point: step_in_direction,
-                        depth: location.depth,
-                    };
-                    if !walked.contains(&step_node) {
-                        // Step into any adjacent empty space
-                        if self.area.get(&step_in_direction) == Some(&Space::Empty) {
-                            // If this is the goal space, return now
-                            if let Some(&portal) = self.portals.get(&step_in_direction) {
-                                if portal.value == ['Z', 'Z'] && location.depth == 0 {
-                                    return steps;
-                                }
-                            }
-
-                            // Otherwise, add it to the frontier
-                            frontier.push(step_node);
-                            walked.insert(step_node);
-                        }
-                    }
-                }
-
-                // Also step into any portals connected to the current space
-                if let Some(&portal) = self.portals.get(&location.point) {
-                    // The portal can be taken if stepping through it would result in a non-negative
-                    let portal_depth = location.depth + portal.next_depth;
-                    if portal_depth >= 0 {
-                        // Find matching portal
-                        for (&k, &v) in self.portals.iter() {
-                            let portal_node = Node {
-                                point: k,
-                                depth: portal_depth,
-                            };
-                            if k != location.point
-                                && v.value == portal.value
-                                && !walked.contains(&portal_node)
-                            {
-                                frontier.push(portal_node);
-                                walked.insert(portal_node);
-                            }
-                        }
-                    }
+        let graph = self.build_graph();
+        let entrance = graph.index_of(&self.find_portal(['A', 'A'])).unwrap();
+        let exit = graph.index_of(&self.find_portal(['Z', 'Z'])).unwrap();
+
+        // Stepping onto a portal takes you to the other end of it in one more step, one level in or out
+        let mut partners: HashMap<usize, (usize, i32)> = HashMap::new();
+        for (&p, portal) in &self.portals {
+            for (&q, other) in &self.portals {
+                if p != q && portal.value == other.value {
+                    let partner = (graph.index_of(&q).unwrap(), portal.next_depth);
+                    partners.insert(graph.index_of(&p).unwrap(), partner);
                 }
             }
-
-            //println!("frontier: {:?}", frontier);
-            if frontier.is_empty() == true {
-                break;
-            }
         }
 
-        panic!("Could not find portal ZZ");
+        // Search over where we are and how deep, which can never be above the outermost level
+        let neighbors = |&(idx, depth): &(usize, i32)| {
+            graph
+                .edges(idx)
+                .iter()
+                .map(|edge| ((edge.to, depth), edge.dist))
+                .chain(
+                    partners
+                        .get(&idx)
+                        .map(|&(partner, next_depth)| ((partner, depth + next_depth), 1))
+                        .filter(|((_, depth), _)| *depth >= 0),
+                )
+                .collect::<Vec<_>>()
+        };
+        let result = search::dijkstra([(entrance, 0)], neighbors, |&node| node == (exit, 0));
+        result.goal_distance().expect("Could not find portal ZZ")
     }
 }
 
 #[aoc(day20, part2)]
 pub fn solve(input: &str) -> u32 {
     let map = Map::from_string(input);
-    //map.display();
-
     let steps = map.a_to_z();
     println!("Steps: {}", steps);
     steps
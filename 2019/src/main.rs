@@ -1,3 +1,30 @@
-use aoc_runner_derive::aoc_main;
+use common::aoc_main;
 
-aoc_main! { lib = advent_of_code_2019 }
+aoc_main! {
+    lib = advent_of_code_2019;
+    day1_part1, day1_part2,
+    day2_part1, day2_part2,
+    day3_part1, day3_part2,
+    day4_part1, day4_part2,
+    day5_part1, day5_part2,
+    day6_part1, day6_part2,
+    day7_part1, day7_part2,
+    day8_part1, day8_part2,
+    day9_part1, day9_part2,
+    day10_part1, day10_part2,
+    day11_part1, day11_part2,
+    day12_part1, day12_part2,
+    day13_part1, day13_part2,
+    day14_part1, day14_part2,
+    day15_part1, day15_part2,
+    day16_part1, day16_part2,
+    day17_part1, day17_part2,
+    day18_part1, day18_part2,
+    day19_part1, day19_part2,
+    day20_part1, day20_part2,
+    day21_part1, day21_part2,
+    day22_part1, day22_part2,
+    day23_part1, day23_part2,
+    day24_part1, day24_part2,
+    day25_part1,
+}
@@ -67,17 +67,13 @@ pub fn input_generator(input: &str) -> Vec<u32> {
 #[aoc(day1, part1)]
 pub fn part1(input: &[u32]) -> u32 {
     let (a, b) = find_sum2_2020(input);
-    let product = a * b;
-    assert_eq!(product, 436404);
-    product
+    a * b
 }
 
 #[aoc(day1, part2)]
 pub fn part2(input: &[u32]) -> u32 {
     let (a, b, c) = find_sum3_2020(input);
-    let product = a * b * c;
-    assert_eq!(product, 274879808);
-    product
+    a * b * c
 }
 
 #[cfg(test)]
@@ -129,16 +129,12 @@ pub fn input_generator(input: &str) -> Vec<Entry> {
 
 #[aoc(day2, part1)]
 pub fn part1(input: &[Entry]) -> usize {
-    let valid_count = count_valid_passwords1(input);
-    assert_eq!(valid_count, 456);
-    valid_count
+    count_valid_passwords1(input)
 }
 
 #[aoc(day2, part2)]
 pub fn part2(input: &[Entry]) -> usize {
-    let valid_count = count_valid_passwords2(input);
-    assert_eq!(valid_count, 308);
-    valid_count
+    count_valid_passwords2(input)
 }
 
 #[cfg(test)]
@@ -66,37 +66,29 @@
     What do you get if you multiply together the number of trees encountered on each of the listed slopes?
 */
 
-use common::{modulo, Point2, TileSet};
+use common::{Grid, Point2, Tiles};
 
 pub struct Map {
-    tileset: TileSet,
-    x_max: i32,
-    y_max: i32,
+    trees: Grid<bool>,
 }
 
 impl Map {
     fn from_string(input: &str) -> Self {
-        let tileset = TileSet::from_string::<'#'>(input);
-        let range = tileset.get_range().unwrap();
-
-        Self {
-            tileset,
-            x_max: range.x.1,
-            y_max: range.y.1,
-        }
+        let trees = Grid::from_string_with(input.trim(), |c| Some(c == '#'));
+        Self { trees }
     }
 
     fn ride_toboggan(&self, x_delta: i32, y_delta: i32) -> usize {
+        let trees = self.trees.wrapping();
         let mut p = Point2::origin();
         let mut tree_count = 0;
 
-        while p.y <= self.y_max {
-            if self.tileset.contains(&p) {
+        while p.y < self.trees.height() as i32 {
+            if trees.get(p) == Some(&true) {
                 tree_count += 1;
             }
 
-            p.x = modulo(p.x + x_delta, self.x_max + 1);
-            p.y += y_delta;
+            p += (x_delta, y_delta);
         }
 
         tree_count
@@ -118,16 +110,12 @@ pub fn input_generator(input: &str) -> Map {
 
 #[aoc(day3, part1)]
 pub fn part1(input: &Map) -> usize {
-    let tree_count = input.ride_toboggan(3, 1);
-    assert_eq!(tree_count, 207);
-    tree_count
+    input.ride_toboggan(3, 1)
 }
 
 #[aoc(day3, part2)]
 pub fn part2(input: &Map) -> usize {
-    let tree_product = input.ride_toboggan_many();
-    assert_eq!(tree_product, 2655892800);
-    tree_product
+    input.ride_toboggan_many()
 }
 
 #[cfg(test)]
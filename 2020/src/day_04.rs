@@ -307,16 +307,12 @@ pub fn input_generator(input: &str) -> Vec<Passport> {
 
 #[aoc(day4, part1)]
 pub fn part1(input: &[Passport]) -> usize {
-    let count = input.iter().filter(|pass| pass.is_valid1()).count();
-    assert_eq!(count, 192);
-    count
+    input.iter().filter(|pass| pass.is_valid1()).count()
 }
 
 #[aoc(day4, part2)]
 pub fn part2(input: &[Passport]) -> usize {
-    let count = input.iter().filter(|pass| pass.is_valid2()).count();
-    assert_eq!(count, 101);
-    count
+    input.iter().filter(|pass| pass.is_valid2()).count()
 }
 
 #[cfg(test)]
@@ -191,16 +191,12 @@ pub fn input_generator(input: &str) -> BagCookBook {
 
 #[aoc(day7, part1)]
 pub fn part1(input: &BagCookBook) -> usize {
-    let count = input.count_contains_gold();
-    assert_eq!(count, 372);
-    count
+    input.count_contains_gold()
 }
 
 #[aoc(day7, part2)]
 pub fn part2(input: &BagCookBook) -> u32 {
-    let count = input.count_bags_in_gold();
-    assert_eq!(count, 8015);
-    count
+    input.count_bags_in_gold()
 }
 
 #[cfg(test)]
@@ -2,13 +2,17 @@
 
 */
 
-use common::{signed, trim_start};
+use common::{
+    machine::{Cpu, Flow, InstructionSet, Machine, Stop},
+    signed, trim_start,
+};
 use nom::{
     character::complete::{alpha1, char},
     multi::many1,
     sequence::separated_pair,
     IResult,
 };
+use std::collections::HashSet;
 
 #[derive(Clone)]
 pub enum Instruction {
@@ -40,76 +44,57 @@ impl Instruction {
     }
 }
 
+impl InstructionSet for Instruction {
+    type Registers = i32; // Accumulator
+
+    fn execute(&self, accumulator: &mut i32) -> Flow {
+        match *self {
+            Self::Jmp(x) => return Flow::Jump(x as isize),
+            Self::Acc(x) => *accumulator += x,
+            Self::Nop(_) => (),
+        }
+        Flow::Next
+    }
+}
+
 #[derive(Clone)]
 pub struct GameConsole {
-    program: Vec<Instruction>,
-    ip: usize,
-    accumulator: i32,
-    executed: Vec<bool>,
+    cpu: Cpu<Instruction>,
 }
 
 impl GameConsole {
     fn parser(input: &str) -> IResult<&str, Self> {
         let (input, program) = many1(Instruction::parser)(input)?;
-
-        let program_len = program.len();
         Ok((
             input,
             Self {
-                program,
-                ip: 0,
-                accumulator: 0,
-                executed: vec![false; program_len],
+                cpu: Cpu::new(program, 0),
             },
         ))
     }
 
-    fn execute_next_instruction(&mut self) {
-        match self.program[self.ip] {
-            Instruction::Jmp(x) => {
-                if x >= 0 {
-                    self.ip += x as usize;
-                } else {
-                    self.ip -= (-x) as usize;
-                }
-            }
-            Instruction::Acc(x) => {
-                self.accumulator += x;
-                self.ip += 1;
-            }
-            Instruction::Nop(_) => self.ip += 1,
-        }
-    }
-
     fn execute(&mut self) -> (bool, i32) {
-        loop {
-            if self.ip >= self.program.len() {
-                return (false, self.accumulator);
-            }
-            if self.executed[self.ip] == true {
-                return (true, self.accumulator);
-            }
-
-            self.executed[self.ip] = true;
-            self.execute_next_instruction();
-        }
+        let is_infinite_loop = matches!(self.cpu.run_until_loop(), Stop::Loop(_));
+        (is_infinite_loop, self.cpu.regs)
     }
 
     fn fix_corruption(&mut self) -> i32 {
+        let mut executed = HashSet::new();
         loop {
-            if self.ip >= self.program.len() {
+            if self.cpu.is_halted() {
                 panic!("Program terminated normally!");
             }
-            if self.executed[self.ip] == true {
+            if executed.insert(self.cpu.pc()) == false {
                 panic!("No corrupted instruction found!");
             }
 
+            let ip = self.cpu.pc();
             if matches!(
-                self.program[self.ip],
+                self.cpu.program[ip],
                 Instruction::Jmp(_) | Instruction::Nop(_)
             ) {
                 let mut new_console = self.clone();
-                new_console.program[self.ip] = self.program[self.ip].transform();
+                new_console.cpu.program[ip] = self.cpu.program[ip].transform();
 
                 let (is_infinite_loop, acc_value) = new_console.execute();
                 if is_infinite_loop == false {
@@ -117,8 +102,7 @@ impl GameConsole {
                 }
             }
 
-            self.executed[self.ip] = true;
-            self.execute_next_instruction();
+            self.cpu.step();
         }
     }
 }
@@ -133,16 +117,13 @@ pub fn part1(input: &GameConsole) -> i32 {
     let mut console = input.clone(); // aoc-runner can only give an immutable reference, so to get mutable I have to clone
     let (is_infinite_loop, acc_value) = console.execute();
     assert!(is_infinite_loop);
-    assert_eq!(acc_value, 1939);
     acc_value
 }
 
 #[aoc(day8, part2)]
 pub fn part2(input: &GameConsole) -> i32 {
     let mut console = input.clone(); // aoc-runner can only give an immutable reference, so to get mutable I have to clone
-    let acc_value = console.fix_corruption();
-    assert_eq!(acc_value, 2212);
-    acc_value
+    console.fix_corruption()
 }
 
 #[cfg(test)]
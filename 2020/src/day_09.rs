@@ -134,17 +134,13 @@ pub fn input_generator(input: &str) -> Vec<u64> {
 
 #[aoc(day9, part1)]
 pub fn part1(input: &[u64]) -> u64 {
-    let non_sum = find_first_non_sum(input, 25);
-    assert_eq!(non_sum, 1639024365);
-    non_sum
+    find_first_non_sum(input, 25)
 }
 
 #[aoc(day9, part2)]
 pub fn part2(input: &[u64]) -> u64 {
     let non_sum = find_first_non_sum(input, 25);
-    let encryption_weakness = find_contiguous_sum(input, non_sum);
-    assert_eq!(encryption_weakness, 219202240);
-    encryption_weakness
+    find_contiguous_sum(input, non_sum)
 }
 
 #[cfg(test)]
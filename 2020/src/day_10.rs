@@ -190,16 +190,12 @@ pub fn input_generator(input: &str) -> Vec<u32> {
 #[aoc(day10, part1)]
 pub fn part1(input: &[u32]) -> usize {
     let (one, _two, three) = find_jolt_differences(input);
-    let product = one * three;
-    assert_eq!(product, 2030);
-    product
+    one * three
 }
 
 #[aoc(day10, part2)]
 pub fn part2(input: &[u32]) -> usize {
-    let arrangements = count_arrangements(input);
-    assert_eq!(arrangements, 42313823813632);
-    arrangements
+    count_arrangements(input)
 }
 
 #[cfg(test)]
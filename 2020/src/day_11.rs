@@ -203,7 +203,8 @@
     Given the new visibility method and the rule change for occupied seats becoming empty, once equilibrium is reached, how many seats end up occupied?
 */
 
-use common::{Mode, Point2, Range2};
+use common::automaton::Dense;
+use common::{Grid, Mode, Point2, TileChar};
 
 #[derive(Clone, Copy, PartialEq)]
 enum Tile {
@@ -212,194 +213,85 @@ enum Tile {
     Occupied,
 }
 
-impl Tile {
-    fn from_char(c: char) -> Self {
-        match c {
-            '.' => Self::Floor,
-            'L' => Self::Empty,
-            '#' => Self::Occupied,
-            _ => panic!("Unknown character: {}", c),
-        }
-    }
-
-    fn to_char(self) -> char {
+impl TileChar for Tile {
+    fn to_char(&self) -> char {
         match self {
             Self::Floor => '.',
             Self::Empty => 'L',
             Self::Occupied => '#',
         }
     }
-}
 
-fn get_tile_flat(p: &Point2, x_size: usize, y_size: usize, tiles: &[Tile]) -> Tile {
-    if p.x >= 0 && p.x < x_size as i32 && p.y >= 0 && p.y < y_size as i32 {
-        let idx = point_to_idx(p, x_size);
-        tiles[idx]
-    } else {
-        Tile::Floor
+    fn from_char(c: char) -> Option<Self> {
+        match c {
+            '.' => Some(Self::Floor),
+            'L' => Some(Self::Empty),
+            '#' => Some(Self::Occupied),
+            _ => None,
+        }
     }
-}
 
-fn point_to_idx(p: &Point2, x_size: usize) -> usize {
-    p.y as usize * x_size + p.x as usize
-}
-
-fn idx_to_point(idx: usize, x_size: usize) -> Point2 {
-    let x = (idx % x_size) as i32;
-    let y = (idx / x_size) as i32;
-    Point2 { x, y }
+    fn all_chars() -> Vec<char> {
+        vec!['.', 'L', '#']
+    }
 }
 
 #[derive(Clone)]
 pub struct WaitingArea {
-    tiles: Vec<Tile>,
-    x_size: usize,
-    y_size: usize,
-    active: Vec<Point2>,
-    neighbors_direct: Vec<Vec<Point2>>,
-    neighbors_visible: Vec<Vec<Point2>>,
+    tiles: Grid<Tile>,
 }
 
 impl WaitingArea {
     fn from_string(input: &str) -> Self {
-        let mut tiles = Vec::new();
-        let mut x_size: Option<usize> = None;
-        for line in input.trim().lines() {
-            tiles.extend(line.chars().map(Tile::from_char));
-
-            if let Some(x) = x_size {
-                assert_eq!(x, line.len());
-            } else {
-                x_size = Some(line.len());
-            }
-        }
-        let x_size = x_size.unwrap();
-        let y_size = tiles.len() / x_size;
-
-        // Build list of which tiles are active
-        let active = tiles
-            .iter()
-            .enumerate()
-            .filter(|(_i, seat)| seat == &&Tile::Empty)
-            .map(|(i, _seat)| {
-                let x = (i % x_size) as i32;
-                let y = (i / x_size) as i32;
-                Point2 { x, y }
-            })
-            .collect();
-
-        // Build index of non-floor neighbors directly adjacent to each tile
-        let neighbors_direct = (0..tiles.len())
-            .map(|i| {
-                idx_to_point(i, x_size)
-                    .adjacents()
-                    .filter(|adj| get_tile_flat(adj, x_size, y_size, &tiles) == Tile::Empty)
-                    .collect()
-            })
-            .collect();
-
-        // Build index of non-floor neighbors visible from each tile
-        let mut neighbors_visible = Vec::new();
-        for i in 0..tiles.len() {
-            let mut visible_dirs: Vec<Point2> = Vec::new();
-            for dir in Point2::origin().adjacents() {
-                let mut walk = idx_to_point(i, x_size);
-                let range = Range2 {
-                    x: (0, x_size as i32 + 1),
-                    y: (0, y_size as i32 + 1),
-                };
-                while range.contains(walk) {
-                    walk += dir;
-                    if get_tile_flat(&walk, x_size, y_size, &tiles) == Tile::Empty {
-                        visible_dirs.push(walk);
-                        break;
-                    }
-                }
-                // If we left the waiting area bounds, nothing is visible so don't save anything
-            }
-            neighbors_visible.push(visible_dirs);
-        }
-
         Self {
-            tiles,
-            x_size,
-            y_size,
-            active,
-            neighbors_direct,
-            neighbors_visible,
+            tiles: Grid::from_string(input.trim()),
         }
     }
 
-    fn get_tile(&self, p: &Point2) -> Tile {
-        get_tile_flat(p, self.x_size, self.y_size, &self.tiles)
-    }
-
-    fn set_tile(&mut self, p: &Point2, tile: Tile) {
-        let idx = point_to_idx(p, self.x_size);
-        self.tiles[idx] = tile;
-    }
-
-    fn count_neighbors_direct(&self, p: &Point2) -> usize {
-        let idx = point_to_idx(p, self.x_size);
-        self.neighbors_direct[idx]
-            .iter()
-            .filter(|adj| self.get_tile(adj) == Tile::Occupied)
-            .count()
-    }
-
-    fn count_neighbors_visible(&self, p: &Point2) -> usize {
-        let idx = point_to_idx(p, self.x_size);
-        self.neighbors_visible[idx]
-            .iter()
-            .filter(|adj| self.get_tile(adj) == Tile::Occupied)
-            .count()
+    // Seats directly adjacent to each tile
+    fn neighbors_direct(tiles: &Grid<Tile>, p: Point2) -> Vec<Point2> {
+        tiles
+            .adjacents(p)
+            .filter(|adj| tiles[*adj] != Tile::Floor)
+            .collect()
     }
 
-    fn step(&mut self, mode: Mode) -> bool {
-        let mut changes: Vec<(Point2, Tile)> = Vec::new();
-        for p in self.active.iter() {
-            match (mode, self.get_tile(p)) {
-                (_, Tile::Floor) => (),
-                (Mode::M1, Tile::Empty) => {
-                    // Become occupied if there are no direct neighbors occupied
-                    if self.count_neighbors_direct(p) == 0 {
-                        changes.push((*p, Tile::Occupied));
-                    }
-                }
-                (Mode::M1, Tile::Occupied) => {
-                    // Become empty if four or more direct neighbors are also occupied
-                    if self.count_neighbors_direct(p) >= 4 {
-                        changes.push((*p, Tile::Empty));
-                    }
-                }
-                (Mode::M2, Tile::Empty) => {
-                    // Become occupied if there are no visible neighbors occupied
-                    if self.count_neighbors_visible(p) == 0 {
-                        changes.push((*p, Tile::Occupied));
-                    }
-                }
-                (Mode::M2, Tile::Occupied) => {
-                    // Become empty if five or more visible neighbors are also occupied
-                    if self.count_neighbors_visible(p) >= 5 {
-                        changes.push((*p, Tile::Empty));
-                    }
-                }
-            }
-        }
-
-        for change in &changes {
-            self.set_tile(&change.0, change.1);
-        }
-        changes.is_empty()
+    // Seats visible from each tile. If the view reaches the waiting area bounds then nothing is visible in that
+    // direction.
+    fn neighbors_visible(tiles: &Grid<Tile>, p: Point2) -> Vec<Point2> {
+        Point2::origin()
+            .adjacents()
+            .filter_map(|dir| {
+                tiles
+                    .ray(p, dir)
+                    .find(|(_walk, seat)| seat != &&Tile::Floor)
+                    .map(|(walk, _seat)| walk)
+            })
+            .collect()
     }
 
     fn simulate(&mut self, mode: Mode) {
-        while self.step(mode) == false {}
+        let (mut automaton, tolerance) = match mode {
+            Mode::M1 => (Dense::new(self.tiles.clone(), Self::neighbors_direct), 4),
+            Mode::M2 => (Dense::new(self.tiles.clone(), Self::neighbors_visible), 5),
+        };
+        automaton.run_until_stable(|seat, neighbors| {
+            let occupied = neighbors.filter(|n| n == &&Tile::Occupied).count();
+            match seat {
+                Tile::Floor => Tile::Floor,
+                // Become occupied if there are no neighbors occupied
+                Tile::Empty if occupied == 0 => Tile::Occupied,
+                // Become empty if enough neighbors are also occupied
+                Tile::Occupied if occupied >= tolerance => Tile::Empty,
+                _ => *seat,
+            }
+        });
+        self.tiles = automaton.into_cells();
     }
 
     fn total_occupied(&self) -> usize {
         self.tiles
-            .iter()
+            .values()
             .filter(|seat| seat == &&Tile::Occupied)
             .count()
     }
@@ -407,14 +299,7 @@ impl WaitingArea {
 
 impl std::fmt::Display for WaitingArea {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        for y in 0..self.y_size as i32 {
-            for x in 0..self.x_size as i32 {
-                let p = Point2 { x, y };
-                write!(f, "{}", self.get_tile(&p).to_char())?;
-            }
-            writeln!(f)?;
-        }
-        Ok(())
+        write!(f, "{}", self.tiles)
     }
 }
 
@@ -427,18 +312,14 @@ pub fn input_generator(input: &str) -> WaitingArea {
 pub fn part1(input: &WaitingArea) -> usize {
     let mut waiting_area = input.clone();
     waiting_area.simulate(Mode::M1);
-    let occupied = waiting_area.total_occupied();
-    assert_eq!(occupied, 2183);
-    occupied
+    waiting_area.total_occupied()
 }
 
 #[aoc(day11, part2)]
 pub fn part2(input: &WaitingArea) -> usize {
     let mut waiting_area = input.clone();
     waiting_area.simulate(Mode::M2);
-    let occupied = waiting_area.total_occupied();
-    assert_eq!(occupied, 1990);
-    occupied
+    waiting_area.total_occupied()
 }
 
 #[cfg(test)]
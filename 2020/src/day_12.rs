@@ -212,16 +212,12 @@ pub fn input_generator(input: &str) -> Vec<Instruction> {
 
 #[aoc(day12, part1)]
 pub fn part1(input: &[Instruction]) -> u32 {
-    let distance = handle_instructions(input, Mode::M1);
-    assert_eq!(distance, 2057);
-    distance
+    handle_instructions(input, Mode::M1)
 }
 
 #[aoc(day12, part2)]
 pub fn part2(input: &[Instruction]) -> u32 {
-    let distance = handle_instructions(input, Mode::M2);
-    assert_eq!(distance, 71504);
-    distance
+    handle_instructions(input, Mode::M2)
 }
 
 #[cfg(test)]
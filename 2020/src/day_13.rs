@@ -199,16 +199,12 @@ pub fn input_generator(input: &str) -> Schedule {
 #[aoc(day13, part1)]
 pub fn part1(input: &Schedule) -> u64 {
     let (bus_id, wait_time) = input.find_earliest_bus();
-    let product = bus_id * wait_time;
-    assert_eq!(product, 156);
-    product
+    bus_id * wait_time
 }
 
 #[aoc(day13, part2)]
 pub fn part2(input: &Schedule) -> u64 {
-    let syzygy = input.find_earliest_syzygy();
-    assert_eq!(syzygy, 404517869995362);
-    syzygy
+    input.find_earliest_syzygy()
 }
 
 #[cfg(test)]
@@ -84,7 +84,10 @@
     Execute the initialization program using an emulator for a version 2 decoder chip. What is the sum of all values left in memory after it completes?
 */
 
-use common::{trim_start, unsigned, Mode};
+use common::{
+    machine::{Cpu, Flow, InstructionSet, Machine},
+    trim_start, unsigned, Mode,
+};
 use nom::{
     branch::alt,
     bytes::complete::tag,
@@ -148,7 +151,7 @@ impl Mask {
         tmp
     }
 
-    fn apply_address(&self, addr: usize) -> MaskAddrIterator {
+    fn apply_address(&self, addr: usize) -> MaskAddrIterator<'_> {
         MaskAddrIterator {
             mask: self,
             addr,
@@ -226,6 +229,7 @@ impl Iterator for MaskAddrIterator<'_> {
     }
 }
 
+#[derive(Clone)]
 pub enum Instruction {
     UpdateMask(Mask),
     WriteMemory(usize, u64),
@@ -250,37 +254,48 @@ impl Instruction {
     }
 }
 
-struct Computer {
+impl InstructionSet for Instruction {
+    type Registers = Computer;
+
+    fn execute(&self, computer: &mut Computer) -> Flow {
+        match self {
+            Self::UpdateMask(mask) => computer.mask = *mask,
+            Self::WriteMemory(addr, value) => match computer.mode {
+                Mode::M1 => {
+                    computer
+                        .memory
+                        .insert(*addr, computer.mask.apply_value(*value));
+                }
+                Mode::M2 => {
+                    for a in computer.mask.apply_address(*addr) {
+                        computer.memory.insert(a, *value);
+                    }
+                }
+            },
+        }
+        Flow::Next
+    }
+}
+
+pub struct Computer {
+    mode: Mode,
     mask: Mask,
     memory: HashMap<usize, u64>,
 }
 
 impl Computer {
-    fn new() -> Self {
+    fn new(mode: Mode) -> Self {
         Self {
+            mode,
             mask: Mask::new(),
             memory: HashMap::new(),
         }
     }
 
-    fn execute(&mut self, instruction: &Instruction, mode: Mode) {
-        match instruction {
-            Instruction::UpdateMask(mask) => self.mask = *mask,
-            Instruction::WriteMemory(addr, value) => match mode {
-                Mode::M1 => {
-                    self.memory.insert(*addr, self.mask.apply_value(*value));
-                }
-                Mode::M2 => {
-                    for a in self.mask.apply_address(*addr) {
-                        self.memory.insert(a, *value);
-                    }
-                }
-            },
-        }
-    }
-
-    fn execute_all(&mut self, instructions: &[Instruction], mode: Mode) {
-        instructions.iter().for_each(|i| self.execute(i, mode));
+    fn execute_all(self, instructions: &[Instruction]) -> Self {
+        let mut cpu = Cpu::new(instructions.to_vec(), self);
+        cpu.run();
+        cpu.regs
     }
 
     fn memory_sum(&self) -> u64 {
@@ -295,20 +310,14 @@ pub fn input_generator(input: &str) -> Vec<Instruction> {
 
 #[aoc(day14, part1)]
 pub fn part1(input: &[Instruction]) -> u64 {
-    let mut computer = Computer::new();
-    computer.execute_all(input, Mode::M1);
-    let memory_sum = computer.memory_sum();
-    assert_eq!(memory_sum, 7997531787333);
-    memory_sum
+    let computer = Computer::new(Mode::M1).execute_all(input);
+    computer.memory_sum()
 }
 
 #[aoc(day14, part2)]
 pub fn part2(input: &[Instruction]) -> u64 {
-    let mut computer = Computer::new();
-    computer.execute_all(input, Mode::M2);
-    let memory_sum = computer.memory_sum();
-    assert_eq!(memory_sum, 3564822193820);
-    memory_sum
+    let computer = Computer::new(Mode::M2).execute_all(input);
+    computer.memory_sum()
 }
 
 #[cfg(test)]
@@ -360,14 +369,12 @@ mem[26] = 1";
 
     #[test]
     fn test_memory_sum() {
-        let mut computer = Computer::new();
         let instructions = input_generator(EXAMPLE_INPUT1);
-        computer.execute_all(&instructions, Mode::M1);
+        let computer = Computer::new(Mode::M1).execute_all(&instructions);
         assert_eq!(computer.memory_sum(), 165);
 
-        let mut computer = Computer::new();
         let instructions = input_generator(EXAMPLE_INPUT2);
-        computer.execute_all(&instructions, Mode::M2);
+        let computer = Computer::new(Mode::M2).execute_all(&instructions);
         assert_eq!(computer.memory_sum(), 208);
     }
 }
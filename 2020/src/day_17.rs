@@ -391,231 +391,57 @@
     Starting with your given initial configuration, simulate six cycles in a 4-dimensional space. How many cubes are left in the active state after the sixth cycle?
 */
 
-use common::TileSet;
-use std::collections::HashMap;
-
-type Point2D = (i32, i32);
-type Point3D = (i32, i32, i32);
-type Point4D = (i32, i32, i32, i32);
-type DimRange = (i32, i32);
-
-const NEIGHBOR_OFFSETS_3D: [Point3D; 26] = [
-    (-1, -1, -1),
-    (-1, -1, 0),
-    (-1, -1, 1),
-    (-1, 0, -1),
-    (-1, 0, 0),
-    (-1, 0, 1),
-    (-1, 1, -1),
-    (-1, 1, 0),
-    (-1, 1, 1),
-    (0, -1, -1),
-    (0, -1, 0),
-    (0, -1, 1),
-    (0, 0, -1),
-    (0, 0, 1),
-    (0, 1, -1),
-    (0, 1, 0),
-    (0, 1, 1),
-    (1, -1, -1),
-    (1, -1, 0),
-    (1, -1, 1),
-    (1, 0, -1),
-    (1, 0, 0),
-    (1, 0, 1),
-    (1, 1, -1),
-    (1, 1, 0),
-    (1, 1, 1),
-];
-
-const NEIGHBOR_OFFSETS_4D: [Point4D; 80] = [
-    (-1, -1, -1, -1),
-    (-1, -1, -1, 0),
-    (-1, -1, -1, 1),
-    (-1, -1, 0, -1),
-    (-1, -1, 0, 0),
-    (-1, -1, 0, 1),
-    (-1, -1, 1, -1),
-    (-1, -1, 1, 0),
-    (-1, -1, 1, 1),
-    (-1, 0, -1, -1),
-    (-1, 0, -1, 0),
-    (-1, 0, -1, 1),
-    (-1, 0, 0, -1),
-    (-1, 0, 0, 0),
-    (-1, 0, 0, 1),
-    (-1, 0, 1, -1),
-    (-1, 0, 1, 0),
-    (-1, 0, 1, 1),
-    (-1, 1, -1, -1),
-    (-1, 1, -1, 0),
-    (-1, 1, -1, 1),
-    (-1, 1, 0, -1),
-    (-1, 1, 0, 0),
-    (-1, 1, 0, 1),
-    (-1, 1, 1, -1),
-    (-1, 1, 1, 0),
-    (-1, 1, 1, 1),
-    (0, -1, -1, -1),
-    (0, -1, -1, 0),
-    (0, -1, -1, 1),
-    (0, -1, 0, -1),
-    (0, -1, 0, 0),
-    (0, -1, 0, 1),
-    (0, -1, 1, -1),
-    (0, -1, 1, 0),
-    (0, -1, 1, 1),
-    (0, 0, -1, -1),
-    (0, 0, -1, 0),
-    (0, 0, -1, 1),
-    (0, 0, 0, -1),
-    (0, 0, 0, 1),
-    (0, 0, 1, -1),
-    (0, 0, 1, 0),
-    (0, 0, 1, 1),
-    (0, 1, -1, -1),
-    (0, 1, -1, 0),
-    (0, 1, -1, 1),
-    (0, 1, 0, -1),
-    (0, 1, 0, 0),
-    (0, 1, 0, 1),
-    (0, 1, 1, -1),
-    (0, 1, 1, 0),
-    (0, 1, 1, 1),
-    (1, -1, -1, -1),
-    (1, -1, -1, 0),
-    (1, -1, -1, 1),
-    (1, -1, 0, -1),
-    (1, -1, 0, 0),
-    (1, -1, 0, 1),
-    (1, -1, 1, -1),
-    (1, -1, 1, 0),
-    (1, -1, 1, 1),
-    (1, 0, -1, -1),
-    (1, 0, -1, 0),
-    (1, 0, -1, 1),
-    (1, 0, 0, -1),
-    (1, 0, 0, 0),
-    (1, 0, 0, 1),
-    (1, 0, 1, -1),
-    (1, 0, 1, 0),
-    (1, 0, 1, 1),
-    (1, 1, -1, -1),
-    (1, 1, -1, 0),
-    (1, 1, -1, 1),
-    (1, 1, 0, -1),
-    (1, 1, 0, 0),
-    (1, 1, 0, 1),
-    (1, 1, 1, -1),
-    (1, 1, 1, 0),
-    (1, 1, 1, 1),
-];
-
-struct State {
-    is_active: bool,
-    active_neighbors: u8,
-}
+use common::automaton::{self, Sparse};
+use common::{PointN, TileSet};
 
-impl State {
-    fn new() -> Self {
-        Self {
-            is_active: false,
-            active_neighbors: 0,
-        }
-    }
-}
-struct PocketDimension3D {
-    cubes: HashMap<Point3D, State>,
+const AXIS_NAMES: [&str; 4] = ["x", "y", "z", "w"];
+
+struct PocketDimension<const D: usize> {
+    cubes: Sparse<PointN<D>>,
 }
 
-impl PocketDimension3D {
-    fn from_2d(tileset: &TileSet) -> Self {
-        let mut cubes = HashMap::new();
-        for p in tileset.iter() {
-            Self::set_active(&mut cubes, &(p.x, p.y, 0));
-        }
-        Self { cubes }
-    }
+type PocketDimension3D = PocketDimension<3>;
+type PocketDimension4D = PocketDimension<4>;
 
-    fn set_active(cubes: &mut HashMap<Point3D, State>, point: &Point3D) {
-        for neighbor in Self::neighbors(point) {
-            let entry = cubes.entry(neighbor).or_insert_with(State::new);
-            entry.active_neighbors += 1;
+impl<const D: usize> PocketDimension<D> {
+    fn from_2d(tileset: &TileSet) -> Self {
+        Self {
+            cubes: Sparse::new(tileset.iter().map(|p| PointN::from_point2(*p))),
         }
-        let entry = cubes.entry(*point).or_insert_with(State::new);
-        entry.is_active = true;
-    }
-
-    fn neighbors(point: &Point3D) -> impl Iterator<Item = Point3D> + '_ {
-        NEIGHBOR_OFFSETS_3D
-            .iter()
-            .map(move |offset| (point.0 + offset.0, point.1 + offset.1, point.2 + offset.2))
-    }
-
-    fn get_range(&self) -> (DimRange, DimRange, DimRange) {
-        let mut cubes_iter = self
-            .cubes
-            .iter()
-            .filter(|(_point, state)| state.is_active == true)
-            .map(|(point, _state)| point);
-        let first = cubes_iter.next().expect("No cubes in list");
-        cubes_iter.fold(
-            ((first.0, first.0), (first.1, first.1), (first.2, first.2)),
-            |(acc_x, acc_y, acc_z), p| {
-                (
-                    (acc_x.0.min(p.0), acc_x.1.max(p.0)),
-                    (acc_y.0.min(p.1), acc_y.1.max(p.1)),
-                    (acc_z.0.min(p.2), acc_z.1.max(p.2)),
-                )
-            },
-        )
     }
 
     fn step(&mut self) {
-        let mut new_cubes = HashMap::new();
-
-        for (cube, state) in self.cubes.iter() {
-            if state.is_active == true {
-                // Active. 2 or 3 to stay alive, otherwise die.
-                if state.active_neighbors == 2 || state.active_neighbors == 3 {
-                    Self::set_active(&mut new_cubes, cube);
-                }
-            } else {
-                // Inactive. 3 to become alive, otherwise stay dead.
-                if state.active_neighbors == 3 {
-                    Self::set_active(&mut new_cubes, cube);
-                }
-            }
-        }
-
-        self.cubes = new_cubes;
+        self.simulate(1);
     }
 
     fn simulate(&mut self, steps: usize) {
-        for _ in 0..steps {
-            self.step();
-        }
+        self.cubes.run(steps, |p| p.adjacents(), automaton::life);
     }
 
     fn count_active_cubes(&self) -> usize {
-        self.cubes
-            .iter()
-            .filter(|(_point, state)| state.is_active == true)
-            .count()
+        self.cubes.count()
     }
 }
 
-impl std::fmt::Display for PocketDimension3D {
+impl<const D: usize> std::fmt::Display for PocketDimension<D> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let (x_range, y_range, z_range) = self.get_range();
-        for z in z_range.0..=z_range.1 {
-            writeln!(f, "z={}", z)?;
-            for y in y_range.0..=y_range.1 {
-                for x in x_range.0..=x_range.1 {
-                    if let Some(State {
-                        is_active: true, ..
-                    }) = self.cubes.get(&(x, y, z))
-                    {
+        let range = PointN::get_range(self.cubes.active()).expect("No cubes in list");
+
+        // Each 2D slice is the points in the range with x and y at their lowest
+        let mut slices = range;
+        slices.0[0].1 = slices.0[0].0;
+        slices.0[1].1 = slices.0[1].0;
+        for slice in slices.points() {
+            let labels: Vec<String> = (2..D)
+                .map(|axis| format!("{}={}", AXIS_NAMES[axis], slice[axis]))
+                .collect();
+            writeln!(f, "{}", labels.join(", "))?;
+            for y in range.0[1].0..=range.0[1].1 {
+                for x in range.0[0].0..=range.0[0].1 {
+                    let mut p = slice;
+                    p[0] = x;
+                    p[1] = y;
+                    if self.cubes.is_active(&p) {
                         write!(f, "#")?;
                     } else {
                         write!(f, ".")?;
@@ -629,125 +455,6 @@ impl std::fmt::Display for PocketDimension3D {
     }
 }
 
-struct PocketDimension4D {
-    hypercubes: HashMap<Point4D, State>,
-}
-
-impl PocketDimension4D {
-    fn from_2d(tileset: &TileSet) -> Self {
-        let mut hypercubes = HashMap::new();
-        for p in tileset.iter() {
-            Self::set_active(&mut hypercubes, &(p.x, p.y, 0, 0));
-        }
-
-        Self { hypercubes }
-    }
-
-    fn set_active(hypercubes: &mut HashMap<Point4D, State>, point: &Point4D) {
-        for neighbor in Self::neighbors(point) {
-            let entry = hypercubes.entry(neighbor).or_insert_with(State::new);
-            entry.active_neighbors += 1;
-        }
-        let entry = hypercubes.entry(*point).or_insert_with(State::new);
-        entry.is_active = true;
-    }
-
-    fn neighbors(point: &Point4D) -> impl Iterator<Item = Point4D> + '_ {
-        NEIGHBOR_OFFSETS_4D.iter().map(move |offset| {
-            (
-                point.0 + offset.0,
-                point.1 + offset.1,
-                point.2 + offset.2,
-                point.3 + offset.3,
-            )
-        })
-    }
-
-    fn get_range(&self) -> (DimRange, DimRange, DimRange, DimRange) {
-        let mut hypercubes_iter = self
-            .hypercubes
-            .iter()
-            .filter(|(_point, state)| state.is_active == true)
-            .map(|(point, _state)| point);
-        let first = hypercubes_iter.next().expect("No cubes in list");
-        hypercubes_iter.fold(
-            (
-                (first.0, first.0),
-                (first.1, first.1),
-                (first.2, first.2),
-                (first.3, first.3),
-            ),
-            |(acc_x, acc_y, acc_z, acc_w), p| {
-                (
-                    (acc_x.0.min(p.0), acc_x.1.max(p.0)),
-                    (acc_y.0.min(p.1), acc_y.1.max(p.1)),
-                    (acc_z.0.min(p.2), acc_z.1.max(p.2)),
-                    (acc_w.0.min(p.3), acc_w.1.max(p.3)),
-                )
-            },
-        )
-    }
-
-    fn step(&mut self) {
-        let mut new_hypercubes = HashMap::new();
-
-        for (hypercube, state) in self.hypercubes.iter() {
-            if state.is_active == true {
-                // Active. 2 or 3 to stay alive, otherwise die.
-                if state.active_neighbors == 2 || state.active_neighbors == 3 {
-                    Self::set_active(&mut new_hypercubes, hypercube);
-                }
-            } else {
-                // Inactive. 3 to become alive, otherwise stay dead.
-                if state.active_neighbors == 3 {
-                    Self::set_active(&mut new_hypercubes, hypercube);
-                }
-            }
-        }
-
-        self.hypercubes = new_hypercubes;
-    }
-
-    fn simulate(&mut self, steps: usize) {
-        for _ in 0..steps {
-            self.step();
-        }
-    }
-
-    fn count_active_cubes(&self) -> usize {
-        self.hypercubes
-            .iter()
-            .filter(|(_point, state)| state.is_active == true)
-            .count()
-    }
-}
-
-impl std::fmt::Display for PocketDimension4D {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let (x_range, y_range, z_range, w_range) = self.get_range();
-        for w in w_range.0..=w_range.1 {
-            for z in z_range.0..=z_range.1 {
-                writeln!(f, "z={}, w={}", z, w)?;
-                for y in y_range.0..=y_range.1 {
-                    for x in x_range.0..=x_range.1 {
-                        if let Some(State {
-                            is_active: true, ..
-                        }) = self.hypercubes.get(&(x, y, z, w))
-                        {
-                            write!(f, "#")?;
-                        } else {
-                            write!(f, ".")?;
-                        }
-                    }
-                    writeln!(f)?;
-                }
-                writeln!(f)?;
-            }
-        }
-        Ok(())
-    }
-}
-
 #[aoc_generator(day17)]
 pub fn input_generator(input: &str) -> TileSet {
     TileSet::from_string::<'#'>(input)
@@ -757,18 +464,14 @@ pub fn input_generator(input: &str) -> TileSet {
 pub fn part1(input: &TileSet) -> usize {
     let mut pocket_dimension = PocketDimension3D::from_2d(input);
     pocket_dimension.simulate(6);
-    let active_cubes = pocket_dimension.count_active_cubes();
-    assert_eq!(active_cubes, 401);
-    active_cubes
+    pocket_dimension.count_active_cubes()
 }
 
 #[aoc(day17, part2)]
 pub fn part2(input: &TileSet) -> usize {
     let mut pocket_dimension = PocketDimension4D::from_2d(input);
     pocket_dimension.simulate(6);
-    let active_cubes = pocket_dimension.count_active_cubes();
-    assert_eq!(active_cubes, 2224);
-    active_cubes
+    pocket_dimension.count_active_cubes()
 }
 
 #[cfg(test)]
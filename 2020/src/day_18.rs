@@ -192,7 +192,6 @@ pub fn part1(input: &[ExprInfix]) -> u64 {
         .iter()
         .map(|expr| expr.to_postfix(precedence1).evaluate())
         .sum();
-    assert_eq!(sum, 280014646144);
     sum
 }
 
@@ -202,7 +201,6 @@ pub fn part2(input: &[ExprInfix]) -> u64 {
         .iter()
         .map(|expr| expr.to_postfix(precedence2).evaluate())
         .sum();
-    assert_eq!(sum, 9966990988262);
     sum
 }
 
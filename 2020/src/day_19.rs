@@ -344,26 +344,22 @@ pub fn input_generator(input: &str) -> Comms {
 
 #[aoc(day19, part1)]
 pub fn part1(input: &Comms) -> usize {
-    let count = input
+    input
         .messages
         .iter()
         .filter(|&m| input.match_message(m))
-        .count();
-    assert_eq!(count, 272);
-    count
+        .count()
 }
 
 #[aoc(day19, part2)]
 pub fn part2(input: &Comms) -> usize {
     let mut comms = input.clone();
     comms.patch_rules();
-    let count = comms
+    comms
         .messages
         .iter()
         .filter(|&m| comms.match_message(m))
-        .count();
-    assert_eq!(count, 374);
-    count
+        .count()
 }
 
 #[cfg(test)]
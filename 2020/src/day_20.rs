@@ -257,7 +257,9 @@
     How many # are not part of a sea monster?
 */
 
-use common::{modulo, trim_start, unsigned, Point2, TileChar, TileMap, TileSet};
+use common::{
+    modulo, trim_start, unsigned, Grid, Orientation, Point2, TileChar, TileSet, Transform,
+};
 use nom::{
     bytes::complete::tag,
     character::complete::char,
@@ -283,32 +285,6 @@ fn bit_reverse(input: &u32, size: usize) -> u32 {
     output
 }
 
-fn transform(pixels: &TileSet, orientation: TileOrientation) -> TileSet {
-    assert!(orientation.rotation < MAX_ROT);
-    let mut range = pixels.get_range().unwrap();
-    let mut output = TileSet::new();
-    for p in pixels.iter() {
-        let mut p_new = *p;
-        if orientation.flipped == true {
-            p_new.x = range.x.1 - (p_new.x - range.x.0);
-        }
-
-        for _ in 0..orientation.rotation {
-            let tmp = p_new.x;
-            p_new.x = range.x.0 + (range.y.1 - p_new.y);
-            p_new.y = range.y.0 + (tmp - range.x.0);
-
-            // Swap the ranges too since we're not zero-centered
-            let tmp = range.x;
-            range.x = (range.x.0, range.x.0 + range.y.1 - range.y.0);
-            range.y = (range.y.0, range.y.0 + tmp.1 - tmp.0);
-        }
-
-        output.insert(p_new);
-    }
-    output
-}
-
 #[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd)]
 enum TileSide {
     Top = 0,
@@ -344,12 +320,6 @@ impl TileSide {
     }
 }
 
-#[derive(Clone, Copy, Debug, Default, Eq, Ord, PartialEq, PartialOrd)]
-struct TileOrientation {
-    rotation: u32,
-    flipped: bool,
-}
-
 #[derive(Clone, Debug)]
 pub struct ImageTile {
     id: u64,
@@ -412,11 +382,11 @@ impl ImageTile {
         ))
     }
 
-    fn get_side_id(&self, side: TileSide, orientation: TileOrientation) -> u32 {
+    fn get_side_id(&self, side: TileSide, orientation: Orientation) -> u32 {
         // Side IDs are encoded left to right and top to bottom. So, when rotating,
         // the ID will flip sometimes - for example rotating 180 degrees means all
         // sides are now reverse of their original encoding.
-        let rot_idx = modulo(MAX_ROT - modulo(orientation.rotation, MAX_ROT), MAX_ROT);
+        let rot_idx = modulo(MAX_ROT - orientation.rotation as u32, MAX_ROT);
         let idx = modulo(side as u32 + rot_idx, MAX_ROT) as usize;
 
         let ary = if orientation.flipped == false {
@@ -442,13 +412,11 @@ impl ImageTile {
             .collect()
     }
 
-    fn all_orientations() -> Vec<(TileSide, TileOrientation)> {
+    fn all_orientations() -> Vec<(TileSide, Orientation)> {
         let mut output = Vec::new();
         for side in TileSide::iter() {
-            for rotation in 0..MAX_ROT {
-                for &flipped in &[false, true] {
-                    output.push((side, TileOrientation { rotation, flipped }));
-                }
+            for orientation in Orientation::all() {
+                output.push((side, orientation));
             }
         }
         output
@@ -475,7 +443,7 @@ impl std::fmt::Display for ImageTile {
 #[derive(Debug)]
 struct Image {
     tiles: HashMap<u64, ImageTile>,
-    possibilities: HashMap<u32, Vec<(u64, TileSide, TileOrientation)>>,
+    possibilities: HashMap<u32, Vec<(u64, TileSide, Orientation)>>,
     combined_pixels: TileSet,
 }
 
@@ -513,8 +481,7 @@ impl Image {
             .filter(|tile_id| {
                 TileSide::iter()
                     .filter(|&side| {
-                        let side_id =
-                            self.tiles[tile_id].get_side_id(side, TileOrientation::default());
+                        let side_id = self.tiles[tile_id].get_side_id(side, Orientation::default());
                         let mut possible_tiles: Vec<u64> = self.possibilities[&side_id]
                             .iter()
                             .map(|&(t, ..)| t)
@@ -544,21 +511,21 @@ impl Image {
         fn place_tile(
             point: Point2,
             tile_id: u64,
-            orientation: TileOrientation,
-            tile_map: &mut HashMap<Point2, (u64, TileOrientation)>,
+            orientation: Orientation,
+            tile_map: &mut HashMap<Point2, (u64, Orientation)>,
             unplaced_tiles: &mut Vec<u64>,
         ) {
             unplaced_tiles.remove(unplaced_tiles.iter().position(|x| *x == tile_id).unwrap());
             tile_map.insert(point, (tile_id, orientation));
         }
 
-        let mut tile_map: HashMap<Point2, (u64, TileOrientation)> = HashMap::new();
+        let mut tile_map: HashMap<Point2, (u64, Orientation)> = HashMap::new();
         let mut unplaced_tiles: Vec<u64> = self.tiles.keys().copied().collect();
         let mut frontier: Vec<Point2> = Point2::origin().orthogonals().collect();
         place_tile(
             Point2::origin(),
             corners[0],
-            TileOrientation::default(),
+            Orientation::default(),
             &mut tile_map,
             &mut unplaced_tiles,
         );
@@ -569,7 +536,7 @@ impl Image {
             }
 
             // Get the candidate tiles; in practice there will be at most one!
-            let mut candidates: Vec<(u64, TileSide, TileOrientation)> = Vec::new();
+            let mut candidates: Vec<(u64, TileSide, Orientation)> = Vec::new();
             for side in TileSide::iter() {
                 if let Some(&(tile_id, orientation)) =
                     tile_map.get(&(next_point + side.to_unit_point()))
@@ -593,7 +560,7 @@ impl Image {
             assert_eq!(tile_ids.len(), 1);
 
             // Find the first candidate that meets all requirements
-            let mut picked: Option<(u64, TileOrientation)> = None;
+            let mut picked: Option<(u64, Orientation)> = None;
             for (candidate_tile_id, _, candidate_orientation) in candidates {
                 let all_ok = TileSide::iter().all(|side| {
                     if let Some(&(tile_id, orientation)) =
@@ -630,7 +597,7 @@ impl Image {
         // Now create the image from each tile
         self.combined_pixels.clear();
         for (tile_point, (tile_id, tile_orientation)) in tile_map {
-            let transformed_pixels = transform(&self.tiles[&tile_id].pixels, tile_orientation);
+            let transformed_pixels = self.tiles[&tile_id].pixels.transform(tile_orientation);
             let range = transformed_pixels.get_range().unwrap();
             let x_size = range.x.1 - range.x.0 - 1;
             let y_size = range.y.1 - range.y.0 - 1;
@@ -648,7 +615,7 @@ impl Image {
     }
 
     fn find_sea_monsters(&self) -> usize {
-        #[derive(PartialEq)]
+        #[derive(Clone, PartialEq)]
         enum PixelTile {
             Empty,
             Wave,
@@ -678,59 +645,39 @@ impl Image {
             }
         }
 
+        let range = self.combined_pixels.get_range().unwrap();
+        let offset = Point2 {
+            x: range.x.0,
+            y: range.y.0,
+        };
+        let mut pixels: Grid<PixelTile> =
+            Grid::from_fn(range.width() as usize, range.height() as usize, |p| {
+                if self.combined_pixels.contains(&(p + offset)) == true {
+                    PixelTile::Wave
+                } else {
+                    PixelTile::Empty
+                }
+            });
+
         let sea_monster = "\
 ..................#.
 #....##....##....###
 .#..#..#..#..#..#...";
-        let sea_monster_pixels = TileSet::from_string::<'#'>(sea_monster);
-        let sea_range = sea_monster_pixels.get_range().unwrap();
-        let sea_x_width = sea_range.x.1 - sea_range.x.0;
-        let sea_y_width = sea_range.y.1 - sea_range.y.0;
+        let sea_monster: Grid<Option<PixelTile>> =
+            Grid::from_string_with(sea_monster, |c| Some((c == '#').then_some(PixelTile::Wave)));
+        let highlight = sea_monster.map(|t| t.as_ref().map(|_| PixelTile::Monster));
 
         // Check each orientation; only one should show sea monsters
-        for &flipped in &[false, true] {
-            for rotation in 0.. {
-                let orientation = TileOrientation { rotation, flipped };
-                let input = transform(&self.combined_pixels, orientation);
-                let mut pixels_highlighted: TileMap<PixelTile, '.'> =
-                    TileMap::new().with_tiles(input.iter().map(|p| (*p, PixelTile::Wave)));
-                let range = input.get_range().unwrap();
-                for y in range.y.0..=range.y.1 - sea_y_width {
-                    for x in range.x.0..=range.x.1 - sea_x_width {
-                        let mut found_monster = true;
-                        for sea_p in sea_monster_pixels.iter() {
-                            let offset_p = Point2 { x, y } + sea_p;
-                            if input.contains(&offset_p) == false {
-                                found_monster = false;
-                                break;
-                            }
-                        }
-
-                        if found_monster == true {
-                            for sea_p in sea_monster_pixels.iter() {
-                                let offset_p = Point2 { x, y } + sea_p;
-                                pixels_highlighted.insert(offset_p, PixelTile::Monster);
-                            }
-                        }
-                    }
-                }
-
-                if pixels_highlighted
-                    .values()
-                    .filter(|c| c == &&PixelTile::Monster)
-                    .count()
-                    != 0
-                {
-                    // Found the answer!
-                    //println!("{}", pixels_highlighted);
-                    return pixels_highlighted
-                        .values()
-                        .filter(|c| c == &&PixelTile::Wave)
-                        .count();
-                }
-            }
+        let found = pixels.find_pattern_oriented(&sea_monster);
+        if found.is_empty() == true {
+            panic!("No sea monsters found!");
         }
-        panic!("No sea monsters found!");
+        for (orientation, at) in found {
+            pixels.stamp(&highlight.transform(orientation), at);
+        }
+
+        //println!("{}", pixels);
+        pixels.values().filter(|c| c == &&PixelTile::Wave).count()
     }
 }
 
@@ -749,18 +696,14 @@ pub fn input_generator(input: &str) -> Vec<ImageTile> {
 pub fn part1(input: &[ImageTile]) -> u64 {
     let image = Image::from_image_tiles(input);
     let corners = image.find_corners();
-    let product = corners.iter().product();
-    assert_eq!(product, 111936085519519);
-    product
+    corners.iter().product()
 }
 
 #[aoc(day20, part2)]
 pub fn part2(input: &[ImageTile]) -> usize {
     let mut image = Image::from_image_tiles(input);
     image.assemble();
-    let roughness = image.find_sea_monsters();
-    assert_eq!(roughness, 1792);
-    roughness
+    image.find_sea_monsters()
 }
 
 #[cfg(test)]
@@ -935,7 +878,7 @@ Tile 3079:
             ((TileSide::Left, 3, true), 0b0011010010),
         ];
         for &((s, r, f), ans) in expected.iter() {
-            let o = TileOrientation {
+            let o = Orientation {
                 rotation: r,
                 flipped: f,
             };
@@ -961,32 +904,32 @@ Tile 3079:
         let expected = TileSet::new().with_tiles(tiles);
 
         let transformed = expected.clone();
-        let orientation = TileOrientation {
+        let orientation = Orientation {
             rotation: 1,
             flipped: false,
         };
-        let transformed = transform(&transformed, orientation);
-        let transformed = transform(&transformed, orientation);
-        let transformed = transform(&transformed, orientation);
-        let transformed = transform(&transformed, orientation);
+        let transformed = transformed.transform(orientation);
+        let transformed = transformed.transform(orientation);
+        let transformed = transformed.transform(orientation);
+        let transformed = transformed.transform(orientation);
         assert_eq!(expected, transformed);
 
         let transformed = expected.clone();
-        let orientation = TileOrientation {
+        let orientation = Orientation {
             rotation: 2,
             flipped: false,
         };
-        let transformed = transform(&transformed, orientation);
-        let transformed = transform(&transformed, orientation);
+        let transformed = transformed.transform(orientation);
+        let transformed = transformed.transform(orientation);
         assert_eq!(expected, transformed);
 
         let transformed = expected.clone();
-        let orientation = TileOrientation {
+        let orientation = Orientation {
             rotation: 0,
             flipped: true,
         };
-        let transformed = transform(&transformed, orientation);
-        let transformed = transform(&transformed, orientation);
+        let transformed = transformed.transform(orientation);
+        let transformed = transformed.transform(orientation);
         assert_eq!(expected, transformed);
     }
 
@@ -1024,13 +967,10 @@ Tile 3079:
 
         let original = image.combined_pixels.clone();
         let mut any = false;
-        for &flipped in &[false, true] {
-            for rotation in 0..MAX_ROT {
-                let orientation = TileOrientation { rotation, flipped };
-                image.combined_pixels = transform(&original, orientation);
-                if image.to_string().trim() == expected {
-                    any = true;
-                }
+        for (_, transformed) in original.orientations() {
+            image.combined_pixels = transformed;
+            if image.to_string().trim() == expected {
+                any = true;
             }
         }
         assert!(any);
@@ -184,17 +184,13 @@ pub fn input_generator(input: &str) -> FoodList {
 
 #[aoc(day21, part1)]
 pub fn part1(input: &FoodList) -> usize {
-    let count = input.no_allergen_count();
-    assert_eq!(count, 2061);
-    count
+    input.no_allergen_count()
 }
 
 #[aoc(day21, part2)]
 pub fn part2(input: &FoodList) -> String {
     let pairs = input.match_ingredients_allergens();
-    let string = FoodList::ingredient_string(&pairs);
-    assert_eq!(string, "cdqvp,dglm,zhqjs,rbpg,xvtrfz,tgmzqjz,mfqgx,rffqhl");
-    string
+    FoodList::ingredient_string(&pairs)
 }
 
 #[cfg(test)]
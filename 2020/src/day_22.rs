@@ -550,18 +550,14 @@ pub fn input_generator(input: &str) -> Game {
 pub fn part1(input: &Game) -> u32 {
     let mut game = input.clone();
     game.play_game();
-    let score = game.score();
-    assert_eq!(score, 32472);
-    score
+    game.score()
 }
 
 #[aoc(day22, part2)]
 pub fn part2(input: &Game) -> u32 {
     let mut game = input.clone();
     game.play_game_recursive();
-    let score = game.score();
-    assert_eq!(score, 36463);
-    score
+    game.score()
 }
 
 #[cfg(test)]
@@ -207,9 +207,7 @@ pub fn input_generator(input: &str) -> Vec<u32> {
 pub fn part1(input: &[u32]) -> String {
     let mut crab_cups = CrabCups::from_list(input, 9);
     crab_cups.make_move(100);
-    let labels = crab_cups.order(crab_cups.cups[1], 8);
-    assert_eq!(labels, "82573496");
-    labels
+    crab_cups.order(crab_cups.cups[1], 8)
 }
 
 #[aoc(day23, part2)]
@@ -218,9 +216,7 @@ pub fn part2(input: &[u32]) -> u64 {
     crab_cups.make_move(10000000);
     let a = crab_cups.cups[1];
     let b = crab_cups.cups[a as usize];
-    let label_product = a as u64 * b as u64;
-    assert_eq!(label_product, 11498506800);
-    label_product
+    a as u64 * b as u64
 }
 
 #[cfg(test)]
@@ -76,87 +76,20 @@
     How many tiles will be black after 100 days?
 */
 
-use common::trim_start;
-use nom::{branch::alt, bytes::complete::tag, combinator::value, multi::many1, IResult};
-use std::collections::{HashMap, HashSet};
-
-#[derive(Clone, Copy)]
-pub enum HexDir {
-    East,
-    SouthEast,
-    SouthWest,
-    West,
-    NorthWest,
-    NorthEast,
-}
-
-impl HexDir {
-    fn parser(input: &str) -> IResult<&str, Self> {
-        trim_start(alt((
-            value(Self::East, tag("e")),
-            value(Self::SouthEast, tag("se")),
-            value(Self::SouthWest, tag("sw")),
-            value(Self::West, tag("w")),
-            value(Self::NorthWest, tag("nw")),
-            value(Self::NorthEast, tag("ne")),
-        )))(input)
-    }
-}
-
-#[derive(Clone, Copy, Eq, Hash, PartialEq)]
-struct HexPoint {
-    x: i8,
-    y: i8,
-    z: i8,
-}
-
-impl HexPoint {
-    fn new() -> Self {
-        Self { x: 0, y: 0, z: 0 }
-    }
-
-    fn step(&self, dir: &HexDir) -> Self {
-        let (dx, dy, dz) = match dir {
-            HexDir::East => (1, -1, 0),
-            HexDir::SouthEast => (0, -1, 1),
-            HexDir::SouthWest => (-1, 0, 1),
-            HexDir::West => (-1, 1, 0),
-            HexDir::NorthWest => (0, 1, -1),
-            HexDir::NorthEast => (1, 0, -1),
-        };
-
-        Self {
-            x: self.x + dx,
-            y: self.y + dy,
-            z: self.z + dz,
-        }
-    }
-
-    fn adjacents(&self) -> [Self; 6] {
-        [
-            self.step(&HexDir::East),
-            self.step(&HexDir::SouthEast),
-            self.step(&HexDir::SouthWest),
-            self.step(&HexDir::West),
-            self.step(&HexDir::NorthWest),
-            self.step(&HexDir::NorthEast),
-        ]
-    }
-}
+use common::automaton::Sparse;
+use common::hex::{self, Hex, PointyDir};
+use std::collections::HashSet;
 
 struct Floor {
-    tiles: HashSet<HexPoint>,
+    tiles: Sparse<Hex>,
 }
 
 impl Floor {
-    fn from_rules(rules: &[Vec<HexDir>]) -> Self {
+    fn from_rules(rules: &[Vec<PointyDir>]) -> Self {
         let mut tiles = HashSet::new();
         for rule in rules {
             // Walk through the directions
-            let mut point = HexPoint::new();
-            for dir in rule {
-                point = point.step(dir);
-            }
+            let point = hex::walk(rule);
 
             // Toggle the final tile
             if tiles.contains(&point) == true {
@@ -166,71 +99,51 @@ impl Floor {
             }
         }
 
-        Self { tiles }
+        Self {
+            tiles: Sparse::new(tiles),
+        }
     }
 
     fn count_black(&self) -> usize {
-        self.tiles.len()
+        self.tiles.count()
     }
 
     fn days_passed(&mut self, num: usize) {
-        let mut counts: HashMap<HexPoint, u8> = HashMap::new();
-        for _ in 0..num {
-            counts.clear();
-            for t in &self.tiles {
-                for adj in &t.adjacents() {
-                    let e = counts.entry(*adj).or_insert(0);
-                    *e += 1;
+        self.tiles.run(
+            num,
+            |tile| tile.neighbors(),
+            |black, count| {
+                if black {
+                    // Black tiles flip to white with zero or more than two black neighbors
+                    count == 1 || count == 2
+                } else {
+                    // White tiles flip to black with exactly two black neighbors
+                    count == 2
                 }
-            }
-
-            self.tiles = counts
-                .iter()
-                .filter_map(|(&tile, &count)| {
-                    if self.tiles.contains(&tile) {
-                        // Black tile rules
-                        if count == 0 || count > 2 {
-                            None // Flip to white (don't do anything)
-                        } else {
-                            Some(tile) // Keep as black
-                        }
-                    } else {
-                        // White tile rules
-                        if count == 2 {
-                            Some(tile) // Flip to black
-                        } else {
-                            None // Keep as white (don't do anything)
-                        }
-                    }
-                })
-                .collect();
-        }
+            },
+        );
     }
 }
 
 #[aoc_generator(day24)]
-pub fn input_generator(input: &str) -> Vec<Vec<HexDir>> {
+pub fn input_generator(input: &str) -> Vec<Vec<PointyDir>> {
     input
         .lines()
-        .map(|line| many1(HexDir::parser)(line).unwrap().1)
+        .map(|line| hex::path_parser(line).unwrap().1)
         .collect()
 }
 
 #[aoc(day24, part1)]
-pub fn part1(input: &[Vec<HexDir>]) -> usize {
+pub fn part1(input: &[Vec<PointyDir>]) -> usize {
     let floor = Floor::from_rules(input);
-    let black = floor.count_black();
-    assert_eq!(black, 469);
-    black
+    floor.count_black()
 }
 
 #[aoc(day24, part2)]
-pub fn part2(input: &[Vec<HexDir>]) -> usize {
+pub fn part2(input: &[Vec<PointyDir>]) -> usize {
     let mut floor = Floor::from_rules(input);
     floor.days_passed(100);
-    let black = floor.count_black();
-    assert_eq!(black, 4353);
-    black
+    floor.count_black()
 }
 
 #[cfg(test)]
@@ -108,9 +108,7 @@ pub fn input_generator(input: &str) -> Handshake {
 
 #[aoc(day25, part1)]
 pub fn part1(input: &Handshake) -> u64 {
-    let encryption_key = input.crack_encryption();
-    assert_eq!(encryption_key, 12285001);
-    encryption_key
+    input.crack_encryption()
 }
 
 #[cfg(test)]
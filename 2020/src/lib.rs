@@ -1,4 +1,5 @@
 #![deny(clippy::allow_attributes)]
+#![cfg_attr(test, expect(clippy::bool_assert_comparison))]
 #![expect(clippy::bool_comparison)]
 #![expect(dead_code)]
 #![forbid(unsafe_code)]
@@ -97,16 +97,12 @@ pub fn input_generator(input: &str) -> Vec<u32> {
 
 #[aoc(day1, part1)]
 pub fn part1(input: &[u32]) -> usize {
-    let count = count_larger_measurements(input, 1);
-    assert_eq!(count, 1713);
-    count
+    count_larger_measurements(input, 1)
 }
 
 #[aoc(day1, part2)]
 pub fn part2(input: &[u32]) -> usize {
-    let count = count_larger_measurements(input, 3);
-    assert_eq!(count, 1734);
-    count
+    count_larger_measurements(input, 3)
 }
 
 #[cfg(test)]
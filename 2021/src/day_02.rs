@@ -133,18 +133,14 @@ pub fn input_generator(input: &str) -> Vec<Command> {
 pub fn part1(input: &[Command]) -> i32 {
     let mut sub = Submarine::new();
     sub.follow_commands1(input);
-    let score = sub.calc_pos_score();
-    assert_eq!(score, 1840243);
-    score
+    sub.calc_pos_score()
 }
 
 #[aoc(day2, part2)]
 pub fn part2(input: &[Command]) -> i32 {
     let mut sub = Submarine::new();
     sub.follow_commands2(input);
-    let score = sub.calc_pos_score();
-    assert_eq!(score, 1727785422);
-    score
+    sub.calc_pos_score()
 }
 
 #[cfg(test)]
@@ -197,16 +197,12 @@ pub fn input_generator(input: &str) -> Diagnostic {
 
 #[aoc(day3, part1)]
 pub fn part1(input: &Diagnostic) -> u32 {
-    let power = input.power_consumption();
-    assert_eq!(power, 738234);
-    power
+    input.power_consumption()
 }
 
 #[aoc(day3, part2)]
 pub fn part2(input: &Diagnostic) -> u32 {
-    let life = input.life_support_rating();
-    assert_eq!(life, 3969126);
-    life
+    input.life_support_rating()
 }
 
 #[cfg(test)]
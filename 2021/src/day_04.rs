@@ -204,17 +204,13 @@ pub fn input_generator(input: &str) -> BingoGame {
 #[aoc(day4, part1)]
 pub fn part1(input: &BingoGame) -> u32 {
     let mut game = input.clone();
-    let score = game.play_to_win();
-    assert_eq!(score, 49686);
-    score
+    game.play_to_win()
 }
 
 #[aoc(day4, part2)]
 pub fn part2(input: &BingoGame) -> u32 {
     let mut game = input.clone();
-    let score = game.play_to_lose();
-    assert_eq!(score, 26878);
-    score
+    game.play_to_lose()
 }
 
 #[cfg(test)]
@@ -151,17 +151,13 @@ pub fn input_generator(input: &str) -> Vec<Line> {
 #[aoc(day5, part1)]
 pub fn part1(input: &[Line]) -> usize {
     let field = Field::from_lines(input, Mode::M1);
-    let count = field.count_overlaps();
-    assert_eq!(count, 7142);
-    count
+    field.count_overlaps()
 }
 
 #[aoc(day5, part2)]
 pub fn part2(input: &[Line]) -> usize {
     let field = Field::from_lines(input, Mode::M2);
-    let count = field.count_overlaps();
-    assert_eq!(count, 20012);
-    count
+    field.count_overlaps()
 }
 
 #[cfg(test)]
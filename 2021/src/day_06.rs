@@ -98,18 +98,14 @@ pub fn input_generator(input: &str) -> FishSchool {
 pub fn part1(input: &FishSchool) -> u64 {
     let mut school = input.clone();
     school.step(80);
-    let total = school.total_fish();
-    assert_eq!(total, 383160);
-    total
+    school.total_fish()
 }
 
 #[aoc(day6, part2)]
 pub fn part2(input: &FishSchool) -> u64 {
     let mut school = input.clone();
     school.step(256);
-    let total = school.total_fish();
-    assert_eq!(total, 1721148811504);
-    total
+    school.total_fish()
 }
 
 #[cfg(test)]
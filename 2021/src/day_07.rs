@@ -101,17 +101,13 @@ pub fn input_generator(input: &str) -> Vec<u32> {
 #[aoc(day7, part1)]
 pub fn part1(input: &[u32]) -> u32 {
     let best = find_best_position(input, Mode::M1);
-    let fuel = total_fuel(input, best, Mode::M1);
-    assert_eq!(fuel, 355592);
-    fuel
+    total_fuel(input, best, Mode::M1)
 }
 
 #[aoc(day7, part2)]
 pub fn part2(input: &[u32]) -> u32 {
     let best = find_best_position(input, Mode::M2);
-    let fuel = total_fuel(input, best, Mode::M2);
-    assert_eq!(fuel, 101618069);
-    fuel
+    total_fuel(input, best, Mode::M2)
 }
 
 #[cfg(test)]
@@ -219,16 +219,12 @@ pub fn input_generator(input: &str) -> Vec<Entry> {
 
 #[aoc(day8, part1)]
 pub fn part1(input: &[Entry]) -> usize {
-    let count = count_1478(input);
-    assert_eq!(count, 261);
-    count
+    count_1478(input)
 }
 
 #[aoc(day8, part2)]
 pub fn part2(input: &[Entry]) -> usize {
-    let value_sum = solve_entries(input);
-    assert_eq!(value_sum, 987553);
-    value_sum
+    solve_entries(input)
 }
 
 #[cfg(test)]
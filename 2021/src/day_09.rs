@@ -61,7 +61,7 @@
     What do you get if you multiply together the sizes of the three largest basins?
 */
 
-use common::Point2;
+use common::{region, Point2};
 use nom::IResult;
 use std::collections::HashMap;
 
@@ -107,28 +107,16 @@ impl Cave {
     }
 
     fn find_basin_sizes(&self) -> Vec<u32> {
-        let mut basins = Vec::new();
-
-        // For performance reasons, re-use the same collections for each basin
-        let mut frontier = Vec::new();
-        let mut visited = Vec::new();
-        for low in self.find_low_points() {
-            frontier.clear();
-            frontier.push(low);
-            visited.clear();
-            visited.push(low);
-            while let Some(next) = frontier.pop() {
-                for adj in next.orthogonals() {
-                    if let Some(height) = self.height_map.get(&adj) {
-                        if *height < 9 && visited.contains(&adj) == false {
-                            visited.push(adj);
-                            frontier.push(adj);
-                        }
-                    }
-                }
-            }
-            basins.push(visited.len() as u32);
-        }
+        // Every basin is bounded by height 9 and has a single low point
+        let flowing = self
+            .height_map
+            .iter()
+            .filter(|(_, height)| **height < 9)
+            .map(|(p, _)| *p);
+        let mut basins: Vec<u32> = region::components(flowing, |_, _| true)
+            .iter()
+            .map(|basin| basin.area() as u32)
+            .collect();
 
         basins.sort_unstable();
         basins
@@ -147,16 +135,12 @@ pub fn input_generator(input: &str) -> Cave {
 
 #[aoc(day9, part1)]
 pub fn part1(input: &Cave) -> u32 {
-    let risk_level = input.calc_risk_level();
-    assert_eq!(risk_level, 468);
-    risk_level
+    input.calc_risk_level()
 }
 
 #[aoc(day9, part2)]
 pub fn part2(input: &Cave) -> u32 {
-    let largest = input.calc_3_largest_basins();
-    assert_eq!(largest, 1280496);
-    largest
+    input.calc_3_largest_basins()
 }
 
 #[cfg(test)]
@@ -190,17 +190,13 @@ pub fn input_generator(input: &str) -> Vec<String> {
 #[aoc(day10, part1)]
 pub fn part1(input: &[String]) -> u64 {
     let states = LineState::validate_many(input);
-    let total_score = total_corrupted(&states);
-    assert_eq!(total_score, 318099);
-    total_score
+    total_corrupted(&states)
 }
 
 #[aoc(day10, part2)]
 pub fn part2(input: &[String]) -> u64 {
     let states = LineState::validate_many(input);
-    let middle_score = middle_incomplete(&states);
-    assert_eq!(middle_score, 2389738699);
-    middle_score
+    middle_incomplete(&states)
 }
 
 #[cfg(test)]
@@ -440,7 +440,6 @@ pub fn input_generator(input: &str) -> OctoGrid {
 pub fn part1(input: &OctoGrid) -> usize {
     let mut grid = input.clone();
     let (flashes, _) = grid.step(100);
-    assert_eq!(flashes, 1601);
     flashes
 }
 
@@ -448,7 +447,6 @@ pub fn part1(input: &OctoGrid) -> usize {
 pub fn part2(input: &OctoGrid) -> u32 {
     let mut grid = input.clone();
     let (_, sync_step) = grid.step(1000);
-    assert_eq!(sync_step, Some(368));
     sync_step.unwrap()
 }
 
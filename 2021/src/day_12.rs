@@ -259,17 +259,13 @@ pub fn input_generator(input: &str) -> CaveSystem {
 #[aoc(day12, part1)]
 pub fn part1(input: &CaveSystem) -> usize {
     let paths = input.enumerate_paths(Mode::M1);
-    let len = paths.len();
-    assert_eq!(len, 4304);
-    len
+    paths.len()
 }
 
 #[aoc(day12, part2)]
 pub fn part2(input: &CaveSystem) -> usize {
     let paths = input.enumerate_paths(Mode::M2);
-    let len = paths.len();
-    assert_eq!(len, 118242);
-    len
+    paths.len()
 }
 
 #[cfg(test)]
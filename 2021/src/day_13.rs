@@ -231,9 +231,7 @@ pub fn input_generator(input: &str) -> Paper {
 pub fn part1(input: &Paper) -> usize {
     let mut paper = input.clone();
     paper.fold_next();
-    let count = paper.count_dots();
-    assert_eq!(count, 755);
-    count
+    paper.count_dots()
 }
 
 #[aoc(day13, part2)]
@@ -241,19 +239,7 @@ pub fn part2(input: &Paper) -> String {
     let mut paper = input.clone();
     paper.fold_all();
     // input.blank_char = ' '; // Use this to read it more easily
-    let code = paper.to_string();
-    assert_eq!(
-        code,
-        "
-###..#....#..#...##.###..###...##...##.
-#..#.#....#.#.....#.#..#.#..#.#..#.#..#
-###..#....##......#.#..#.###..#..#.#...
-#..#.#....#.#.....#.###..#..#.####.#.##
-#..#.#....#.#..#..#.#.#..#..#.#..#.#..#
-###..####.#..#..##..#..#.###..#..#..###
-"
-    );
-    code
+    paper.to_string()
 }
 
 #[cfg(test)]
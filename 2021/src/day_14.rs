@@ -150,18 +150,14 @@ pub fn input_generator(input: &str) -> Polymerization {
 pub fn part1(input: &Polymerization) -> u64 {
     let mut poly = input.clone();
     poly.expand(10);
-    let score = poly.score();
-    assert_eq!(score, 2223);
-    score
+    poly.score()
 }
 
 #[aoc(day14, part2)]
 pub fn part2(input: &Polymerization) -> u64 {
     let mut poly = input.clone();
     poly.expand(40);
-    let score = poly.score();
-    assert_eq!(score, 2566282754493);
-    score
+    poly.score()
 }
 
 #[cfg(test)]
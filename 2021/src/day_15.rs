@@ -153,7 +153,7 @@
     Using the full map, what is the lowest total risk of any path from the top left to the bottom right?
 */
 
-use common::{modulo, Point2, Range2};
+use common::{modulo, search, Point2, Range2};
 use std::collections::HashMap;
 
 #[derive(Clone)]
@@ -209,47 +209,19 @@ impl Cave {
         modulo(risk + dx + dy - 1, 9) + 1
     }
 
-    fn find_low_risk_path(&mut self) -> u32 {
-        let mut graph: HashMap<Point2, u32> = HashMap::new();
-        let mut frontier: Vec<(Point2, u32)> = vec![(Point2::origin(), 0)];
+    fn find_low_risk_path(&self) -> u32 {
         let end = Point2 {
             x: self.range.x.1,
             y: self.range.y.1,
         };
-
-        'pathfind: while let Some((curr_point, curr_risk)) = frontier.pop() {
-            for adj in curr_point.orthogonals() {
-                if let Some(risk) = self.risk_map.get(&adj) {
-                    let next_risk = curr_risk + risk;
-                    let mut write = false;
-                    if let Some(existing) = graph.get(&adj) {
-                        // This location has already been seen before
-                        if next_risk < *existing {
-                            // This path is better. Replace the existing.
-                            write = true;
-                        } else {
-                            // Not better. Skip it.
-                        }
-                    } else {
-                        // This is the first time seeing this location
-                        write = true;
-                    }
-
-                    if write == true {
-                        graph.insert(adj, next_risk);
-                        frontier.push((adj, next_risk));
-                    }
-                }
-
-                if adj == end {
-                    break 'pathfind;
-                }
-            }
-
-            frontier.sort_unstable_by_key(|x| std::cmp::Reverse(x.1));
-        }
-
-        graph[&end]
+        let neighbors = |p: &Point2| {
+            p.orthogonals()
+                .filter_map(|adj| self.risk_map.get(&adj).map(|risk| (adj, *risk)))
+                .collect::<Vec<_>>()
+        };
+        search::dijkstra([Point2::origin()], neighbors, |p| *p == end)
+            .goal_distance()
+            .unwrap()
     }
 }
 
@@ -260,19 +232,14 @@ pub fn input_generator(input: &str) -> Cave {
 
 #[aoc(day15, part1)]
 pub fn part1(input: &Cave) -> u32 {
-    let mut cave = input.clone();
-    let risk = cave.find_low_risk_path();
-    assert_eq!(risk, 366);
-    risk
+    input.find_low_risk_path()
 }
 
 #[aoc(day15, part2)]
 pub fn part2(input: &Cave) -> u32 {
     let mut cave = input.clone();
     cave.x5();
-    let risk = cave.find_low_risk_path();
-    assert_eq!(risk, 2829);
-    risk
+    cave.find_low_risk_path()
 }
 
 #[cfg(test)]
@@ -347,7 +314,7 @@ mod test {
 
     #[test]
     fn test_find_low_risk_path() {
-        let mut cave = input_generator(EXAMPLE_INPUT1);
+        let cave = input_generator(EXAMPLE_INPUT1);
         let risk = cave.find_low_risk_path();
         assert_eq!(risk, 40);
     }
@@ -303,18 +303,14 @@ pub fn input_generator(input: &str) -> Vec<u8> {
 pub fn part1(input: &[u8]) -> u64 {
     let mut reader = BitReader::new(input);
     let packet = Packet::read(&mut reader).unwrap();
-    let version_sum = packet.version_sum();
-    assert_eq!(version_sum, 883);
-    version_sum
+    packet.version_sum()
 }
 
 #[aoc(day16, part2)]
 pub fn part2(input: &[u8]) -> u64 {
     let mut reader = BitReader::new(input);
     let packet = Packet::read(&mut reader).unwrap();
-    let value = packet.value();
-    assert_eq!(value, 1675198555015);
-    value
+    packet.value()
 }
 
 #[cfg(test)]
@@ -230,16 +230,12 @@ pub fn input_generator(input: &str) -> Range2 {
 
 #[aoc(day17, part1)]
 pub fn part1(input: &Range2) -> i32 {
-    let best = find_most_stylish(input);
-    assert_eq!(best, 5050);
-    best
+    find_most_stylish(input)
 }
 
 #[aoc(day17, part2)]
 pub fn part2(input: &Range2) -> u32 {
-    let count = count_hits(input);
-    assert_eq!(count, 2223);
-    count
+    count_hits(input)
 }
 
 #[cfg(test)]
@@ -287,7 +287,7 @@ impl Line {
                         Element::Number(right_n),
                         Element::PairClose,
                     ];
-                    self.data.splice(idx..=idx, new.into_iter());
+                    self.data.splice(idx..=idx, new);
                     return true;
                 }
             }
@@ -419,17 +419,13 @@ pub fn input_generator(input: &str) -> Vec<Line> {
 #[aoc(day18, part1)]
 pub fn part1(input: &[Line]) -> u32 {
     let lines = input.to_vec();
-    let ans = do_homework(lines);
-    assert_eq!(ans, 4347);
-    ans
+    do_homework(lines)
 }
 
 #[aoc(day18, part2)]
 pub fn part2(input: &[Line]) -> u32 {
     let lines = input.to_vec();
-    let ans = sum_of_two(lines);
-    assert_eq!(ans, 4721);
-    ans
+    sum_of_two(lines)
 }
 
 #[cfg(test)]
@@ -639,17 +639,13 @@ pub fn input_generator(input: &str) -> Vec<Scanner> {
 #[aoc(day19, part1)]
 pub fn part1(input: &[Scanner]) -> usize {
     let beacons = unique_beacons(input);
-    let num_beacons = beacons.len();
-    assert_eq!(num_beacons, 467);
-    num_beacons
+    beacons.len()
 }
 
 #[aoc(day19, part2)]
 pub fn part2(input: &[Scanner]) -> u32 {
     let oriented = find_all_positions(input);
-    let distance = largest_scanner_distance(&oriented);
-    assert_eq!(distance, 12226);
-    distance
+    largest_scanner_distance(&oriented)
 }
 
 #[cfg(test)]
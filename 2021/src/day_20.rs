@@ -210,38 +210,21 @@
     Start again with the original input image and apply the image enhancement algorithm 50 times. How many pixels are lit in the resulting image?
 */
 
-use common::{Point2, Range2};
-use std::collections::HashSet;
+use common::{Grid, Point2, Tiles};
 
 #[derive(Clone)]
 pub struct Image {
     algo: Vec<bool>,
-    data: HashSet<Point2>,
-    range: Range2,
+    pixels: Grid<bool>,
     infinity_is_lit: bool,
     infinity_flips: bool,
 }
 
 impl Image {
     fn from_string(input: &str) -> Self {
-        let mut lines = input.lines();
-        let algo: Vec<bool> = lines.next().unwrap().chars().map(|c| c == '#').collect();
-
-        let _ = lines.next();
-
-        let mut data = HashSet::new();
-        for (y, line) in lines.enumerate() {
-            for (x, c) in line.chars().enumerate() {
-                if c == '#' {
-                    data.insert(Point2 {
-                        x: x as i32,
-                        y: y as i32,
-                    });
-                }
-            }
-        }
-
-        let range = Point2::get_range(&data).unwrap();
+        let (algo, pixels) = input.split_once("\n\n").unwrap();
+        let algo: Vec<bool> = algo.chars().map(|c| c == '#').collect();
+        let pixels = Grid::from_string_with(pixels.trim(), |c| Some(c == '#'));
 
         let infinity_flips = match (algo[0], algo.last().unwrap()) {
             (false, false) => false,
@@ -252,53 +235,34 @@ impl Image {
 
         Self {
             algo,
-            data,
-            range,
+            pixels,
             infinity_is_lit: false,
             infinity_flips,
         }
     }
 
-    fn value(&self, p: &Point2) -> bool {
-        if self.range.contains(*p) == true {
-            self.data.contains(p)
-        } else if self.infinity_is_lit == true {
-            true
-        } else {
-            false
-        }
-    }
-
     fn neighbors_as_int(&self, p: &Point2) -> usize {
+        let pixels = self.pixels.infinite(self.infinity_is_lit);
         let mut n = 0;
         for y in [-1, 0, 1] {
             for x in [-1, 0, 1] {
                 n <<= 1;
-                n |= usize::from(self.value(&(p.x + x, p.y + y).into()));
+                n |= usize::from(pixels[(p.x + x, p.y + y).into()]);
             }
         }
         n
     }
 
     fn enhance(&mut self, n: u32) {
-        let mut new_pixels: Vec<Point2> = Vec::new();
         for _ in 0..n {
-            new_pixels.clear();
-
-            for y in self.range.y.0 - 1..=self.range.y.1 + 1 {
-                for x in self.range.x.0 - 1..=self.range.x.1 + 1 {
-                    let pixel = Point2 { x, y };
-                    let lookup = self.neighbors_as_int(&pixel);
-                    assert!(lookup < self.algo.len());
-                    if self.algo[lookup] == true {
-                        new_pixels.push(pixel);
-                    }
-                }
-            }
-
-            self.data.clear();
-            self.data.extend(new_pixels.drain(..));
-            self.range = Point2::get_range(&self.data).unwrap();
+            // The image grows by one pixel on every side, which shifts everything along by one
+            let width = self.pixels.width() + 2;
+            let height = self.pixels.height() + 2;
+            self.pixels = Grid::from_fn(width, height, |pixel| {
+                let lookup = self.neighbors_as_int(&(pixel - (1, 1)));
+                assert!(lookup < self.algo.len());
+                self.algo[lookup]
+            });
 
             if self.infinity_flips == true {
                 self.infinity_is_lit = !self.infinity_is_lit;
@@ -307,15 +271,15 @@ impl Image {
     }
 
     fn count_lit_pixels(&self) -> usize {
-        self.data.len()
+        self.pixels.values().filter(|lit| **lit == true).count()
     }
 }
 
 impl std::fmt::Display for Image {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        for y in self.range.y.0..=self.range.y.1 {
-            for x in self.range.x.0..=self.range.x.1 {
-                if self.value(&(x, y).into()) == true {
+        for row in self.pixels.rows() {
+            for lit in row {
+                if *lit == true {
                     write!(f, "#")?;
                 } else {
                     write!(f, ".")?;
@@ -336,18 +300,14 @@ pub fn input_generator(input: &str) -> Image {
 pub fn part1(input: &Image) -> usize {
     let mut image = input.clone();
     image.enhance(2);
-    let lit = image.count_lit_pixels();
-    assert_eq!(lit, 5563);
-    lit
+    image.count_lit_pixels()
 }
 
 #[aoc(day20, part2)]
 pub fn part2(input: &Image) -> usize {
     let mut image = input.clone();
     image.enhance(50);
-    let lit = image.count_lit_pixels();
-    assert_eq!(lit, 19743);
-    lit
+    image.count_lit_pixels()
 }
 
 #[cfg(test)]
@@ -48,9 +48,9 @@
     Using your given starting positions, determine every possible outcome. Find the player that wins in more universes; in how many universes does that player win?
 */
 
-use common::{modulo, unsigned};
+use common::{modulo, state_search, unsigned};
 use nom::{bytes::complete::tag, character::complete::multispace0, sequence::tuple, IResult};
-use std::{cmp::max, collections::HashMap};
+use std::cmp::max;
 
 struct DeterministicGame {
     p1: (u8, u32),    // Current pawn, score
@@ -151,57 +151,45 @@ impl DiracState {
 }
 
 struct DiracGame {
-    universes: HashMap<DiracState, u64>,
+    start: DiracState,
     p1_wins: u64,
     p2_wins: u64,
 }
 
 impl DiracGame {
     fn from_starting(start: &(u8, u8)) -> Self {
-        let mut universes = HashMap::new();
-        universes.insert(
-            DiracState {
+        Self {
+            start: DiracState {
                 p1: (start.0, 0),
                 p2: (start.1, 0),
                 whose_turn: false,
             },
-            1,
-        );
-
-        Self {
-            universes,
             p1_wins: 0,
             p2_wins: 0,
         }
     }
 
-    fn split(&mut self) {
-        let mut next = HashMap::new();
-        for (curr_state, curr_count) in &self.universes {
-            for (roll, roll_count) in [(3, 1), (4, 3), (5, 6), (6, 7), (7, 6), (8, 3), (9, 1)] {
-                let mut next_state = curr_state.clone();
-                if let Some(winner) = next_state.turn(roll) {
-                    if winner == false {
-                        self.p1_wins += curr_count * roll_count;
-                    } else {
-                        self.p2_wins += curr_count * roll_count;
-                    }
-                } else {
-                    let entry = next.entry(next_state).or_insert(0);
-                    *entry += curr_count * roll_count;
+    // The number of universes in which each player wins, starting from the given state
+    fn wins(state: &DiracState, recurse: &mut dyn FnMut(&DiracState) -> (u64, u64)) -> (u64, u64) {
+        let mut wins = (0, 0);
+        for (roll, roll_count) in [(3, 1), (4, 3), (5, 6), (6, 7), (7, 6), (8, 3), (9, 1)] {
+            let mut next_state = state.clone();
+            match next_state.turn(roll) {
+                Some(false) => wins.0 += roll_count,
+                Some(true) => wins.1 += roll_count,
+                None => {
+                    let next_wins = recurse(&next_state);
+                    wins.0 += next_wins.0 * roll_count;
+                    wins.1 += next_wins.1 * roll_count;
                 }
             }
         }
-        self.universes = next;
+        wins
     }
 
     fn play(&mut self) -> u64 {
-        loop {
-            if self.universes.is_empty() == true {
-                return max(self.p1_wins, self.p2_wins);
-            }
-            self.split();
-        }
+        (self.p1_wins, self.p2_wins) = state_search::memoize(&self.start, Self::wins);
+        max(self.p1_wins, self.p2_wins)
     }
 }
 
@@ -225,17 +213,13 @@ pub fn input_generator(input: &str) -> (u8, u8) {
 #[aoc(day21, part1)]
 pub fn part1(input: &(u8, u8)) -> u32 {
     let mut game = DeterministicGame::from_starting(input);
-    let score = game.play();
-    assert_eq!(score, 711480);
-    score
+    game.play()
 }
 
 #[aoc(day21, part2)]
 pub fn part2(input: &(u8, u8)) -> u64 {
     let mut game = DiracGame::from_starting(input);
-    let score = game.play();
-    assert_eq!(score, 265845890886828);
-    score
+    game.play()
 }
 
 #[cfg(test)]
@@ -339,17 +339,13 @@ pub fn part1(input: &[Cuboid]) -> usize {
     let mut cuboids = input.to_vec();
     restrict_range_50(&mut cuboids);
     let grid = reboot(&cuboids);
-    let count = count_on(&grid);
-    assert_eq!(count, 587785);
-    count
+    count_on(&grid)
 }
 
 #[aoc(day22, part2)]
 pub fn part2(input: &[Cuboid]) -> usize {
     let grid = reboot(input);
-    let count = count_on(&grid);
-    assert_eq!(count, 1167985679908143);
-    count
+    count_on(&grid)
 }
 
 #[cfg(test)]
@@ -685,17 +685,13 @@ impl<const N: usize> std::fmt::Display for State<N> {
 #[aoc(day23, part1)]
 pub fn part1(input: &str) -> u32 {
     let burrow = Burrow::<2>::from_string(input);
-    let cost = burrow.organize();
-    assert_eq!(cost, 12240);
-    cost
+    burrow.organize()
 }
 
 #[aoc(day23, part2)]
 pub fn part2(input: &str) -> u32 {
     let burrow = Burrow::<4>::from_string(input);
-    let cost = burrow.organize();
-    assert_eq!(cost, 44618);
-    cost
+    burrow.organize()
 }
 
 #[cfg(test)]
@@ -61,7 +61,10 @@
     What is the smallest model number accepted by MONAD?
 */
 
-use common::signed;
+use common::{
+    machine::{Cpu, Flow, InstructionSet, Machine, Registers},
+    signed,
+};
 use nom::{
     branch::alt,
     bytes::complete::tag,
@@ -71,7 +74,9 @@ use nom::{
     sequence::{preceded, separated_pair},
     IResult,
 };
+use std::collections::VecDeque;
 
+#[derive(Clone, Copy)]
 pub enum Reg {
     W,
     X,
@@ -96,6 +101,7 @@ impl Reg {
     }
 }
 
+#[derive(Clone, Copy)]
 pub enum Operand {
     Reg(Reg),
     Num(i64),
@@ -108,6 +114,7 @@ impl Operand {
     }
 }
 
+#[derive(Clone)]
 pub enum Op {
     Input(Reg),
     Add(Reg, Operand),
@@ -165,88 +172,81 @@ impl Op {
     }
 }
 
-struct Alu {
-    regs: [i64; 4],
+impl InstructionSet for Op {
+    type Registers = Alu;
+
+    fn execute(&self, alu: &mut Alu) -> Flow {
+        match self {
+            Self::Input(r) => {
+                *alu.reg_mut(r) = alu.input.pop_front().unwrap();
+            }
+            Self::Add(r, o) => {
+                let b = alu.operand(o);
+                *alu.reg_mut(r) += b;
+            }
+            Self::Mul(r, o) => {
+                let b = alu.operand(o);
+                *alu.reg_mut(r) *= b;
+            }
+            Self::Div(r, o) => {
+                let b = alu.operand(o);
+                assert_ne!(b, 0);
+                *alu.reg_mut(r) /= b;
+            }
+            Self::Mod(r, o) => {
+                let b = alu.operand(o);
+                let a = alu.reg_mut(r);
+                assert!(*a >= 0);
+                assert!(b > 0);
+                *a %= b;
+            }
+            Self::Eql(r, o) => {
+                let b = alu.operand(o);
+                let a = alu.reg_mut(r);
+                *a = i64::from(*a == b);
+            }
+        }
+        Flow::Next
+    }
+}
+
+pub struct Alu {
+    regs: Registers<i64, 4>,
+    input: VecDeque<i64>,
 }
 
 impl Alu {
     fn new() -> Self {
-        Self { regs: [0; 4] }
+        Self {
+            regs: Registers::default(),
+            input: VecDeque::new(),
+        }
     }
 
     fn reg(&self, reg: &Reg) -> i64 {
-        match reg {
-            Reg::W => self.regs[0],
-            Reg::X => self.regs[1],
-            Reg::Y => self.regs[2],
-            Reg::Z => self.regs[3],
-        }
+        self.regs[*reg as usize]
     }
 
     fn reg_mut(&mut self, reg: &Reg) -> &mut i64 {
-        match reg {
-            Reg::W => &mut self.regs[0],
-            Reg::X => &mut self.regs[1],
-            Reg::Y => &mut self.regs[2],
-            Reg::Z => &mut self.regs[3],
+        &mut self.regs[*reg as usize]
+    }
+
+    fn operand(&self, operand: &Operand) -> i64 {
+        match operand {
+            Operand::Reg(reg) => self.reg(reg),
+            Operand::Num(num) => *num,
         }
     }
 
-    fn execute<I>(&mut self, ops: &[Op], mut input: I)
+    fn execute<I>(&mut self, ops: &[Op], input: I)
     where
         I: Iterator<Item = i64>,
     {
-        for op in ops {
-            match op {
-                Op::Input(r) => {
-                    let a = self.reg_mut(r);
-                    *a = input.next().unwrap();
-                }
-                Op::Add(r, o) => {
-                    let b = match o {
-                        Operand::Reg(or) => self.reg(or),
-                        Operand::Num(num) => *num,
-                    };
-                    let a = self.reg_mut(r);
-                    *a += b;
-                }
-                Op::Mul(r, o) => {
-                    let b = match o {
-                        Operand::Reg(or) => self.reg(or),
-                        Operand::Num(num) => *num,
-                    };
-                    let a = self.reg_mut(r);
-                    *a *= b;
-                }
-                Op::Div(r, o) => {
-                    let b = match o {
-                        Operand::Reg(or) => self.reg(or),
-                        Operand::Num(num) => *num,
-                    };
-                    let a = self.reg_mut(r);
-                    assert_ne!(b, 0);
-                    *a /= b;
-                }
-                Op::Mod(r, o) => {
-                    let b = match o {
-                        Operand::Reg(or) => self.reg(or),
-                        Operand::Num(num) => *num,
-                    };
-                    let a = self.reg_mut(r);
-                    assert!(*a >= 0);
-                    assert!(b > 0);
-                    *a %= b;
-                }
-                Op::Eql(r, o) => {
-                    let b = match o {
-                        Operand::Reg(or) => self.reg(or),
-                        Operand::Num(num) => *num,
-                    };
-                    let a = self.reg_mut(r);
-                    *a = i64::from(*a == b);
-                }
-            }
-        }
+        let alu = std::mem::replace(self, Self::new());
+        let mut cpu = Cpu::new(ops.to_vec(), alu);
+        cpu.regs.input.extend(input);
+        cpu.run();
+        *self = cpu.regs;
     }
 }
 
@@ -264,25 +264,8 @@ fn digits_from_n(mut n: i64) -> Vec<i64> {
     digits
 }
 
-fn digits_to_n(digits: [i64; 14]) -> i64 {
-    let s = format!(
-        "{}{}{}{}{}{}{}{}{}{}{}{}{}{}",
-        digits[0],
-        digits[1],
-        digits[2],
-        digits[3],
-        digits[4],
-        digits[5],
-        digits[6],
-        digits[7],
-        digits[8],
-        digits[9],
-        digits[10],
-        digits[11],
-        digits[12],
-        digits[13]
-    );
-    s.parse().unwrap()
+fn digits_to_n(digits: &[i64]) -> i64 {
+    digits.iter().fold(0, |n, d| n * 10 + d)
 }
 
 fn monad_is_valid(ops: &[Op], n: i64) -> bool {
@@ -292,8 +275,8 @@ fn monad_is_valid(ops: &[Op], n: i64) -> bool {
 }
 
 /*
-    I couldn't figure out how to programmatically reach the answers so instead I analyzed the input program. Through
-    observation there are 14 blocks of code which are all nearly identical except for a few specific constants. Each
+    At first I couldn't figure out how to programmatically reach the answers so instead I analyzed the input program.
+    Through observation there are 14 blocks of code which are all nearly identical except for a few specific constants. Each
     block begins with an input statement and ends just before the next input statement. Thus there is one block per
     input digit. Each block looks like this:
 
@@ -394,63 +377,344 @@ fn monad_is_valid(ops: &[Op], n: i64) -> bool {
     In order to find the highest (lowest) valid value, we can then pick the highest (lowest) possible value for each
     digit and assign the others according to the requirements.
 
-    Because the above analysis gives a pre-determined answer, the implementation of the ALU above is not actually used
-    except to validate the analysis and its answers.
+    The symbolic ALU below automates this analysis so that it works for any input. Each register holds an expression
+    of the input digits, which is simplified using the range of values it can take. For example z % 26 is just the
+    last w + C pushed onto z, and x == w is always false when x can't be between 1 and 9. When eql can't be decided,
+    execution forks on whether it's true or false. Each path through the program ends with a set of constraints on
+    the digits (w4 == w3 + 6, etc.) and the highest (lowest) valid number is found by searching the digits from most
+    significant to least, abandoning any choice which makes a constraint impossible.
 */
 
-fn monad_is_valid_shortcut(n: i64) -> bool {
-    let digits = digits_from_n(n);
-    if digits[3] != digits[2] + 6 // w4 == w3 + 6
-        || digits[6] != digits[5] + 1 // w7 == w6 + 1
-        || digits[7] != digits[4] + 5 // w8 == w5 + 5
-        || digits[8] != digits[1] - 1 // w9 == w2 - 1
-        || digits[11] != digits[10] + 8 // w12 == w11 + 8
-        || digits[12] != digits[9] - 2 // w13 == w10 - 2
-        || digits[13] != digits[0] - 8
-    // w14 == w1 - 8
-    {
-        false
-    } else {
-        true
+#[derive(Clone, Debug, Eq, PartialEq)]
+enum Expr {
+    Const(i64),
+    Input(usize), // Index of the input digit
+    Add(Box<Expr>, Box<Expr>),
+    Mul(Box<Expr>, Box<Expr>),
+    Div(Box<Expr>, Box<Expr>),
+    Mod(Box<Expr>, Box<Expr>),
+}
+
+impl Expr {
+    // The simplifying constructors below only cover what MONAD needs, anything else is kept as is
+    fn add(a: Self, b: Self) -> Self {
+        match (a, b) {
+            (Self::Const(a), Self::Const(b)) => Self::Const(a + b),
+            (Self::Const(0), b) => b,
+            (a, Self::Const(0)) => a,
+            (a @ Self::Const(_), b) => Self::add(b, a),
+            (Self::Add(x, c), Self::Const(b)) if matches!(*c, Self::Const(_)) => {
+                Self::add(*x, Self::add(*c, Self::Const(b)))
+            }
+            (a, b) => Self::Add(Box::new(a), Box::new(b)),
+        }
+    }
+
+    fn mul(a: Self, b: Self) -> Self {
+        match (a, b) {
+            (Self::Const(a), Self::Const(b)) => Self::Const(a * b),
+            (Self::Const(0), _) | (_, Self::Const(0)) => Self::Const(0),
+            (Self::Const(1), b) => b,
+            (a, Self::Const(1)) => a,
+            (a @ Self::Const(_), b) => Self::mul(b, a),
+            (a, b) => Self::Mul(Box::new(a), Box::new(b)),
+        }
+    }
+
+    fn div(a: Self, b: Self) -> Self {
+        match (a, b) {
+            (Self::Const(a), Self::Const(b)) if b != 0 => Self::Const(a / b),
+            (a, Self::Const(1)) => a,
+            (a, Self::Const(k)) if k > 0 => {
+                let (lo, hi) = a.range(&[]);
+                if lo >= 0 && hi < k {
+                    return Self::Const(0);
+                }
+                // (p * k + q) / k == p as long as q is a valid remainder
+                match a.split_multiple(k) {
+                    Some((p, q)) if q.is_remainder_of(k) => p,
+                    _ => Self::Div(Box::new(a), Box::new(Self::Const(k))),
+                }
+            }
+            (a, b) => Self::Div(Box::new(a), Box::new(b)),
+        }
+    }
+
+    fn modulo(a: Self, b: Self) -> Self {
+        match (a, b) {
+            (Self::Const(a), Self::Const(b)) if b != 0 => Self::Const(a % b),
+            (a, Self::Const(k)) if k > 0 => {
+                if a.is_remainder_of(k) {
+                    return a;
+                }
+                // (p * k + q) % k == q % k
+                match a.split_multiple(k) {
+                    Some((_, q)) if q.range(&[]).0 >= 0 => Self::modulo(q, Self::Const(k)),
+                    _ => Self::Mod(Box::new(a), Box::new(Self::Const(k))),
+                }
+            }
+            (a, b) => Self::Mod(Box::new(a), Box::new(b)),
+        }
+    }
+
+    // None if the result depends on the inputs, there's no expression for it since execution forks instead
+    fn eql(a: &Self, b: &Self) -> Option<bool> {
+        if a == b {
+            return Some(true);
+        }
+        let (a_lo, a_hi) = a.range(&[]);
+        let (b_lo, b_hi) = b.range(&[]);
+        if a_hi < b_lo || b_hi < a_lo {
+            Some(false)
+        } else if a_lo == a_hi && b_lo == b_hi {
+            Some(a_lo == b_lo)
+        } else {
+            None
+        }
+    }
+
+    // Splits into a non-negative multiple of k plus whatever is left
+    fn split_multiple(&self, k: i64) -> Option<(Self, Self)> {
+        match self {
+            Self::Add(a, q) => match a.as_ref() {
+                Self::Mul(p, c) if **c == Self::Const(k) && p.range(&[]).0 >= 0 => {
+                    Some((p.as_ref().clone(), q.as_ref().clone()))
+                }
+                _ => None,
+            },
+            Self::Mul(p, c) if **c == Self::Const(k) && p.range(&[]).0 >= 0 => {
+                Some((p.as_ref().clone(), Self::Const(0)))
+            }
+            _ => None,
+        }
+    }
+
+    fn is_remainder_of(&self, k: i64) -> bool {
+        let (lo, hi) = self.range(&[]);
+        lo >= 0 && hi < k
+    }
+
+    // Inclusive bounds on the value given the known input digits, unknown digits are anything from 1 to 9
+    fn range(&self, inputs: &[Option<i64>]) -> (i64, i64) {
+        let corners = |a: (i64, i64), b: (i64, i64), f: fn(i64, i64) -> i64| {
+            let values = [f(a.0, b.0), f(a.0, b.1), f(a.1, b.0), f(a.1, b.1)];
+            (*values.iter().min().unwrap(), *values.iter().max().unwrap())
+        };
+
+        match self {
+            Self::Const(n) => (*n, *n),
+            Self::Input(idx) => match inputs.get(*idx) {
+                Some(Some(n)) => (*n, *n),
+                _ => (1, 9),
+            },
+            Self::Add(a, b) => corners(a.range(inputs), b.range(inputs), i64::saturating_add),
+            Self::Mul(a, b) => corners(a.range(inputs), b.range(inputs), i64::saturating_mul),
+            Self::Div(a, b) => {
+                let b = b.range(inputs);
+                if b.0 > 0 || b.1 < 0 {
+                    corners(a.range(inputs), b, i64::saturating_div)
+                } else {
+                    (i64::MIN, i64::MAX)
+                }
+            }
+            Self::Mod(a, b) => {
+                let (a_lo, a_hi) = a.range(inputs);
+                let (b_lo, b_hi) = b.range(inputs);
+                if a_lo == a_hi && b_lo == b_hi && b_lo != 0 {
+                    (a_lo % b_lo, a_lo % b_lo)
+                } else if a_lo >= 0 && a_hi < b_lo {
+                    (a_lo, a_hi)
+                } else if a_lo >= 0 && b_lo > 0 {
+                    (0, b_hi - 1)
+                } else {
+                    (i64::MIN, i64::MAX)
+                }
+            }
+        }
     }
 }
 
-fn highest_valid() -> i64 {
-    let w1 = 9;
-    let w2 = 9;
-    let w3 = 3;
-    let w4 = w3 + 6;
-    let w5 = 4;
-    let w6 = 8;
-    let w7 = w6 + 1;
-    let w8 = w5 + 5;
-    let w9 = w2 - 1;
-    let w10 = 9;
-    let w11 = 1;
-    let w12 = w11 + 8;
-    let w13 = w10 - 2;
-    let w14 = w1 - 8;
-
-    digits_to_n([w1, w2, w3, w4, w5, w6, w7, w8, w9, w10, w11, w12, w13, w14])
+#[derive(Clone, Debug)]
+enum Constraint {
+    Eq(Expr, Expr),
+    Ne(Expr, Expr),
+}
+
+impl Constraint {
+    // Whether the constraint can still hold once the rest of the inputs are known
+    fn is_satisfiable(&self, inputs: &[Option<i64>]) -> bool {
+        match self {
+            Self::Eq(a, b) => {
+                let (a_lo, a_hi) = a.range(inputs);
+                let (b_lo, b_hi) = b.range(inputs);
+                a_lo <= b_hi && b_lo <= a_hi
+            }
+            Self::Ne(a, b) => {
+                let (a_lo, a_hi) = a.range(inputs);
+                let (b_lo, b_hi) = b.range(inputs);
+                !(a_lo == a_hi && b_lo == b_hi && a_lo == b_lo)
+            }
+        }
+    }
 }
 
-fn lowest_valid() -> i64 {
-    let w1 = 9;
-    let w2 = 2;
-    let w3 = 1;
-    let w4 = w3 + 6;
-    let w5 = 1;
-    let w6 = 1;
-    let w7 = w6 + 1;
-    let w8 = w5 + 5;
-    let w9 = w2 - 1;
-    let w10 = 3;
-    let w11 = 1;
-    let w12 = w11 + 8;
-    let w13 = w10 - 2;
-    let w14 = w1 - 8;
-
-    digits_to_n([w1, w2, w3, w4, w5, w6, w7, w8, w9, w10, w11, w12, w13, w14])
+// Runs the ALU with every register holding an expression of the inputs. Whenever eql can't be decided the execution
+// forks, one path assuming the operands are equal and the other assuming they aren't.
+#[derive(Clone)]
+struct SymbolicAlu {
+    regs: [Expr; 4],
+    inputs: usize,
+    constraints: Vec<Constraint>,
+}
+
+impl SymbolicAlu {
+    fn new() -> Self {
+        Self {
+            regs: [
+                Expr::Const(0),
+                Expr::Const(0),
+                Expr::Const(0),
+                Expr::Const(0),
+            ],
+            inputs: 0,
+            constraints: Vec::new(),
+        }
+    }
+
+    fn operand(&self, operand: &Operand) -> Expr {
+        match operand {
+            Operand::Reg(reg) => self.regs[*reg as usize].clone(),
+            Operand::Num(num) => Expr::Const(*num),
+        }
+    }
+
+    fn execute(mut self, op: &Op) -> Vec<Self> {
+        let (reg, value) = match op {
+            Op::Input(r) => {
+                self.inputs += 1;
+                (r, Expr::Input(self.inputs - 1))
+            }
+            Op::Add(r, o) => (
+                r,
+                Expr::add(self.regs[*r as usize].clone(), self.operand(o)),
+            ),
+            Op::Mul(r, o) => (
+                r,
+                Expr::mul(self.regs[*r as usize].clone(), self.operand(o)),
+            ),
+            Op::Div(r, o) => (
+                r,
+                Expr::div(self.regs[*r as usize].clone(), self.operand(o)),
+            ),
+            Op::Mod(r, o) => (
+                r,
+                Expr::modulo(self.regs[*r as usize].clone(), self.operand(o)),
+            ),
+            Op::Eql(r, o) => {
+                let a = self.regs[*r as usize].clone();
+                let b = self.operand(o);
+                match Expr::eql(&a, &b) {
+                    Some(equal) => (r, Expr::Const(i64::from(equal))),
+                    None => {
+                        let mut not_equal = self.clone();
+                        not_equal.regs[*r as usize] = Expr::Const(0);
+                        not_equal
+                            .constraints
+                            .push(Constraint::Ne(a.clone(), b.clone()));
+                        self.regs[*r as usize] = Expr::Const(1);
+                        self.constraints.push(Constraint::Eq(a, b));
+                        return vec![self, not_equal];
+                    }
+                }
+            }
+        };
+        self.regs[*reg as usize] = value;
+        vec![self]
+    }
+}
+
+// The sets of constraints on the input digits under which MONAD finishes with z == 0
+struct Monad {
+    digits: usize,
+    paths: Vec<Vec<Constraint>>,
+}
+
+impl Monad {
+    fn analyze(ops: &[Op]) -> Self {
+        let mut alus = vec![SymbolicAlu::new()];
+        for op in ops {
+            alus = alus.into_iter().flat_map(|alu| alu.execute(op)).collect();
+        }
+
+        let digits = alus.first().map_or(0, |alu| alu.inputs);
+        let paths = alus
+            .into_iter()
+            .filter_map(|mut alu| {
+                let z = alu.regs[Reg::Z as usize].clone();
+                if z != Expr::Const(0) {
+                    alu.constraints.push(Constraint::Eq(z, Expr::Const(0)));
+                }
+                let unknown = vec![None; digits];
+                alu.constraints
+                    .iter()
+                    .all(|c| c.is_satisfiable(&unknown))
+                    .then_some(alu.constraints)
+            })
+            .collect();
+        Self { digits, paths }
+    }
+
+    // First number found on each path, picking each digit in turn from the given order and backtracking whenever a
+    // constraint can no longer hold
+    fn search(&self, order: &[i64]) -> Vec<i64> {
+        fn assign(
+            path: &[Constraint],
+            order: &[i64],
+            inputs: &mut Vec<Option<i64>>,
+            idx: usize,
+        ) -> bool {
+            if idx == inputs.len() {
+                return true;
+            }
+            for &digit in order {
+                inputs[idx] = Some(digit);
+                if path.iter().all(|c| c.is_satisfiable(inputs))
+                    && assign(path, order, inputs, idx + 1)
+                {
+                    return true;
+                }
+            }
+            inputs[idx] = None;
+            false
+        }
+
+        self.paths
+            .iter()
+            .filter_map(|path| {
+                let mut inputs = vec![None; self.digits];
+                assign(path, order, &mut inputs, 0)
+                    .then(|| digits_to_n(&inputs.into_iter().flatten().collect::<Vec<i64>>()))
+            })
+            .collect()
+    }
+}
+
+fn highest_valid(ops: &[Op]) -> i64 {
+    let monad = Monad::analyze(ops);
+    monad
+        .search(&[9, 8, 7, 6, 5, 4, 3, 2, 1])
+        .into_iter()
+        .max()
+        .expect("No valid model number")
+}
+
+fn lowest_valid(ops: &[Op]) -> i64 {
+    let monad = Monad::analyze(ops);
+    monad
+        .search(&[1, 2, 3, 4, 5, 6, 7, 8, 9])
+        .into_iter()
+        .min()
+        .expect("No valid model number")
 }
 
 #[aoc_generator(day24)]
@@ -460,25 +724,15 @@ pub fn input_generator(input: &str) -> Vec<Op> {
 
 #[aoc(day24, part1)]
 pub fn part1(input: &[Op]) -> i64 {
-    let answer = highest_valid();
+    let answer = highest_valid(input);
     assert!(monad_is_valid(input, answer));
-    assert_eq!(
-        monad_is_valid(input, answer),
-        monad_is_valid_shortcut(answer)
-    );
-    assert_eq!(answer, 99394899891971);
     answer
 }
 
 #[aoc(day24, part2)]
 pub fn part2(input: &[Op]) -> i64 {
-    let answer = lowest_valid();
+    let answer = lowest_valid(input);
     assert!(monad_is_valid(input, answer));
-    assert_eq!(
-        monad_is_valid(input, answer),
-        monad_is_valid_shortcut(answer)
-    );
-    assert_eq!(answer, 92171126131911);
     answer
 }
 
@@ -562,6 +816,7 @@ mod w 2
     #[test]
     fn test_monads() {
         let ops = input_generator(include_str!("../input/2021/day24.txt"));
+        let monad = Monad::analyze(&ops);
         for w1 in (9..=9).rev() {
             let w14 = w1 - 8;
             for w2 in (2..=9).rev() {
@@ -576,11 +831,11 @@ mod w 2
                                 let w13 = w10 - 2;
                                 for w11 in (1..=1).rev() {
                                     let w12 = w11 + 8;
-                                    let n = digits_to_n([
+                                    let n = digits_to_n(&[
                                         w1, w2, w3, w4, w5, w6, w7, w8, w9, w10, w11, w12, w13, w14,
                                     ]);
                                     assert!(monad_is_valid(&ops, n));
-                                    assert!(monad_is_valid_shortcut(n));
+                                    assert!(monad.is_valid(n));
                                 }
                             }
                         }
@@ -589,4 +844,91 @@ mod w 2
             }
         }
     }
+
+    impl Monad {
+        fn is_valid(&self, n: i64) -> bool {
+            self.is_valid_digits(&digits_from_n(n))
+        }
+
+        fn is_valid_digits(&self, digits: &[i64]) -> bool {
+            assert_eq!(digits.len(), self.digits);
+            let inputs: Vec<Option<i64>> = digits.iter().copied().map(Some).collect();
+            self.paths
+                .iter()
+                .any(|path| path.iter().all(|c| c.is_satisfiable(&inputs)))
+        }
+    }
+
+    // Builds a MONAD-shaped program from the A, B and C constants of each block
+    fn synthetic_monad(blocks: &[(i64, i64, i64)]) -> String {
+        blocks
+            .iter()
+            .map(|(a, b, c)| {
+                format!(
+                    "inp w\nmul x 0\nadd x z\nmod x 26\ndiv z {a}\nadd x {b}\neql x w\neql x 0\nmul y 0\n\
+                     add y 25\nmul y x\nadd y 1\nmul z y\nmul y 0\nadd y w\nadd y {c}\nmul y x\nadd z y\n"
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_symbolic_synthetic() {
+        // Pairs up digits 1 and 6, 2 and 3, and 4 and 5 with different constants to the real input:
+        //  w3 == w2 + 9 - 11, w5 == w4 + 2 - 5, w6 == w1 + 4 - 1
+        let program = synthetic_monad(&[
+            (1, 12, 4),
+            (1, 14, 9),
+            (26, -11, 7),
+            (1, 10, 2),
+            (26, -5, 13),
+            (26, -1, 5),
+        ]);
+        let ops = input_generator(&program);
+        let monad = Monad::analyze(&ops);
+        assert_eq!(monad.digits, 6);
+        assert_eq!(monad.paths.len(), 1);
+
+        let mut valid = Vec::new();
+        for n in 0..9_i64.pow(6) {
+            let digits: Vec<i64> = (0..6).rev().map(|i| n / 9_i64.pow(i) % 9 + 1).collect();
+            let mut alu = Alu::new();
+            alu.execute(&ops, digits.iter().copied());
+            let brute_force = alu.reg(&Reg::Z) == 0;
+            assert_eq!(monad.is_valid_digits(&digits), brute_force);
+            if brute_force {
+                assert_eq!(digits[2], digits[1] - 2);
+                assert_eq!(digits[4], digits[3] - 3);
+                assert_eq!(digits[5], digits[0] + 3);
+                valid.push(digits_to_n(&digits));
+            }
+        }
+        assert_eq!(valid.len(), 6 * 7 * 6);
+        assert_eq!(highest_valid(&ops), *valid.iter().max().unwrap());
+        assert_eq!(lowest_valid(&ops), *valid.iter().min().unwrap());
+        assert_eq!(highest_valid(&ops), 697969);
+        assert_eq!(lowest_valid(&ops), 131414);
+    }
+
+    #[test]
+    fn test_symbolic() {
+        // z ends up as 0 as long as the second digit isn't three times the first
+        let ops = input_generator(EXAMPLE_INPUT2);
+        assert_eq!(highest_valid(&ops), 99);
+        assert_eq!(lowest_valid(&ops), 11);
+
+        let ops = input_generator(include_str!("../input/2021/day24.txt"));
+        let monad = Monad::analyze(&ops);
+        assert_eq!(monad.paths.len(), 1);
+        for n in [
+            11111111111111,
+            99999999999999,
+            99394899891971,
+            99394899891972,
+            92171126131911,
+            92171126131912,
+        ] {
+            assert_eq!(monad.is_valid(n), monad_is_valid(&ops, n));
+        }
+    }
 }
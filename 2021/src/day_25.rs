@@ -403,9 +403,7 @@ pub fn input_generator(input: &str) -> Floor {
 #[aoc(day25, part1)]
 pub fn part1(input: &Floor) -> u32 {
     let mut floor = input.clone();
-    let answer = floor.find_no_movement();
-    assert_eq!(answer, 482);
-    answer
+    floor.find_no_movement()
 }
 
 #[cfg(test)]
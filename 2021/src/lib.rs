@@ -1,6 +1,6 @@
 #![deny(clippy::allow_attributes)]
+#![cfg_attr(test, expect(clippy::bool_assert_comparison))]
 #![expect(clippy::bool_comparison)]
-#![expect(clippy::needless_bool)]
 #![forbid(unsafe_code)]
 
 #[macro_use]
@@ -79,16 +79,12 @@ pub fn input_generator(input: &str) -> Vec<Vec<u64>> {
 
 #[aoc(day1, part1)]
 pub fn part1(input: &[Vec<u64>]) -> u64 {
-    let most = find_top_calories(input, 1);
-    assert_eq!(most, 67633);
-    most
+    find_top_calories(input, 1)
 }
 
 #[aoc(day1, part2)]
 pub fn part2(input: &[Vec<u64>]) -> u64 {
-    let top3 = find_top_calories(input, 3);
-    assert_eq!(top3, 199628);
-    top3
+    find_top_calories(input, 3)
 }
 
 #[cfg(test)]
@@ -182,16 +182,12 @@ pub fn input_generator(input: &str) -> Vec<Strategy> {
 
 #[aoc(day2, part1)]
 pub fn part1(input: &[Strategy]) -> u64 {
-    let score = score1(input);
-    assert_eq!(score, 11475);
-    score
+    score1(input)
 }
 
 #[aoc(day2, part2)]
 pub fn part2(input: &[Strategy]) -> u64 {
-    let score = score2(input);
-    assert_eq!(score, 16862);
-    score
+    score2(input)
 }
 
 #[cfg(test)]
@@ -106,16 +106,12 @@ pub fn input_generator(input: &str) -> Vec<(HashSet<char>, HashSet<char>)> {
 
 #[aoc(day3, part1)]
 pub fn part1(input: &[(HashSet<char>, HashSet<char>)]) -> u64 {
-    let total = total_priority(input);
-    assert_eq!(total, 8515);
-    total
+    total_priority(input)
 }
 
 #[aoc(day3, part2)]
 pub fn part2(input: &[(HashSet<char>, HashSet<char>)]) -> u64 {
-    let total = group_priority(input);
-    assert_eq!(total, 2434);
-    total
+    group_priority(input)
 }
 
 #[cfg(test)]
@@ -54,9 +54,10 @@
     In how many assignment pairs do the ranges overlap?
 */
 
-use std::{num::ParseIntError, ops::RangeInclusive, str::FromStr};
+use common::IntervalSet;
+use std::{num::ParseIntError, str::FromStr};
 
-pub struct Pair(RangeInclusive<u64>, RangeInclusive<u64>);
+pub struct Pair(IntervalSet<u64>, IntervalSet<u64>);
 
 impl FromStr for Pair {
     type Err = ParseIntError;
@@ -72,29 +73,24 @@ impl FromStr for Pair {
         let right_begin: u64 = begin.parse()?;
         let right_end: u64 = end.parse()?;
 
-        Ok(Self(left_begin..=left_end, right_begin..=right_end))
+        Ok(Self(
+            IntervalSet::from_range(left_begin..=left_end),
+            IntervalSet::from_range(right_begin..=right_end),
+        ))
     }
 }
 
 fn count_fully_contained(input: &[Pair]) -> usize {
     input
         .iter()
-        .filter(|x| {
-            (x.0.contains(x.1.start()) && x.0.contains(x.1.end()))
-                || (x.1.contains(x.0.start()) && x.1.contains(x.0.end()))
-        })
+        .filter(|x| x.0.difference(&x.1).is_empty() || x.1.difference(&x.0).is_empty())
         .count()
 }
 
 fn count_overlapped(input: &[Pair]) -> usize {
     input
         .iter()
-        .filter(|x| {
-            x.0.contains(x.1.start())
-                || x.0.contains(x.1.end())
-                || x.1.contains(x.0.start())
-                || x.1.contains(x.0.end())
-        })
+        .filter(|x| !x.0.intersection(&x.1).is_empty())
         .count()
 }
 
@@ -109,16 +105,12 @@ pub fn input_generator(input: &str) -> Vec<Pair> {
 
 #[aoc(day4, part1)]
 pub fn part1(input: &[Pair]) -> usize {
-    let fully_contained = count_fully_contained(input);
-    assert_eq!(fully_contained, 518);
-    fully_contained
+    count_fully_contained(input)
 }
 
 #[aoc(day4, part2)]
 pub fn part2(input: &[Pair]) -> usize {
-    let overlapped = count_overlapped(input);
-    assert_eq!(overlapped, 909);
-    overlapped
+    count_overlapped(input)
 }
 
 #[cfg(test)]
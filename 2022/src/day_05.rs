@@ -124,9 +124,8 @@ impl Supplies {
     fn rearrange2(&mut self) {
         for procedure in &self.procedures {
             let start = self.stacks[procedure.from - 1].len() - procedure.num;
-            #[expect(clippy::needless_collect)] // Borrow checker forbids it
             let tmp: Vec<char> = self.stacks[procedure.from - 1].drain(start..).collect();
-            self.stacks[procedure.to - 1].extend(tmp.into_iter());
+            self.stacks[procedure.to - 1].extend(tmp);
         }
     }
 
@@ -182,18 +181,14 @@ pub fn input_generator(input: &str) -> Supplies {
 pub fn part1(input: &Supplies) -> String {
     let mut input = input.clone();
     input.rearrange1();
-    let top = input.read_top();
-    assert_eq!(top, "FJSRQCFTN");
-    top
+    input.read_top()
 }
 
 #[aoc(day5, part2)]
 pub fn part2(input: &Supplies) -> String {
     let mut input = input.clone();
     input.rearrange2();
-    let top = input.read_top();
-    assert_eq!(top, "CJVLJQPHS");
-    top
+    input.read_top()
 }
 
 #[cfg(test)]
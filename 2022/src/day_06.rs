@@ -69,16 +69,12 @@ fn find_som(input: &str) -> usize {
 
 #[aoc(day6, part1)]
 pub fn part1(input: &str) -> usize {
-    let sop = find_sop(input);
-    assert_eq!(sop, 1896);
-    sop
+    find_sop(input)
 }
 
 #[aoc(day6, part2)]
 pub fn part2(input: &str) -> usize {
-    let sop = find_som(input);
-    assert_eq!(sop, 3452);
-    sop
+    find_som(input)
 }
 
 #[cfg(test)]
@@ -288,17 +288,13 @@ pub fn input_generator(input: &str) -> Vec<CmdOrOut> {
 #[aoc(day7, part1)]
 pub fn part1(input: &[CmdOrOut]) -> u64 {
     let fs = Fs::new(input);
-    let total = fs.total_sizes_at_most_100k();
-    assert_eq!(total, 1453349);
-    total
+    fs.total_sizes_at_most_100k()
 }
 
 #[aoc(day7, part2)]
 pub fn part2(input: &[CmdOrOut]) -> u64 {
     let fs = Fs::new(input);
-    let to_delete = fs.smallest_to_delete(70_000_000, 30_000_000);
-    assert_eq!(to_delete, 2948823);
-    to_delete
+    fs.smallest_to_delete(70_000_000, 30_000_000)
 }
 
 #[cfg(test)]
@@ -66,122 +66,63 @@
 
 use std::str::FromStr;
 
-use itertools::Itertools;
+use common::{Grid, Point2};
 
 pub struct Trees {
-    data: Vec<Vec<u8>>,
+    heights: Grid<u8>,
 }
 
 impl FromStr for Trees {
     type Err = ();
 
     fn from_str(input: &str) -> Result<Self, Self::Err> {
-        let data = input
-            .lines()
-            .map(|line| line.chars().map(|c| c as u8 - b'0').collect())
-            .collect();
+        let heights = Grid::parser_with(input, |c| c.to_digit(10).map(|d| d as u8))
+            .map_err(|_| ())?
+            .1;
 
-        Ok(Self { data })
+        Ok(Self { heights })
     }
 }
 
 impl Trees {
-    fn count_visible(&self) -> u64 {
-        let mut is_vis: Vec<Vec<bool>> = vec![vec![false; self.data[0].len()]; self.data.len()];
-        let mut biggest;
-
-        #[expect(clippy::needless_range_loop)]
-        // False alarm, we really are using this to index multiple structures
-        for row in 0..self.data.len() {
-            // Left to right
-            biggest = None;
-            for col in 0..self.data[0].len() {
-                let curr = self.data[row][col];
-                is_vis[row][col] = is_vis[row][col] || biggest.map_or(true, |big| curr > big);
-                biggest = Some(biggest.map_or(curr, |big| big.max(curr)));
-            }
-
-            // Right to left
-            biggest = None;
-            for col in (0..self.data[0].len()).rev() {
-                let curr = self.data[row][col];
-                is_vis[row][col] = is_vis[row][col] || biggest.map_or(true, |big| curr > big);
-                biggest = Some(biggest.map_or(curr, |big| big.max(curr)));
-            }
-        }
+    fn is_visible(&self, p: Point2) -> bool {
+        let my_size = self.heights[p];
+        Point2::origin()
+            .orthogonals()
+            .any(|dir| self.heights.ray(p, dir).all(|(_, other)| *other < my_size))
+    }
 
-        #[expect(clippy::needless_range_loop)]
-        // False alarm, we really are using this to index multiple structures
-        for col in 0..self.data[0].len() {
-            // Left to right
-            biggest = None;
-            for row in 0..self.data.len() {
-                let curr = self.data[row][col];
-                is_vis[row][col] = is_vis[row][col] || biggest.map_or(true, |big| curr > big);
-                biggest = Some(biggest.map_or(curr, |big| big.max(curr)));
-            }
+    fn count_visible(&self) -> u64 {
+        self.heights
+            .points()
+            .filter(|p| self.is_visible(*p))
+            .count() as u64
+    }
 
-            // Right to left
-            biggest = None;
-            for row in (0..self.data.len()).rev() {
-                let curr = self.data[row][col];
-                is_vis[row][col] = is_vis[row][col] || biggest.map_or(true, |big| curr > big);
-                biggest = Some(biggest.map_or(curr, |big| big.max(curr)));
+    // Trees seen in one direction, up to and including the first one that blocks the view
+    fn viewing_distance(&self, p: Point2, dir: Point2) -> usize {
+        let my_size = self.heights[p];
+        let mut count = 0;
+        for (_, other) in self.heights.ray(p, dir) {
+            count += 1;
+            if *other >= my_size {
+                break;
             }
         }
-
-        is_vis.into_iter().flatten().filter(|vis| *vis).count() as u64
+        count
     }
 
-    fn scenic_score(&self, row: usize, col: usize) -> u64 {
-        let my_size = self.data[row][col];
-
-        let mut proceed = true;
-        let up = (0..col)
-            .rev()
-            .take_while(|other_col| {
-                proceed && {
-                    proceed = self.data[row][*other_col] < my_size;
-                    true
-                }
-            })
-            .count();
-        proceed = true;
-        let down = (col + 1..self.data[0].len())
-            .take_while(|other_col| {
-                proceed && {
-                    proceed = self.data[row][*other_col] < my_size;
-                    true
-                }
-            })
-            .count();
-        proceed = true;
-        let left = (0..row)
-            .rev()
-            .take_while(|other_row| {
-                proceed && {
-                    proceed = self.data[*other_row][col] < my_size;
-                    true
-                }
-            })
-            .count();
-        proceed = true;
-        let right = (row + 1..self.data.len())
-            .take_while(|other_row| {
-                proceed && {
-                    proceed = self.data[*other_row][col] < my_size;
-                    true
-                }
-            })
-            .count();
-
-        (up * down * left * right) as u64
+    fn scenic_score(&self, p: Point2) -> u64 {
+        Point2::origin()
+            .orthogonals()
+            .map(|dir| self.viewing_distance(p, dir) as u64)
+            .product()
     }
 
     fn best_scenic_score(&self) -> u64 {
-        (0..self.data.len())
-            .cartesian_product(0..self.data[0].len())
-            .map(|(row, col)| self.scenic_score(row, col))
+        self.heights
+            .points()
+            .map(|p| self.scenic_score(p))
             .max()
             .unwrap()
     }
@@ -194,16 +135,12 @@ pub fn input_generator(input: &str) -> Trees {
 
 #[aoc(day8, part1)]
 pub fn part1(input: &Trees) -> u64 {
-    let visible = input.count_visible();
-    assert_eq!(visible, 1700);
-    visible
+    input.count_visible()
 }
 
 #[aoc(day8, part2)]
 pub fn part2(input: &Trees) -> u64 {
-    let score = input.best_scenic_score();
-    assert_eq!(score, 470596);
-    score
+    input.best_scenic_score()
 }
 
 #[cfg(test)]
@@ -228,10 +165,10 @@ mod test {
     fn test_scenic_score() {
         let input = input_generator(EXAMPLE_INPUT);
 
-        let score = input.scenic_score(1, 2);
+        let score = input.scenic_score((2, 1).into());
         assert_eq!(score, 4);
 
-        let score = input.scenic_score(3, 2);
+        let score = input.scenic_score((2, 3).into());
         assert_eq!(score, 8);
     }
 
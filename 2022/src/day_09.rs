@@ -746,17 +746,13 @@ pub fn input_generator(input: &str) -> Vec<Motion> {
 #[aoc(day9, part1)]
 pub fn part1(input: &[Motion]) -> usize {
     let mut rope = Rope::<2>::new();
-    let visited = rope.step_all(input);
-    assert_eq!(visited, 6470);
-    visited
+    rope.step_all(input)
 }
 
 #[aoc(day9, part2)]
 pub fn part2(input: &[Motion]) -> usize {
     let mut rope = Rope::<10>::new();
-    let visited = rope.step_all(input);
-    assert_eq!(visited, 2658);
-    visited
+    rope.step_all(input)
 }
 
 #[cfg(test)]
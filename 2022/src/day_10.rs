@@ -312,12 +312,14 @@
     Render the image given by your program. What eight capital letters appear on your CRT?
 */
 
+use common::machine::{Cpu, Flow, InstructionSet, Machine};
 use std::{
     iter,
     num::{NonZeroUsize, ParseIntError},
     str::FromStr,
 };
 
+#[derive(Clone)]
 pub enum Instruction {
     Add(i64),
     Nop,
@@ -337,24 +339,34 @@ impl FromStr for Instruction {
     }
 }
 
+impl InstructionSet for Instruction {
+    type Registers = i64; // X
+
+    fn execute(&self, x: &mut i64) -> Flow {
+        if let Self::Add(n) = self {
+            *x += n;
+        }
+        Flow::Next
+    }
+
+    fn cycles(&self) -> usize {
+        match self {
+            Self::Add(_) => 2,
+            Self::Nop => 1,
+        }
+    }
+}
+
 struct Signal {
     data: Vec<i64>,
 }
 
 impl Signal {
+    // Value of X during each cycle
     fn emit(instructions: &[Instruction]) -> Self {
-        let mut x = 1;
-        let data = instructions
-            .iter()
-            .flat_map(|inst| match inst {
-                Instruction::Add(n) => {
-                    let tmp = x;
-                    x += *n;
-                    iter::repeat(tmp).take(2)
-                }
-                Instruction::Nop => iter::repeat(x).take(1),
-            })
-            .collect();
+        let mut cpu = Cpu::new(instructions.to_vec(), 1);
+        let mut data = Vec::new();
+        cpu.run_with_hook(|_, cpu| data.push(cpu.regs));
         Self { data }
     }
 
@@ -401,26 +413,13 @@ pub fn input_generator(input: &str) -> Vec<Instruction> {
 #[aoc(day10, part1)]
 pub fn part1(input: &[Instruction]) -> i64 {
     let signal = Signal::emit(input);
-    let strength = signal.six_strengths();
-    assert_eq!(strength, 12840);
-    strength
+    signal.six_strengths()
 }
 
 #[aoc(day10, part2)]
 pub fn part2(input: &[Instruction]) -> String {
     let signal = Signal::emit(input);
-    let image = signal.crt_image();
-    assert_eq!(
-        image,
-        "
-####.#..#...##.####.###....##.####.####.
-...#.#.#.....#.#....#..#....#.#.......#.
-..#..##......#.###..###.....#.###....#..
-.#...#.#.....#.#....#..#....#.#.....#...
-#....#.#..#..#.#....#..#.#..#.#....#....
-####.#..#..##..#....###...##..#....####."
-    );
-    image
+    signal.crt_image()
 }
 
 #[cfg(test)]
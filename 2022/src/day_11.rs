@@ -474,18 +474,14 @@ pub fn input_generator(input: &str) -> Barrel {
 pub fn part1(input: &Barrel) -> u64 {
     let mut barrel = input.clone();
     barrel.rounds(20, Mode::M1);
-    let mb = barrel.monkey_business();
-    assert_eq!(mb, 117624);
-    mb
+    barrel.monkey_business()
 }
 
 #[aoc(day11, part2)]
 pub fn part2(input: &Barrel) -> u64 {
     let mut barrel = input.clone();
     barrel.rounds(10_000, Mode::M2);
-    let mb = barrel.monkey_business();
-    assert_eq!(mb, 16792940265);
-    mb
+    barrel.monkey_business()
 }
 
 #[cfg(test)]
@@ -52,12 +52,9 @@
     What is the fewest steps required to move starting from any square with elevation a to the location that should get the best signal?
 */
 
-use std::{
-    cmp::Reverse,
-    collections::{HashMap, HashSet},
-};
+use std::collections::HashMap;
 
-use common::Point2;
+use common::{search, Point2};
 
 pub struct Hill {
     start: Point2,
@@ -99,68 +96,41 @@ impl Hill {
         }
     }
 
-    fn steps_to_end(&self) -> usize {
-        let mut frontier: Vec<(usize, Point2)> = vec![(0, self.start)];
-        let mut visited: HashSet<Point2> = HashSet::new();
-        visited.insert(self.start);
-        while let Some((steps, curr)) = frontier.pop() {
-            let curr_char = self.map[&curr];
-            let next: Vec<_> = curr
-                .orthogonals()
-                .filter(|p| !visited.contains(p))
-                .filter(|p| {
-                    if let Some(c) = self.map.get(p) {
-                        let diff = *c as i32 - curr_char as i32;
-                        diff <= 1
+    // Climbing can go up at most one step but down any amount, descending from the end is the reverse of that
+    fn neighbors(&self, p: Point2, descending: bool) -> Vec<Point2> {
+        let curr_char = self.map[&p];
+        p.orthogonals()
+            .filter(|next| {
+                self.map.get(next).is_some_and(|c| {
+                    let diff = *c as i32 - curr_char as i32;
+                    if descending {
+                        diff >= -1
                     } else {
-                        false
+                        diff <= 1
                     }
                 })
-                .collect(); // There's gotta be a more elegant way to do this without allocating but checking `visited` fails the borrow checker
-
-            if next.contains(&self.end) {
-                return steps + 1;
-            }
+            })
+            .collect()
+    }
 
-            visited.extend(next.iter());
-            frontier.extend(next.iter().map(|p| (steps + 1, *p)));
-            frontier.sort_unstable_by_key(|k| Reverse(k.0));
-        }
-        panic!("Couldn't find path to end");
+    fn steps_to_end(&self) -> usize {
+        search::bfs(
+            [self.start],
+            |p| self.neighbors(*p, false),
+            |p| *p == self.end,
+        )
+        .goal_distance()
+        .expect("Couldn't find path to end")
     }
 
     fn steps_from_end(&self) -> usize {
-        let mut frontier: Vec<(usize, Point2)> = vec![(0, self.end)];
-        let mut visited: HashSet<Point2> = HashSet::new();
-        visited.insert(self.end);
-        while let Some((steps, curr)) = frontier.pop() {
-            let curr_char = self.map[&curr];
-            let next: Vec<_> = curr
-                .orthogonals()
-                .filter(|p| !visited.contains(p))
-                .filter(|p| {
-                    if let Some(c) = self.map.get(p) {
-                        let diff = *c as i32 - curr_char as i32;
-                        diff >= -1
-                    } else {
-                        false
-                    }
-                })
-                .collect(); // There's gotta be a more elegant way to do this without allocating but checking `visited` fails the borrow checker
-
-            if next
-                .iter()
-                .filter_map(|p| self.map.get(p))
-                .any(|c| *c == 'a')
-            {
-                return steps + 1;
-            }
-
-            visited.extend(next.iter());
-            frontier.extend(next.iter().map(|p| (steps + 1, *p)));
-            frontier.sort_unstable_by_key(|k| Reverse(k.0));
-        }
-        panic!("Couldn't find path from end");
+        search::bfs(
+            [self.end],
+            |p| self.neighbors(*p, true),
+            |p| self.map[p] == 'a',
+        )
+        .goal_distance()
+        .expect("Couldn't find path from end")
     }
 }
 
@@ -171,16 +141,12 @@ pub fn input_generator(input: &str) -> Hill {
 
 #[aoc(day12, part1)]
 pub fn part1(input: &Hill) -> usize {
-    let steps = input.steps_to_end();
-    assert_eq!(steps, 520);
-    steps
+    input.steps_to_end()
 }
 
 #[aoc(day12, part2)]
 pub fn part2(input: &Hill) -> usize {
-    let steps = input.steps_from_end();
-    assert_eq!(steps, 508);
-    steps
+    input.steps_from_end()
 }
 
 #[cfg(test)]
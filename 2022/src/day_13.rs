@@ -275,16 +275,12 @@ pub fn input_generator(input: &str) -> Vec<Packet> {
 
 #[aoc(day13, part1)]
 pub fn part1(input: &[Packet]) -> u64 {
-    let score = score_pairs_in_order(input);
-    assert_eq!(score, 6484);
-    score
+    score_pairs_in_order(input)
 }
 
 #[aoc(day13, part2)]
 pub fn part2(input: &[Packet]) -> u64 {
-    let score = score_sorted_packets(input);
-    assert_eq!(score, 19305);
-    score
+    score_sorted_packets(input)
 }
 
 #[cfg(test)]
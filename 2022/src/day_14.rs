@@ -252,17 +252,13 @@ pub fn input_generator(input: &str) -> Paths {
 #[aoc(day14, part1)]
 pub fn part1(input: &Paths) -> usize {
     let mut cave = Cave::from_paths(input);
-    let count = cave.sim_sand(Mode::M1);
-    assert_eq!(count, 672);
-    count
+    cave.sim_sand(Mode::M1)
 }
 
 #[aoc(day14, part2)]
 pub fn part2(input: &Paths) -> usize {
     let mut cave = Cave::from_paths(input);
-    let count = cave.sim_sand(Mode::M2);
-    assert_eq!(count, 26831);
-    count
+    cave.sim_sand(Mode::M2)
 }
 
 #[cfg(test)]
@@ -105,11 +105,12 @@
     Find the only possible position for the distress beacon. What is its tuning frequency?
 */
 
-use common::Point2;
+use common::{IntervalSet, Point2};
 use itertools::Itertools;
 use lazy_static::lazy_static;
 use rayon::prelude::*;
 use regex::Regex;
+use std::ops::RangeInclusive;
 
 #[derive(Clone, Debug)]
 pub struct Sensor {
@@ -139,102 +140,46 @@ impl Sensor {
         Self { pos, beacon, dist }
     }
 
-    fn intersect_horizontal(&self, y: i32) -> Option<Line> {
+    fn intersect_horizontal(&self, y: i32) -> Option<RangeInclusive<i32>> {
         let dist = self.pos.y.abs_diff(y);
         if dist <= self.dist {
             let width_half = (self.dist - dist) as i32;
-            let new_line = Line {
-                left: self.pos.x - width_half,
-                right: self.pos.x + width_half,
-            };
-            Some(new_line)
+            Some(self.pos.x - width_half..=self.pos.x + width_half)
         } else {
             None
         }
     }
 }
 
-#[derive(Clone, Copy, Debug)]
-struct Line {
-    left: i32,
-    right: i32,
-}
-
-impl Line {
-    fn len(&self) -> usize {
-        (self.left..=self.right).count()
-    }
-
-    fn overlaps(&self, other: &Self) -> bool {
-        other.left <= self.right && other.right >= self.left
-    }
-
-    fn union(&self, other: &Self) -> Option<Line> {
-        if self.overlaps(other) {
-            Some(Line {
-                left: self.left.min(other.left),
-                right: self.right.max(other.right),
-            })
-        } else {
-            None
-        }
-    }
-}
-
-fn calc_lines_at_y(sensors: &[Sensor], y: i32) -> Vec<Line> {
-    let lines: Vec<Line> = sensors
+fn calc_lines_at_y(sensors: &[Sensor], y: i32) -> IntervalSet<i32> {
+    sensors
         .iter()
         .filter_map(|sensor| sensor.intersect_horizontal(y))
-        .sorted_by_key(|line| line.left)
-        .collect();
-
-    let mut output = Vec::new();
-    let mut line_merge: Option<Line> = None;
-    for line in lines {
-        if let Some(l) = &line_merge {
-            if let Some(u) = l.union(&line) {
-                // Union worked, save it
-                line_merge = Some(u);
-            } else {
-                // Union didn't work, push the completed
-                output.push(*l);
-                line_merge = Some(line);
-            }
-        } else {
-            // First one
-            line_merge = Some(line);
-        }
-    }
-    output.push(line_merge.unwrap());
-    output
+        .collect()
 }
 
 fn count_visible(sensors: &[Sensor], y: i32) -> usize {
     let lines = calc_lines_at_y(sensors, y);
 
-    let beacons: Vec<Point2> = sensors.iter().map(|s| s.beacon).sorted().dedup().collect();
-    lines
+    let beacons = sensors
         .iter()
-        .map(|line| {
-            let count = beacons
-                .iter()
-                .filter(|beacon| beacon.y == y && (line.left..=line.right).contains(&beacon.x))
-                .count();
-            line.len() - count
-        })
-        .sum()
+        .map(|s| s.beacon)
+        .filter(|beacon| beacon.y == y && lines.contains(beacon.x))
+        .unique()
+        .count();
+    lines.covered() as usize - beacons
 }
 
-// For each horizontal line, generate the line segments that are seen by the sensors. Merge them and look for any place
-// there's a gap and assume that's the target. There's gotta be a better way but this is fast enough for now.
+// For each horizontal line, generate the line segments that are seen by the sensors and look for any place in the search
+// area they don't cover. There's gotta be a better way but this is fast enough for now.
 fn tuning_frequency(sensors: &[Sensor], max: i32) -> usize {
-    let (y, lines) = (0..=max)
+    let (y, gaps) = (0..=max)
         .into_par_iter()
-        .map(|y| (y, calc_lines_at_y(sensors, y)))
-        .find_any(|(_, lines)| lines.len() == 2)
+        .map(|y| (y, calc_lines_at_y(sensors, y).gaps(0..=max)))
+        .find_any(|(_, gaps)| !gaps.is_empty())
         .expect("Nothing found");
-    assert_eq!(lines[0].right + 1, lines[1].left - 1);
-    let x = lines[0].right + 1;
+    assert_eq!(gaps.covered(), 1);
+    let x = gaps.min().unwrap();
     x as usize * 4_000_000 + y as usize
 }
 
@@ -245,16 +190,12 @@ pub fn input_generator(input: &str) -> Vec<Sensor> {
 
 #[aoc(day15, part1)]
 pub fn part1(input: &[Sensor]) -> usize {
-    let count = count_visible(input, 2_000_000);
-    assert_eq!(count, 4827924);
-    count
+    count_visible(input, 2_000_000)
 }
 
 #[aoc(day15, part2)]
 pub fn part2(input: &[Sensor]) -> usize {
-    let freq = tuning_frequency(input, 4_000_000);
-    assert_eq!(freq, 12977110973564);
-    freq
+    tuning_frequency(input, 4_000_000)
 }
 
 #[cfg(test)]
@@ -284,7 +225,6 @@ Sensor at x=20, y=1: closest beacon is at x=15, y=3";
         assert_eq!(count, 26);
     }
 
-    #[ignore] // Sometimes fails for some reason, probably due to multithreading in test
     #[test]
     fn test_tuning_frequency() {
         let input = input_generator(EXAMPLE_INPUT);
@@ -228,6 +228,8 @@ use itertools::Itertools;
 use lazy_static::lazy_static;
 use regex::Regex;
 
+use common::graph::Graph;
+
 pub struct Valve {
     name: String,
     flow_rate: u64,
@@ -309,33 +311,17 @@ impl<'a> Network<'a> {
                 .collect();
         }
 
-        // Calculate adjacency matrix from the graph as this is the starting point for Floyd-Warshall
-        let mut adjacency_matrix: Vec<Vec<Option<u32>>> =
-            vec![vec![None; nodes.len()]; nodes.len()];
+        // Floyd-Warshall to get shortest distance between each node pair. All distances are 1 by definition.
+        let mut graph: Graph<usize> = Graph::new();
+        for i in 0..nodes.len() {
+            graph.add_node(i);
+        }
         for (i, node) in nodes.iter().enumerate() {
-            adjacency_matrix[i][i] = Some(0); // Not clear if this should be 0, None, or if it even matters
             for tunnel in &node.tunnels {
-                adjacency_matrix[i][*tunnel] = Some(1); // All distances are 1 by definition
-            }
-        }
-
-        // Floyd-Warshall to get shortest distance between each node pair
-        let mut shortest_paths = adjacency_matrix; // TODO: collect the option out, after fw all items are Some in a fully connected graph
-        for k in 0..nodes.len() {
-            for i in 0..nodes.len() {
-                for j in 0..nodes.len() {
-                    shortest_paths[i][j] = match (
-                        shortest_paths[i][j],
-                        shortest_paths[i][k],
-                        shortest_paths[k][j],
-                    ) {
-                        (None, Some(ik), Some(kj)) => Some(ik + kj), // No existing path, use new one
-                        (Some(curr), Some(ik), Some(kj)) => Some(curr.min(ik + kj)), // Shorter of existing path and new one
-                        (x, _, _) => x, // No new path, use existing (if any)
-                    };
-                }
+                graph.add_edge(i, *tunnel, 1, ());
             }
         }
+        let shortest_paths = graph.floyd_warshall(); // TODO: collect the option out, after fw all items are Some in a fully connected graph
 
         // Since the nodes are sorted by flow rate, the first zero is the count of non-zero flows
         let n_valves_with_flow = nodes.iter().position(|node| node.flow_rate == 0).unwrap();
@@ -517,17 +503,13 @@ pub fn input_generator(input: &str) -> Vec<Valve> {
 #[aoc(day16, part1)]
 pub fn part1(input: &[Valve]) -> u64 {
     let network = Network::from_valves(input);
-    let max = network.release_pressure(30);
-    assert_eq!(max, 1947);
-    max
+    network.release_pressure(30)
 }
 
 #[aoc(day16, part2)]
 pub fn part2(input: &[Valve]) -> u64 {
     let network = Network::from_valves(input);
-    let max = network.release_pressure_with_help(26);
-    assert_eq!(max, 2556);
-    max
+    network.release_pressure_with_help(26)
 }
 
 #[cfg(test)]
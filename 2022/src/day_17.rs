@@ -617,16 +617,12 @@ pub fn input_generator(input: &str) -> String {
 
 #[aoc(day17, part1)]
 pub fn part1(input: &str) -> usize {
-    let height = tetris_with_cycles(input, 2022);
-    assert_eq!(height, 3130);
-    height
+    tetris_with_cycles(input, 2022)
 }
 
 #[aoc(day17, part2)]
 pub fn part2(input: &str) -> usize {
-    let height = tetris_with_cycles(input, 1_000_000_000_000);
-    assert_eq!(height, 1556521739139);
-    height
+    tetris_with_cycles(input, 1_000_000_000_000)
 }
 
 #[cfg(test)]
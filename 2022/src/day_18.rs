@@ -39,91 +39,39 @@
     What is the exterior surface area of your scanned lava droplet?
 */
 
-use std::{
-    collections::{HashMap, HashSet},
-    str::FromStr,
-};
+use std::str::FromStr;
 
+use common::region::{self, Region};
 use common::Point3;
 
 fn calc_surface_area(pixels: &[Point3]) -> usize {
-    // This map tracks the interstitials - just the exposed faces.
-    // Double the pixel coords so that odd numbers are faces and even numbers are pixel centers.
-    let mut set: HashSet<Point3> = HashSet::new();
-
-    for pixel in pixels {
-        let double_pixel = Point3::from((pixel.x * 2, pixel.y * 2, pixel.z * 2));
-        for offset in [
-            (-1, 0, 0),
-            (1, 0, 0),
-            (0, -1, 0),
-            (0, 1, 0),
-            (0, 0, -1),
-            (0, 0, 1),
-        ] {
-            // Insert the face, if it was already there then go back and remove it.
-            // Since each face can only be shared by two pixels, there's no danger of
-            // re-adding it after it was removed.
-            let face = double_pixel + offset;
-            let new_val = set.insert(face);
-            if !new_val {
-                set.remove(&face);
-            }
-        }
-    }
-
-    set.len()
-}
-
-#[derive(Debug, PartialEq)]
-enum Kind {
-    Lava,
-    Air,
-    Water,
+    Region::new(pixels.iter().copied()).perimeter()
 }
 
 fn calc_flooded(pixels: &[Point3]) -> usize {
-    let mut map: HashMap<Point3, Kind> = pixels.iter().map(|p| (*p, Kind::Lava)).collect();
-    let range = Point3::get_range(pixels).unwrap();
-
-    // Pre-fill with air. Note we leave a border to account for areas the outside may be closed off.
-    for x in range.x.0 - 1..=range.x.1 + 1 {
-        for y in range.y.0 - 1..=range.y.1 + 1 {
-            for z in range.z.0 - 1..=range.z.1 + 1 {
+    let lava = Region::new(pixels.iter().copied());
+    let range = lava.range().unwrap();
+
+    // Air which doesn't reach the edge of the range is trapped inside. Every face of a pocket touches lava, so take
+    // them away from the total to leave just the outside.
+    let mut air = Vec::new();
+    for x in range.x.0..=range.x.1 {
+        for y in range.y.0..=range.y.1 {
+            for z in range.z.0..=range.z.1 {
                 let p = Point3::from((x, y, z));
-                map.entry(p).or_insert(Kind::Air);
-            }
-        }
-    }
-
-    // Everything is either air or lava at this point. Flood fill with water from the outside.
-    // Follow the air, and since each lava-water face is uniquely "owned" by a water pixel we can just
-    // count as we go.
-    let mut count = 0;
-    let start = (range.x.0, range.y.0, range.z.0).into();
-    let mut frontier: Vec<Point3> = vec![start];
-    while let Some(curr) = frontier.pop() {
-        for offset in [
-            (-1, 0, 0),
-            (1, 0, 0),
-            (0, -1, 0),
-            (0, 1, 0),
-            (0, 0, -1),
-            (0, 0, 1),
-        ] {
-            let adj = curr + offset;
-            match map.get(&adj) {
-                Some(Kind::Air) => {
-                    map.insert(adj, Kind::Water);
-                    frontier.push(adj);
+                if !lava.contains(&p) {
+                    air.push(p);
                 }
-                Some(Kind::Lava) => count += 1,
-                _ => (),
             }
         }
     }
+    let pockets: usize = region::components(air, |_, _| true)
+        .iter()
+        .filter(|pocket| !pocket.touches_boundary(&range))
+        .map(|pocket| pocket.perimeter())
+        .sum();
 
-    count
+    lava.perimeter() - pockets
 }
 
 #[aoc_generator(day18)]
@@ -137,16 +85,12 @@ pub fn input_generator(input: &str) -> Vec<Point3> {
 
 #[aoc(day18, part1)]
 pub fn part1(input: &[Point3]) -> usize {
-    let area = calc_surface_area(input);
-    assert_eq!(area, 3390);
-    area
+    calc_surface_area(input)
 }
 
 #[aoc(day18, part2)]
 pub fn part2(input: &[Point3]) -> usize {
-    let area = calc_flooded(input);
-    assert_eq!(area, 2058);
-    area
+    calc_flooded(input)
 }
 
 #[cfg(test)]
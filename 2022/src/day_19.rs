@@ -382,8 +382,10 @@ use std::{
 use lazy_static::lazy_static;
 use regex::Regex;
 
+use common::state_search::{BranchAndBound, State};
+
 // Ore, Clay, Obsidian, Geode
-#[derive(Clone, Copy, Debug, Eq)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
 struct ItemCount([u16; 4]);
 
 impl From<[u16; 4]> for ItemCount {
@@ -452,9 +454,9 @@ impl Ord for ItemCount {
             self.0[2].cmp(&other.0[2]),
             self.0[3].cmp(&other.0[3]),
         ];
-        if compare.iter().any(|v| *v == Ordering::Less) {
+        if compare.contains(&Ordering::Less) {
             Ordering::Less
-        } else if compare.iter().any(|v| *v == Ordering::Greater) {
+        } else if compare.contains(&Ordering::Greater) {
             Ordering::Greater
         } else {
             Ordering::Equal
@@ -462,12 +464,6 @@ impl Ord for ItemCount {
     }
 }
 
-impl PartialEq for ItemCount {
-    fn eq(&self, other: &Self) -> bool {
-        self.0 == other.0
-    }
-}
-
 lazy_static! {
     static ref RE: Regex = Regex::new(r"Blueprint (\d+): Each ore robot costs (\d+) ore. Each clay robot costs (\d+) ore. Each obsidian robot costs (\d+) ore and (\d+) clay. Each geode robot costs (\d+) ore and (\d+) obsidian.").unwrap();
 }
@@ -504,9 +500,6 @@ impl Blueprint {
     }
 
     fn most_geodes(&self, time: usize) -> u64 {
-        let inventory: ItemCount = [0, 0, 0, 0].into();
-        let robots: ItemCount = [1, 0, 0, 0].into();
-
         let max_costs = self
             .costs
             .iter()
@@ -520,35 +513,39 @@ impl Blueprint {
             })
             .into();
 
-        self.most_geodes_recurse(time, inventory, robots, max_costs, 0)
+        let start = Mining {
+            costs: self.costs,
+            max_costs,
+            time,
+            inventory: [0, 0, 0, 0].into(),
+            robots: [1, 0, 0, 0].into(),
+        };
+        BranchAndBound::new().maximize(start).score as u64
     }
 
-    fn most_geodes_recurse(
-        &self,
-        time: usize,
-        inventory: ItemCount,
-        robots: ItemCount,
-        max_costs: ItemCount,
-        best_so_far: u64,
-    ) -> u64 {
-        if time == 0 {
-            return inventory.0[3] as u64;
-        }
+    fn quality_level(&self, time: usize) -> u64 {
+        self.id * self.most_geodes(time)
+    }
+}
 
-        // Skip this if it doesn't have a chance to be better than the best we've seen so far.
-        // Assume best case - if we bought a geode robot every minute for the remaining time (ignoring whether that's actually possible).
-        // With t time left we only get to produce on t - 1 of those minutes, so in total we could get t * (t - 1) / 2 geodes from new robots.
-        let max_possible =
-            inventory.0[3] as usize + time * robots.0[3] as usize + time * (time - 1) / 2;
-        if max_possible as u64 <= best_so_far {
-            return inventory.0[3] as u64;
-        }
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+struct Mining {
+    costs: [ItemCount; 4],
+    max_costs: ItemCount,
+    time: usize,
+    inventory: ItemCount,
+    robots: ItemCount,
+}
 
-        let mut max_geodes = 0;
+impl State for Mining {
+    type Score = usize;
+
+    fn successors(&self) -> Vec<Self> {
+        let mut next = Vec::new();
         for i in 0..4 {
             // Skip this if we generate enough to make any robot every minute (no point in making more if we already have more than enough)
             // Note: this doesn't apply to geodes since you can never have enough of them
-            if i < 3 && robots.0[i] >= max_costs.0[i] {
+            if i < 3 && self.robots.0[i] >= self.max_costs.0[i] {
                 continue;
             }
 
@@ -556,10 +553,10 @@ impl Blueprint {
             // the usual 1 minute time step is handled afterwards since that always needs to happen. Note we
             // only skip down to time == 1 because building in the last minute is pointless and it
             // keeps the logic simple to always step 1 minute at the recursive step.
-            let mut next_time = time;
-            let mut next_inventory = inventory;
+            let mut next_time = self.time;
+            let mut next_inventory = self.inventory;
             while next_time > 1 && next_inventory < self.costs[i] {
-                next_inventory += robots;
+                next_inventory += self.robots;
                 next_time -= 1;
             }
 
@@ -568,27 +565,35 @@ impl Blueprint {
                 next_inventory -= self.costs[i];
                 let mut new_robot = [0, 0, 0, 0];
                 new_robot[i] = 1;
-                robots + ItemCount::from(new_robot)
+                self.robots + ItemCount::from(new_robot)
             } else {
                 // We ran out of time and couldn't build the robot
-                robots
+                self.robots
             };
 
-            let geodes = self.most_geodes_recurse(
-                next_time - 1,
-                next_inventory + robots,
-                next_robots,
-                max_costs,
-                best_so_far.max(max_geodes),
-            );
-            max_geodes = max_geodes.max(geodes);
+            next.push(Self {
+                time: next_time - 1,
+                inventory: next_inventory + self.robots,
+                robots: next_robots,
+                ..self.clone()
+            });
         }
+        next
+    }
 
-        max_geodes
+    fn is_terminal(&self) -> bool {
+        self.time == 0
     }
 
-    fn quality_level(&self, time: usize) -> u64 {
-        self.id * self.most_geodes(time)
+    // The geode robots we already have will keep going until the end even if nothing else gets built
+    fn score(&self) -> usize {
+        self.inventory.0[3] as usize + self.time * self.robots.0[3] as usize
+    }
+
+    // Assume best case - if we bought a geode robot every minute for the remaining time (ignoring whether that's actually possible).
+    // With t time left we only get to produce on t - 1 of those minutes, so in total we could get t * (t - 1) / 2 geodes from new robots.
+    fn upper_bound(&self) -> usize {
+        self.score() + self.time * (self.time - 1) / 2
     }
 }
 
@@ -610,16 +615,12 @@ pub fn input_generator(input: &str) -> Vec<Blueprint> {
 
 #[aoc(day19, part1)]
 pub fn part1(input: &[Blueprint]) -> u64 {
-    let total = total_quality(input, 24);
-    assert_eq!(total, 1599);
-    total
+    total_quality(input, 24)
 }
 
 #[aoc(day19, part2)]
 pub fn part2(input: &[Blueprint]) -> u64 {
-    let total = first_n_product(input, 32, 3);
-    assert_eq!(total, 14112);
-    total
+    first_n_product(input, 32, 3)
 }
 
 #[cfg(test)]
@@ -172,17 +172,13 @@ pub fn input_generator(input: &str) -> Vec<i64> {
 #[aoc(day20, part1)]
 pub fn part1(input: &[i64]) -> i64 {
     let mut file = ElfFile::from_slice(input);
-    let coords = file.grove_coordinates();
-    assert_eq!(coords, 14526);
-    coords
+    file.grove_coordinates()
 }
 
 #[aoc(day20, part2)]
 pub fn part2(input: &[i64]) -> i64 {
     let mut file = ElfFile::from_slice(input);
-    let coords = file.encrypted_grove_coordinates();
-    assert_eq!(coords, 9738258246847);
-    coords
+    file.encrypted_grove_coordinates()
 }
 
 #[cfg(test)]
@@ -201,17 +201,13 @@ pub fn input_generator(input: &str) -> Vec<(String, Yell)> {
 #[aoc(day21, part1)]
 pub fn part1(input: &[(String, Yell)]) -> i64 {
     let gang = MonkeyGang::from_names_yells(input, Mode::M1);
-    let root = gang.evaluate_monkey("root").unwrap();
-    assert_eq!(root, 85616733059734);
-    root
+    gang.evaluate_monkey("root").unwrap()
 }
 
 #[aoc(day21, part2)]
 pub fn part2(input: &[(String, Yell)]) -> i64 {
     let gang = MonkeyGang::from_names_yells(input, Mode::M2);
-    let humn = gang.humn_after_all();
-    assert_eq!(humn, 3560324848168);
-    humn
+    gang.humn_after_all()
 }
 
 #[cfg(test)]
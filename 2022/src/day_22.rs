@@ -433,17 +433,13 @@ pub fn input_generator(input: &str) -> Board {
 #[aoc(day22, part1)]
 pub fn part1(input: &Board) -> i32 {
     let wrap_pairs = input.wrap_pairs_2d();
-    let password = input.trace_path(&wrap_pairs);
-    assert_eq!(password, 117102);
-    password
+    input.trace_path(&wrap_pairs)
 }
 
 #[aoc(day22, part2)]
 pub fn part2(input: &Board) -> i32 {
     let wrap_pairs = input.wrap_pairs_3d();
-    let password = input.trace_path(&wrap_pairs);
-    assert_eq!(password, 135297);
-    password
+    input.trace_path(&wrap_pairs)
 }
 
 #[cfg(test)]
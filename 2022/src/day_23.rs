@@ -314,17 +314,13 @@ pub fn input_generator(input: &str) -> Grove {
 #[aoc(day23, part1)]
 pub fn part1(input: &Grove) -> u64 {
     let mut grove = input.clone();
-    let empty = grove.diffuse(10);
-    assert_eq!(empty, 4070);
-    empty
+    grove.diffuse(10)
 }
 
 #[aoc(day23, part2)]
 pub fn part2(input: &Grove) -> usize {
     let mut grove = input.clone();
-    let rounds = grove.diffuse_until_stable();
-    assert_eq!(rounds, 881);
-    rounds
+    grove.diffuse_until_stable()
 }
 
 #[cfg(test)]
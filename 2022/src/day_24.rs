@@ -245,7 +245,7 @@
 
 use std::iter;
 
-use common::{Cardinal, Point2, TileChar, TileMap};
+use common::{Cardinal, Point2, Range2, TileChar, TileMap};
 use itertools::Itertools;
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
@@ -299,8 +299,7 @@ pub struct Valley {
     time: usize,
     start: Point2,
     end: Point2,
-    width: usize,
-    height: usize,
+    interior: Range2, // Inside the walls, which is where blizzards wrap around
 }
 
 impl Valley {
@@ -317,16 +316,17 @@ impl Valley {
         let end = *blizz_pts.last().unwrap();
 
         let range = sim.get_range().unwrap();
-        let width = i32::abs_diff(range.x.0, range.x.1) as usize + 1;
-        let height = i32::abs_diff(range.y.0, range.y.1) as usize + 1;
+        let interior = Range2 {
+            x: (range.x.0 + 1, range.x.1 - 1),
+            y: (range.y.0 + 1, range.y.1 - 1),
+        };
 
         Self {
             sim,
             time: 0,
             start,
             end,
-            width,
-            height,
+            interior,
         }
     }
 
@@ -346,27 +346,14 @@ impl Valley {
                 for cardinal in Cardinal::all() {
                     let bit = 1 << cardinal as u8;
                     if *blizz & bit != 0 {
-                        let adj_pt = pt.step(cardinal, 1);
-                        if let Tile::Blizzard(adj_blizz) =
+                        // Assume no blizzards move into the start or end location (for the example and my input this isn't possible)
+                        let adj_pt = self.interior.wrap(pt.step(cardinal, 1));
+                        let Tile::Blizzard(adj_blizz) =
                             new_sim.entry(adj_pt).or_insert(Tile::Blizzard(0))
-                        {
-                            *adj_blizz |= bit;
-                        } else {
-                            // Assume no blizzards move / wrap into the start or end location (for the example and my input this isn't possible)
-                            let adj_pt = match cardinal {
-                                Cardinal::North => (pt.x, self.height as i32 - 2),
-                                Cardinal::South => (pt.x, 1),
-                                Cardinal::East => (1, pt.y),
-                                Cardinal::West => (self.width as i32 - 2, pt.y),
-                            }
-                            .into();
-                            let Tile::Blizzard(adj_blizz) =
-                                new_sim.entry(adj_pt).or_insert(Tile::Blizzard(0))
-                            else {
-                                panic!("Should be blizzard")
-                            };
-                            *adj_blizz |= bit;
-                        }
+                        else {
+                            panic!("Should be blizzard")
+                        };
+                        *adj_blizz |= bit;
                     }
                 }
             }
@@ -417,17 +404,13 @@ pub fn input_generator(input: &str) -> Valley {
 #[aoc(day24, part1)]
 pub fn part1(input: &Valley) -> usize {
     let mut valley = input.clone();
-    let end = valley.find_goals(&[valley.end]);
-    assert_eq!(end, 232);
-    end
+    valley.find_goals(&[valley.end])
 }
 
 #[aoc(day24, part2)]
 pub fn part2(input: &Valley) -> usize {
     let mut valley = input.clone();
-    let end = valley.find_goals(&[valley.end, valley.start, valley.end]);
-    assert_eq!(end, 715);
-    end
+    valley.find_goals(&[valley.end, valley.start, valley.end])
 }
 
 #[cfg(test)]
@@ -188,9 +188,7 @@ pub fn input_generator(input: &str) -> Vec<Snafu> {
 
 #[aoc(day25, part1)]
 pub fn part1(input: &[Snafu]) -> String {
-    let snafu = solve(input);
-    assert_eq!(snafu, "2-21=02=1-121-2-11-0");
-    snafu
+    solve(input)
 }
 
 #[cfg(test)]
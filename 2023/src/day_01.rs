@@ -106,16 +106,12 @@ pub fn input_generator(input: &str) -> Vec<String> {
 
 #[aoc(day1, part1)]
 pub fn part1(input: &[String]) -> u32 {
-    let value = calibration_sum_digit(input);
-    assert_eq!(value, 55123);
-    value
+    calibration_sum_digit(input)
 }
 
 #[aoc(day1, part2)]
 pub fn part2(input: &[String]) -> u32 {
-    let value = calibration_sum_all(input);
-    assert_eq!(value, 55260);
-    value
+    calibration_sum_all(input)
 }
 
 #[cfg(test)]
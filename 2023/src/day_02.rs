@@ -138,16 +138,12 @@ pub fn input_generator(input: &str) -> Vec<Game> {
 
 #[aoc(day2, part1)]
 pub fn part1(input: &[Game]) -> u32 {
-    let value = check_load(input);
-    assert_eq!(value, 3099);
-    value
+    check_load(input)
 }
 
 #[aoc(day2, part2)]
 pub fn part2(input: &[Game]) -> u32 {
-    let value = power_sets(input);
-    assert_eq!(value, 72970);
-    value
+    power_sets(input)
 }
 
 #[cfg(test)]
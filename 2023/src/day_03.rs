@@ -58,6 +58,7 @@
 
 use std::ops::RangeInclusive;
 
+use common::Grid;
 use nom::AsChar;
 
 struct Number {
@@ -75,13 +76,13 @@ impl Number {
     }
 }
 
-fn find_numbers(schematic: &[Vec<char>]) -> Vec<Number> {
+fn find_numbers(schematic: &Grid<char>) -> Vec<Number> {
     let mut numbers = Vec::new();
 
     let mut curr_num = None;
     let mut curr_start = None;
     let mut curr_end = None;
-    for (y, row) in schematic.iter().enumerate() {
+    for (y, row) in schematic.rows().enumerate() {
         for (x, c) in row.iter().enumerate() {
             if c.is_dec_digit() {
                 let digit = c.to_digit(10).unwrap();
@@ -130,10 +131,10 @@ struct Symbol {
     y: i32,
 }
 
-fn find_symbols(schematic: &[Vec<char>]) -> Vec<Symbol> {
+fn find_symbols(schematic: &Grid<char>) -> Vec<Symbol> {
     let mut symbols = Vec::new();
 
-    for (y, row) in schematic.iter().enumerate() {
+    for (y, row) in schematic.rows().enumerate() {
         for (x, &c) in row.iter().enumerate() {
             if !c.is_dec_digit() && c != '.' {
                 let sym = Symbol {
@@ -149,7 +150,7 @@ fn find_symbols(schematic: &[Vec<char>]) -> Vec<Symbol> {
     symbols
 }
 
-fn find_part_numbers(schematic: &[Vec<char>]) -> Vec<u32> {
+fn find_part_numbers(schematic: &Grid<char>) -> Vec<u32> {
     let numbers = find_numbers(schematic);
     let symbols = find_symbols(schematic);
 
@@ -168,11 +169,11 @@ fn find_part_numbers(schematic: &[Vec<char>]) -> Vec<u32> {
     part_numbers
 }
 
-fn sum_part_numbers(schematic: &[Vec<char>]) -> u32 {
+fn sum_part_numbers(schematic: &Grid<char>) -> u32 {
     find_part_numbers(schematic).into_iter().sum()
 }
 
-fn sum_gear_ratios(schematic: &[Vec<char>]) -> u32 {
+fn sum_gear_ratios(schematic: &Grid<char>) -> u32 {
     let numbers = find_numbers(schematic);
     let symbols = find_symbols(schematic);
 
@@ -200,22 +201,18 @@ fn sum_gear_ratios(schematic: &[Vec<char>]) -> u32 {
 }
 
 #[aoc_generator(day3)]
-pub fn input_generator(input: &str) -> Vec<Vec<char>> {
-    input.lines().map(|line| line.chars().collect()).collect()
+pub fn input_generator(input: &str) -> Grid<char> {
+    Grid::from_string_with(input, Some)
 }
 
 #[aoc(day3, part1)]
-pub fn part1(input: &[Vec<char>]) -> u32 {
-    let value = sum_part_numbers(input);
-    assert_eq!(value, 532331);
-    value
+pub fn part1(input: &Grid<char>) -> u32 {
+    sum_part_numbers(input)
 }
 
 #[aoc(day3, part2)]
-pub fn part2(input: &[Vec<char>]) -> u32 {
-    let value = sum_gear_ratios(input);
-    assert_eq!(value, 82301120);
-    value
+pub fn part2(input: &Grid<char>) -> u32 {
+    sum_gear_ratios(input)
 }
 
 #[cfg(test)]
@@ -117,16 +117,12 @@ pub fn input_generator(input: &str) -> Vec<Card> {
 
 #[aoc(day4, part1)]
 pub fn part1(input: &[Card]) -> u32 {
-    let value = all_points(input);
-    assert_eq!(value, 20667);
-    value
+    all_points(input)
 }
 
 #[aoc(day4, part2)]
 pub fn part2(input: &[Card]) -> u64 {
-    let value = scratchception(input);
-    assert_eq!(value, 5833065);
-    value
+    scratchception(input)
 }
 
 #[cfg(test)]
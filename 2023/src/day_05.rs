@@ -109,7 +109,7 @@
     Consider all of the initial seed numbers listed in the ranges on the first line of the almanac. What is the lowest location number that corresponds to any of the initial seed numbers?
 */
 
-use std::ops::Range;
+use std::ops::RangeInclusive;
 
 use nom::{
     self,
@@ -120,7 +120,7 @@ use nom::{
     IResult,
 };
 
-use common::{to_owned, trim, unsigned};
+use common::{to_owned, trim, unsigned, IntervalSet};
 
 struct MapRange {
     dst: u64,
@@ -146,30 +146,9 @@ impl MapRange {
         None
     }
 
-    fn intersect(&self, range: Range<u64>) -> (Option<Range<u64>>, [Option<Range<u64>>; 2]) {
-        if (range.start < (self.src + self.len)) && (range.end > self.src) {
-            let start_offset = std::cmp::max(range.start, self.src) - self.src;
-            let end_offset = std::cmp::min(range.end, self.src + self.len) - self.src;
-            let start = self.dst + start_offset;
-            let end = self.dst + end_offset;
-            let intersection = Some(start..end);
-
-            let before = if range.start < self.src {
-                Some(range.start..self.src)
-            } else {
-                None
-            };
-
-            let after = if range.end > (self.src + self.len) {
-                Some(self.src + self.len..range.end)
-            } else {
-                None
-            };
-
-            (intersection, [before, after])
-        } else {
-            (None, [Some(range), None])
-        }
+    // The values this maps from, inclusive. Only valid for a range that isn't empty.
+    fn source(&self) -> RangeInclusive<u64> {
+        self.src..=self.src + self.len - 1
     }
 }
 
@@ -208,26 +187,13 @@ impl Map {
         other
     }
 
-    fn convert_ranges(&self, ranges: Vec<Range<u64>>) -> Vec<Range<u64>> {
-        let mut done_ranges = Vec::new();
-        for range in ranges {
-            let mut curr_ranges = vec![range];
-
-            for map_range in &self.map_ranges {
-                let mut tmp_ranges = Vec::new();
-
-                for curr in curr_ranges.drain(..) {
-                    let (done, not_done) = map_range.intersect(curr);
-                    done_ranges.extend(done.into_iter());
-                    tmp_ranges.extend(not_done.into_iter().flatten());
-                }
-
-                curr_ranges.append(&mut tmp_ranges);
-            }
-
-            done_ranges.extend(curr_ranges);
-        }
-        done_ranges
+    fn convert_ranges(&self, ranges: &IntervalSet<u64>) -> IntervalSet<u64> {
+        ranges.map_ranges(
+            self.map_ranges
+                .iter()
+                .filter(|r| r.len > 0)
+                .map(|r| (r.source(), r.dst)),
+        )
     }
 }
 
@@ -255,18 +221,18 @@ impl Almanac {
     }
 
     fn lowest_location_hard(&self) -> u64 {
-        let mut ranges: Vec<Range<u64>> = self
+        let mut ranges: IntervalSet<u64> = self
             .seeds
             .chunks_exact(2)
-            .map(|chunk| chunk[0]..chunk[0] + chunk[1])
+            .filter(|chunk| chunk[1] > 0)
+            .map(|chunk| chunk[0]..=chunk[0] + chunk[1] - 1)
             .collect();
 
         for map in &self.maps {
-            ranges = map.convert_ranges(ranges);
+            ranges = map.convert_ranges(&ranges);
         }
 
-        // All conversions have been done, the lowest value in each range is always the start of the range
-        ranges.into_iter().map(|range| range.start).min().unwrap()
+        ranges.min().unwrap()
     }
 }
 
@@ -277,16 +243,12 @@ pub fn input_generator(input: &str) -> Almanac {
 
 #[aoc(day5, part1)]
 pub fn part1(input: &Almanac) -> u64 {
-    let value = input.lowest_location();
-    assert_eq!(value, 175622908);
-    value
+    input.lowest_location()
 }
 
 #[aoc(day5, part2)]
 pub fn part2(input: &Almanac) -> u64 {
-    let value = input.lowest_location_hard();
-    assert_eq!(value, 5200543);
-    value
+    input.lowest_location_hard()
 }
 
 #[cfg(test)]
@@ -343,19 +305,38 @@ humidity-to-location map:
     }
 
     #[test]
-    fn test_maprange_intersect() {
-        let m0 = MapRange {
-            dst: 20,
-            src: 10,
-            len: 5,
+    fn test_convert_ranges() {
+        let map = Map {
+            _from: "a".to_string(),
+            _to: "b".to_string(),
+            map_ranges: vec![MapRange {
+                dst: 20,
+                src: 10,
+                len: 5,
+            }],
         };
-        assert_eq!(m0.intersect(0..5), (None, [Some(0..5), None]));
-        assert_eq!(m0.intersect(10..15), (Some(20..25), [None, None]));
-        assert_eq!(m0.intersect(5..15), (Some(20..25), [Some(5..10), None]));
-        assert_eq!(m0.intersect(10..20), (Some(20..25), [None, Some(15..20)]));
-        assert_eq!(
-            m0.intersect(0..20),
-            (Some(20..25), [Some(0..10), Some(15..20)])
+        let convert = |range| -> Vec<RangeInclusive<u64>> {
+            map.convert_ranges(&IntervalSet::from_range(range))
+                .intervals()
+                .collect()
+        };
+        assert_eq!(convert(0..=4), [0..=4]);
+        assert_eq!(convert(10..=14), [20..=24]);
+        assert_eq!(convert(5..=14), [5..=9, 20..=24]);
+        assert_eq!(convert(10..=19), [15..=24]);
+        assert_eq!(convert(0..=19), [0..=9, 15..=24]);
+    }
+
+    #[test]
+    fn test_empty_ranges() {
+        let input = input_generator(
+            "\
+seeds: 0 0 5 1
+
+seed-to-soil map:
+7 0 0
+3 5 1",
         );
+        assert_eq!(input.lowest_location_hard(), 3);
     }
 }
@@ -149,16 +149,12 @@ pub fn input_generator(input: &str) -> TimeSheet {
 
 #[aoc(day6, part1)]
 pub fn part1(input: &TimeSheet) -> u64 {
-    let value = input.margin();
-    assert_eq!(value, 316800);
-    value
+    input.margin()
 }
 
 #[aoc(day6, part2)]
 pub fn part2(input: &TimeSheet) -> u64 {
-    let value = input.margin_combined();
-    assert_eq!(value, 45647654);
-    value
+    input.margin_combined()
 }
 
 #[cfg(test)]
@@ -243,16 +243,12 @@ pub fn input_generator(input: &str) -> Vec<Hand> {
 
 #[aoc(day7, part1)]
 pub fn part1(input: &[Hand]) -> u64 {
-    let value = winnings(input, Mode::M1);
-    assert_eq!(value, 249204891);
-    value
+    winnings(input, Mode::M1)
 }
 
 #[aoc(day7, part2)]
 pub fn part2(input: &[Hand]) -> u64 {
-    let value = winnings(input, Mode::M2);
-    assert_eq!(value, 249666369);
-    value
+    winnings(input, Mode::M2)
 }
 
 #[cfg(test)]
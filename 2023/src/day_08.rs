@@ -237,17 +237,13 @@ pub fn input_generator(input: &str) -> Network {
 
 #[aoc(day8, part1)]
 pub fn part1(input: &Network) -> usize {
-    let value = input.traverse();
-    assert_eq!(value, 13301);
-    value
+    input.traverse()
 }
 
 #[aoc(day8, part2)]
 pub fn part2(input: &Network) -> usize {
     //input.inspect_ghostly();
-    let value = input.traverse_ghostly();
-    assert_eq!(value, 7309459565207);
-    value
+    input.traverse_ghostly()
 }
 
 #[cfg(test)]
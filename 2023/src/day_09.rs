@@ -51,16 +51,12 @@ pub fn input_generator(input: &str) -> Vec<Vec<i32>> {
 
 #[aoc(day9, part1)]
 pub fn part1(input: &[Vec<i32>]) -> i32 {
-    let value = extrapolate_all(input, true);
-    assert_eq!(value, 1980437560);
-    value
+    extrapolate_all(input, true)
 }
 
 #[aoc(day9, part2)]
 pub fn part2(input: &[Vec<i32>]) -> i32 {
-    let value = extrapolate_all(input, false);
-    assert_eq!(value, 977);
-    value
+    extrapolate_all(input, false)
 }
 
 #[cfg(test)]
@@ -202,16 +202,12 @@ pub fn input_generator(input: &str) -> Map {
 
 #[aoc(day10, part1)]
 pub fn part1(input: &Map) -> usize {
-    let value = input.follow_the_white_rabbit().len() / 2;
-    assert_eq!(value, 6831);
-    value
+    input.follow_the_white_rabbit().len() / 2
 }
 
 #[aoc(day10, part2)]
 pub fn part2(input: &Map) -> usize {
-    let value = input.enclosed();
-    assert_eq!(value, 305);
-    value
+    input.enclosed()
 }
 
 #[cfg(test)]
@@ -197,16 +197,12 @@ pub fn input_generator(input: &str) -> GalaxyMap {
 
 #[aoc(day11, part1)]
 pub fn part1(input: &GalaxyMap) -> u64 {
-    let value = input.all_distances(2);
-    assert_eq!(value, 10422930);
-    value
+    input.all_distances(2)
 }
 
 #[aoc(day11, part2)]
 pub fn part2(input: &GalaxyMap) -> u64 {
-    let value = input.all_distances(1_000_000);
-    assert_eq!(value, 699909023130);
-    value
+    input.all_distances(1_000_000)
 }
 
 #[cfg(test)]
@@ -247,17 +247,13 @@ pub fn input_generator(input: &str) -> Vec<ConditionRecord> {
 #[aoc(day12, part1)]
 pub fn part1(input: &[ConditionRecord]) -> u64 {
     let records = input.to_vec();
-    let value = all_ways(records);
-    assert_eq!(value, 7361);
-    value
+    all_ways(records)
 }
 
 #[aoc(day12, part2)]
 pub fn part2(input: &[ConditionRecord]) -> u64 {
     let records = input.iter().cloned().map(|cr| cr.mega()).collect();
-    let value = all_ways(records);
-    assert_eq!(value, 83317216247365);
-    value
+    all_ways(records)
 }
 
 #[cfg(test)]
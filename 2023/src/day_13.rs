@@ -110,53 +110,39 @@
     In each pattern, fix the smudge and find the different line of reflection. What number do you get after summarizing the new reflection line in each pattern in your notes?
 */
 
+use common::{Grid, Transform};
+
 pub struct Mirror {
     rows: Vec<u32>,
     cols: Vec<u32>,
 }
 
+// Finds a line of reflection between lines, which is given as the number of lines BEFORE it
+fn find_line(lines: &[u32], allow_smudge: bool) -> Option<usize> {
+    (1..lines.len()).find(|&reflection| {
+        let mut smudged = false;
+        let (left, right) = lines.split_at(reflection);
+        right.iter().zip(left.iter().rev()).all(|(a, b)| {
+            let bit_diff = a ^ b;
+            if bit_diff == 0 {
+                true
+            } else if allow_smudge && bit_diff.is_power_of_two() && !smudged {
+                smudged = true;
+                true
+            } else {
+                false
+            }
+        }) && (!allow_smudge || smudged)
+    })
+}
+
 impl Mirror {
     fn find_reflection(&self, allow_smudge: bool) -> (Option<usize>, Option<usize>) {
-        // Rows
-        for reflection in 1..self.rows.len() {
-            // BEFORE this row
-            let mut smudged = false;
-            let (left, right) = self.rows.split_at(reflection);
-            if right.iter().zip(left.iter().rev()).all(|(a, b)| {
-                let bit_diff = a ^ b;
-                if bit_diff == 0 {
-                    true
-                } else if allow_smudge && bit_diff.is_power_of_two() && !smudged {
-                    smudged = true;
-                    true
-                } else {
-                    false
-                }
-            }) && (!allow_smudge || smudged)
-            {
-                return (Some(reflection), None);
-            }
+        if let Some(reflection) = find_line(&self.rows, allow_smudge) {
+            return (Some(reflection), None);
         }
-
-        // Cols
-        for reflection in 1..self.cols.len() {
-            // BEFORE this col
-            let mut smudged = false;
-            let (left, right) = self.cols.split_at(reflection);
-            if right.iter().zip(left.iter().rev()).all(|(a, b)| {
-                let bit_diff = a ^ b;
-                if bit_diff == 0 {
-                    true
-                } else if allow_smudge && bit_diff.is_power_of_two() && !smudged {
-                    smudged = true;
-                    true
-                } else {
-                    false
-                }
-            }) && (!allow_smudge || smudged)
-            {
-                return (None, Some(reflection));
-            }
+        if let Some(reflection) = find_line(&self.cols, allow_smudge) {
+            return (None, Some(reflection));
         }
 
         println!("{self}");
@@ -166,25 +152,15 @@ impl Mirror {
 
 impl From<&str> for Mirror {
     fn from(value: &str) -> Self {
-        let rows: Vec<u32> = value
-            .lines()
-            .map(|line| {
-                line.chars()
-                    .map(|c| c == '#')
-                    .enumerate()
-                    .fold(0, |acc, (i, b)| acc | ((b as u32) << i))
-            })
-            .collect();
-
-        let width = value.lines().next().unwrap().len();
-        let cols = (0..width)
-            .map(|i| {
-                rows.iter()
-                    .map(|row| (row & (1 << i)) != 0)
-                    .enumerate()
-                    .fold(0, |acc, (i, b)| acc | ((b as u32) << i))
-            })
-            .collect();
+        fn to_bits(line: &[bool]) -> u32 {
+            line.iter()
+                .enumerate()
+                .fold(0, |acc, (i, b)| acc | ((*b as u32) << i))
+        }
+
+        let grid = Grid::from_string_with(value, |c| Some(c == '#'));
+        let rows = grid.rows().map(to_bits).collect();
+        let cols = grid.transpose().rows().map(to_bits).collect();
 
         Self { rows, cols }
     }
@@ -226,16 +202,12 @@ pub fn input_generator(input: &str) -> Vec<Mirror> {
 
 #[aoc(day13, part1)]
 pub fn part1(input: &[Mirror]) -> u64 {
-    let value = score_reflections(input, false);
-    assert_eq!(value, 30575);
-    value
+    score_reflections(input, false)
 }
 
 #[aoc(day13, part2)]
 pub fn part2(input: &[Mirror]) -> u64 {
-    let value = score_reflections(input, true);
-    assert_eq!(value, 37478);
-    value
+    score_reflections(input, true)
 }
 
 #[cfg(test)]
@@ -99,14 +99,13 @@
     Run the spin cycle for 1000000000 cycles. Afterward, what is the total load on the north support beams?
 */
 
-use std::collections::HashMap;
-
-use common::{Cardinal, Point2, TileChar, TileMap};
+use common::{cycle, Cardinal, Grid, Orientation, Point2, TileChar, Transform};
 
 #[derive(Clone, Copy, Eq, Hash, PartialEq)]
 enum Rocks {
     Round,
     Cube,
+    Empty,
 }
 
 impl TileChar for Rocks {
@@ -114,6 +113,7 @@ impl TileChar for Rocks {
         match *self {
             Rocks::Round => 'O',
             Rocks::Cube => '#',
+            Rocks::Empty => '.',
         }
     }
 
@@ -121,96 +121,54 @@ impl TileChar for Rocks {
         match c {
             'O' => Some(Rocks::Round),
             '#' => Some(Rocks::Cube),
+            '.' => Some(Rocks::Empty),
             _ => None,
         }
     }
 
     fn all_chars() -> Vec<char> {
-        vec!['O', '#']
+        vec!['O', '#', '.']
     }
 }
 
 #[derive(Clone)]
 pub struct Platform {
-    rocks: TileMap<Rocks>,
+    rocks: Grid<Rocks>,
 }
 
 impl Platform {
-    fn tilt(&mut self, dir: Cardinal) {
-        let range = self.rocks.get_range().unwrap();
-
-        match dir {
-            Cardinal::North => {
-                for x in range.x.0..=range.x.1 {
-                    let mut write_p = Point2 { x, y: range.y.0 };
-                    for y in range.y.0..=range.y.1 {
-                        let p = Point2 { x, y };
-                        match self.rocks.get(&p) {
-                            Some(Rocks::Cube) => write_p.y = y + 1,
-                            Some(Rocks::Round) => {
-                                self.rocks.remove(&p);
-                                self.rocks.insert(write_p, Rocks::Round);
-                                write_p.y += 1;
-                            }
-                            None => (),
-                        }
-                    }
-                }
-            }
-            Cardinal::South => {
-                for x in range.x.0..=range.x.1 {
-                    let mut write_p = Point2 { x, y: range.y.1 };
-                    for y in (range.y.0..=range.y.1).rev() {
-                        let p = Point2 { x, y };
-                        match self.rocks.get(&p) {
-                            Some(Rocks::Cube) => write_p.y = y - 1,
-                            Some(Rocks::Round) => {
-                                self.rocks.remove(&p);
-                                self.rocks.insert(write_p, Rocks::Round);
-                                write_p.y -= 1;
-                            }
-                            None => (),
-                        }
-                    }
-                }
-            }
-            Cardinal::East => {
-                for y in range.y.0..=range.y.1 {
-                    let mut write_p = Point2 { x: range.x.1, y };
-                    for x in (range.x.0..=range.x.1).rev() {
-                        let p = Point2 { x, y };
-                        match self.rocks.get(&p) {
-                            Some(Rocks::Cube) => write_p.x = x - 1,
-                            Some(Rocks::Round) => {
-                                self.rocks.remove(&p);
-                                self.rocks.insert(write_p, Rocks::Round);
-                                write_p.x -= 1;
-                            }
-                            None => (),
-                        }
-                    }
-                }
-            }
-            Cardinal::West => {
-                for y in range.y.0..=range.y.1 {
-                    let mut write_p = Point2 { x: range.x.0, y };
-                    for x in range.x.0..=range.x.1 {
-                        let p = Point2 { x, y };
-                        match self.rocks.get(&p) {
-                            Some(Rocks::Cube) => write_p.x = x + 1,
-                            Some(Rocks::Round) => {
-                                self.rocks.remove(&p);
-                                self.rocks.insert(write_p, Rocks::Round);
-                                write_p.x += 1;
-                            }
-                            None => (),
-                        }
+    fn tilt_north(&mut self) {
+        for x in 0..self.rocks.width() as i32 {
+            let mut write_p = Point2 { x, y: 0 };
+            for y in 0..self.rocks.height() as i32 {
+                let p = Point2 { x, y };
+                match self.rocks[p] {
+                    Rocks::Cube => write_p.y = y + 1,
+                    Rocks::Round => {
+                        self.rocks[p] = Rocks::Empty;
+                        self.rocks[write_p] = Rocks::Round;
+                        write_p.y += 1;
                     }
+                    Rocks::Empty => (),
                 }
             }
         }
     }
 
+    fn tilt(&mut self, dir: Cardinal) {
+        // Turn the platform so that the direction to tilt in is north, then turn it back afterwards
+        let rotation = match dir {
+            Cardinal::North => 0,
+            Cardinal::East => 3,
+            Cardinal::South => 2,
+            Cardinal::West => 1,
+        };
+        let turn = Orientation::new(rotation, false);
+        self.rocks = self.rocks.transform(turn);
+        self.tilt_north();
+        self.rocks = self.rocks.transform(turn.inverse());
+    }
+
     fn spin_cycle(&mut self) {
         for dir in [
             Cardinal::North,
@@ -222,49 +180,23 @@ impl Platform {
         }
     }
 
-    fn hash_token(&self) -> Vec<(Point2, Rocks)> {
-        let mut vec: Vec<(Point2, Rocks)> = self.rocks.iter().map(|(p, r)| (*p, *r)).collect();
-        vec.sort_by_key(|(p, _r)| *p);
-        vec
-    }
-
     fn spin_n(&mut self, n: usize) {
-        let mut cache: HashMap<Vec<(Point2, Rocks)>, usize> = HashMap::new();
-
-        let mut i = 0;
-        while i < n {
-            if let Some(prev_idx) = cache.insert(self.hash_token(), i) {
-                let cycle = i - prev_idx;
-                let remaining = n - i;
-                let skips = remaining / cycle;
-                i += skips * cycle;
-                cache.clear(); // This just avoids trying to skip again
-            }
-            self.spin_cycle();
-            i += 1;
-        }
+        *self = cycle::run_to(self.clone(), n, Self::spin_cycle, |p| p.rocks.clone());
     }
 
     fn calc_load(&self) -> i32 {
-        let mut load = 0;
-        let range = self.rocks.get_range().unwrap();
-
-        for x in range.x.0..=range.x.1 {
-            for y in range.y.0..=range.y.1 {
-                let p = Point2 { x, y };
-                if matches!(self.rocks.get(&p), Some(Rocks::Round)) {
-                    load += range.y.1 - y + 1;
-                }
-            }
-        }
-
-        load
+        let height = self.rocks.height() as i32;
+        self.rocks
+            .iter()
+            .filter(|(_, r)| **r == Rocks::Round)
+            .map(|(p, _)| height - p.y)
+            .sum()
     }
 }
 
 impl From<&str> for Platform {
     fn from(value: &str) -> Self {
-        let rocks = TileMap::from_string(value);
+        let rocks = Grid::from_string(value);
         Self { rocks }
     }
 }
@@ -278,18 +210,14 @@ pub fn input_generator(input: &str) -> Platform {
 pub fn part1(input: &Platform) -> i32 {
     let mut platform = input.clone();
     platform.tilt(Cardinal::North);
-    let value = platform.calc_load();
-    assert_eq!(value, 109098);
-    value
+    platform.calc_load()
 }
 
 #[aoc(day14, part2)]
 pub fn part2(input: &Platform) -> i32 {
     let mut platform = input.clone();
     platform.spin_n(1_000_000_000);
-    let value = platform.calc_load();
-    assert_eq!(value, 100064);
-    value
+    platform.calc_load()
 }
 
 #[cfg(test)]
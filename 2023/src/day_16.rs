@@ -217,16 +217,12 @@ pub fn input_generator(input: &str) -> Contraption {
 
 #[aoc(day16, part1)]
 pub fn part1(input: &Contraption) -> u64 {
-    let value = input.energize(Point2::origin(), Cardinal::East);
-    assert_eq!(value, 7979);
-    value
+    input.energize(Point2::origin(), Cardinal::East)
 }
 
 #[aoc(day16, part2)]
 pub fn part2(input: &Contraption) -> u64 {
-    let value = input.max_energize();
-    assert_eq!(value, 8437);
-    value
+    input.max_energize()
 }
 
 #[cfg(test)]
@@ -93,9 +93,9 @@
     Directing the ultra crucible from the lava pool to the machine parts factory, what is the least heat loss it can incur?
 */
 
-use std::collections::{BTreeSet, HashMap, HashSet};
+use std::collections::HashMap;
 
-use common::{Cardinal, Point2, Turn};
+use common::{search, Cardinal, Point2, Turn};
 
 pub struct City {
     blocks: HashMap<Point2, u32>,
@@ -113,44 +113,29 @@ impl City {
             y: range.y.1,
         };
 
-        let mut frontier: BTreeSet<(u32, Point2, Cardinal, u8)> = BTreeSet::new();
-        for dir in [Cardinal::East, Cardinal::South] {
-            frontier.insert((0, start, dir, 0));
-        }
-        let mut visited: HashSet<(Point2, Cardinal, u8)> = HashSet::new();
-        while let Some((curr_heat, curr_p, curr_dir, curr_count)) = frontier.pop_first() {
-            /*println!("frontier len: {}", frontier.len());
-            println!("curr: {} {} {} {}", curr_dist, curr_p, curr_dir, curr_count);*/
-
-            if curr_p == goal && curr_count >= turn_min {
-                return curr_heat;
+        // A node is a position along with the direction it was entered in and how far it's been travelled in a line
+        let neighbors = |&(p, dir, count): &(Point2, Cardinal, u8)| {
+            let mut next = Vec::new();
+            if count >= turn_min {
+                next.extend([dir.turn(Turn::Left), dir.turn(Turn::Right)].map(|d| (d, 1)));
             }
-
-            if curr_count >= turn_min {
-                for turn in [Turn::Left, Turn::Right] {
-                    let next_dir = curr_dir.turn(turn);
-                    let next_p = curr_p.step(next_dir, 1);
-                    if let Some(heat_loss) = self.blocks.get(&next_p) {
-                        let next = (curr_heat + heat_loss, next_p, next_dir, 1);
-                        if visited.insert((next.1, next.2, next.3)) {
-                            frontier.insert(next);
-                        }
-                    }
-                }
+            if count < straight_max {
+                next.push((dir, count + 1));
             }
+            next.into_iter().filter_map(move |(next_dir, next_count)| {
+                let next_p = p.step(next_dir, 1);
+                self.blocks
+                    .get(&next_p)
+                    .map(|heat_loss| ((next_p, next_dir, next_count), *heat_loss))
+            })
+        };
 
-            if curr_count < straight_max {
-                let next_p = curr_p.step(curr_dir, 1);
-                if let Some(heat_loss) = self.blocks.get(&next_p) {
-                    let next = (curr_heat + heat_loss, next_p, curr_dir, curr_count + 1);
-                    if visited.insert((next.1, next.2, next.3)) {
-                        frontier.insert(next);
-                    }
-                }
-            }
-        }
-
-        panic!("No path found");
+        let starts = [Cardinal::East, Cardinal::South].map(|dir| (start, dir, 0));
+        search::dijkstra(starts, neighbors, |&(p, _, count)| {
+            p == goal && count >= turn_min
+        })
+        .goal_distance()
+        .expect("No path found")
     }
 }
 
@@ -180,16 +165,12 @@ pub fn input_generator(input: &str) -> City {
 
 #[aoc(day17, part1)]
 pub fn part1(input: &City) -> u32 {
-    let value = input.heat_loss(false);
-    assert_eq!(value, 1001);
-    value
+    input.heat_loss(false)
 }
 
 #[aoc(day17, part2)]
 pub fn part2(input: &City) -> u32 {
-    let value = input.heat_loss(true);
-    assert_eq!(value, 1197);
-    value
+    input.heat_loss(true)
 }
 
 #[cfg(test)]
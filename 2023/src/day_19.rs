@@ -62,7 +62,7 @@
     Consider only your list of workflows; the list of part ratings that the Elves wanted you to sort is no longer relevant. How many distinct combinations of ratings will be accepted by the Elves' workflows?
 */
 
-use common::{to_owned, trim_start};
+use common::{to_owned, trim_start, IntervalSet};
 use nom::{
     branch::alt,
     bytes::complete::tag,
@@ -72,7 +72,7 @@ use nom::{
     sequence::{delimited, pair, preceded, terminated, tuple},
     IResult,
 };
-use std::{collections::HashMap, ops::RangeInclusive};
+use std::collections::HashMap;
 
 #[derive(Clone)]
 enum Op {
@@ -256,10 +256,10 @@ impl System {
         self.count_accepted(
             "in",
             Range4 {
-                x: 1..=4000,
-                m: 1..=4000,
-                a: 1..=4000,
-                s: 1..=4000,
+                x: IntervalSet::from_range(1..=4000),
+                m: IntervalSet::from_range(1..=4000),
+                a: IntervalSet::from_range(1..=4000),
+                s: IntervalSet::from_range(1..=4000),
             },
         )
     }
@@ -273,10 +273,10 @@ impl From<&str> for System {
 
 #[derive(Clone)]
 struct Range4 {
-    x: RangeInclusive<u32>,
-    m: RangeInclusive<u32>,
-    a: RangeInclusive<u32>,
-    s: RangeInclusive<u32>,
+    x: IntervalSet<u32>,
+    m: IntervalSet<u32>,
+    a: IntervalSet<u32>,
+    s: IntervalSet<u32>,
 }
 
 impl Range4 {
@@ -291,8 +291,11 @@ impl Range4 {
         };
 
         let (pass, fail) = match cond.op {
-            Op::Lt => (*field.start()..=cond.val - 1, cond.val..=*field.end()),
-            Op::Gt => (cond.val + 1..=*field.end(), *field.start()..=cond.val),
+            Op::Lt => field.split_at(cond.val),
+            Op::Gt => {
+                let (fail, pass) = field.split_at(cond.val + 1);
+                (pass, fail)
+            }
         };
 
         match cond.field {
@@ -304,13 +307,11 @@ impl Range4 {
         }
     }
 
-    #[rustfmt::skip]
     fn volume(self) -> u64 {
-        let x = (*self.x.end() - *self.x.start() + 1) as u64;
-        let m = (*self.m.end() - *self.m.start() + 1) as u64;
-        let a = (*self.a.end() - *self.a.start() + 1) as u64;
-        let s = (*self.s.end() - *self.s.start() + 1) as u64;
-        x * m * a * s
+        [self.x, self.m, self.a, self.s]
+            .iter()
+            .map(|field| field.covered() as u64)
+            .product()
     }
 }
 
@@ -321,16 +322,12 @@ pub fn input_generator(input: &str) -> System {
 
 #[aoc(day19, part1)]
 pub fn part1(input: &System) -> u32 {
-    let value = input.process();
-    assert_eq!(value, 377025);
-    value
+    input.process()
 }
 
 #[aoc(day19, part2)]
 pub fn part2(input: &System) -> u64 {
-    let value = input.combinations();
-    assert_eq!(value, 135506683246673);
-    value
+    input.combinations()
 }
 
 #[cfg(test)]
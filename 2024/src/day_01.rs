@@ -107,16 +107,12 @@ pub fn input_generator(input: &str) -> Vec<(u32, u32)> {
 
 #[aoc(day1, part1)]
 pub fn part1(input: &[(u32, u32)]) -> u64 {
-    let value = total_distance(input);
-    assert_eq!(value, 1882714);
-    value
+    total_distance(input)
 }
 
 #[aoc(day1, part2)]
 pub fn part2(input: &[(u32, u32)]) -> u64 {
-    let value = total_similarity(input);
-    assert_eq!(value, 19437052);
-    value
+    total_similarity(input)
 }
 
 #[cfg(test)]
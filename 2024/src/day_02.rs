@@ -124,16 +124,12 @@ pub fn input_generator(input: &str) -> Vec<Vec<u32>> {
 
 #[aoc(day2, part1)]
 pub fn part1(input: &[Vec<u32>]) -> u64 {
-    let value = count_safe_reports(input);
-    assert_eq!(value, 230);
-    value
+    count_safe_reports(input)
 }
 
 #[aoc(day2, part2)]
 pub fn part2(input: &[Vec<u32>]) -> u64 {
-    let value = count_dampened_reports(input);
-    assert_eq!(value, 301);
-    value
+    count_dampened_reports(input)
 }
 
 #[cfg(test)]
@@ -36,8 +36,10 @@
     Handle the new instructions; what do you get if you add up all of the results of just the enabled multiplications?
 */
 
+use common::machine::{Cpu, Flow, InstructionSet, Machine};
 use regex::Regex;
 
+#[derive(Clone)]
 enum Op {
     Mul(u32, u32),
     Do,
@@ -58,36 +60,48 @@ fn uncorrupt(mem: &str) -> Vec<Op> {
         .collect()
 }
 
-fn computer_mul(ops: &[Op]) -> u64 {
-    ops.iter()
-        .map(|op| match op {
-            Op::Mul(a, b) => (*a as u64) * (*b as u64),
-            _ => 0,
-        })
-        .sum()
-}
+impl InstructionSet for Op {
+    type Registers = Computer;
 
-fn computer_all(ops: &[Op]) -> u64 {
-    let mut enabled = true;
-    ops.iter()
-        .map(|op| match op {
+    fn execute(&self, computer: &mut Computer) -> Flow {
+        match self {
             Op::Mul(a, b) => {
-                if enabled {
-                    (*a as u64) * (*b as u64)
-                } else {
-                    0
+                if computer.enabled {
+                    computer.sum += (*a as u64) * (*b as u64);
                 }
             }
-            Op::Do => {
-                enabled = true;
-                0
-            }
-            Op::Dont => {
-                enabled = false;
-                0
-            }
-        })
-        .sum()
+            Op::Do => computer.enabled = true,
+            Op::Dont => computer.enabled = !computer.conditional,
+        }
+        Flow::Next
+    }
+}
+
+struct Computer {
+    conditional: bool, // Whether do() and don't() are followed
+    enabled: bool,
+    sum: u64,
+}
+
+fn computer(ops: &[Op], conditional: bool) -> u64 {
+    let mut cpu = Cpu::new(
+        ops.to_vec(),
+        Computer {
+            conditional,
+            enabled: true,
+            sum: 0,
+        },
+    );
+    cpu.run();
+    cpu.regs.sum
+}
+
+fn computer_mul(ops: &[Op]) -> u64 {
+    computer(ops, false)
+}
+
+fn computer_all(ops: &[Op]) -> u64 {
+    computer(ops, true)
 }
 
 #[aoc_generator(day3)]
@@ -98,17 +112,13 @@ pub fn input_generator(input: &str) -> String {
 #[aoc(day3, part1)]
 pub fn part1(input: &str) -> u64 {
     let ops = uncorrupt(input);
-    let value = computer_mul(&ops);
-    assert_eq!(value, 174561379);
-    value
+    computer_mul(&ops)
 }
 
 #[aoc(day3, part2)]
 pub fn part2(input: &str) -> u64 {
     let ops = uncorrupt(input);
-    let value = computer_all(&ops);
-    assert_eq!(value, 106921067);
-    value
+    computer_all(&ops)
 }
 
 #[cfg(test)]
@@ -65,129 +65,48 @@
     Flip the word search from the instructions back over to the word search side and try again. How many times does an X-MAS appear?
 */
 
-use std::collections::HashMap;
-
-use common::Point2;
-
-enum Direction {
-    UpLeft,
-    Up,
-    UpRight,
-    Right,
-    DownRight,
-    Down,
-    DownLeft,
-    Left,
-}
+use common::Grid;
 
 pub struct WordSearch {
-    letters: HashMap<Point2, char>,
+    letters: Grid<char>,
+}
+
+// Dots can be any letter
+fn pattern(input: &str) -> Grid<Option<char>> {
+    Grid::from_string_with(input, |c| Some((c != '.').then_some(c)))
 }
 
 impl WordSearch {
     fn parse(input: &str) -> Self {
-        let mut letters = HashMap::new();
-        for (y, line) in input.lines().enumerate() {
-            for (x, c) in line.chars().enumerate() {
-                let p = (x as i32, y as i32).into();
-                letters.insert(p, c);
-            }
-        }
+        let letters = Grid::from_string_with(input, Some);
         Self { letters }
     }
 
-    fn get_4_letters(&self, start: Point2, dir: Direction) -> Option<[char; 4]> {
-        let mut output = ['-'; 4];
-
-        let mut p = start;
-        for out in &mut output {
-            if let Some(c) = self.letters.get(&p) {
-                *out = *c;
-            } else {
-                return None;
-            }
-
-            p += match dir {
-                Direction::UpLeft => (-1, -1),
-                Direction::Up => (0, -1),
-                Direction::UpRight => (1, -1),
-                Direction::Right => (1, 0),
-                Direction::DownRight => (1, 1),
-                Direction::Down => (0, 1),
-                Direction::DownLeft => (-1, 1),
-                Direction::Left => (-1, 0),
-            }
-        }
-
-        Some(output)
-    }
-
     fn count_xmas(&self) -> u64 {
-        let mut count = 0;
-
-        let range = Point2::get_range(self.letters.keys()).unwrap();
-        for y in range.y.0..=range.y.1 {
-            for x in range.x.0..=range.x.1 {
-                let p = Point2 { x, y };
-                if self.letters.get(&p) == Some(&'X') {
-                    for dir in [
-                        Direction::UpLeft,
-                        Direction::Up,
-                        Direction::UpRight,
-                        Direction::Right,
-                        Direction::DownRight,
-                        Direction::Down,
-                        Direction::DownLeft,
-                        Direction::Left,
-                    ] {
-                        if self.get_4_letters(p, dir) == Some(['X', 'M', 'A', 'S']) {
-                            count += 1;
-                        }
-                    }
-                }
-            }
-        }
-
-        count
-    }
-
-    fn get_x_letters(&self, start: Point2) -> Option<[char; 4]> {
-        let mut output = ['-'; 4];
-
-        let points = [(-1, -1), (1, -1), (1, 1), (-1, 1)];
-        for (p, out) in points.iter().zip(output.iter_mut()) {
-            if let Some(letter) = self.letters.get(&(start + p)) {
-                *out = *letter;
-            } else {
-                return None;
-            }
-        }
-
-        Some(output)
+        // Turning these every way covers all eight directions
+        let straight = pattern("XMAS");
+        let diagonal = pattern(
+            "\
+X...
+.M..
+..A.
+...S",
+        );
+
+        let count = self.letters.find_pattern_oriented(&straight).len()
+            + self.letters.find_pattern_oriented(&diagonal).len();
+        count as u64
     }
 
     fn count_x_mas(&self) -> u64 {
-        let mut count = 0;
-
-        let range = Point2::get_range(self.letters.keys()).unwrap();
-        for y in range.y.0..=range.y.1 {
-            for x in range.x.0..=range.x.1 {
-                let p = Point2 { x, y };
-                if self.letters.get(&p) == Some(&'A') {
-                    if let Some(x) = self.get_x_letters(p) {
-                        if x == ['M', 'M', 'S', 'S']
-                            || x == ['M', 'S', 'S', 'M']
-                            || x == ['S', 'S', 'M', 'M']
-                            || x == ['S', 'M', 'M', 'S']
-                        {
-                            count += 1;
-                        }
-                    }
-                }
-            }
-        }
-
-        count
+        let x_mas = pattern(
+            "\
+M.S
+.A.
+M.S",
+        );
+
+        self.letters.find_pattern_oriented(&x_mas).len() as u64
     }
 }
 
@@ -198,16 +117,12 @@ pub fn input_generator(input: &str) -> WordSearch {
 
 #[aoc(day4, part1)]
 pub fn part1(input: &WordSearch) -> u64 {
-    let value = input.count_xmas();
-    assert_eq!(value, 2493);
-    value
+    input.count_xmas()
 }
 
 #[aoc(day4, part2)]
 pub fn part2(input: &WordSearch) -> u64 {
-    let value = input.count_x_mas();
-    assert_eq!(value, 1890);
-    value
+    input.count_x_mas()
 }
 
 #[cfg(test)]
@@ -150,7 +150,7 @@ impl Manual {
         let mut output = Vec::from(page_list);
 
         output.sort_by(|a, b| {
-            if self.rules.get(a).map_or(false, |rule| rule.contains(b)) {
+            if self.rules.get(a).is_some_and(|rule| rule.contains(b)) {
                 Ordering::Less
             } else {
                 Ordering::Greater
@@ -177,16 +177,12 @@ pub fn input_generator(input: &str) -> Manual {
 
 #[aoc(day5, part1)]
 pub fn part1(input: &Manual) -> u64 {
-    let value = input.page_lists_correct_sum();
-    assert_eq!(value, 4924);
-    value
+    input.page_lists_correct_sum()
 }
 
 #[aoc(day5, part2)]
 pub fn part2(input: &Manual) -> u64 {
-    let value = input.page_lists_incorrect_sum();
-    assert_eq!(value, 6085);
-    value
+    input.page_lists_incorrect_sum()
 }
 
 #[cfg(test)]
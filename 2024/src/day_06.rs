@@ -285,16 +285,12 @@ pub fn input_generator(input: &str) -> LabMap {
 
 #[aoc(day6, part1)]
 pub fn part1(input: &LabMap) -> u64 {
-    let value = input.trace_guard_path().unwrap();
-    assert_eq!(value, 5534);
-    value
+    input.trace_guard_path().unwrap()
 }
 
 #[aoc(day6, part2)]
 pub fn part2(input: &LabMap) -> u64 {
-    let value = input.find_loops();
-    assert_eq!(value, 2262);
-    value
+    input.find_loops()
 }
 
 #[cfg(test)]
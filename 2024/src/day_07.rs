@@ -148,16 +148,12 @@ pub fn input_generator(input: &str) -> Calibration {
 
 #[aoc(day7, part1)]
 pub fn part1(input: &Calibration) -> u64 {
-    let value = input.calib2_total();
-    assert_eq!(value, 20665830408335);
-    value
+    input.calib2_total()
 }
 
 #[aoc(day7, part2)]
 pub fn part2(input: &Calibration) -> u64 {
-    let value = input.calib3_total();
-    assert_eq!(value, 354060705047464);
-    value
+    input.calib3_total()
 }
 
 #[cfg(test)]
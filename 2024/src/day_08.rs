@@ -199,16 +199,12 @@ pub fn input_generator(input: &str) -> CityMap {
 
 #[aoc(day8, part1)]
 pub fn part1(input: &CityMap) -> u64 {
-    let value = input.count_antinodes();
-    assert_eq!(value, 249);
-    value
+    input.count_antinodes()
 }
 
 #[aoc(day8, part2)]
 pub fn part2(input: &CityMap) -> u64 {
-    let value = input.count_antinodes_harmonic();
-    assert_eq!(value, 905);
-    value
+    input.count_antinodes_harmonic()
 }
 
 #[cfg(test)]
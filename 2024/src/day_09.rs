@@ -180,16 +180,12 @@ pub fn input_generator(input: &str) -> FileSystem {
 
 #[aoc(day9, part1)]
 pub fn part1(input: &FileSystem) -> u64 {
-    let value = input.compact_blocks();
-    assert_eq!(value, 6367087064415);
-    value
+    input.compact_blocks()
 }
 
 #[aoc(day9, part2)]
 pub fn part2(input: &FileSystem) -> u64 {
-    let value = input.compact_files();
-    assert_eq!(value, 6390781891880);
-    value
+    input.compact_files()
 }
 
 #[cfg(test)]
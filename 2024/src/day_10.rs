@@ -114,8 +114,9 @@
     You're not sure how, but the reindeer seems to have crafted some tiny flags out of toothpicks and bits of paper and is using them to mark trailheads on your topographic map. What is the sum of the ratings of all trailheads?
 */
 
-use std::collections::{HashMap, HashSet};
+use std::collections::HashMap;
 
+use common::search::{self, ShortestPaths};
 use common::Point2;
 
 pub struct Topo {
@@ -142,50 +143,41 @@ impl Topo {
     }
 
     fn trailhead_score(&self) -> u64 {
-        self.tiles
-            .iter()
-            .filter_map(|(p, height)| {
-                if *height == 0 {
-                    let mut peak_set = HashSet::new();
-                    self.peaks_from(*p, &mut peak_set);
-                    Some(peak_set.len() as u64)
-                } else {
-                    None
-                }
-            })
+        self.trailheads()
+            .map(|trails| self.peaks(&trails).count() as u64)
             .sum()
     }
 
     fn trailhead_rating(&self) -> u64 {
-        self.tiles
-            .iter()
-            .filter_map(|(p, height)| {
-                if *height == 0 {
-                    let mut peak_set = HashSet::new();
-                    Some(self.peaks_from(*p, &mut peak_set))
-                } else {
-                    None
-                }
+        self.trailheads()
+            .map(|trails| {
+                let counts = trails.path_counts();
+                self.peaks(&trails).map(|p| counts[p]).sum::<u64>()
             })
             .sum()
     }
 
-    fn peaks_from(&self, p: Point2, peaks: &mut HashSet<Point2>) -> u64 {
-        let height = self.tiles.get(&p).unwrap();
-        if *height == 9 {
-            peaks.insert(p);
-            1
-        } else {
-            p.orthogonals()
-                .map(|adj| {
-                    if self.tiles.get(&adj) == Some(&(height + 1)) {
-                        self.peaks_from(adj, peaks)
-                    } else {
-                        0
-                    }
-                })
-                .sum()
-        }
+    // Every step of a trail climbs by exactly one so every trail is a shortest path, and all of them from a trailhead
+    // can be found at once
+    fn trailheads(&self) -> impl Iterator<Item = ShortestPaths<Point2, usize>> + '_ {
+        self.tiles
+            .iter()
+            .filter(|(_, height)| **height == 0)
+            .map(|(p, _)| search::bfs_all([*p], |p| self.uphill(p), |_| false))
+    }
+
+    fn uphill(&self, p: &Point2) -> Vec<Point2> {
+        let height = self.tiles[p];
+        p.orthogonals()
+            .filter(|adj| self.tiles.get(adj) == Some(&(height + 1)))
+            .collect()
+    }
+
+    fn peaks<'a>(
+        &'a self,
+        trails: &'a ShortestPaths<Point2, usize>,
+    ) -> impl Iterator<Item = &'a Point2> + 'a {
+        trails.distances().keys().filter(|p| self.tiles[p] == 9)
     }
 }
 
@@ -196,16 +188,12 @@ pub fn input_generator(input: &str) -> Topo {
 
 #[aoc(day10, part1)]
 pub fn part1(input: &Topo) -> u64 {
-    let value = input.trailhead_score();
-    assert_eq!(value, 789);
-    value
+    input.trailhead_score()
 }
 
 #[aoc(day10, part2)]
 pub fn part2(input: &Topo) -> u64 {
-    let value = input.trailhead_rating();
-    assert_eq!(value, 1735);
-    value
+    input.trailhead_rating()
 }
 
 #[cfg(test)]
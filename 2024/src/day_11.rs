@@ -62,7 +62,7 @@ use std::collections::HashMap;
 
 fn even_digits(n: u64) -> bool {
     let log10 = n.ilog10();
-    log10 % 2 != 0
+    !log10.is_multiple_of(2)
 }
 
 fn blink(stones: &[u64], times: usize) -> u64 {
@@ -110,16 +110,12 @@ pub fn input_generator(input: &str) -> Vec<u64> {
 
 #[aoc(day11, part1)]
 pub fn part1(input: &[u64]) -> u64 {
-    let value = blink(input, 25);
-    assert_eq!(value, 186424);
-    value
+    blink(input, 25)
 }
 
 #[aoc(day11, part2)]
 pub fn part2(input: &[u64]) -> u64 {
-    let value = blink(input, 75);
-    assert_eq!(value, 219838428124832);
-    value
+    blink(input, 75)
 }
 
 #[cfg(test)]
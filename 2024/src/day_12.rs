@@ -133,125 +133,34 @@
     What is the new total price of fencing all regions on your map?
 */
 
-use std::collections::{HashMap, HashSet};
-
-use common::Point2;
+use common::region::{self, Region};
+use common::{Grid, Point2};
 
 pub struct Garden {
-    plots: HashMap<Point2, char>,
+    plots: Grid<char>,
 }
 
 impl Garden {
     fn parse(input: &str) -> Self {
-        let mut plots = HashMap::new();
-
-        for (y, line) in input.lines().enumerate() {
-            for (x, c) in line.chars().enumerate() {
-                let p = Point2 {
-                    x: x as i32,
-                    y: y as i32,
-                };
-                plots.insert(p, c);
-            }
-        }
-
+        let plots = Grid::from_string_with(input, Some);
         Self { plots }
     }
 
-    fn fence_cost(&self) -> u64 {
-        let mut regions: Vec<Vec<(Point2, usize)>> = Vec::new();
-        let mut visited = HashSet::new();
-
-        for (p, c) in &self.plots {
-            if !visited.contains(p) {
-                let mut region = Vec::new();
-                let mut frontier = vec![*p];
-                while let Some(curr) = frontier.pop() {
-                    if visited.insert(curr) {
-                        let neighbors: Vec<Point2> = curr
-                            .orthogonals()
-                            .filter(|adj| self.plots.get(adj) == Some(c))
-                            .collect();
-
-                        region.push((curr, neighbors.len()));
-                        frontier.extend(neighbors.into_iter());
-                    }
-                }
-
-                regions.push(region);
-            }
-        }
+    fn regions(&self) -> Vec<Region<Point2>> {
+        region::components(self.plots.points(), |a, b| self.plots[a] == self.plots[b])
+    }
 
-        regions
+    fn fence_cost(&self) -> u64 {
+        self.regions()
             .into_iter()
-            .map(|region| {
-                let area = region.len();
-                let perimeter: usize = region.into_iter().map(|(_, neighbors)| 4 - neighbors).sum();
-                (area * perimeter) as u64
-            })
+            .map(|region| (region.area() * region.perimeter()) as u64)
             .sum()
     }
 
     fn fence_cost2(&self) -> u64 {
-        let mut regions: Vec<Vec<(Point2, usize)>> = Vec::new();
-        let mut visited = HashSet::new();
-
-        for (p, c) in &self.plots {
-            if !visited.contains(p) {
-                let mut region = Vec::new();
-                let mut frontier = vec![*p];
-                while let Some(curr) = frontier.pop() {
-                    if visited.insert(curr) {
-                        let [ul, u, l, dl, d, ur, r] =
-                            [(-1, -1), (0, -1), (-1, 0), (-1, 1), (0, 1), (1, -1), (1, 0)]
-                                .map(|off| self.plots.get(&(curr + off)));
-
-                        // Top edge looks like:
-                        // .. or c. or c.
-                        // .x    .x    cx
-                        let is_top_edge = u != Some(c) && (l != Some(c) || ul == Some(c));
-
-                        // Bottom edge looks like:
-                        // .x or .x or cx
-                        // ..    c.    c.
-                        let is_bot_edge = d != Some(c) && (l != Some(c) || dl == Some(c));
-
-                        // Left edge looks like:
-                        // .. or c. or cc
-                        // .x    .x or .x
-                        let is_left_edge = l != Some(c) && (u != Some(c) || ul == Some(c));
-
-                        // Right edge looks like:
-                        // .. or .c or cc
-                        // x.    x.    x.
-                        let is_right_edge = r != Some(c) && (u != Some(c) || ur == Some(c));
-
-                        let edge_count = [is_top_edge, is_bot_edge, is_left_edge, is_right_edge]
-                            .into_iter()
-                            .filter(|b| *b)
-                            .count();
-
-                        region.push((curr, edge_count));
-
-                        let neighbors: Vec<Point2> = curr
-                            .orthogonals()
-                            .filter(|adj| self.plots.get(adj) == Some(c))
-                            .collect();
-                        frontier.extend(neighbors.into_iter());
-                    }
-                }
-
-                regions.push(region);
-            }
-        }
-
-        regions
+        self.regions()
             .into_iter()
-            .map(|region| {
-                let area = region.len();
-                let perimeter: usize = region.into_iter().map(|(_, edges)| edges).sum();
-                (area * perimeter) as u64
-            })
+            .map(|region| (region.area() * region.sides()) as u64)
             .sum()
     }
 }
@@ -263,16 +172,12 @@ pub fn input_generator(input: &str) -> Garden {
 
 #[aoc(day12, part1)]
 pub fn part1(input: &Garden) -> u64 {
-    let value = input.fence_cost();
-    assert_eq!(value, 1450816);
-    value
+    input.fence_cost()
 }
 
 #[aoc(day12, part2)]
 pub fn part2(input: &Garden) -> u64 {
-    let value = input.fence_cost2();
-    assert_eq!(value, 865662);
-    value
+    input.fence_cost2()
 }
 
 #[cfg(test)]
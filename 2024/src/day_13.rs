@@ -71,66 +71,12 @@
     Using the corrected prize coordinates, figure out how to win as many prizes as possible. What is the fewest tokens you would have to spend to win all possible prizes?
 */
 
-use std::{
-    fmt::Display,
-    ops::{Add, AddAssign, Mul, Sub},
-};
-
-#[derive(Clone, Copy, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
-struct Point2 {
-    x: i64,
-    y: i64,
-}
-
-impl Add<Point2> for Point2 {
-    type Output = Self;
-
-    fn add(self, other: Self) -> Self::Output {
-        Point2 {
-            x: self.x + other.x,
-            y: self.y + other.y,
-        }
-    }
-}
-
-impl AddAssign<Point2> for Point2 {
-    fn add_assign(&mut self, other: Self) {
-        *self = *self + other;
-    }
-}
-
-impl Sub<Point2> for Point2 {
-    type Output = Self;
-
-    fn sub(self, other: Point2) -> Self::Output {
-        Point2 {
-            x: self.x - other.x,
-            y: self.y - other.y,
-        }
-    }
-}
-
-impl Mul<i64> for Point2 {
-    type Output = Self;
-
-    fn mul(self, other: i64) -> Self::Output {
-        Point2 {
-            x: self.x * other,
-            y: self.y * other,
-        }
-    }
-}
-
-impl Display for Point2 {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "({} {})", self.x, self.y)
-    }
-}
+use common::Point2;
 
 pub struct Machine {
-    a: Point2,
-    b: Point2,
-    prize: Point2,
+    a: Point2<i64>,
+    b: Point2<i64>,
+    prize: Point2<i64>,
 }
 
 impl Machine {
@@ -164,7 +110,7 @@ impl Machine {
         Self { a, b, prize }
     }
 
-    fn win(&self, offset: Point2) -> Option<u64> {
+    fn win(&self, offset: Point2<i64>) -> Option<u64> {
         let prize = self.prize + offset;
 
         let a_dydx = self.a.y as f32 / self.a.x as f32;
@@ -216,7 +162,7 @@ impl Arcade {
         Self { machines }
     }
 
-    fn win(&self, offset: Point2) -> u64 {
+    fn win(&self, offset: Point2<i64>) -> u64 {
         self.machines
             .iter()
             .filter_map(|machine| machine.win(offset))
@@ -231,19 +177,15 @@ pub fn input_generator(input: &str) -> Arcade {
 
 #[aoc(day13, part1)]
 pub fn part1(input: &Arcade) -> u64 {
-    let value = input.win(Point2 { x: 0, y: 0 });
-    assert_eq!(value, 36954);
-    value
+    input.win(Point2 { x: 0, y: 0 })
 }
 
 #[aoc(day13, part2)]
 pub fn part2(input: &Arcade) -> u64 {
-    let value = input.win(Point2 {
+    input.win(Point2 {
         x: 10000000000000,
         y: 10000000000000,
-    });
-    assert_eq!(value, 79352015273424);
-    value
+    })
 }
 
 #[cfg(test)]
@@ -124,7 +124,7 @@
 
 use std::collections::HashSet;
 
-use common::{modulo, Point2};
+use common::{Point2, Range2};
 
 #[derive(Clone)]
 pub struct Robot {
@@ -151,9 +151,8 @@ impl Robot {
         Self { pos, vel }
     }
 
-    fn step(&mut self, max_x: i32, max_y: i32) {
-        self.pos.x = modulo(self.pos.x + self.vel.x, max_x);
-        self.pos.y = modulo(self.pos.y + self.vel.y, max_y);
+    fn step(&mut self, area: &Range2) {
+        self.pos = area.wrap(self.pos + self.vel);
     }
 }
 
@@ -169,10 +168,14 @@ impl RobotMap {
     }
 
     fn safety_factor(&self, steps: usize, max_x: i32, max_y: i32) -> u64 {
+        let area = Range2 {
+            x: (0, max_x - 1),
+            y: (0, max_y - 1),
+        };
         let mut bots = self.robots.clone();
         for _ in 0..steps {
             for bot in &mut bots {
-                bot.step(max_x, max_y);
+                bot.step(&area);
             }
         }
 
@@ -196,6 +199,11 @@ impl RobotMap {
     }
 
     fn easter_egg(&self, max_x: i32, max_y: i32) -> u64 {
+        let area = Range2 {
+            x: (0, max_x - 1),
+            y: (0, max_y - 1),
+        };
+
         // Assume they converge around a single location and it happens when variance is minimized
         let mut bots = self.robots.clone();
         let mut var_min = i64::MAX;
@@ -219,7 +227,7 @@ impl RobotMap {
             }
 
             for bot in &mut bots {
-                bot.step(max_x, max_y);
+                bot.step(&area);
             }
         }
 
@@ -248,16 +256,12 @@ pub fn input_generator(input: &str) -> RobotMap {
 
 #[aoc(day14, part1)]
 pub fn part1(input: &RobotMap) -> u64 {
-    let value = input.safety_factor(100, 101, 103);
-    assert_eq!(value, 211692000);
-    value
+    input.safety_factor(100, 101, 103)
 }
 
 #[aoc(day14, part2)]
 pub fn part2(input: &RobotMap) -> u64 {
-    let value = input.easter_egg(101, 103);
-    assert_eq!(value, 6587);
-    value
+    input.easter_egg(101, 103)
 }
 
 #[cfg(test)]
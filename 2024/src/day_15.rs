@@ -583,7 +583,7 @@ impl Warehouse {
                     }
                 }
 
-                frontier.extend(tmp.into_iter());
+                frontier.extend(tmp);
             }
 
             if can_move {
@@ -617,16 +617,12 @@ pub fn input_generator(input: &str) -> Warehouse {
 
 #[aoc(day15, part1)]
 pub fn part1(input: &Warehouse) -> u64 {
-    let value = input.predict();
-    assert_eq!(value, 1514333);
-    value
+    input.predict()
 }
 
 #[aoc(day15, part2)]
 pub fn part2(input: &Warehouse) -> u64 {
-    let value = input.predict_wide();
-    assert_eq!(value, 1528453);
-    value
+    input.predict_wide()
 }
 
 #[cfg(test)]
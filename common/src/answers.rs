@@ -0,0 +1,139 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+// Identifies a puzzle input so that answers are only ever checked against the input they were found for. This is
+// 64-bit FNV-1a of the input as the runner sees it, i.e. without trailing newlines.
+pub fn input_hash(input: &str) -> String {
+    let hash = input
+        .trim_end_matches('\n')
+        .bytes()
+        .fold(0xcbf2_9ce4_8422_2325_u64, |hash, byte| {
+            (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+        });
+    format!("{:016x}", hash)
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Verdict {
+    Match,
+    Mismatch(String), // The expected answer
+    Unknown,
+}
+
+// Known answers, as stored in each year's answers.txt. Each line is "day part input-hash answer", with any newlines
+// or backslashes in the answer escaped. Blank lines and lines starting with '#' are ignored.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Answers {
+    answers: HashMap<(u32, u32, String), String>,
+}
+
+impl Answers {
+    pub fn from_string(input: &str) -> Self {
+        let mut answers = HashMap::new();
+        for (line_num, line) in input.lines().enumerate() {
+            if line.trim().is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let fields: Vec<&str> = line.splitn(4, ' ').collect();
+            let [day, part, hash, answer] = fields[..] else {
+                panic!("Invalid answer on line {}: {}", line_num + 1, line);
+            };
+            let (Ok(day), Ok(part)) = (day.parse(), part.parse()) else {
+                panic!("Invalid answer on line {}: {}", line_num + 1, line);
+            };
+            answers.insert((day, part, hash.to_owned()), Self::unescape(answer));
+        }
+        Self { answers }
+    }
+
+    // A missing file just means nothing is known yet
+    pub fn load(path: &Path) -> Self {
+        fs::read_to_string(path)
+            .map(|text| Self::from_string(&text))
+            .unwrap_or_default()
+    }
+
+    pub fn get(&self, day: u32, part: u32, input: &str) -> Option<&str> {
+        self.answers
+            .get(&(day, part, input_hash(input)))
+            .map(String::as_str)
+    }
+
+    pub fn verify(&self, day: u32, part: u32, input: &str, answer: &str) -> Verdict {
+        match self.get(day, part, input) {
+            Some(expected) if expected == answer => Verdict::Match,
+            Some(expected) => Verdict::Mismatch(expected.to_owned()),
+            None => Verdict::Unknown,
+        }
+    }
+
+    // The line to add to answers.txt to record an answer
+    pub fn line(day: u32, part: u32, input: &str, answer: &str) -> String {
+        format!(
+            "{} {} {} {}",
+            day,
+            part,
+            input_hash(input),
+            answer.replace('\\', "\\\\").replace('\n', "\\n")
+        )
+    }
+
+    fn unescape(answer: &str) -> String {
+        let mut output = String::new();
+        let mut chars = answer.chars();
+        while let Some(c) = chars.next() {
+            match (c, chars.clone().next()) {
+                ('\\', Some('n')) => {
+                    output.push('\n');
+                    chars.next();
+                }
+                ('\\', Some('\\')) => {
+                    output.push('\\');
+                    chars.next();
+                }
+                _ => output.push(c),
+            }
+        }
+        output
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_input_hash() {
+        assert_eq!(input_hash(""), "cbf29ce484222325");
+        assert_eq!(input_hash("a"), "af63dc4c8601ec8c");
+        assert_eq!(input_hash("1\n2\n"), input_hash("1\n2"));
+        assert_ne!(input_hash("1\n2"), input_hash("1\n3"));
+    }
+
+    #[test]
+    fn test_verify() {
+        let answers = Answers::from_string(&format!(
+            "# Comment\n\n{}\n{}\n",
+            Answers::line(6, 1, "input", "5534"),
+            Answers::line(10, 2, "input", "\n#..#\n#\\.#"),
+        ));
+        assert_eq!(answers.verify(6, 1, "input", "5534"), Verdict::Match);
+        assert_eq!(
+            answers.verify(6, 1, "input\n", "5535"),
+            Verdict::Mismatch("5534".to_owned())
+        );
+        assert_eq!(
+            answers.verify(6, 1, "other input", "5534"),
+            Verdict::Unknown
+        );
+        assert_eq!(answers.verify(6, 2, "input", "5534"), Verdict::Unknown);
+        assert_eq!(answers.get(10, 2, "input"), Some("\n#..#\n#\\.#"));
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid answer on line 1: 6 one abc 5534")]
+    fn test_invalid() {
+        Answers::from_string("6 one abc 5534");
+    }
+}
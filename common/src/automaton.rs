@@ -0,0 +1,245 @@
+use crate::{Grid, Point2};
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
+
+// Every cell of a grid updated at once each step. Which cells count as neighbors is worked out once up front, so it
+// can be something costly like the first seat visible in each direction. Points outside the grid are left out.
+#[derive(Clone, Debug)]
+pub struct Dense<T> {
+    cells: Grid<T>,
+    buffer: Grid<T>,
+    neighbors: Grid<Vec<Point2>>,
+    generation: usize,
+}
+
+impl<T: Clone + PartialEq> Dense<T> {
+    pub fn new<I>(cells: Grid<T>, mut neighbors: impl FnMut(&Grid<T>, Point2) -> I) -> Self
+    where
+        I: IntoIterator<Item = Point2>,
+    {
+        let neighbors = Grid::from_fn(cells.width(), cells.height(), |p| {
+            neighbors(&cells, p)
+                .into_iter()
+                .filter(|n| cells.contains(*n))
+                .collect()
+        });
+        Self {
+            buffer: cells.clone(),
+            cells,
+            neighbors,
+            generation: 0,
+        }
+    }
+
+    // The eight surrounding cells
+    pub fn with_adjacents(cells: Grid<T>) -> Self {
+        Self::new(cells, |_, p| p.adjacents())
+    }
+
+    // The four cells sharing an edge
+    pub fn with_orthogonals(cells: Grid<T>) -> Self {
+        Self::new(cells, |_, p| p.orthogonals())
+    }
+
+    pub fn cells(&self) -> &Grid<T> {
+        &self.cells
+    }
+
+    pub fn into_cells(self) -> Grid<T> {
+        self.cells
+    }
+
+    // How many steps have been run
+    pub fn generation(&self) -> usize {
+        self.generation
+    }
+
+    // Works out every cell's next state from its current state and its neighbors' current states, returning how many
+    // cells changed
+    pub fn step(&mut self, mut rule: impl FnMut(&T, Neighbors<'_, T>) -> T) -> usize {
+        let mut changes = 0;
+        for (p, next) in self.buffer.iter_mut() {
+            let curr = &self.cells[p];
+            let neighbors = Neighbors {
+                cells: &self.cells,
+                points: self.neighbors[p].iter(),
+            };
+            *next = rule(curr, neighbors);
+            if next != curr {
+                changes += 1;
+            }
+        }
+        std::mem::swap(&mut self.cells, &mut self.buffer);
+        self.generation += 1;
+        changes
+    }
+
+    // The total number of changes over all the steps
+    pub fn run(&mut self, steps: usize, mut rule: impl FnMut(&T, Neighbors<'_, T>) -> T) -> usize {
+        (0..steps).map(|_| self.step(&mut rule)).sum()
+    }
+
+    // Steps until one changes nothing, returning how many steps it took including that last one
+    pub fn run_until_stable(&mut self, mut rule: impl FnMut(&T, Neighbors<'_, T>) -> T) -> usize {
+        let start = self.generation;
+        while self.step(&mut rule) > 0 {}
+        self.generation - start
+    }
+}
+
+// The current states of a cell's neighbors
+pub struct Neighbors<'a, T> {
+    cells: &'a Grid<T>,
+    points: std::slice::Iter<'a, Point2>,
+}
+
+impl<'a, T> Iterator for Neighbors<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.points.next().map(|p| &self.cells[*p])
+    }
+}
+
+// Cells that are either on or off, where only the ones that are on are stored. Good for automata that spread out
+// without limit. Any cell that's off and has no neighbors on stays off.
+#[derive(Clone, Debug)]
+pub struct Sparse<P> {
+    active: HashSet<P>,
+    generation: usize,
+}
+
+impl<P: Copy + Eq + Hash> Sparse<P> {
+    pub fn new(active: impl IntoIterator<Item = P>) -> Self {
+        Self {
+            active: active.into_iter().collect(),
+            generation: 0,
+        }
+    }
+
+    pub fn active(&self) -> &HashSet<P> {
+        &self.active
+    }
+
+    pub fn is_active(&self, p: &P) -> bool {
+        self.active.contains(p)
+    }
+
+    pub fn count(&self) -> usize {
+        self.active.len()
+    }
+
+    pub fn generation(&self) -> usize {
+        self.generation
+    }
+
+    // The rule decides whether a cell is on next step from whether it's on now and how many of its neighbors are.
+    // Returns how many cells changed.
+    pub fn step<I>(
+        &mut self,
+        mut neighbors: impl FnMut(P) -> I,
+        mut rule: impl FnMut(bool, usize) -> bool,
+    ) -> usize
+    where
+        I: IntoIterator<Item = P>,
+    {
+        let mut counts: HashMap<P, usize> = self.active.iter().map(|p| (*p, 0)).collect();
+        for p in &self.active {
+            for n in neighbors(*p) {
+                *counts.entry(n).or_insert(0) += 1;
+            }
+        }
+
+        let next: HashSet<P> = counts
+            .into_iter()
+            .filter(|(p, count)| rule(self.active.contains(p), *count))
+            .map(|(p, _count)| p)
+            .collect();
+        let changes = self.active.symmetric_difference(&next).count();
+        self.active = next;
+        self.generation += 1;
+        changes
+    }
+
+    pub fn run<I>(
+        &mut self,
+        steps: usize,
+        mut neighbors: impl FnMut(P) -> I,
+        mut rule: impl FnMut(bool, usize) -> bool,
+    ) -> usize
+    where
+        I: IntoIterator<Item = P>,
+    {
+        (0..steps)
+            .map(|_| self.step(&mut neighbors, &mut rule))
+            .sum()
+    }
+
+    pub fn run_until_stable<I>(
+        &mut self,
+        mut neighbors: impl FnMut(P) -> I,
+        mut rule: impl FnMut(bool, usize) -> bool,
+    ) -> usize
+    where
+        I: IntoIterator<Item = P>,
+    {
+        let start = self.generation;
+        while self.step(&mut neighbors, &mut rule) > 0 {}
+        self.generation - start
+    }
+}
+
+// Conway's game of life, on when born with exactly three neighbors on or surviving with two or three
+pub fn life(active: bool, count: usize) -> bool {
+    count == 3 || (active && count == 2)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn glider() -> [Point2; 5] {
+        [(1, 0), (2, 1), (0, 2), (1, 2), (2, 2)].map(Point2::from)
+    }
+
+    #[test]
+    fn test_dense_life() {
+        let mut grid = Grid::new(6, 6, false);
+        for p in glider() {
+            grid[p] = true;
+        }
+        let mut automaton = Dense::with_adjacents(grid);
+        let rule = |on: &bool, n: Neighbors<'_, bool>| life(*on, n.filter(|n| **n).count());
+
+        // A glider changes 4 cells each step and moves one diagonally every four
+        assert_eq!(automaton.step(rule), 4);
+        assert_eq!(automaton.run(3, rule), 12);
+        assert_eq!(automaton.generation(), 4);
+        let moved: Vec<Point2> = glider().iter().map(|p| p + (1, 1)).collect();
+        assert!(automaton
+            .cells()
+            .iter()
+            .all(|(p, on)| *on == moved.contains(&p)));
+
+        // Then it gets stuck in the corner as a block
+        assert_eq!(automaton.run_until_stable(rule), 12);
+        assert_eq!(automaton.cells().values().filter(|on| **on).count(), 4);
+    }
+
+    #[test]
+    fn test_sparse_life() {
+        let mut automaton = Sparse::new(glider());
+        let neighbors = |p: Point2| p.adjacents();
+        assert_eq!(automaton.run(4, neighbors, life), 16);
+        let moved: HashSet<Point2> = glider().iter().map(|p| p + (1, 1)).collect();
+        assert_eq!(automaton.active(), &moved);
+
+        // A blinker never settles but a block never changes
+        let mut automaton = Sparse::new([(0, 0), (1, 0), (2, 0)].map(Point2::from));
+        assert_eq!(automaton.step(neighbors, life), 4);
+        assert_eq!(automaton.step(neighbors, life), 4);
+        let mut automaton = Sparse::new([(0, 0), (1, 0), (0, 1), (1, 1)].map(Point2::from));
+        assert_eq!(automaton.run_until_stable(neighbors, life), 1);
+        assert_eq!(automaton.count(), 4);
+    }
+}
@@ -0,0 +1,42 @@
+use num::traits::{WrappingAdd, WrappingMul, WrappingSub};
+use num::{Integer, PrimInt, Signed};
+use std::fmt::{Debug, Display};
+use std::hash::Hash;
+use std::str::FromStr;
+
+// The signed integer types that points and ranges can be made of. i32 is the default, with i64 for when that would
+// overflow.
+pub trait Coord:
+    PrimInt
+    + Signed
+    + Integer
+    + WrappingAdd
+    + WrappingSub
+    + WrappingMul
+    + Hash
+    + Debug
+    + Display
+    + Default
+    + FromStr
+{
+    // The same width but unsigned, big enough for any distance between two coordinates
+    type Unsigned: PrimInt + Hash + Debug + Display + Default + std::iter::Sum;
+
+    fn unsigned_abs(self) -> Self::Unsigned;
+}
+
+macro_rules! impl_coord {
+    ($($t:ty => $u:ty),*) => {
+        $(
+            impl Coord for $t {
+                type Unsigned = $u;
+
+                fn unsigned_abs(self) -> $u {
+                    <$t>::unsigned_abs(self)
+                }
+            }
+        )*
+    };
+}
+
+impl_coord!(i8 => u8, i16 => u16, i32 => u32, i64 => u64, i128 => u128, isize => usize);
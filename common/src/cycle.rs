@@ -0,0 +1,306 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+// Where a sequence of states starts repeating. The state after start + length steps is the same as after start steps,
+// and so on forever after.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Cycle {
+    pub start: usize,
+    pub length: usize,
+}
+
+impl Cycle {
+    // The earliest step which ends up in the same state as step n
+    pub fn reduce(&self, n: usize) -> usize {
+        if n < self.start {
+            n
+        } else {
+            self.start + (n - self.start) % self.length
+        }
+    }
+}
+
+// Every state up to and including the first repeat, so that any later one can be looked up
+#[derive(Clone, Debug)]
+pub struct History<S> {
+    pub cycle: Cycle,
+    states: Vec<S>,
+}
+
+impl<S> History<S> {
+    pub fn state_at(&self, n: usize) -> &S {
+        &self.states[self.cycle.reduce(n)]
+    }
+
+    // For when the key only captures part of the state and something else drifts by the same amount each time round
+    // the cycle, e.g. a pattern which repeats but moves along. The score is extrapolated from that drift.
+    pub fn score_at(&self, n: usize, score: impl Fn(&S) -> i64) -> i64 {
+        let reduced = self.cycle.reduce(n);
+        if n < self.cycle.start {
+            return score(&self.states[n]);
+        }
+        let start = self.cycle.start;
+        let drift = score(&self.states[start + self.cycle.length]) - score(&self.states[start]);
+        let cycles = ((n - start) / self.cycle.length) as i64;
+        score(&self.states[reduced]) + cycles * drift
+    }
+}
+
+// Steps until a state repeats, going by its key. Every state is remembered, so this is quick to find the cycle but
+// uses more memory than brent or floyd.
+pub fn find_cycle<S, K>(start: S, step: impl FnMut(&mut S), key: impl FnMut(&S) -> K) -> History<S>
+where
+    S: Clone,
+    K: Eq + Hash,
+{
+    match find_cycle_within(start, usize::MAX, step, key) {
+        Ok(history) => history,
+        Err(_) => unreachable!(),
+    }
+}
+
+// Steps n times, skipping ahead as soon as a cycle is found
+pub fn run_to<S, K>(start: S, n: usize, step: impl FnMut(&mut S), key: impl FnMut(&S) -> K) -> S
+where
+    S: Clone,
+    K: Eq + Hash,
+{
+    match find_cycle_within(start, n, step, key) {
+        Ok(history) => history.state_at(n).clone(),
+        Err(state) => state,
+    }
+}
+
+// Returns the state after the step limit if no cycle was found by then
+fn find_cycle_within<S, K>(
+    start: S,
+    limit: usize,
+    mut step: impl FnMut(&mut S),
+    mut key: impl FnMut(&S) -> K,
+) -> Result<History<S>, S>
+where
+    S: Clone,
+    K: Eq + Hash,
+{
+    let mut seen: HashMap<K, usize> = HashMap::new();
+    let mut states = Vec::new();
+    let mut state = start;
+    for i in 0..=limit {
+        if let Some(prev) = seen.insert(key(&state), i) {
+            states.push(state);
+            let cycle = Cycle {
+                start: prev,
+                length: i - prev,
+            };
+            return Ok(History { cycle, states });
+        }
+        if i == limit {
+            break;
+        }
+        states.push(state.clone());
+        step(&mut state);
+    }
+    Err(state)
+}
+
+// Brent's algorithm, which only ever keeps two states and compares keys for equality
+pub fn brent<S, K>(start: S, mut step: impl FnMut(&mut S), mut key: impl FnMut(&S) -> K) -> Cycle
+where
+    S: Clone,
+    K: Eq,
+{
+    // Find the length by moving the hare ahead in powers of two until it meets the tortoise
+    let mut power = 1;
+    let mut length = 1;
+    let mut tortoise = key(&start);
+    let mut hare = start.clone();
+    step(&mut hare);
+    loop {
+        let hare_key = key(&hare);
+        if hare_key == tortoise {
+            break;
+        }
+        if power == length {
+            tortoise = hare_key;
+            power *= 2;
+            length = 0;
+        }
+        step(&mut hare);
+        length += 1;
+    }
+
+    // Then the start by moving two states one length apart in step until they match
+    let mut tortoise = start.clone();
+    let mut hare = start;
+    for _ in 0..length {
+        step(&mut hare);
+    }
+    let mut cycle_start = 0;
+    while key(&tortoise) != key(&hare) {
+        step(&mut tortoise);
+        step(&mut hare);
+        cycle_start += 1;
+    }
+
+    Cycle {
+        start: cycle_start,
+        length,
+    }
+}
+
+// Floyd's tortoise and hare, the hare taking two steps for each of the tortoise's
+pub fn floyd<S, K>(start: S, mut step: impl FnMut(&mut S), mut key: impl FnMut(&S) -> K) -> Cycle
+where
+    S: Clone,
+    K: Eq,
+{
+    let mut tortoise = start.clone();
+    let mut hare = start.clone();
+    step(&mut tortoise);
+    step(&mut hare);
+    step(&mut hare);
+    while key(&tortoise) != key(&hare) {
+        step(&mut tortoise);
+        step(&mut hare);
+        step(&mut hare);
+    }
+
+    // The distance from the start to the cycle is the same as from the meeting point to the cycle
+    let mut cycle_start = 0;
+    let mut tortoise = start;
+    while key(&tortoise) != key(&hare) {
+        step(&mut tortoise);
+        step(&mut hare);
+        cycle_start += 1;
+    }
+
+    let mut length = 1;
+    let mut hare = tortoise.clone();
+    step(&mut hare);
+    while key(&tortoise) != key(&hare) {
+        step(&mut hare);
+        length += 1;
+    }
+
+    Cycle {
+        start: cycle_start,
+        length,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    // 0, 1, 2, 3, 4, 5, 6, 7, 3, 4, ...
+    fn step(x: &mut u32) {
+        *x = if *x == 7 { 3 } else { *x + 1 };
+    }
+
+    fn key(x: &u32) -> u32 {
+        *x
+    }
+
+    #[test]
+    fn test_find_cycle() {
+        let history = find_cycle(0, step, key);
+        assert_eq!(
+            history.cycle,
+            Cycle {
+                start: 3,
+                length: 5
+            }
+        );
+        assert_eq!(*history.state_at(2), 2);
+        assert_eq!(*history.state_at(8), 3);
+        assert_eq!(*history.state_at(1_000_000_000), 5);
+        assert_eq!(history.cycle.reduce(1_000_000_000), 5);
+    }
+
+    #[test]
+    fn test_run_to() {
+        assert_eq!(run_to(0, 0, step, key), 0);
+        assert_eq!(run_to(0, 6, step, key), 6);
+        assert_eq!(run_to(0, 12, step, key), 7);
+        assert_eq!(run_to(0, 1_000_000_000, step, key), 5);
+    }
+
+    #[test]
+    fn test_score_at() {
+        // A pattern which moves along by 3 every time round a cycle of 2
+        let history = find_cycle(
+            (0, 0),
+            |(phase, offset)| {
+                *offset += if *phase == 0 { 1 } else { 2 };
+                *phase = (*phase + 1) % 2;
+            },
+            |(phase, _)| *phase,
+        );
+        assert_eq!(
+            history.cycle,
+            Cycle {
+                start: 0,
+                length: 2
+            }
+        );
+        for n in 0..10 {
+            let expected = (n / 2) * 3 + (n % 2);
+            assert_eq!(
+                history.score_at(n as usize, |(_, offset)| *offset),
+                expected
+            );
+        }
+        assert_eq!(
+            history.score_at(50_000_000_000, |(_, offset)| *offset),
+            75_000_000_000
+        );
+    }
+
+    #[test]
+    fn test_brent_floyd() {
+        assert_eq!(
+            brent(0, step, key),
+            Cycle {
+                start: 3,
+                length: 5
+            }
+        );
+        assert_eq!(
+            floyd(0, step, key),
+            Cycle {
+                start: 3,
+                length: 5
+            }
+        );
+
+        // Straight into the cycle, and a cycle of one
+        assert_eq!(
+            brent(4, step, key),
+            Cycle {
+                start: 0,
+                length: 5
+            }
+        );
+        assert_eq!(
+            floyd(4, step, key),
+            Cycle {
+                start: 0,
+                length: 5
+            }
+        );
+        assert_eq!(
+            brent(1, |x| *x = 9, key),
+            Cycle {
+                start: 1,
+                length: 1
+            }
+        );
+        assert_eq!(
+            floyd(1, |x| *x = 9, key),
+            Cycle {
+                start: 1,
+                length: 1
+            }
+        );
+    }
+}
@@ -0,0 +1,278 @@
+use crate::{Point2, Tiles};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::hash::Hash;
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Edge<A> {
+    pub to: usize,
+    pub dist: u32,
+    pub annotation: A, // Anything of note passed along the way, e.g. doors
+}
+
+// A weighted, directed graph. Nodes are referred to by their index, which is the order they were added in.
+#[derive(Clone, Debug)]
+pub struct Graph<N, A = ()> {
+    nodes: Vec<N>,
+    index: HashMap<N, usize>,
+    edges: Vec<Vec<Edge<A>>>,
+}
+
+impl<N: Clone + Eq + Hash, A> Graph<N, A> {
+    pub fn new() -> Self {
+        Self {
+            nodes: Vec::new(),
+            index: HashMap::new(),
+            edges: Vec::new(),
+        }
+    }
+
+    // Returns the index of the node, which is the existing one if it was already added
+    pub fn add_node(&mut self, node: N) -> usize {
+        if let Some(&idx) = self.index.get(&node) {
+            return idx;
+        }
+        self.nodes.push(node.clone());
+        self.edges.push(Vec::new());
+        self.index.insert(node, self.nodes.len() - 1);
+        self.nodes.len() - 1
+    }
+
+    pub fn add_edge(&mut self, from: usize, to: usize, dist: u32, annotation: A) {
+        self.edges[from].push(Edge {
+            to,
+            dist,
+            annotation,
+        });
+    }
+
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    pub fn node(&self, idx: usize) -> &N {
+        &self.nodes[idx]
+    }
+
+    pub fn nodes(&self) -> &[N] {
+        &self.nodes
+    }
+
+    pub fn index_of(&self, node: &N) -> Option<usize> {
+        self.index.get(node).copied()
+    }
+
+    pub fn edges(&self, idx: usize) -> &[Edge<A>] {
+        &self.edges[idx]
+    }
+
+    // Shortest distance between every pair of nodes, indexed [from][to]. None where there's no path.
+    pub fn floyd_warshall(&self) -> Vec<Vec<Option<u32>>> {
+        let n = self.len();
+        let mut dist = vec![vec![None; n]; n];
+        for (i, edges) in self.edges.iter().enumerate() {
+            dist[i][i] = Some(0);
+            for edge in edges {
+                dist[i][edge.to] =
+                    Some(dist[i][edge.to].map_or(edge.dist, |d: u32| d.min(edge.dist)));
+            }
+        }
+
+        for k in 0..n {
+            let via = dist[k].clone();
+            for row in dist.iter_mut() {
+                let Some(ik) = row[k] else {
+                    continue;
+                };
+                for (ij, kj) in row.iter_mut().zip(&via) {
+                    if let Some(kj) = kj {
+                        *ij = Some(ij.map_or(ik + kj, |ij| ij.min(ik + kj)));
+                    }
+                }
+            }
+        }
+        dist
+    }
+}
+
+impl Graph<Point2> {
+    // Compresses a grid down to just its interesting points, joined by the number of steps between them. Paths go
+    // through passable tiles but stop at the first interesting point reached, so an edge never passes through
+    // another node.
+    pub fn from_tiles<S: Tiles>(
+        tiles: &S,
+        passable: impl FnMut(Point2, &S::Tile) -> bool,
+        interesting: impl FnMut(Point2, &S::Tile) -> bool,
+    ) -> Self {
+        Self::from_tiles_annotated(tiles, passable, interesting, |_, _, _| ())
+    }
+}
+
+impl<A: Clone + Default> Graph<Point2, A> {
+    // As from_tiles, with each edge also carrying what annotate makes of every tile stepped onto along the way,
+    // including the node at the end. Only one shortest path between each pair of nodes is followed, so this is best
+    // suited to mazes where that path is unique.
+    pub fn from_tiles_annotated<S: Tiles>(
+        tiles: &S,
+        mut passable: impl FnMut(Point2, &S::Tile) -> bool,
+        mut interesting: impl FnMut(Point2, &S::Tile) -> bool,
+        mut annotate: impl FnMut(&mut A, Point2, &S::Tile),
+    ) -> Self {
+        // Reading order so that node indices don't depend on hashing
+        let mut points = Vec::new();
+        if let Some(range) = tiles.bounds() {
+            for y in range.y.0..=range.y.1 {
+                for x in range.x.0..=range.x.1 {
+                    let p = Point2 { x, y };
+                    if let Some(t) = tiles.tile(p) {
+                        if passable(p, t) && interesting(p, t) {
+                            points.push(p);
+                        }
+                    }
+                }
+            }
+        }
+
+        let mut graph = Self::new();
+        for p in &points {
+            graph.add_node(*p);
+        }
+
+        for (from, start) in points.into_iter().enumerate() {
+            let mut visited = HashSet::from([start]);
+            let mut frontier = VecDeque::from([(start, 0, A::default())]);
+            while let Some((curr, dist, annotation)) = frontier.pop_front() {
+                for next in curr.orthogonals() {
+                    let Some(tile) = tiles.tile(next) else {
+                        continue;
+                    };
+                    if !passable(next, tile) || !visited.insert(next) {
+                        continue;
+                    }
+
+                    let mut next_annotation = annotation.clone();
+                    annotate(&mut next_annotation, next, tile);
+                    if interesting(next, tile) {
+                        let to = graph.index_of(&next).unwrap();
+                        graph.add_edge(from, to, dist + 1, next_annotation);
+                    } else {
+                        frontier.push_back((next, dist + 1, next_annotation));
+                    }
+                }
+            }
+        }
+        graph
+    }
+}
+
+impl<N: Clone + Eq + Hash, A> Default for Graph<N, A> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Grid;
+
+    fn tiles(input: &str) -> HashMap<Point2, char> {
+        let mut tiles = HashMap::new();
+        for (y, line) in input.lines().enumerate() {
+            for (x, c) in line.chars().enumerate() {
+                tiles.insert((x as i32, y as i32).into(), c);
+            }
+        }
+        tiles
+    }
+
+    #[test]
+    fn test_from_tiles() {
+        let tiles = Grid::from_string_with(
+            "\
+#######
+#a.A.c#
+#####b#
+#######",
+            Some,
+        );
+        let graph = Graph::from_tiles(&tiles, |_, c| *c != '#', |_, c| c.is_ascii_lowercase());
+        assert_eq!(graph.len(), 3);
+        assert_eq!(graph.nodes(), [(1, 1), (5, 1), (5, 2)].map(Point2::from));
+
+        // 'b' is only reachable through 'c' so it has no edge to 'a'
+        let [a, c, b] = [(1, 1), (5, 1), (5, 2)].map(|p| graph.index_of(&p.into()).unwrap());
+        assert_eq!(
+            graph.edges(a),
+            [Edge {
+                to: c,
+                dist: 4,
+                annotation: ()
+            }]
+        );
+        assert_eq!(
+            graph.edges(b),
+            [Edge {
+                to: c,
+                dist: 1,
+                annotation: ()
+            }]
+        );
+        let mut from_c: Vec<(usize, u32)> = graph.edges(c).iter().map(|e| (e.to, e.dist)).collect();
+        from_c.sort();
+        assert_eq!(from_c, [(a, 4), (b, 1)]);
+    }
+
+    #[test]
+    fn test_from_tiles_annotated() {
+        let tiles = tiles(
+            "\
+#######
+#a.A.c#
+#####b#
+#######",
+        );
+        let graph = Graph::from_tiles_annotated(
+            &tiles,
+            |_, c| *c != '#',
+            |_, c| c.is_ascii_lowercase(),
+            |doors: &mut String, _, c| {
+                if c.is_ascii_uppercase() {
+                    doors.push(*c);
+                }
+            },
+        );
+        let [a, c, b] = [(1, 1), (5, 1), (5, 2)].map(|p| graph.index_of(&p.into()).unwrap());
+        assert_eq!(graph.edges(a)[0].annotation, "A");
+        assert_eq!(graph.edges(b)[0].annotation, "");
+        let mut from_c: Vec<(usize, String)> = graph
+            .edges(c)
+            .iter()
+            .map(|e| (e.to, e.annotation.clone()))
+            .collect();
+        from_c.sort();
+        assert_eq!(from_c, [(a, "A".to_owned()), (b, "".to_owned())]);
+    }
+
+    #[test]
+    fn test_floyd_warshall() {
+        let mut graph: Graph<char> = Graph::new();
+        let [a, b, c, d] = ['a', 'b', 'c', 'd'].map(|n| graph.add_node(n));
+        assert_eq!(graph.add_node('b'), b);
+        graph.add_edge(a, b, 1, ());
+        graph.add_edge(b, c, 2, ());
+        graph.add_edge(a, c, 5, ());
+        graph.add_edge(c, a, 1, ());
+
+        let dist = graph.floyd_warshall();
+        assert_eq!(dist[a][c], Some(3));
+        assert_eq!(dist[c][b], Some(2));
+        assert_eq!(dist[b][a], Some(3));
+        assert_eq!(dist[d][d], Some(0));
+        assert_eq!(dist[a][d], None);
+        assert_eq!(dist[d][a], None);
+    }
+}
@@ -0,0 +1,421 @@
+use nom::{
+    branch::alt,
+    bytes::complete::tag,
+    character::complete::{char, space0},
+    combinator::{opt, value},
+    multi::many1,
+    sequence::{preceded, terminated},
+    IResult,
+};
+use std::collections::HashMap;
+use std::ops::{Add, AddAssign, Sub};
+
+// A hex tile in axial coordinates. q runs along a row, or column for flat top hexes, and r runs diagonally across
+// them. The third cube coordinate s is left out since q + r + s is always 0, see Cube.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Hex {
+    pub q: i32,
+    pub r: i32,
+}
+
+// The same tile in cube coordinates, where each of the three axes is one of the three lines through the hex's sides
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct Cube {
+    pub q: i32,
+    pub r: i32,
+    pub s: i32,
+}
+
+impl Hex {
+    pub const fn origin() -> Self {
+        Self { q: 0, r: 0 }
+    }
+
+    pub fn to_cube(self) -> Cube {
+        Cube {
+            q: self.q,
+            r: self.r,
+            s: -self.q - self.r,
+        }
+    }
+
+    pub fn step(&self, dir: impl HexDir) -> Self {
+        *self + dir.offset()
+    }
+
+    // The six touching tiles, the same for flat and pointy top
+    pub fn neighbors(&self) -> impl Iterator<Item = Self> {
+        let origin = *self;
+        PointyDir::ALL.into_iter().map(move |dir| origin.step(dir))
+    }
+
+    // The fewest steps between two tiles
+    pub fn distance(a: Self, b: Self) -> u32 {
+        let delta = (a - b).to_cube();
+        (delta.q.unsigned_abs() + delta.r.unsigned_abs() + delta.s.unsigned_abs()) / 2
+    }
+
+    // A sixth of a turn clockwise around the center
+    pub fn rotate_cw(&self, center: Self) -> Self {
+        let c = (*self - center).to_cube();
+        center + Cube::new(-c.r, -c.s).to_hex()
+    }
+
+    pub fn rotate_ccw(&self, center: Self) -> Self {
+        let c = (*self - center).to_cube();
+        center + Cube::new(-c.s, -c.q).to_hex()
+    }
+
+    // The tiles on a straight line between the two, both ends included
+    pub fn line(a: Self, b: Self) -> Vec<Self> {
+        let n = Self::distance(a, b);
+        if n == 0 {
+            return vec![a];
+        }
+
+        // Nudged a little so that points exactly between two tiles always go the same way
+        let (a, b) = (a.to_cube(), b.to_cube());
+        let lerp = |from: i32, to: i32, t: f64| from as f64 + (to - from) as f64 * t;
+        (0..=n)
+            .map(|i| {
+                let t = i as f64 / n as f64;
+                Cube::round(
+                    lerp(a.q, b.q, t) + 1e-6,
+                    lerp(a.r, b.r, t) + 2e-6,
+                    lerp(a.s, b.s, t) - 3e-6,
+                )
+                .to_hex()
+            })
+            .collect()
+    }
+}
+
+impl Cube {
+    pub fn new(q: i32, r: i32) -> Self {
+        Self { q, r, s: -q - r }
+    }
+
+    pub fn to_hex(self) -> Hex {
+        Hex {
+            q: self.q,
+            r: self.r,
+        }
+    }
+
+    // The tile a point with fractional coordinates falls in
+    pub fn round(q: f64, r: f64, s: f64) -> Self {
+        let (mut rq, mut rr, mut rs) = (q.round(), r.round(), s.round());
+        let (dq, dr, ds) = ((rq - q).abs(), (rr - r).abs(), (rs - s).abs());
+
+        // Fix up whichever was rounded the most so they still add up to 0
+        if dq > dr && dq > ds {
+            rq = -rr - rs;
+        } else if dr > ds {
+            rr = -rq - rs;
+        } else {
+            rs = -rq - rr;
+        }
+        Self {
+            q: rq as i32,
+            r: rr as i32,
+            s: rs as i32,
+        }
+    }
+}
+
+impl From<Cube> for Hex {
+    fn from(cube: Cube) -> Self {
+        cube.to_hex()
+    }
+}
+
+impl From<Hex> for Cube {
+    fn from(hex: Hex) -> Self {
+        hex.to_cube()
+    }
+}
+
+impl Add for Hex {
+    type Output = Self;
+    fn add(self, other: Self) -> Self {
+        Self {
+            q: self.q + other.q,
+            r: self.r + other.r,
+        }
+    }
+}
+
+impl AddAssign for Hex {
+    fn add_assign(&mut self, other: Self) {
+        *self = *self + other;
+    }
+}
+
+impl Sub for Hex {
+    type Output = Self;
+    fn sub(self, other: Self) -> Self {
+        Self {
+            q: self.q - other.q,
+            r: self.r - other.r,
+        }
+    }
+}
+
+impl std::fmt::Display for Hex {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "(q={}, r={})", self.q, self.r)
+    }
+}
+
+// A set of six directions, named for whichever way the hexes are laid out
+pub trait HexDir: Copy + Sized {
+    const ALL: [Self; 6];
+
+    fn offset(self) -> Hex;
+
+    // The short name, e.g. "ne"
+    fn parser(input: &str) -> IResult<&str, Self>;
+
+    fn opposite(self) -> Self {
+        let i = Self::ALL
+            .iter()
+            .position(|d| d.offset() == self.offset())
+            .unwrap();
+        Self::ALL[(i + 3) % 6]
+    }
+}
+
+// Hexes with a point at the top and bottom, so that they sit side by side in rows
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum PointyDir {
+    East,
+    SouthEast,
+    SouthWest,
+    West,
+    NorthWest,
+    NorthEast,
+}
+
+impl HexDir for PointyDir {
+    const ALL: [Self; 6] = [
+        Self::East,
+        Self::SouthEast,
+        Self::SouthWest,
+        Self::West,
+        Self::NorthWest,
+        Self::NorthEast,
+    ];
+
+    fn offset(self) -> Hex {
+        let (q, r) = match self {
+            Self::East => (1, 0),
+            Self::SouthEast => (0, 1),
+            Self::SouthWest => (-1, 1),
+            Self::West => (-1, 0),
+            Self::NorthWest => (0, -1),
+            Self::NorthEast => (1, -1),
+        };
+        Hex { q, r }
+    }
+
+    fn parser(input: &str) -> IResult<&str, Self> {
+        alt((
+            value(Self::SouthEast, tag("se")),
+            value(Self::SouthWest, tag("sw")),
+            value(Self::NorthWest, tag("nw")),
+            value(Self::NorthEast, tag("ne")),
+            value(Self::East, tag("e")),
+            value(Self::West, tag("w")),
+        ))(input)
+    }
+}
+
+// Hexes with a flat side at the top and bottom, so that they stack in columns
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum FlatDir {
+    North,
+    NorthEast,
+    SouthEast,
+    South,
+    SouthWest,
+    NorthWest,
+}
+
+impl HexDir for FlatDir {
+    const ALL: [Self; 6] = [
+        Self::North,
+        Self::NorthEast,
+        Self::SouthEast,
+        Self::South,
+        Self::SouthWest,
+        Self::NorthWest,
+    ];
+
+    fn offset(self) -> Hex {
+        let (q, r) = match self {
+            Self::North => (0, -1),
+            Self::NorthEast => (1, -1),
+            Self::SouthEast => (1, 0),
+            Self::South => (0, 1),
+            Self::SouthWest => (-1, 1),
+            Self::NorthWest => (-1, 0),
+        };
+        Hex { q, r }
+    }
+
+    fn parser(input: &str) -> IResult<&str, Self> {
+        alt((
+            value(Self::NorthEast, tag("ne")),
+            value(Self::NorthWest, tag("nw")),
+            value(Self::SouthEast, tag("se")),
+            value(Self::SouthWest, tag("sw")),
+            value(Self::North, tag("n")),
+            value(Self::South, tag("s")),
+        ))(input)
+    }
+}
+
+// Directions either run together like "nwwswee" or are separated by commas like "ne,ne,s"
+pub fn path_parser<D: HexDir>(input: &str) -> IResult<&str, Vec<D>> {
+    preceded(
+        space0,
+        many1(terminated(D::parser, opt(preceded(space0, char(','))))),
+    )(input)
+}
+
+// Where following the directions from the origin ends up
+pub fn walk<D: HexDir>(path: &[D]) -> Hex {
+    path.iter().fold(Hex::origin(), |hex, dir| hex.step(*dir))
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Layout {
+    PointyTop,
+    FlatTop,
+}
+
+impl Layout {
+    // Draws the tiles as text for debugging, with each tile's neighbors around it the way they'd look on paper. Tiles
+    // that aren't given are drawn as background.
+    pub fn render(&self, tiles: impl IntoIterator<Item = (Hex, char)>, background: char) -> String {
+        // Pointy top rows are shifted half a tile along from the one above, and flat top columns half a tile down
+        let position = |hex: Hex| match self {
+            Self::PointyTop => (2 * hex.q + hex.r, hex.r),
+            Self::FlatTop => (hex.q, 2 * hex.r + hex.q),
+        };
+        let placed: HashMap<(i32, i32), char> = tiles
+            .into_iter()
+            .map(|(hex, c)| (position(hex), c))
+            .collect();
+        let Some(min_x) = placed.keys().map(|p| p.0).min() else {
+            return String::new();
+        };
+        let max_x = placed.keys().map(|p| p.0).max().unwrap();
+        let min_y = placed.keys().map(|p| p.1).min().unwrap();
+        let max_y = placed.keys().map(|p| p.1).max().unwrap();
+
+        let mut output = String::new();
+        for y in min_y..=max_y {
+            let mut line = String::new();
+            for x in min_x..=max_x {
+                let on_grid = match self {
+                    Self::PointyTop => (x - y).rem_euclid(2) == 0,
+                    Self::FlatTop => (y - x).rem_euclid(2) == 0,
+                };
+                line.push(match placed.get(&(x, y)) {
+                    Some(c) => *c,
+                    None if on_grid => background,
+                    None => ' ',
+                });
+            }
+            output.push_str(line.trim_end());
+            output.push('\n');
+        }
+        output
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_path() {
+        let (rest, path) = path_parser::<PointyDir>("nwwswee").unwrap();
+        assert_eq!(rest, "");
+        assert_eq!(
+            path,
+            [
+                PointyDir::NorthWest,
+                PointyDir::West,
+                PointyDir::SouthWest,
+                PointyDir::East,
+                PointyDir::East
+            ]
+        );
+        assert_eq!(walk(&path), Hex::origin());
+
+        let (_, path) = path_parser::<FlatDir>("ne,ne,s,s").unwrap();
+        assert_eq!(Hex::distance(walk(&path), Hex::origin()), 2);
+        let (_, path) = path_parser::<FlatDir>("se,sw,se,sw,sw").unwrap();
+        assert_eq!(Hex::distance(walk(&path), Hex::origin()), 3);
+    }
+
+    #[test]
+    fn test_neighbors() {
+        let hex = Hex { q: 2, r: -1 };
+        assert_eq!(hex.neighbors().count(), 6);
+        assert!(hex.neighbors().all(|n| Hex::distance(hex, n) == 1));
+        for dir in PointyDir::ALL {
+            assert_eq!(hex.step(dir).step(dir.opposite()), hex);
+        }
+        for dir in FlatDir::ALL {
+            assert_eq!(hex.step(dir).step(dir.opposite()), hex);
+        }
+    }
+
+    #[test]
+    fn test_rotate() {
+        let center = Hex { q: 1, r: 1 };
+        let hex = center.step(PointyDir::East).step(PointyDir::East);
+        assert_eq!(
+            hex.rotate_cw(center),
+            center.step(PointyDir::SouthEast).step(PointyDir::SouthEast)
+        );
+        let mut turned = hex;
+        for _ in 0..6 {
+            turned = turned.rotate_ccw(center);
+            assert_eq!(Hex::distance(turned, center), 2);
+        }
+        assert_eq!(turned, hex);
+        assert_eq!(hex.rotate_cw(center).rotate_ccw(center), hex);
+    }
+
+    #[test]
+    fn test_line() {
+        let a = Hex::origin();
+        let b = Hex { q: 3, r: -1 };
+        let line = Hex::line(a, b);
+        assert_eq!(line.len(), 4);
+        assert_eq!(line[0], a);
+        assert_eq!(line[3], b);
+        for pair in line.windows(2) {
+            assert_eq!(Hex::distance(pair[0], pair[1]), 1);
+        }
+        assert_eq!(Hex::line(b, b), [b]);
+    }
+
+    #[test]
+    fn test_render() {
+        let tiles: Vec<(Hex, char)> = Hex::origin()
+            .neighbors()
+            .map(|n| (n, '#'))
+            .chain([(Hex::origin(), 'O')])
+            .collect();
+        assert_eq!(
+            Layout::PointyTop.render(tiles.clone(), '.'),
+            " # #\n# O #\n # #\n"
+        );
+        assert_eq!(Layout::FlatTop.render(tiles, '.'), " #\n# #\n O\n# #\n #\n");
+    }
+}
@@ -0,0 +1,527 @@
+mod asm;
+mod debug;
+mod disasm;
+mod io;
+
+pub use asm::*;
+pub use debug::*;
+pub use disasm::*;
+pub use io::*;
+
+use crate::signed;
+use nom::{
+    character::complete::{char, multispace0},
+    multi::separated_list1,
+    sequence::preceded,
+    IResult,
+};
+use std::collections::HashMap;
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Opcode {
+    Add,
+    Mul,
+    In,
+    Out,
+    Jnz,
+    Jz,
+    Lt,
+    Eq,
+    Rel,
+    Halt,
+}
+
+impl Opcode {
+    pub fn from_value(value: i64) -> Option<Self> {
+        Some(match value {
+            1 => Self::Add,
+            2 => Self::Mul,
+            3 => Self::In,
+            4 => Self::Out,
+            5 => Self::Jnz,
+            6 => Self::Jz,
+            7 => Self::Lt,
+            8 => Self::Eq,
+            9 => Self::Rel,
+            99 => Self::Halt,
+            _ => return None,
+        })
+    }
+
+    pub fn value(self) -> i64 {
+        match self {
+            Self::Add => 1,
+            Self::Mul => 2,
+            Self::In => 3,
+            Self::Out => 4,
+            Self::Jnz => 5,
+            Self::Jz => 6,
+            Self::Lt => 7,
+            Self::Eq => 8,
+            Self::Rel => 9,
+            Self::Halt => 99,
+        }
+    }
+
+    pub fn param_count(self) -> usize {
+        match self {
+            Self::Add | Self::Mul | Self::Lt | Self::Eq => 3,
+            Self::Jnz | Self::Jz => 2,
+            Self::In | Self::Out | Self::Rel => 1,
+            Self::Halt => 0,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum ParamMode {
+    Position,
+    Immediate,
+    Relative,
+}
+
+impl ParamMode {
+    pub fn from_value(value: i64) -> Option<Self> {
+        Some(match value {
+            0 => Self::Position,
+            1 => Self::Immediate,
+            2 => Self::Relative,
+            _ => return None,
+        })
+    }
+
+    pub fn value(self) -> i64 {
+        match self {
+            Self::Position => 0,
+            Self::Immediate => 1,
+            Self::Relative => 2,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Instruction {
+    pub opcode: Opcode,
+    pub modes: [ParamMode; 3],
+}
+
+impl Instruction {
+    pub fn decode(word: i64) -> Option<Self> {
+        if word < 0 {
+            return None;
+        }
+
+        let opcode = Opcode::from_value(word % 100)?;
+        let mut modes = [ParamMode::Position; 3];
+        let mut mode_digits = word / 100;
+        for mode in &mut modes {
+            *mode = ParamMode::from_value(mode_digits % 10)?;
+            mode_digits /= 10;
+        }
+        if mode_digits != 0 {
+            return None; // Leftover digits don't correspond to any parameter
+        }
+
+        Some(Self { opcode, modes })
+    }
+
+    pub fn encode(&self) -> i64 {
+        self.modes
+            .iter()
+            .rev()
+            .fold(0, |acc, mode| acc * 10 + mode.value())
+            * 100
+            + self.opcode.value()
+    }
+
+    pub fn size(&self) -> usize {
+        1 + self.opcode.param_count()
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum State {
+    Running,
+    InputNeeded, // Paused on an input instruction; running again without new input pauses again
+    Halted,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Program {
+    code: Vec<i64>,
+    mem: HashMap<usize, i64>, // Anything beyond the initial code lives here and defaults to zero
+    pc: usize,
+    relative_base: i64,
+    state: State,
+    queue: Queue, // Used when the program is run without any other I/O attached
+}
+
+impl Program {
+    pub fn new(code: &[i64]) -> Self {
+        Self {
+            code: code.to_vec(),
+            mem: HashMap::new(),
+            pc: 0,
+            relative_base: 0,
+            state: State::Running,
+            queue: Queue::new(),
+        }
+    }
+
+    pub fn with_input<I>(self, input: I) -> Self
+    where
+        I: IntoIterator<Item = i64>,
+    {
+        let mut queue = self.queue;
+        queue.input.extend(input);
+        Self { queue, ..self }
+    }
+
+    pub fn from_string(input: &str) -> Self {
+        Self::parser(input).unwrap().1
+    }
+
+    pub fn parser(input: &str) -> IResult<&str, Self> {
+        let (input, code) = separated_list1(
+            preceded(multispace0, char(',')),
+            preceded(multispace0, signed),
+        )(input)?;
+        let (input, _) = multispace0(input)?;
+        Ok((input, Self::new(&code)))
+    }
+
+    pub fn code(&self) -> &[i64] {
+        &self.code
+    }
+
+    pub fn pc(&self) -> usize {
+        self.pc
+    }
+
+    pub fn relative_base(&self) -> i64 {
+        self.relative_base
+    }
+
+    pub fn state(&self) -> State {
+        self.state
+    }
+
+    pub fn is_halted(&self) -> bool {
+        self.state == State::Halted
+    }
+
+    pub fn get(&self, addr: usize) -> i64 {
+        if addr < self.code.len() {
+            self.code[addr]
+        } else {
+            self.mem.get(&addr).copied().unwrap_or(0)
+        }
+    }
+
+    pub fn set(&mut self, addr: usize, value: i64) {
+        if addr < self.code.len() {
+            self.code[addr] = value;
+        } else {
+            self.mem.insert(addr, value);
+        }
+    }
+
+    pub fn push_input(&mut self, value: i64) {
+        self.queue.input.push_back(value);
+    }
+
+    pub fn output(&self) -> &[i64] {
+        &self.queue.output
+    }
+
+    pub fn take_output(&mut self) -> Vec<i64> {
+        std::mem::take(&mut self.queue.output)
+    }
+
+    pub fn current_instruction(&self) -> Instruction {
+        let word = self.get(self.pc);
+        Instruction::decode(word)
+            .unwrap_or_else(|| panic!("Invalid instruction {} at address {}", word, self.pc))
+    }
+
+    fn to_addr(value: i64) -> usize {
+        usize::try_from(value).unwrap_or_else(|_| panic!("Invalid address: {}", value))
+    }
+
+    fn param_addr(&self, inst: &Instruction, param_idx: usize) -> usize {
+        let addr = self.pc + 1 + param_idx;
+        match inst.modes[param_idx] {
+            ParamMode::Position => Self::to_addr(self.get(addr)),
+            ParamMode::Immediate => addr,
+            ParamMode::Relative => Self::to_addr(self.relative_base + self.get(addr)),
+        }
+    }
+
+    fn param(&self, inst: &Instruction, param_idx: usize) -> i64 {
+        self.get(self.param_addr(inst, param_idx))
+    }
+
+    pub fn step(&mut self) -> State {
+        let mut queue = std::mem::take(&mut self.queue);
+        let state = self.step_with(&mut queue);
+        self.queue = queue;
+        state
+    }
+
+    // Runs until the program halts or needs more input than it has been given
+    pub fn run(&mut self) -> State {
+        let mut queue = std::mem::take(&mut self.queue);
+        let state = self.run_with(&mut queue);
+        self.queue = queue;
+        state
+    }
+
+    pub fn step_with<T: Io + ?Sized>(&mut self, io: &mut T) -> State {
+        if self.state == State::Halted {
+            return self.state;
+        }
+
+        let inst = self.current_instruction();
+        self.state = State::Running;
+        let mut next_pc = self.pc + inst.size();
+        match inst.opcode {
+            Opcode::Add => {
+                let value = self.param(&inst, 0) + self.param(&inst, 1);
+                self.set(self.param_addr(&inst, 2), value);
+            }
+            Opcode::Mul => {
+                let value = self.param(&inst, 0) * self.param(&inst, 1);
+                self.set(self.param_addr(&inst, 2), value);
+            }
+            Opcode::In => {
+                if let Some(value) = io.input() {
+                    self.set(self.param_addr(&inst, 0), value);
+                } else {
+                    // Don't advance the PC so that running again retries this instruction
+                    self.state = State::InputNeeded;
+                    next_pc = self.pc;
+                }
+            }
+            Opcode::Out => {
+                let value = self.param(&inst, 0);
+                io.output(value);
+            }
+            Opcode::Jnz => {
+                if self.param(&inst, 0) != 0 {
+                    next_pc = Self::to_addr(self.param(&inst, 1));
+                }
+            }
+            Opcode::Jz => {
+                if self.param(&inst, 0) == 0 {
+                    next_pc = Self::to_addr(self.param(&inst, 1));
+                }
+            }
+            Opcode::Lt => {
+                let value = (self.param(&inst, 0) < self.param(&inst, 1)) as i64;
+                self.set(self.param_addr(&inst, 2), value);
+            }
+            Opcode::Eq => {
+                let value = (self.param(&inst, 0) == self.param(&inst, 1)) as i64;
+                self.set(self.param_addr(&inst, 2), value);
+            }
+            Opcode::Rel => {
+                self.relative_base += self.param(&inst, 0);
+            }
+            Opcode::Halt => {
+                // Don't advance the PC so that running again just halts immediately
+                self.state = State::Halted;
+                next_pc = self.pc;
+            }
+        }
+        self.pc = next_pc;
+        self.state
+    }
+
+    // Runs until the program halts or the I/O has no input available
+    pub fn run_with<T: Io + ?Sized>(&mut self, io: &mut T) -> State {
+        loop {
+            let state = self.step_with(io);
+            if state != State::Running {
+                return state;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn run(code: &[i64], input: &[i64]) -> Program {
+        let mut program = Program::new(code).with_input(input.iter().copied());
+        program.run();
+        program
+    }
+
+    #[test]
+    fn test_decode() {
+        assert_eq!(
+            Instruction::decode(1002),
+            Some(Instruction {
+                opcode: Opcode::Mul,
+                modes: [
+                    ParamMode::Position,
+                    ParamMode::Immediate,
+                    ParamMode::Position
+                ],
+            })
+        );
+        assert_eq!(
+            Instruction::decode(21101),
+            Some(Instruction {
+                opcode: Opcode::Add,
+                modes: [
+                    ParamMode::Immediate,
+                    ParamMode::Immediate,
+                    ParamMode::Relative
+                ],
+            })
+        );
+        assert_eq!(Instruction::decode(99).unwrap().opcode, Opcode::Halt);
+        assert_eq!(Instruction::decode(0), None);
+        assert_eq!(Instruction::decode(98), None);
+        assert_eq!(Instruction::decode(301), None);
+        assert_eq!(Instruction::decode(1001101), None);
+        assert_eq!(Instruction::decode(-1), None);
+
+        for word in [1, 2, 1002, 21101, 203, 1105, 22207, 109, 99] {
+            assert_eq!(Instruction::decode(word).unwrap().encode(), word);
+        }
+    }
+
+    #[test]
+    fn test_from_string() {
+        let program = Program::from_string("1,9,10,3,2,3,11,0,99,30,40,50\n");
+        assert_eq!(program.code(), [1, 9, 10, 3, 2, 3, 11, 0, 99, 30, 40, 50]);
+
+        let program = Program::from_string("109, -1,\n204,1");
+        assert_eq!(program.code(), [109, -1, 204, 1]);
+    }
+
+    #[test]
+    fn test_add_mul() {
+        assert_eq!(run(&[1, 0, 0, 0, 99], &[]).code(), [2, 0, 0, 0, 99]);
+        assert_eq!(run(&[2, 3, 0, 3, 99], &[]).code(), [2, 3, 0, 6, 99]);
+        assert_eq!(
+            run(&[2, 4, 4, 5, 99, 0], &[]).code(),
+            [2, 4, 4, 5, 99, 9801]
+        );
+        assert_eq!(
+            run(&[1, 1, 1, 4, 99, 5, 6, 0, 99], &[]).code(),
+            [30, 1, 1, 4, 2, 5, 6, 0, 99]
+        );
+        assert_eq!(
+            run(&[1, 9, 10, 3, 2, 3, 11, 0, 99, 30, 40, 50], &[]).code(),
+            [3500, 9, 10, 70, 2, 3, 11, 0, 99, 30, 40, 50]
+        );
+    }
+
+    #[test]
+    fn test_modes() {
+        assert_eq!(run(&[1002, 4, 3, 4, 33], &[]).code(), [1002, 4, 3, 4, 99]);
+        assert_eq!(
+            run(&[1101, 100, -1, 4, 0], &[]).code(),
+            [1101, 100, -1, 4, 99]
+        );
+    }
+
+    #[test]
+    fn test_input_output() {
+        let program = run(&[3, 0, 4, 0, 99], &[1]);
+        assert_eq!(program.code(), [1, 0, 4, 0, 99]);
+        assert_eq!(program.output(), [1]);
+    }
+
+    #[test]
+    fn test_compare_jump() {
+        let code = [3, 9, 8, 9, 10, 9, 4, 9, 99, -1, 8];
+        assert_eq!(run(&code, &[5]).output(), [0]);
+        assert_eq!(run(&code, &[8]).output(), [1]);
+
+        let code = [3, 9, 7, 9, 10, 9, 4, 9, 99, -1, 8];
+        assert_eq!(run(&code, &[5]).output(), [1]);
+        assert_eq!(run(&code, &[8]).output(), [0]);
+
+        let code = [3, 3, 1108, -1, 8, 3, 4, 3, 99];
+        assert_eq!(run(&code, &[5]).output(), [0]);
+        assert_eq!(run(&code, &[8]).output(), [1]);
+
+        let code = [3, 3, 1107, -1, 8, 3, 4, 3, 99];
+        assert_eq!(run(&code, &[5]).output(), [1]);
+        assert_eq!(run(&code, &[8]).output(), [0]);
+
+        let code = [3, 12, 6, 12, 15, 1, 13, 14, 13, 4, 13, 99, -1, 0, 1, 9];
+        assert_eq!(run(&code, &[0]).output(), [0]);
+        assert_eq!(run(&code, &[1]).output(), [1]);
+
+        let code = [3, 3, 1105, -1, 9, 1101, 0, 0, 12, 4, 12, 99, 1];
+        assert_eq!(run(&code, &[0]).output(), [0]);
+        assert_eq!(run(&code, &[1]).output(), [1]);
+
+        let code = [
+            3, 21, 1008, 21, 8, 20, 1005, 20, 22, 107, 8, 21, 20, 1006, 20, 31, 1106, 0, 36, 98, 0,
+            0, 1002, 21, 125, 20, 4, 20, 1105, 1, 46, 104, 999, 1105, 1, 46, 1101, 1000, 1, 20, 4,
+            20, 1105, 1, 46, 98, 99,
+        ];
+        assert_eq!(run(&code, &[2]).output(), [999]);
+        assert_eq!(run(&code, &[8]).output(), [1000]);
+        assert_eq!(run(&code, &[10]).output(), [1001]);
+    }
+
+    #[test]
+    fn test_relative_and_large_memory() {
+        let code = [
+            109, 1, 204, -1, 1001, 100, 1, 100, 1008, 100, 16, 101, 1006, 101, 0, 99,
+        ];
+        assert_eq!(run(&code, &[]).output(), code);
+
+        let code = [1102, 34915192, 34915192, 7, 4, 7, 99, 0];
+        assert_eq!(run(&code, &[]).output(), [1219070632396864]);
+
+        let code = [104, 1125899906842624, 99];
+        assert_eq!(run(&code, &[]).output(), [1125899906842624]);
+
+        let code = [109, 2000, 21101, 5, 6, 500, 204, 500, 99];
+        let program = run(&code, &[]);
+        assert_eq!(program.output(), [11]);
+        assert_eq!(program.get(2500), 11);
+        assert_eq!(program.get(1_000_000), 0);
+    }
+
+    #[test]
+    fn test_pause_on_input() {
+        let mut program = Program::new(&[3, 13, 3, 14, 1, 13, 14, 15, 4, 15, 99, 0, 0, 0, 0, 0]);
+        assert_eq!(program.run(), State::InputNeeded);
+        assert_eq!(program.pc(), 0);
+        assert_eq!(program.run(), State::InputNeeded);
+
+        program.push_input(3);
+        assert_eq!(program.run(), State::InputNeeded);
+        assert_eq!(program.pc(), 2);
+
+        program.push_input(4);
+        assert_eq!(program.run(), State::Halted);
+        assert_eq!(program.take_output(), [7]);
+        assert_eq!(program.output(), []);
+
+        assert_eq!(program.run(), State::Halted);
+        assert_eq!(program.pc(), 10);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_invalid_opcode() {
+        run(&[1, 0, 0, 0, 42], &[]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_negative_address() {
+        run(&[4, -1, 99], &[]);
+    }
+}
@@ -0,0 +1,314 @@
+use super::{Instruction, Opcode, ParamMode};
+use crate::signed;
+use nom::{
+    branch::alt,
+    bytes::complete::tag,
+    character::complete::{alpha1, alphanumeric1, char, space0, space1},
+    combinator::{all_consuming, map, opt, recognize},
+    multi::{many0, separated_list0, separated_list1},
+    sequence::{delimited, pair, preceded, terminated, tuple},
+    IResult,
+};
+use std::collections::HashMap;
+
+// Assembles the same syntax as the disassembler prints, plus a few directives:
+//
+//   start:                     Label for the following address
+//   .var count 1               Names a relative base offset, used as [r+count]
+//   .data 1, -2, start         Literal values, labels are replaced by their address
+//   ADD [r+count], #5 -> [100] Instructions, with #immediate, [position] and [r+relative] operands
+//   JNZ [flag], start          A bare label is its immediate address
+//
+// Anything after a ';' is a comment. Invalid source panics with the offending line.
+pub fn assemble(source: &str) -> Vec<i64> {
+    let mut labels = HashMap::new();
+    let mut vars = HashMap::new();
+    let mut statements = Vec::new();
+    let mut addr = 0;
+
+    for (line_num, line) in source.lines().enumerate() {
+        let line = line.split(';').next().unwrap().trim();
+        let (label, statement) = match all_consuming(Statement::line)(line) {
+            Ok((_, parsed)) => parsed,
+            Err(_) => panic!("Invalid assembly on line {}: {}", line_num + 1, line),
+        };
+
+        if let Some(label) = label {
+            if labels.insert(label, addr).is_some() {
+                panic!("Duplicate label on line {}: {}", line_num + 1, label);
+            }
+        }
+        match statement {
+            Some(Statement::Var(name, offset)) => {
+                vars.insert(name, offset);
+            }
+            Some(statement) => {
+                addr += statement.size();
+                statements.push((line_num + 1, statement));
+            }
+            None => (),
+        }
+    }
+
+    let resolve = |line_num: usize, value: &Value, mode: ParamMode| -> i64 {
+        match value {
+            Value::Literal(value) => *value,
+            Value::Name(name) => {
+                let names = if mode == ParamMode::Relative {
+                    &vars
+                } else {
+                    &labels
+                };
+                *names
+                    .get(name)
+                    .unwrap_or_else(|| panic!("Undefined name on line {}: {}", line_num, name))
+            }
+        }
+    };
+
+    let mut code = Vec::with_capacity(addr as usize);
+    for (line_num, statement) in statements {
+        match statement {
+            Statement::Data(values) => {
+                code.extend(
+                    values
+                        .iter()
+                        .map(|value| resolve(line_num, value, ParamMode::Immediate)),
+                );
+            }
+            Statement::Instruction(opcode, args) => {
+                let mut modes = [ParamMode::Position; 3];
+                for (mode, arg) in modes.iter_mut().zip(&args) {
+                    *mode = arg.mode;
+                }
+                code.push(Instruction { opcode, modes }.encode());
+                code.extend(
+                    args.iter()
+                        .map(|arg| resolve(line_num, &arg.value, arg.mode)),
+                );
+            }
+            Statement::Var(..) => unreachable!(),
+        }
+    }
+    code
+}
+
+impl Opcode {
+    pub fn from_mnemonic(mnemonic: &str) -> Option<Self> {
+        Some(match mnemonic.to_ascii_uppercase().as_str() {
+            "ADD" => Self::Add,
+            "MUL" => Self::Mul,
+            "IN" => Self::In,
+            "OUT" => Self::Out,
+            "JNZ" => Self::Jnz,
+            "JZ" => Self::Jz,
+            "LT" => Self::Lt,
+            "EQ" => Self::Eq,
+            "REL" => Self::Rel,
+            "HALT" => Self::Halt,
+            _ => return None,
+        })
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+enum Value<'a> {
+    Literal(i64),
+    Name(&'a str),
+}
+
+impl<'a> Value<'a> {
+    fn parser(input: &'a str) -> IResult<&'a str, Self> {
+        alt((map(signed, Self::Literal), map(identifier, Self::Name)))(input)
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+struct Arg<'a> {
+    mode: ParamMode,
+    value: Value<'a>,
+}
+
+impl<'a> Arg<'a> {
+    fn parser(input: &'a str) -> IResult<&'a str, Self> {
+        let arg = |mode| move |value| Self { mode, value };
+        alt((
+            map(
+                preceded(char('#'), Value::parser),
+                arg(ParamMode::Immediate),
+            ),
+            map(
+                delimited(
+                    pair(char('['), tag("r")),
+                    alt((
+                        map(signed, Value::Literal),
+                        map(preceded(char('+'), identifier), Value::Name),
+                    )),
+                    char(']'),
+                ),
+                arg(ParamMode::Relative),
+            ),
+            map(
+                delimited(char('['), Value::parser, char(']')),
+                arg(ParamMode::Position),
+            ),
+            map(map(identifier, Value::Name), arg(ParamMode::Immediate)),
+        ))(input)
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+enum Statement<'a> {
+    Var(&'a str, i64),
+    Data(Vec<Value<'a>>),
+    Instruction(Opcode, Vec<Arg<'a>>),
+}
+
+impl<'a> Statement<'a> {
+    fn size(&self) -> i64 {
+        match self {
+            Self::Var(..) => 0,
+            Self::Data(values) => values.len() as i64,
+            Self::Instruction(_, args) => 1 + args.len() as i64,
+        }
+    }
+
+    fn line(input: &'a str) -> IResult<&'a str, (Option<&'a str>, Option<Self>)> {
+        pair(
+            opt(terminated(identifier, pair(char(':'), space0))),
+            opt(alt((Self::var, Self::data, Self::instruction))),
+        )(input)
+    }
+
+    fn var(input: &'a str) -> IResult<&'a str, Self> {
+        map(
+            tuple((tag(".var"), space1, identifier, space1, signed)),
+            |(_, _, name, _, offset)| Self::Var(name, offset),
+        )(input)
+    }
+
+    fn data(input: &'a str) -> IResult<&'a str, Self> {
+        map(
+            preceded(
+                pair(tag(".data"), space1),
+                separated_list1(comma, Value::parser),
+            ),
+            Self::Data,
+        )(input)
+    }
+
+    fn instruction(input: &'a str) -> IResult<&'a str, Self> {
+        let (input, mnemonic) = alpha1(input)?;
+        let Some(opcode) = Opcode::from_mnemonic(mnemonic) else {
+            return Err(nom::Err::Error(nom::error::Error::new(
+                input,
+                nom::error::ErrorKind::Tag,
+            )));
+        };
+        let (input, mut args) = preceded(space0, separated_list0(comma, Arg::parser))(input)?;
+        let (input, write) =
+            opt(preceded(delimited(space0, tag("->"), space0), Arg::parser))(input)?;
+
+        // Writes are given after the arrow, but are still in their usual parameter position
+        let write_param = opcode.write_param();
+        match (write_param, write) {
+            (Some(idx), Some(write)) if write.mode != ParamMode::Immediate && idx == args.len() => {
+                args.push(write);
+            }
+            (None, None) => (),
+            _ => {
+                return Err(nom::Err::Error(nom::error::Error::new(
+                    input,
+                    nom::error::ErrorKind::Verify,
+                )))
+            }
+        }
+        if args.len() != opcode.param_count() {
+            return Err(nom::Err::Error(nom::error::Error::new(
+                input,
+                nom::error::ErrorKind::Count,
+            )));
+        }
+
+        Ok((input, Self::Instruction(opcode, args)))
+    }
+}
+
+fn identifier(input: &str) -> IResult<&str, &str> {
+    recognize(pair(
+        alt((alpha1, tag("_"))),
+        many0(alt((alphanumeric1, tag("_")))),
+    ))(input)
+}
+
+fn comma(input: &str) -> IResult<&str, char> {
+    delimited(space0, char(','), space0)(input)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::intcode::{Disassembly, Program};
+
+    #[test]
+    fn test_instructions() {
+        assert_eq!(assemble("ADD [r+3], #5 -> [100]"), [1201, 3, 5, 100]);
+        assert_eq!(assemble("mul #-2, [7] -> [r-1]"), [20102, -2, 7, -1]);
+        assert_eq!(
+            assemble("IN -> [r+0]\nOUT [r+0]\nHALT"),
+            [203, 0, 204, 0, 99]
+        );
+        assert_eq!(assemble("REL #10  ; comment\n\n; blank"), [109, 10]);
+    }
+
+    #[test]
+    fn test_labels_and_vars() {
+        let code = assemble(
+            "
+            .var x 1
+            start:  IN -> [r+x]
+                    JZ [r+x], end
+                    OUT [value]
+                    JNZ #1, start
+            end:    HALT
+            value:  .data 42, start, end
+            ",
+        );
+        assert_eq!(
+            code,
+            [203, 1, 1206, 1, 10, 4, 11, 1105, 1, 0, 99, 42, 0, 10]
+        );
+
+        let mut program = Program::new(&code).with_input([1, 1, 0]);
+        program.run();
+        assert_eq!(program.output(), [42, 42]);
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let code = [3, 9, 1005, 9, 11, 99, 7, 8, 42, 0, -1, 4, 8, 99];
+        let source = Disassembly::new(&code)
+            .to_string()
+            .lines()
+            .map(|line| {
+                // Strip the address column, and turn the data lines into directives
+                let line = line.trim_start().trim_start_matches(char::is_numeric);
+                line.replace("DATA", ".data")
+            })
+            .collect::<Vec<String>>()
+            .join("\n");
+        assert_eq!(assemble(&source), code);
+    }
+
+    #[test]
+    #[should_panic(expected = "Undefined name on line 1: nowhere")]
+    fn test_undefined_label() {
+        assemble("JNZ #1, nowhere");
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid assembly on line 1: ADD #1, #2")]
+    fn test_missing_write() {
+        assemble("ADD #1, #2");
+    }
+}
@@ -0,0 +1,348 @@
+use super::{Instruction, Io, Operand, ParamMode, Program, State};
+use std::collections::HashSet;
+use std::fmt;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Stop {
+    Stepped, // Single step completed without anything else of interest happening
+    InputNeeded,
+    Halted,
+    Breakpoint(usize),   // About to execute the instruction at this PC
+    MemoryAccess(usize), // About to execute an instruction which reads or writes this address
+    Watchpoint { addr: usize, old: i64, new: i64 }, // Just executed an instruction which wrote this address
+    StepLimit,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TraceEntry {
+    pub step: usize,
+    pub pc: usize,
+    pub relative_base: i64,
+    pub inst: Instruction,
+    pub operands: Vec<Operand>,
+    pub reads: Vec<i64>, // Value of each parameter which was read, in order
+    pub write: Option<(usize, i64)>,
+    pub state: State,
+}
+
+impl fmt::Display for TraceEntry {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "[{:>8}] {:>6}  {}",
+            self.step,
+            self.pc,
+            self.inst.opcode.mnemonic()
+        )?;
+
+        let write_param = self.inst.opcode.write_param();
+        let mut reads = self.reads.iter();
+        let mut sep = " ";
+        for (idx, operand) in self.operands.iter().enumerate() {
+            if Some(idx) == write_param {
+                continue;
+            }
+            write!(f, "{}{}", sep, operand)?;
+            if operand.mode != ParamMode::Immediate {
+                if let Some(value) = reads.next() {
+                    write!(f, "={}", value)?;
+                }
+            } else {
+                reads.next();
+            }
+            sep = ", ";
+        }
+
+        if let Some(idx) = write_param {
+            write!(f, " -> {}", self.operands[idx])?;
+            if let Some((_, value)) = self.write {
+                write!(f, "={}", value)?;
+            }
+        }
+        if self.state != State::Running {
+            write!(f, " ({:?})", self.state)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Debugger {
+    program: Program,
+    breakpoints: HashSet<usize>,
+    access_breakpoints: HashSet<usize>,
+    watchpoints: HashSet<usize>,
+    step_limit: Option<usize>,
+    steps: usize,
+    trace: Option<Vec<TraceEntry>>, // Only recorded when enabled
+}
+
+impl Debugger {
+    pub fn new(program: Program) -> Self {
+        Self {
+            program,
+            breakpoints: HashSet::new(),
+            access_breakpoints: HashSet::new(),
+            watchpoints: HashSet::new(),
+            step_limit: None,
+            steps: 0,
+            trace: None,
+        }
+    }
+
+    pub fn with_step_limit(self, limit: usize) -> Self {
+        Self {
+            step_limit: Some(limit),
+            ..self
+        }
+    }
+
+    pub fn with_trace(self) -> Self {
+        Self {
+            trace: Some(Vec::new()),
+            ..self
+        }
+    }
+
+    pub fn program(&self) -> &Program {
+        &self.program
+    }
+
+    pub fn program_mut(&mut self) -> &mut Program {
+        &mut self.program
+    }
+
+    pub fn into_program(self) -> Program {
+        self.program
+    }
+
+    pub fn steps(&self) -> usize {
+        self.steps
+    }
+
+    pub fn break_at(&mut self, pc: usize) {
+        self.breakpoints.insert(pc);
+    }
+
+    pub fn break_on_access(&mut self, addr: usize) {
+        self.access_breakpoints.insert(addr);
+    }
+
+    pub fn watch(&mut self, addr: usize) {
+        self.watchpoints.insert(addr);
+    }
+
+    pub fn clear_breakpoints(&mut self) {
+        self.breakpoints.clear();
+        self.access_breakpoints.clear();
+        self.watchpoints.clear();
+    }
+
+    pub fn trace(&self) -> &[TraceEntry] {
+        self.trace.as_deref().unwrap_or(&[])
+    }
+
+    pub fn take_trace(&mut self) -> Vec<TraceEntry> {
+        self.trace.as_mut().map(std::mem::take).unwrap_or_default()
+    }
+
+    pub fn step(&mut self) -> Stop {
+        let mut queue = std::mem::take(&mut self.program.queue);
+        let stop = self.step_with(&mut queue);
+        self.program.queue = queue;
+        stop
+    }
+
+    pub fn run(&mut self) -> Stop {
+        let mut queue = std::mem::take(&mut self.program.queue);
+        let stop = self.run_with(&mut queue);
+        self.program.queue = queue;
+        stop
+    }
+
+    // Executes a single instruction, ignoring any breakpoints on it
+    pub fn step_with<T: Io + ?Sized>(&mut self, io: &mut T) -> Stop {
+        if self.program.is_halted() {
+            return Stop::Halted;
+        }
+        if self.step_limit.is_some_and(|limit| self.steps >= limit) {
+            return Stop::StepLimit;
+        }
+
+        let pc = self.program.pc;
+        let relative_base = self.program.relative_base;
+        let inst = self.program.current_instruction();
+        let operands: Vec<Operand> = (0..inst.opcode.param_count())
+            .map(|idx| Operand {
+                mode: inst.modes[idx],
+                value: self.program.get(pc + 1 + idx),
+            })
+            .collect();
+        let write_param = inst.opcode.write_param();
+        let reads: Vec<i64> = (0..inst.opcode.param_count())
+            .filter(|&idx| Some(idx) != write_param)
+            .map(|idx| self.program.param(&inst, idx))
+            .collect();
+        let write_addr = write_param.map(|idx| self.program.param_addr(&inst, idx));
+        let old = write_addr.map(|addr| self.program.get(addr));
+
+        let state = self.program.step_with(io);
+        self.steps += 1;
+
+        // A paused input instruction doesn't write anything
+        let write = match (write_addr, state) {
+            (Some(addr), State::Running | State::Halted) => Some((addr, self.program.get(addr))),
+            _ => None,
+        };
+
+        if let Some(trace) = &mut self.trace {
+            trace.push(TraceEntry {
+                step: self.steps,
+                pc,
+                relative_base,
+                inst,
+                operands,
+                reads,
+                write,
+                state,
+            });
+        }
+
+        match (write, old) {
+            (Some((addr, new)), Some(old)) if self.watchpoints.contains(&addr) => {
+                Stop::Watchpoint { addr, old, new }
+            }
+            _ => match state {
+                State::Running => Stop::Stepped,
+                State::InputNeeded => Stop::InputNeeded,
+                State::Halted => Stop::Halted,
+            },
+        }
+    }
+
+    // Runs until the program halts, needs input or hits a breakpoint, watchpoint or the step limit.
+    // Breakpoints on the instruction being resumed from are skipped so that running again continues.
+    pub fn run_with<T: Io + ?Sized>(&mut self, io: &mut T) -> Stop {
+        loop {
+            let stop = self.step_with(io);
+            if stop != Stop::Stepped {
+                return stop;
+            }
+            if let Some(stop) = self.breakpoint() {
+                return stop;
+            }
+        }
+    }
+
+    fn breakpoint(&self) -> Option<Stop> {
+        let pc = self.program.pc;
+        if self.breakpoints.contains(&pc) {
+            return Some(Stop::Breakpoint(pc));
+        }
+        if self.access_breakpoints.is_empty() {
+            return None;
+        }
+
+        let inst = self.program.current_instruction();
+        (0..inst.opcode.param_count())
+            .filter(|&idx| inst.modes[idx] != ParamMode::Immediate)
+            .map(|idx| self.program.param_addr(&inst, idx))
+            .find(|addr| self.access_breakpoints.contains(addr))
+            .map(Stop::MemoryAccess)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    // Counts down from the input to zero, outputting each value
+    fn countdown(start: i64) -> Program {
+        Program::new(&[3, 100, 4, 100, 1001, 100, -1, 100, 1005, 100, 2, 99]).with_input([start])
+    }
+
+    #[test]
+    fn test_step() {
+        let mut debugger = Debugger::new(countdown(2));
+        assert_eq!(debugger.step(), Stop::Stepped);
+        assert_eq!(debugger.program().pc(), 2);
+        assert_eq!(debugger.program().get(100), 2);
+        assert_eq!(debugger.step(), Stop::Stepped);
+        assert_eq!(debugger.program().output(), [2]);
+        assert_eq!(debugger.steps(), 2);
+    }
+
+    #[test]
+    fn test_breakpoints() {
+        let mut debugger = Debugger::new(countdown(3));
+        debugger.break_at(8);
+        assert_eq!(debugger.run(), Stop::Breakpoint(8));
+        assert_eq!(debugger.program().get(100), 2);
+        assert_eq!(debugger.run(), Stop::Breakpoint(8));
+        assert_eq!(debugger.program().get(100), 1);
+
+        debugger.clear_breakpoints();
+        debugger.break_on_access(100);
+        assert_eq!(debugger.run(), Stop::MemoryAccess(100));
+        assert_eq!(debugger.program().pc(), 2);
+
+        debugger.clear_breakpoints();
+        assert_eq!(debugger.run(), Stop::Halted);
+        assert_eq!(debugger.program().output(), [3, 2, 1]);
+    }
+
+    #[test]
+    fn test_watchpoint() {
+        let mut debugger = Debugger::new(countdown(2));
+        debugger.watch(100);
+        assert_eq!(
+            debugger.run(),
+            Stop::Watchpoint {
+                addr: 100,
+                old: 0,
+                new: 2
+            }
+        );
+        assert_eq!(
+            debugger.run(),
+            Stop::Watchpoint {
+                addr: 100,
+                old: 2,
+                new: 1
+            }
+        );
+    }
+
+    #[test]
+    fn test_step_limit() {
+        // Jumps to itself forever
+        let mut debugger = Debugger::new(Program::new(&[1105, 1, 0])).with_step_limit(1000);
+        assert_eq!(debugger.run(), Stop::StepLimit);
+        assert_eq!(debugger.steps(), 1000);
+    }
+
+    #[test]
+    fn test_trace() {
+        let mut debugger = Debugger::new(countdown(1)).with_trace();
+        assert_eq!(debugger.run(), Stop::Halted);
+
+        let trace = debugger.take_trace();
+        assert_eq!(trace.len(), 5);
+        assert_eq!(trace[2].reads, [1, -1]);
+        assert_eq!(trace[2].write, Some((100, 0)));
+        assert_eq!(
+            trace
+                .iter()
+                .map(TraceEntry::to_string)
+                .collect::<Vec<String>>(),
+            [
+                "[       1]      0  IN -> [100]=1",
+                "[       2]      2  OUT [100]=1",
+                "[       3]      4  ADD [100]=1, #-1 -> [100]=0",
+                "[       4]      8  JNZ [100]=0, #2",
+                "[       5]     11  HALT (Halted)",
+            ]
+        );
+        assert_eq!(debugger.trace(), []);
+    }
+}
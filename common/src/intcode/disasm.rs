@@ -0,0 +1,367 @@
+use super::{Instruction, Opcode, ParamMode};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+
+impl Opcode {
+    pub fn mnemonic(self) -> &'static str {
+        match self {
+            Self::Add => "ADD",
+            Self::Mul => "MUL",
+            Self::In => "IN",
+            Self::Out => "OUT",
+            Self::Jnz => "JNZ",
+            Self::Jz => "JZ",
+            Self::Lt => "LT",
+            Self::Eq => "EQ",
+            Self::Rel => "REL",
+            Self::Halt => "HALT",
+        }
+    }
+
+    // Index of the parameter which is written to, if any
+    pub fn write_param(self) -> Option<usize> {
+        match self {
+            Self::Add | Self::Mul | Self::Lt | Self::Eq => Some(2),
+            Self::In => Some(0),
+            Self::Out | Self::Jnz | Self::Jz | Self::Rel | Self::Halt => None,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Operand {
+    pub mode: ParamMode,
+    pub value: i64,
+}
+
+impl fmt::Display for Operand {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.mode {
+            ParamMode::Position => write!(f, "[{}]", self.value),
+            ParamMode::Immediate => write!(f, "#{}", self.value),
+            ParamMode::Relative if self.value < 0 => write!(f, "[r{}]", self.value),
+            ParamMode::Relative => write!(f, "[r+{}]", self.value),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Line {
+    Code {
+        addr: usize,
+        inst: Instruction,
+        operands: Vec<Operand>,
+    },
+    Data {
+        addr: usize,
+        values: Vec<i64>,
+    },
+}
+
+impl Line {
+    pub fn addr(&self) -> usize {
+        match self {
+            Self::Code { addr, .. } | Self::Data { addr, .. } => *addr,
+        }
+    }
+
+    pub fn size(&self) -> usize {
+        match self {
+            Self::Code { inst, .. } => inst.size(),
+            Self::Data { values, .. } => values.len(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Disassembly {
+    pub lines: Vec<Line>,
+    pub labels: BTreeSet<usize>, // Addresses which are jumped to, or returned to after a call
+}
+
+const DATA_PER_LINE: usize = 8;
+
+impl Disassembly {
+    pub fn new(code: &[i64]) -> Self {
+        Self::with_entry_points(code, &[0])
+    }
+
+    // Follows every instruction reachable from the entry points, anything not reached is data
+    pub fn with_entry_points(code: &[i64], entry_points: &[usize]) -> Self {
+        let mut instructions = BTreeMap::new();
+        let mut labels = BTreeSet::new();
+        let mut pending: Vec<usize> = entry_points.to_vec();
+
+        while let Some(addr) = pending.pop() {
+            if instructions.contains_key(&addr) {
+                continue;
+            }
+            let Some(inst) = Self::decode_at(code, addr) else {
+                continue;
+            };
+            let operands = Self::operands(code, addr, &inst);
+            let next = addr + inst.size();
+
+            match inst.opcode {
+                Opcode::Halt => (),
+                Opcode::Jnz | Opcode::Jz => {
+                    let jumps = match operands[0] {
+                        Operand {
+                            mode: ParamMode::Immediate,
+                            value,
+                        } => Some((value != 0) == (inst.opcode == Opcode::Jnz)),
+                        _ => None, // Can't tell until runtime
+                    };
+                    if jumps != Some(false) {
+                        if let Some(target) = Self::immediate_addr(operands[1]) {
+                            labels.insert(target);
+                            pending.push(target);
+                        }
+                    }
+                    if jumps != Some(true) {
+                        pending.push(next);
+                    } else if Self::is_call(&instructions, addr, next) {
+                        // Execution continues after the jump once the call returns
+                        labels.insert(next);
+                        pending.push(next);
+                    }
+                }
+                _ => pending.push(next),
+            }
+
+            instructions.insert(addr, (inst, operands));
+        }
+
+        let mut lines = Vec::new();
+        let mut addr = 0;
+        while addr < code.len() {
+            if let Some((inst, operands)) = instructions.get(&addr) {
+                lines.push(Line::Code {
+                    addr,
+                    inst: *inst,
+                    operands: operands.clone(),
+                });
+                addr += inst.size();
+            } else {
+                // Data runs until the next instruction, label or line limit
+                let mut end = addr + 1;
+                while end < code.len()
+                    && end - addr < DATA_PER_LINE
+                    && !instructions.contains_key(&end)
+                    && !labels.contains(&end)
+                {
+                    end += 1;
+                }
+                lines.push(Line::Data {
+                    addr,
+                    values: code[addr..end].to_vec(),
+                });
+                addr = end;
+            }
+        }
+
+        Self { lines, labels }
+    }
+
+    pub fn is_code(&self, addr: usize) -> bool {
+        self.lines.iter().any(|line| match line {
+            Line::Code { addr: a, inst, .. } => (*a..*a + inst.size()).contains(&addr),
+            Line::Data { .. } => false,
+        })
+    }
+
+    pub fn label(addr: usize) -> String {
+        format!("L{}", addr)
+    }
+
+    fn decode_at(code: &[i64], addr: usize) -> Option<Instruction> {
+        let inst = Instruction::decode(*code.get(addr)?)?;
+        if addr + inst.size() > code.len() {
+            return None;
+        }
+        // Writes never use immediate mode
+        if let Some(idx) = inst.opcode.write_param() {
+            if inst.modes[idx] == ParamMode::Immediate {
+                return None;
+            }
+        }
+        Some(inst)
+    }
+
+    fn operands(code: &[i64], addr: usize, inst: &Instruction) -> Vec<Operand> {
+        (0..inst.opcode.param_count())
+            .map(|idx| Operand {
+                mode: inst.modes[idx],
+                value: code[addr + 1 + idx],
+            })
+            .collect()
+    }
+
+    fn immediate_addr(operand: Operand) -> Option<usize> {
+        match operand.mode {
+            ParamMode::Immediate => usize::try_from(operand.value).ok(),
+            _ => None,
+        }
+    }
+
+    // A call stores the address following its jump just before jumping
+    fn is_call(
+        instructions: &BTreeMap<usize, (Instruction, Vec<Operand>)>,
+        jump_addr: usize,
+        return_addr: usize,
+    ) -> bool {
+        let Some((&addr, (inst, operands))) = instructions.range(..jump_addr).next_back() else {
+            return false;
+        };
+        if addr + inst.size() != jump_addr {
+            return false;
+        }
+        let (a, b) = match operands[..] {
+            [Operand {
+                mode: ParamMode::Immediate,
+                value: a,
+            }, Operand {
+                mode: ParamMode::Immediate,
+                value: b,
+            }, _] => (a, b),
+            _ => return false,
+        };
+        let value = match inst.opcode {
+            Opcode::Add => a.checked_add(b),
+            Opcode::Mul => a.checked_mul(b),
+            _ => return false,
+        };
+        // Data decoded as an add or mul can hold anything, so an overflow just means it isn't a call
+        value.and_then(|v| usize::try_from(v).ok()) == Some(return_addr)
+    }
+
+    fn fmt_operand(&self, operand: Operand, is_target: bool) -> String {
+        match Self::immediate_addr(operand) {
+            Some(target) if is_target && self.labels.contains(&target) => Self::label(target),
+            _ => operand.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for Disassembly {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for line in &self.lines {
+            if self.labels.contains(&line.addr()) {
+                writeln!(f, "{}:", Self::label(line.addr()))?;
+            }
+            write!(f, "{:>6}  ", line.addr())?;
+            match line {
+                Line::Code { inst, operands, .. } => {
+                    let write_param = inst.opcode.write_param();
+                    let is_jump = matches!(inst.opcode, Opcode::Jnz | Opcode::Jz);
+                    let reads: Vec<String> = operands
+                        .iter()
+                        .enumerate()
+                        .filter(|&(idx, _)| Some(idx) != write_param)
+                        .map(|(idx, &operand)| self.fmt_operand(operand, is_jump && idx == 1))
+                        .collect();
+
+                    write!(f, "{}", inst.opcode.mnemonic())?;
+                    if !reads.is_empty() {
+                        write!(f, " {}", reads.join(", "))?;
+                    }
+                    if let Some(idx) = write_param {
+                        write!(f, " -> {}", operands[idx])?;
+                    }
+                    writeln!(f)?;
+                }
+                Line::Data { values, .. } => {
+                    let values: Vec<String> = values.iter().map(i64::to_string).collect();
+                    writeln!(f, "DATA {}", values.join(", "))?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_operands() {
+        let disasm = Disassembly::new(&[22201, 3, -2, 100, 99]);
+        assert_eq!(
+            disasm.lines[0],
+            Line::Code {
+                addr: 0,
+                inst: Instruction::decode(22201).unwrap(),
+                operands: vec![
+                    Operand {
+                        mode: ParamMode::Relative,
+                        value: 3
+                    },
+                    Operand {
+                        mode: ParamMode::Relative,
+                        value: -2
+                    },
+                    Operand {
+                        mode: ParamMode::Relative,
+                        value: 100
+                    },
+                ],
+            }
+        );
+        assert_eq!(
+            Disassembly::new(&[21101, 3, 5, 100, 99]).to_string(),
+            "     0  ADD #3, #5 -> [r+100]\n     4  HALT\n"
+        );
+        assert_eq!(
+            Disassembly::new(&[1201, 3, 5, 100, 99]).to_string(),
+            "     0  ADD [r+3], #5 -> [100]\n     4  HALT\n"
+        );
+    }
+
+    #[test]
+    fn test_code_and_data() {
+        // Reads an input, jumps over the data if it isn't zero then outputs part of the data
+        let code = [3, 9, 1005, 9, 11, 99, 7, 8, 42, 0, -1, 4, 8, 99];
+        let disasm = Disassembly::new(&code);
+        assert_eq!(disasm.labels, BTreeSet::from([11]));
+        assert_eq!(disasm.is_code(0), true);
+        assert_eq!(disasm.is_code(5), true);
+        assert_eq!(disasm.is_code(6), false);
+        assert_eq!(disasm.is_code(12), true);
+        assert_eq!(
+            disasm.to_string(),
+            [
+                "     0  IN -> [9]",
+                "     2  JNZ [9], L11",
+                "     5  HALT",
+                "     6  DATA 7, 8, 42, 0, -1",
+                "L11:",
+                "    11  OUT [8]",
+                "    13  HALT",
+                "",
+            ]
+            .join("\n")
+        );
+    }
+
+    #[test]
+    fn test_unconditional_jumps() {
+        // A call pushes its return address then jumps, anything after the jump is only reached on return
+        let code = [21101, 7, 0, 0, 1105, 1, 9, 99, 99, 2106, 0, 0];
+        let disasm = Disassembly::new(&code);
+        assert_eq!(disasm.labels, BTreeSet::from([7, 9]));
+        assert_eq!(disasm.is_code(7), true);
+        assert_eq!(disasm.is_code(8), false);
+
+        // Never taken, so the target isn't code
+        let disasm = Disassembly::new(&[1106, 1, 4, 99, 5]);
+        assert_eq!(disasm.labels, BTreeSet::new());
+        assert_eq!(disasm.is_code(4), false);
+
+        // Operands that would overflow can't be a return address
+        let disasm = Disassembly::new(&[1101, i64::MAX, 1, 0, 1105, 1, 8, 99, 99]);
+        assert_eq!(disasm.is_code(7), false);
+        let disasm = Disassembly::new(&[1102, i64::MAX, 2, 0, 1105, 1, 8, 99, 99]);
+        assert_eq!(disasm.is_code(7), false);
+    }
+}
@@ -0,0 +1,227 @@
+use std::collections::VecDeque;
+
+pub trait Io {
+    // Returning None pauses the program on the input instruction until it is run again
+    fn input(&mut self) -> Option<i64>;
+    fn output(&mut self, value: i64);
+}
+
+impl<T: Io + ?Sized> Io for &mut T {
+    fn input(&mut self) -> Option<i64> {
+        (**self).input()
+    }
+
+    fn output(&mut self, value: i64) {
+        (**self).output(value)
+    }
+}
+
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Queue {
+    pub input: VecDeque<i64>,
+    pub output: Vec<i64>,
+}
+
+impl Queue {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl Io for Queue {
+    fn input(&mut self) -> Option<i64> {
+        self.input.pop_front()
+    }
+
+    fn output(&mut self, value: i64) {
+        self.output.push(value);
+    }
+}
+
+pub struct Callback<I, O> {
+    input: I,
+    output: O,
+}
+
+impl<I, O> Callback<I, O>
+where
+    I: FnMut() -> Option<i64>,
+    O: FnMut(i64),
+{
+    pub fn new(input: I, output: O) -> Self {
+        Self { input, output }
+    }
+}
+
+impl<I, O> Io for Callback<I, O>
+where
+    I: FnMut() -> Option<i64>,
+    O: FnMut(i64),
+{
+    fn input(&mut self) -> Option<i64> {
+        (self.input)()
+    }
+
+    fn output(&mut self, value: i64) {
+        (self.output)(value)
+    }
+}
+
+pub trait LineIo {
+    // Lines are given without the trailing newline. Returning None pauses the program.
+    fn read_line(&mut self) -> Option<String>;
+    fn write_line(&mut self, line: &str);
+
+    // Called for any output which isn't an ASCII character, such as a final answer
+    fn write_value(&mut self, value: i64) {
+        panic!("Non-ASCII value received: {}", value);
+    }
+}
+
+impl<T: LineIo + ?Sized> LineIo for &mut T {
+    fn read_line(&mut self) -> Option<String> {
+        (**self).read_line()
+    }
+
+    fn write_line(&mut self, line: &str) {
+        (**self).write_line(line)
+    }
+
+    fn write_value(&mut self, value: i64) {
+        (**self).write_value(value)
+    }
+}
+
+pub struct Ascii<T> {
+    inner: T,
+    pending_input: VecDeque<i64>,
+    pending_line: String,
+}
+
+impl<T: LineIo> Ascii<T> {
+    pub fn new(inner: T) -> Self {
+        Self {
+            inner,
+            pending_input: VecDeque::new(),
+            pending_line: String::new(),
+        }
+    }
+
+    pub fn inner(&self) -> &T {
+        &self.inner
+    }
+
+    pub fn inner_mut(&mut self) -> &mut T {
+        &mut self.inner
+    }
+
+    pub fn into_inner(self) -> T {
+        self.inner
+    }
+
+    // Any output received since the last newline, e.g. a prompt
+    pub fn pending_line(&self) -> &str {
+        &self.pending_line
+    }
+}
+
+impl<T: LineIo> Io for Ascii<T> {
+    fn input(&mut self) -> Option<i64> {
+        if self.pending_input.is_empty() {
+            let line = self.inner.read_line()?;
+            self.pending_input
+                .extend(line.bytes().chain(std::iter::once(b'\n')).map(i64::from));
+        }
+        self.pending_input.pop_front()
+    }
+
+    fn output(&mut self, value: i64) {
+        match u8::try_from(value) {
+            Ok(b'\n') => {
+                self.inner.write_line(&self.pending_line);
+                self.pending_line.clear();
+            }
+            Ok(c) if c.is_ascii() => self.pending_line.push(c as char),
+            _ => self.inner.write_value(value),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::intcode::{Program, State};
+
+    // Echoes each input value back as output until a zero is received
+    fn echo() -> Program {
+        Program::new(&[3, 100, 1006, 100, 11, 4, 100, 1105, 1, 0, 0, 99])
+    }
+
+    #[test]
+    fn test_queue() {
+        let mut program = echo();
+        let mut queue = Queue::new();
+        queue.input.extend([1, 2, 3]);
+        assert_eq!(program.run_with(&mut queue), State::InputNeeded);
+        assert_eq!(queue.output, [1, 2, 3]);
+
+        queue.input.push_back(0);
+        assert_eq!(program.run_with(&mut queue), State::Halted);
+        assert_eq!(queue.output, [1, 2, 3]);
+    }
+
+    #[test]
+    fn test_callback() {
+        let mut program = echo();
+        let mut inputs = vec![0, 7, 5];
+        let mut outputs = Vec::new();
+        let state = program.run_with(&mut Callback::new(
+            || inputs.pop(),
+            |value| outputs.push(value * 10),
+        ));
+        assert_eq!(state, State::Halted);
+        assert_eq!(outputs, [50, 70]);
+    }
+
+    struct Console {
+        commands: VecDeque<&'static str>,
+        lines: Vec<String>,
+        values: Vec<i64>,
+    }
+
+    impl LineIo for Console {
+        fn read_line(&mut self) -> Option<String> {
+            self.commands.pop_front().map(str::to_owned)
+        }
+
+        fn write_line(&mut self, line: &str) {
+            self.lines.push(line.to_owned());
+        }
+
+        fn write_value(&mut self, value: i64) {
+            self.values.push(value);
+        }
+    }
+
+    #[test]
+    fn test_ascii() {
+        let mut program = echo();
+        let mut console = Ascii::new(Console {
+            commands: ["hi", "there"].into_iter().collect(),
+            lines: Vec::new(),
+            values: Vec::new(),
+        });
+        assert_eq!(program.run_with(&mut console), State::InputNeeded);
+        assert_eq!(console.inner().lines, ["hi", "there"]);
+        assert_eq!(console.pending_line(), "");
+
+        console.inner_mut().commands.push_back("ok");
+        program.run_with(&mut console);
+        assert_eq!(console.inner().lines, ["hi", "there", "ok"]);
+
+        let mut program = Program::new(&[104, 62, 104, 1000, 99]);
+        program.run_with(&mut console);
+        assert_eq!(console.pending_line(), ">");
+        assert_eq!(console.into_inner().values, [1000]);
+    }
+}
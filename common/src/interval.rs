@@ -0,0 +1,308 @@
+use num::PrimInt;
+use std::ops::RangeInclusive;
+
+// A set of integers kept as sorted, non-overlapping inclusive intervals. Intervals that touch are merged, so inserting
+// 1..=2 and 3..=4 leaves just 1..=4.
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+pub struct IntervalSet<T> {
+    intervals: Vec<(T, T)>,
+}
+
+impl<T: PrimInt> IntervalSet<T> {
+    pub fn new() -> Self {
+        Self {
+            intervals: Vec::new(),
+        }
+    }
+
+    pub fn from_range(range: RangeInclusive<T>) -> Self {
+        let mut set = Self::new();
+        set.insert(range);
+        set
+    }
+
+    // Empty ranges are ignored
+    pub fn insert(&mut self, range: RangeInclusive<T>) {
+        let (mut lo, mut hi) = range.into_inner();
+        if lo > hi {
+            return;
+        }
+
+        // Everything from the first interval ending just before lo to the last one starting just after hi gets merged
+        let first = self
+            .intervals
+            .partition_point(|(_, b)| b.saturating_add(T::one()) < lo);
+        let last = self
+            .intervals
+            .partition_point(|(a, _)| *a <= hi.saturating_add(T::one()));
+        if first < last {
+            lo = lo.min(self.intervals[first].0);
+            hi = hi.max(self.intervals[last - 1].1);
+        }
+        self.intervals.splice(first..last, [(lo, hi)]);
+    }
+
+    pub fn remove(&mut self, range: RangeInclusive<T>) {
+        let (lo, hi) = range.into_inner();
+        if lo > hi {
+            return;
+        }
+
+        let first = self.intervals.partition_point(|(_, b)| *b < lo);
+        let last = self.intervals.partition_point(|(a, _)| *a <= hi);
+        if first >= last {
+            return;
+        }
+
+        // Only the ends of the first and last intervals can stick out past the range
+        let mut kept = Vec::new();
+        if self.intervals[first].0 < lo {
+            kept.push((self.intervals[first].0, lo - T::one()));
+        }
+        if self.intervals[last - 1].1 > hi {
+            kept.push((hi + T::one(), self.intervals[last - 1].1));
+        }
+        self.intervals.splice(first..last, kept);
+    }
+
+    pub fn contains(&self, x: T) -> bool {
+        let i = self.intervals.partition_point(|(_, b)| *b < x);
+        i < self.intervals.len() && self.intervals[i].0 <= x
+    }
+
+    // Whether every value in the range is in the set. Always true for an empty range.
+    pub fn contains_range(&self, range: RangeInclusive<T>) -> bool {
+        let (lo, hi) = range.into_inner();
+        if lo > hi {
+            return true;
+        }
+        let i = self.intervals.partition_point(|(_, b)| *b < lo);
+        i < self.intervals.len() && self.intervals[i].0 <= lo && hi <= self.intervals[i].1
+    }
+
+    // Whether any value in the range is in the set
+    pub fn overlaps(&self, range: RangeInclusive<T>) -> bool {
+        let (lo, hi) = range.into_inner();
+        if lo > hi {
+            return false;
+        }
+        let i = self.intervals.partition_point(|(_, b)| *b < lo);
+        i < self.intervals.len() && self.intervals[i].0 <= hi
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    // In order, lowest first
+    pub fn intervals(&self) -> impl Iterator<Item = RangeInclusive<T>> + '_ {
+        self.intervals.iter().map(|(a, b)| *a..=*b)
+    }
+
+    pub fn min(&self) -> Option<T> {
+        self.intervals.first().map(|(a, _)| *a)
+    }
+
+    pub fn max(&self) -> Option<T> {
+        self.intervals.last().map(|(_, b)| *b)
+    }
+
+    // How many values are in the set. This is a u128 so that a set covering every value of a narrower T still fits,
+    // but it overflows for a set covering all of i128 or u128.
+    pub fn covered(&self) -> u128 {
+        self.intervals.iter().map(|&(a, b)| Self::len(a, b)).sum()
+    }
+
+    // The number of values in a..=b without overflowing T. Only the top half of u128 doesn't fit in an i128.
+    fn len(a: T, b: T) -> u128 {
+        match (a.to_i128(), b.to_i128()) {
+            (Some(a), Some(b)) => b.wrapping_sub(a) as u128 + 1,
+            _ => b.to_u128().unwrap() - a.to_u128().unwrap() + 1,
+        }
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        let mut output = self.clone();
+        output.extend(other.intervals());
+        output
+    }
+
+    pub fn intersection(&self, other: &Self) -> Self {
+        let mut output = Self::new();
+        let (mut i, mut j) = (0, 0);
+        while i < self.intervals.len() && j < other.intervals.len() {
+            let (a, b) = self.intervals[i];
+            let (c, d) = other.intervals[j];
+            if a.max(c) <= b.min(d) {
+                output.intervals.push((a.max(c), b.min(d)));
+            }
+
+            // Whichever ends first can't overlap anything else
+            if b < d {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+        output
+    }
+
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut output = self.clone();
+        for range in other.intervals() {
+            output.remove(range);
+        }
+        output
+    }
+
+    // The parts of the range not in the set
+    pub fn gaps(&self, within: RangeInclusive<T>) -> Self {
+        Self::from_range(within).difference(self)
+    }
+
+    // Everything below the given value, then everything from it upwards
+    pub fn split_at(&self, at: T) -> (Self, Self) {
+        let mut below = self.clone();
+        let mut above = self.clone();
+        below.remove(at..=T::max_value());
+        if at > T::min_value() {
+            above.remove(T::min_value()..=at - T::one());
+        }
+        (below, above)
+    }
+
+    // Moves values through a piecewise linear mapping. Each piece is a source range and where the start of that range
+    // goes to, with everything else in the range moving along with it. Values not covered by any piece stay where they
+    // are. If pieces overlap the first one wins.
+    pub fn map_ranges(&self, pieces: impl IntoIterator<Item = (RangeInclusive<T>, T)>) -> Self {
+        let mut unmapped = self.clone();
+        let mut output = Self::new();
+        for (source, destination) in pieces {
+            let start = *source.start();
+            let hit = unmapped.intersection(&Self::from_range(source.clone()));
+            for (a, b) in hit.intervals {
+                output.insert(destination + (a - start)..=destination + (b - start));
+            }
+            unmapped.remove(source);
+        }
+        output.union(&unmapped)
+    }
+}
+
+impl<T: PrimInt> FromIterator<RangeInclusive<T>> for IntervalSet<T> {
+    fn from_iter<I: IntoIterator<Item = RangeInclusive<T>>>(iter: I) -> Self {
+        let mut set = Self::new();
+        set.extend(iter);
+        set
+    }
+}
+
+impl<T: PrimInt> Extend<RangeInclusive<T>> for IntervalSet<T> {
+    fn extend<I: IntoIterator<Item = RangeInclusive<T>>>(&mut self, iter: I) {
+        for range in iter {
+            self.insert(range);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn ranges(set: &IntervalSet<i32>) -> Vec<RangeInclusive<i32>> {
+        set.intervals().collect()
+    }
+
+    #[test]
+    fn test_insert() {
+        let mut set = IntervalSet::new();
+        set.insert(10..=12);
+        set.insert(1..=2);
+        set.insert(5..=6);
+        assert_eq!(ranges(&set), [1..=2, 5..=6, 10..=12]);
+
+        // Touching intervals merge, overlapping ones too
+        set.insert(3..=4);
+        assert_eq!(ranges(&set), [1..=6, 10..=12]);
+        set.insert(8..=11);
+        assert_eq!(ranges(&set), [1..=6, 8..=12]);
+        set.insert(0..=20);
+        assert_eq!(ranges(&set), [0..=20]);
+        #[expect(clippy::reversed_empty_ranges)]
+        set.insert(30..=25);
+        assert_eq!(ranges(&set), [0..=20]);
+        assert_eq!(set.covered(), 21);
+
+        let mut set = IntervalSet::new();
+        set.insert(u8::MAX - 1..=u8::MAX);
+        set.insert(0..=u8::MAX - 2);
+        assert_eq!(set.intervals().collect::<Vec<_>>(), [0..=u8::MAX]);
+    }
+
+    #[test]
+    fn test_remove() {
+        let mut set: IntervalSet<i32> = [0..=10, 20..=30].into_iter().collect();
+        set.remove(5..=22);
+        assert_eq!(ranges(&set), [0..=4, 23..=30]);
+        set.remove(25..=25);
+        assert_eq!(ranges(&set), [0..=4, 23..=24, 26..=30]);
+        set.remove(-5..=0);
+        assert_eq!(ranges(&set), [1..=4, 23..=24, 26..=30]);
+        set.remove(11..=19);
+        assert_eq!(set.covered(), 11);
+    }
+
+    #[test]
+    fn test_queries() {
+        let set: IntervalSet<i32> = [0..=10, 20..=30].into_iter().collect();
+        assert!(set.contains(0));
+        assert!(set.contains(25));
+        assert_eq!(set.contains(15), false);
+        assert!(set.contains_range(2..=8));
+        assert_eq!(set.contains_range(8..=22), false);
+        assert!(set.overlaps(8..=22));
+        assert_eq!(set.overlaps(11..=19), false);
+        assert_eq!((set.min(), set.max()), (Some(0), Some(30)));
+        assert_eq!(set.covered(), 22);
+        assert_eq!(IntervalSet::from_range(0..=u8::MAX).covered(), 256);
+        assert_eq!(IntervalSet::from_range(i8::MIN..=i8::MAX).covered(), 256);
+        assert_eq!(
+            IntervalSet::from_range(u128::MAX - 1..=u128::MAX).covered(),
+            2
+        );
+        assert_eq!(ranges(&set.gaps(-5..=35)), [-5..=-1, 11..=19, 31..=35]);
+    }
+
+    #[test]
+    fn test_set_operations() {
+        let a: IntervalSet<i32> = [0..=10, 20..=30].into_iter().collect();
+        let b: IntervalSet<i32> = [5..=25, 40..=50].into_iter().collect();
+        assert_eq!(ranges(&a.union(&b)), [0..=30, 40..=50]);
+        assert_eq!(ranges(&a.intersection(&b)), [5..=10, 20..=25]);
+        assert_eq!(ranges(&a.difference(&b)), [0..=4, 26..=30]);
+        assert_eq!(ranges(&b.difference(&a)), [11..=19, 40..=50]);
+
+        let (below, above) = a.split_at(5);
+        assert_eq!(ranges(&below), [0..=4]);
+        assert_eq!(ranges(&above), [5..=10, 20..=30]);
+        let (below, above) = a.split_at(i32::MIN);
+        assert!(below.is_empty());
+        assert_eq!(above, a);
+    }
+
+    #[test]
+    fn test_map_ranges() {
+        // The seed to soil map from 2023 day 5
+        let seeds: IntervalSet<u64> = [79..=92, 55..=67].into_iter().collect();
+        let soil = seeds.map_ranges([(98..=99, 50), (50..=97, 52)]);
+        assert_eq!(soil.intervals().collect::<Vec<_>>(), [57..=69, 81..=94]);
+
+        // Values can be split up and moved either way
+        let set = IntervalSet::from_range(0..=9);
+        let mapped = set.map_ranges([(0..=2, 100), (8..=20, 50), (1..=5, 200)]);
+        assert_eq!(
+            mapped.intervals().collect::<Vec<_>>(),
+            [6..=7, 50..=51, 100..=102, 202..=204]
+        );
+    }
+}
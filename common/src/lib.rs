@@ -1,23 +1,48 @@
 #![deny(clippy::allow_attributes)]
+#![cfg_attr(test, expect(clippy::bool_assert_comparison))]
 
+pub mod answers;
+pub mod automaton;
 mod cardinal;
+mod coord;
+pub mod cycle;
+pub mod graph;
+pub mod hex;
+pub mod intcode;
+mod interval;
 mod linked_list_circ;
+pub mod machine;
 mod mode;
 mod modulus;
 mod parser;
+mod pattern;
 mod point2;
 mod point3;
+mod pointn;
 mod range2;
 mod range3;
+mod rangen;
+pub mod region;
+pub mod runner;
+pub mod search;
+pub mod state_search;
 mod tile;
+mod transform;
+mod view;
 
 pub use cardinal::*;
+pub use coord::*;
+pub use interval::*;
 pub use linked_list_circ::*;
 pub use mode::*;
 pub use modulus::*;
 pub use parser::*;
 pub use point2::*;
 pub use point3::*;
+pub use pointn::*;
 pub use range2::*;
 pub use range3::*;
+pub use rangen::*;
 pub use tile::*;
+pub use transform::*;
+pub use view::*;
@@ -268,11 +268,11 @@ impl<T: Default> LinkedListCirc<T> {
         self.remove_at_idx(target_idx)
     }
 
-    pub fn iter(&self) -> LlIter<T> {
+    pub fn iter(&self) -> LlIter<'_, T> {
         LlIter::new(self)
     }
 
-    pub fn iter_mut(&mut self) -> LlIterMut<T> {
+    pub fn iter_mut(&mut self) -> LlIterMut<'_, T> {
         LlIterMut::new(self)
     }
 }
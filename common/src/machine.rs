@@ -0,0 +1,296 @@
+use std::collections::HashSet;
+use std::ops::{Index, IndexMut};
+
+// What to do after an instruction has executed
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Flow {
+    Next,
+    Jump(isize), // Relative to the instruction which jumped
+    Goto(usize),
+    Halt,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Stop {
+    Halted,
+    Loop(usize), // About to execute the instruction at this PC for a second time
+    StepLimit,
+}
+
+// An instruction which operates on a register file, for use with Cpu
+pub trait InstructionSet {
+    type Registers;
+
+    fn execute(&self, regs: &mut Self::Registers) -> Flow;
+
+    fn cycles(&self) -> usize {
+        1
+    }
+}
+
+// A fixed number of registers which are addressed by index
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Registers<T, const N: usize>(pub [T; N]);
+
+impl<T: Copy + Default, const N: usize> Default for Registers<T, N> {
+    fn default() -> Self {
+        Self([T::default(); N])
+    }
+}
+
+impl<T, const N: usize> Index<usize> for Registers<T, N> {
+    type Output = T;
+    fn index(&self, idx: usize) -> &Self::Output {
+        &self.0[idx]
+    }
+}
+
+impl<T, const N: usize> IndexMut<usize> for Registers<T, N> {
+    fn index_mut(&mut self, idx: usize) -> &mut Self::Output {
+        &mut self.0[idx]
+    }
+}
+
+// Anything which steps through a program one instruction at a time. Only the program counter and a single step need
+// to be implemented, running, tracing, profiling and loop detection are all built on top of those.
+pub trait Machine {
+    type Instruction;
+
+    fn program(&self) -> &[Self::Instruction];
+
+    fn pc(&self) -> usize;
+
+    fn is_halted(&self) -> bool;
+
+    // Executes the instruction at the PC, returns false if the machine had already halted
+    fn step(&mut self) -> bool;
+
+    // Number of cycles the instruction at the PC takes to execute
+    fn cycles(&self) -> usize {
+        1
+    }
+
+    fn run(&mut self) -> Stop {
+        while self.step() {}
+        Stop::Halted
+    }
+
+    fn run_for(&mut self, steps: usize) -> Stop {
+        for _ in 0..steps {
+            if !self.step() {
+                return Stop::Halted;
+            }
+        }
+        if self.is_halted() {
+            Stop::Halted
+        } else {
+            Stop::StepLimit
+        }
+    }
+
+    // Stops before any instruction executes twice. This is only a true infinite loop when the registers can't affect
+    // which instructions are executed.
+    fn run_until_loop(&mut self) -> Stop {
+        let mut executed = HashSet::new();
+        while !self.is_halted() {
+            if !executed.insert(self.pc()) {
+                return Stop::Loop(self.pc());
+            }
+            self.step();
+        }
+        Stop::Halted
+    }
+
+    // Calls the hook during every cycle of every instruction, before the instruction takes effect. Cycles count from 1.
+    fn run_with_hook<F>(&mut self, mut hook: F) -> Stop
+    where
+        F: FnMut(usize, &Self),
+    {
+        let mut cycle = 0;
+        while !self.is_halted() {
+            for _ in 0..self.cycles() {
+                cycle += 1;
+                hook(cycle, self);
+            }
+            self.step();
+        }
+        Stop::Halted
+    }
+
+    // Program counters of each instruction executed, in order
+    fn trace(&mut self, step_limit: usize) -> (Stop, Vec<usize>) {
+        let mut pcs = Vec::new();
+        while !self.is_halted() {
+            if pcs.len() >= step_limit {
+                return (Stop::StepLimit, pcs);
+            }
+            pcs.push(self.pc());
+            self.step();
+        }
+        (Stop::Halted, pcs)
+    }
+
+    // Number of times each instruction was executed when running until halted
+    fn profile(&mut self) -> Vec<usize> {
+        let mut counts = vec![0; self.program().len()];
+        while !self.is_halted() {
+            if let Some(count) = counts.get_mut(self.pc()) {
+                *count += 1;
+            }
+            self.step();
+        }
+        counts
+    }
+}
+
+// A machine which runs any instruction set, halting when the PC leaves the program
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Cpu<I: InstructionSet> {
+    pub program: Vec<I>,
+    pub regs: I::Registers,
+    pc: usize,
+    halted: bool,
+}
+
+impl<I: InstructionSet> Cpu<I> {
+    pub fn new(program: Vec<I>, regs: I::Registers) -> Self {
+        Self {
+            program,
+            regs,
+            pc: 0,
+            halted: false,
+        }
+    }
+}
+
+impl<I: InstructionSet> Machine for Cpu<I> {
+    type Instruction = I;
+
+    fn program(&self) -> &[I] {
+        &self.program
+    }
+
+    fn pc(&self) -> usize {
+        self.pc
+    }
+
+    fn is_halted(&self) -> bool {
+        self.halted || self.pc >= self.program.len()
+    }
+
+    fn step(&mut self) -> bool {
+        if self.is_halted() {
+            return false;
+        }
+
+        match self.program[self.pc].execute(&mut self.regs) {
+            Flow::Next => self.pc += 1,
+            Flow::Jump(offset) => match self.pc.checked_add_signed(offset) {
+                Some(pc) => self.pc = pc,
+                None => self.halted = true,
+            },
+            Flow::Goto(pc) => self.pc = pc,
+            Flow::Halt => self.halted = true,
+        }
+        true
+    }
+
+    fn cycles(&self) -> usize {
+        self.program.get(self.pc).map_or(0, I::cycles)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    // Adds to the first register, jumps if the second register is zero, or slowly copies the first into the second
+    #[derive(Clone, Copy, Debug)]
+    enum Inst {
+        Add(i64),
+        Jz(isize),
+        Copy,
+        Stop,
+    }
+
+    impl InstructionSet for Inst {
+        type Registers = Registers<i64, 2>;
+
+        fn execute(&self, regs: &mut Self::Registers) -> Flow {
+            match *self {
+                Self::Add(n) => regs[0] += n,
+                Self::Jz(offset) if regs[1] == 0 => return Flow::Jump(offset),
+                Self::Jz(_) => (),
+                Self::Copy => regs[1] = regs[0],
+                Self::Stop => return Flow::Halt,
+            }
+            Flow::Next
+        }
+
+        fn cycles(&self) -> usize {
+            match self {
+                Self::Copy => 3,
+                _ => 1,
+            }
+        }
+    }
+
+    fn cpu(program: &[Inst]) -> Cpu<Inst> {
+        Cpu::new(program.to_vec(), Registers::default())
+    }
+
+    #[test]
+    fn test_run() {
+        let mut machine = cpu(&[Inst::Add(3), Inst::Jz(2), Inst::Add(100), Inst::Add(4)]);
+        assert_eq!(machine.run(), Stop::Halted);
+        assert_eq!(machine.regs, Registers([7, 0]));
+        assert_eq!(machine.step(), false);
+
+        let mut machine = cpu(&[Inst::Add(1), Inst::Stop, Inst::Add(1)]);
+        assert_eq!(machine.run(), Stop::Halted);
+        assert_eq!(machine.pc(), 1);
+        assert_eq!(machine.regs[0], 1);
+
+        // Jumping before the start also halts
+        let mut machine = cpu(&[Inst::Jz(-1)]);
+        assert_eq!(machine.run_for(10), Stop::Halted);
+    }
+
+    #[test]
+    fn test_loops() {
+        let program = [Inst::Add(1), Inst::Jz(-1)];
+        let mut machine = cpu(&program);
+        assert_eq!(machine.run_until_loop(), Stop::Loop(0));
+        assert_eq!(machine.regs[0], 1);
+
+        let mut machine = cpu(&program);
+        assert_eq!(machine.run_for(5), Stop::StepLimit);
+        assert_eq!(machine.regs[0], 3);
+
+        let mut machine = cpu(&program);
+        assert_eq!(machine.trace(5), (Stop::StepLimit, vec![0, 1, 0, 1, 0]));
+    }
+
+    #[test]
+    fn test_hooks() {
+        let mut machine = cpu(&[Inst::Add(2), Inst::Copy, Inst::Add(1), Inst::Copy]);
+        let mut seen = Vec::new();
+        machine.run_with_hook(|cycle, m| seen.push((cycle, m.regs[1])));
+        assert_eq!(
+            seen,
+            [
+                (1, 0),
+                (2, 0),
+                (3, 0),
+                (4, 0),
+                (5, 2),
+                (6, 2),
+                (7, 2),
+                (8, 2)
+            ]
+        );
+
+        let mut machine = cpu(&[Inst::Add(1), Inst::Jz(2), Inst::Add(1), Inst::Copy]);
+        assert_eq!(machine.profile(), [1, 1, 0, 1]);
+    }
+}
@@ -0,0 +1,158 @@
+use crate::{Grid, Orientation, Point2, Range2, Transform};
+
+// Patterns are grids where None is a wildcard that matches anything
+impl<T: Clone + PartialEq> Grid<T> {
+    // A copy of the part of the grid covered by range, or None if the range goes outside the grid
+    pub fn window(&self, range: Range2) -> Option<Grid<T>> {
+        let top_left = Point2 {
+            x: range.x.0,
+            y: range.y.0,
+        };
+        let bottom_right = Point2 {
+            x: range.x.1,
+            y: range.y.1,
+        };
+        if range.width() < 1
+            || range.height() < 1
+            || !self.contains(top_left)
+            || !self.contains(bottom_right)
+        {
+            return None;
+        }
+        Some(Grid::from_fn(
+            range.width() as usize,
+            range.height() as usize,
+            |p| self[p + top_left].clone(),
+        ))
+    }
+
+    // Whether the pattern matches with its top left corner at the given point. It has to fit inside the grid.
+    pub fn matches_at(&self, pattern: &Grid<Option<T>>, at: Point2) -> bool {
+        let far_corner = at + (pattern.width() as i32 - 1, pattern.height() as i32 - 1);
+        self.contains(at)
+            && self.contains(far_corner)
+            && pattern.iter().all(|(p, want)| match want {
+                Some(want) => self[at + p] == *want,
+                None => true,
+            })
+    }
+
+    // The top left corner of every match, in reading order. Matches may overlap.
+    pub fn find_pattern(&self, pattern: &Grid<Option<T>>) -> Vec<Point2> {
+        self.points()
+            .filter(|at| self.matches_at(pattern, *at))
+            .collect()
+    }
+
+    // Matches of the pattern turned every way it can be. Orientations which turn the pattern into the same thing as an
+    // earlier one are skipped so that each match is only found once. The points are the top left corners of the
+    // turned pattern.
+    pub fn find_pattern_oriented(&self, pattern: &Grid<Option<T>>) -> Vec<(Orientation, Point2)> {
+        let mut seen: Vec<Grid<Option<T>>> = Vec::new();
+        let mut found = Vec::new();
+        for (orientation, turned) in pattern.orientations() {
+            if seen.contains(&turned) {
+                continue;
+            }
+            found.extend(
+                self.find_pattern(&turned)
+                    .into_iter()
+                    .map(|at| (orientation, at)),
+            );
+            seen.push(turned);
+        }
+        found
+    }
+
+    // Copies everything but the wildcards onto the grid with the stamp's top left corner at the given point. Anything
+    // that would land outside the grid is dropped.
+    pub fn stamp(&mut self, stamp: &Grid<Option<T>>, at: Point2) {
+        for (p, t) in stamp.iter() {
+            if let (Some(t), Some(dest)) = (t, self.get_mut(at + p)) {
+                *dest = t.clone();
+            }
+        }
+    }
+
+    // Stamps the replacement over every match of the pattern, returning where they were. All the matches are found
+    // before anything is replaced.
+    pub fn replace_pattern(
+        &mut self,
+        pattern: &Grid<Option<T>>,
+        replacement: &Grid<Option<T>>,
+    ) -> Vec<Point2> {
+        let found = self.find_pattern(pattern);
+        for at in &found {
+            self.stamp(replacement, *at);
+        }
+        found
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn grid(input: &str) -> Grid<char> {
+        Grid::from_string_with(input, Some)
+    }
+
+    fn pattern(input: &str) -> Grid<Option<char>> {
+        Grid::from_string_with(input, |c| Some((c != '?').then_some(c)))
+    }
+
+    #[test]
+    fn test_window() {
+        let g = grid("abcd\nefgh\nijkl");
+        let range = Range2 {
+            x: (1, 2),
+            y: (1, 2),
+        };
+        assert_eq!(g.window(range), Some(grid("fg\njk")));
+        let range = Range2 {
+            x: (2, 4),
+            y: (0, 0),
+        };
+        assert_eq!(g.window(range), None);
+    }
+
+    #[test]
+    fn test_find_pattern() {
+        let g = grid("abab\nbaba\nabab");
+        assert_eq!(
+            g.find_pattern(&pattern("a?\n?a")),
+            [(0, 0), (2, 0), (1, 1)].map(Point2::from)
+        );
+        assert_eq!(g.find_pattern(&pattern("abab?")), []);
+        assert_eq!(g.find_pattern(&pattern("aba")).len(), 3);
+    }
+
+    #[test]
+    fn test_find_pattern_oriented() {
+        let g = grid("xy..\n....\n..yx\n...y");
+        let found = g.find_pattern_oriented(&pattern("xy"));
+        assert_eq!(
+            found,
+            [
+                (Orientation::new(0, false), (0, 0).into()),
+                (Orientation::new(1, false), (3, 2).into()),
+                (Orientation::new(2, false), (2, 2).into()),
+            ]
+        );
+
+        // Symmetric patterns are only found once however they're turned
+        let g = grid("aa\naa");
+        assert_eq!(g.find_pattern_oriented(&pattern("aa\naa")).len(), 1);
+    }
+
+    #[test]
+    fn test_replace_pattern() {
+        let mut g = grid("aab\nbaa\naaa");
+        let found = g.replace_pattern(&pattern("aa"), &pattern("?X"));
+        assert_eq!(found.len(), 4);
+        assert_eq!(g, grid("aXb\nbaX\naXX"));
+
+        g.stamp(&pattern("ZZ\n?Z"), (2, 1).into());
+        assert_eq!(g, grid("aXb\nbaZ\naXX"));
+    }
+}
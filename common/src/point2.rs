@@ -1,4 +1,4 @@
-use crate::{signed, Cardinal, Range2};
+use crate::{signed, Cardinal, Coord, Range2};
 use auto_ops::{impl_op_ex, impl_op_ex_commutative};
 use nom::{
     character::complete::{char, space0},
@@ -9,15 +9,22 @@ use nom::{
 };
 use std::cmp::Ordering;
 
-#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
-pub struct Point2 {
-    pub x: i32,
-    pub y: i32,
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash)]
+pub struct Point2<T = i32> {
+    pub x: T,
+    pub y: T,
 }
 
-impl Point2 {
-    pub const fn origin() -> Self {
-        Self { x: 0, y: 0 }
+impl<T: Coord> Point2<T> {
+    pub const fn new(x: T, y: T) -> Self {
+        Self { x, y }
+    }
+
+    pub fn zero() -> Self {
+        Self {
+            x: T::zero(),
+            y: T::zero(),
+        }
     }
 
     pub fn parser(input: &str) -> IResult<&str, Self> {
@@ -35,10 +42,8 @@ impl Point2 {
         Ok((input, Self { x, y }))
     }
 
-    pub fn manhattan(a: Self, b: Self) -> u32 {
-        let delta = a - b;
-        let distance = delta.x.abs() + delta.y.abs();
-        distance as u32
+    pub fn manhattan_dist(a: Self, b: Self) -> T::Unsigned {
+        (a.x - b.x).unsigned_abs() + (a.y - b.y).unsigned_abs()
     }
 
     pub fn cmp_xy(a: &Self, b: &Self) -> Ordering {
@@ -59,32 +64,89 @@ impl Point2 {
         }
     }
 
-    pub fn step(&self, direction: Cardinal, count: i32) -> Self {
+    pub fn step(&self, direction: Cardinal, count: T) -> Self {
         match direction {
-            Cardinal::North => *self + (0, -count),
-            Cardinal::South => *self + (0, count),
-            Cardinal::East => *self + (count, 0),
-            Cardinal::West => *self + (-count, 0),
+            Cardinal::North => self.offset(T::zero(), -count),
+            Cardinal::South => self.offset(T::zero(), count),
+            Cardinal::East => self.offset(count, T::zero()),
+            Cardinal::West => self.offset(-count, T::zero()),
         }
     }
 
-    const ORTHOGONALS: [(i32, i32); 4] = [(0, -1), (0, 1), (1, 0), (-1, 0)];
-    pub fn orthogonals(&self) -> impl Iterator<Item = Self> + '_ {
-        Self::ORTHOGONALS.into_iter().map(move |p| self + p)
+    fn offset(&self, dx: T, dy: T) -> Self {
+        Self {
+            x: self.x + dx,
+            y: self.y + dy,
+        }
+    }
+
+    const ORTHOGONALS: [(i8, i8); 4] = [(0, -1), (0, 1), (1, 0), (-1, 0)];
+    pub fn orthogonals(&self) -> impl Iterator<Item = Self> {
+        let origin = *self;
+        Self::ORTHOGONALS
+            .into_iter()
+            .map(move |(dx, dy)| origin.offset(T::from(dx).unwrap(), T::from(dy).unwrap()))
     }
 
-    const DIAGONALS: [(i32, i32); 4] = [(-1, -1), (1, -1), (1, 1), (-1, 1)];
-    pub fn diagonals(&self) -> impl Iterator<Item = Self> + '_ {
-        Self::DIAGONALS.into_iter().map(move |p| self + p)
+    const DIAGONALS: [(i8, i8); 4] = [(-1, -1), (1, -1), (1, 1), (-1, 1)];
+    pub fn diagonals(&self) -> impl Iterator<Item = Self> {
+        let origin = *self;
+        Self::DIAGONALS
+            .into_iter()
+            .map(move |(dx, dy)| origin.offset(T::from(dx).unwrap(), T::from(dy).unwrap()))
     }
 
-    pub fn adjacents(&self) -> impl Iterator<Item = Self> + '_ {
+    pub fn adjacents(&self) -> impl Iterator<Item = Self> {
         self.orthogonals().chain(self.diagonals())
     }
 
-    pub fn get_range<'a, I>(values: I) -> Option<Range2>
+    // None if either coordinate would overflow
+    pub fn checked_add(&self, other: &Self) -> Option<Self> {
+        Some(Self {
+            x: self.x.checked_add(&other.x)?,
+            y: self.y.checked_add(&other.y)?,
+        })
+    }
+
+    pub fn checked_sub(&self, other: &Self) -> Option<Self> {
+        Some(Self {
+            x: self.x.checked_sub(&other.x)?,
+            y: self.y.checked_sub(&other.y)?,
+        })
+    }
+
+    pub fn checked_mul(&self, scale: T) -> Option<Self> {
+        Some(Self {
+            x: self.x.checked_mul(&scale)?,
+            y: self.y.checked_mul(&scale)?,
+        })
+    }
+
+    pub fn wrapping_add(&self, other: &Self) -> Self {
+        Self {
+            x: self.x.wrapping_add(&other.x),
+            y: self.y.wrapping_add(&other.y),
+        }
+    }
+
+    pub fn wrapping_sub(&self, other: &Self) -> Self {
+        Self {
+            x: self.x.wrapping_sub(&other.x),
+            y: self.y.wrapping_sub(&other.y),
+        }
+    }
+
+    pub fn wrapping_mul(&self, scale: T) -> Self {
+        Self {
+            x: self.x.wrapping_mul(&scale),
+            y: self.y.wrapping_mul(&scale),
+        }
+    }
+
+    pub fn bounding_range<'a, I>(values: I) -> Option<Range2<T>>
     where
         I: std::iter::IntoIterator<Item = &'a Self>, // Using IntoIterator instead of Iterator allows the user to pass either an iterator or something that can be turned into one
+        T: 'a,
     {
         let mut point_iter = values.into_iter(); // Note: if 'values' is an Iterator it will just return itself here
         if let Some(point) = point_iter.next() {
@@ -106,56 +168,96 @@ impl Point2 {
     }
 }
 
-impl From<(i32, i32)> for Point2 {
-    fn from(tuple: (i32, i32)) -> Self {
-        Self {
-            x: tuple.0,
-            y: tuple.1,
-        }
+// The default i32 type keeps its concrete signatures so that integer literals still infer it, and so that origin
+// stays usable in const contexts. Other coordinate types use the generic versions above.
+impl Point2 {
+    pub const fn origin() -> Self {
+        Self { x: 0, y: 0 }
     }
-}
 
-impl_op_ex!(+ |a: &Point2, b: &Point2| -> Point2 {
-    Point2 {
-        x: a.x + b.x,
-        y: a.y + b.y,
+    pub fn manhattan(a: Self, b: Self) -> u32 {
+        Self::manhattan_dist(a, b)
     }
-});
 
-impl_op_ex_commutative!(+ |a: &Point2, b: &(i32, i32)| -> Point2 {
-    Point2 {
-        x: a.x + b.0,
-        y: a.y + b.1,
+    pub fn get_range<'a, I>(values: I) -> Option<Range2>
+    where
+        I: std::iter::IntoIterator<Item = &'a Self>,
+    {
+        Self::bounding_range(values)
     }
-});
-
-impl_op_ex!(+= |a: &mut Point2, b: &Point2| { *a = *a + b });
-impl_op_ex!(+= |a: &mut Point2, b: &(i32, i32)| { *a = *a + b });
+}
 
-impl_op_ex!(-|a: &Point2, b: &Point2| -> Point2 {
-    Point2 {
-        x: a.x - b.x,
-        y: a.y - b.y,
+impl<T> From<(T, T)> for Point2<T> {
+    fn from(tuple: (T, T)) -> Self {
+        Self {
+            x: tuple.0,
+            y: tuple.1,
+        }
     }
-});
+}
 
-impl_op_ex!(-|a: &Point2, b: &(i32, i32)| -> Point2 {
-    Point2 {
-        x: a.x - b.0,
-        y: a.y - b.1,
-    }
-});
+// The operators are written out for each coordinate type since a tuple can only be on the left of one for a concrete
+// type
+macro_rules! impl_point2_ops {
+    ($($t:ty),*) => {
+        $(
+            impl_op_ex!(+ |a: &Point2<$t>, b: &Point2<$t>| -> Point2<$t> {
+                Point2 {
+                    x: a.x + b.x,
+                    y: a.y + b.y,
+                }
+            });
+
+            impl_op_ex_commutative!(+ |a: &Point2<$t>, b: &($t, $t)| -> Point2<$t> {
+                Point2 {
+                    x: a.x + b.0,
+                    y: a.y + b.1,
+                }
+            });
+
+            impl_op_ex!(+= |a: &mut Point2<$t>, b: &Point2<$t>| { *a = *a + b });
+            impl_op_ex!(+= |a: &mut Point2<$t>, b: &($t, $t)| { *a = *a + b });
+
+            impl_op_ex!(-|a: &Point2<$t>, b: &Point2<$t>| -> Point2<$t> {
+                Point2 {
+                    x: a.x - b.x,
+                    y: a.y - b.y,
+                }
+            });
+
+            impl_op_ex!(-|a: &Point2<$t>, b: &($t, $t)| -> Point2<$t> {
+                Point2 {
+                    x: a.x - b.0,
+                    y: a.y - b.1,
+                }
+            });
+
+            impl_op_ex!(-= |a: &mut Point2<$t>, b: &Point2<$t>| { *a = *a - b });
+            impl_op_ex!(-= |a: &mut Point2<$t>, b: &($t, $t)| { *a = *a - b });
+
+            impl_op_ex_commutative!(*|a: &Point2<$t>, b: &$t| -> Point2<$t> {
+                Point2 {
+                    x: a.x * b,
+                    y: a.y * b,
+                }
+            });
+
+            impl_op_ex!(*= |a: &mut Point2<$t>, b: &$t| { *a = *a * b });
+
+            impl_op_ex!(-|a: &Point2<$t>| -> Point2<$t> { Point2 { x: -a.x, y: -a.y } });
+        )*
+    };
+}
 
-impl_op_ex!(-= |a: &mut Point2, b: &Point2| { *a = *a - b });
-impl_op_ex!(-= |a: &mut Point2, b: &(i32, i32)| { *a = *a - b });
+impl_point2_ops!(i8, i16, i32, i64, i128, isize);
 
-impl std::fmt::Display for Point2 {
+impl<T: Coord> std::fmt::Display for Point2<T> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "({}, {})", self.x, self.y)
     }
 }
 
-impl std::str::FromStr for Point2 {
+impl<T: Coord> std::str::FromStr for Point2<T> {
     type Err = Error<String>;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         match Point2::parser(s).finish() {
@@ -168,13 +270,13 @@ impl std::str::FromStr for Point2 {
     }
 }
 
-impl PartialOrd for Point2 {
+impl<T: Coord> PartialOrd for Point2<T> {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
         Some(self.cmp(other))
     }
 }
 
-impl Ord for Point2 {
+impl<T: Coord> Ord for Point2<T> {
     fn cmp(&self, other: &Self) -> Ordering {
         Self::cmp_yx(self, other)
     }
@@ -443,4 +545,36 @@ mod test {
             })
         );
     }
+
+    #[test]
+    fn test_wide() {
+        let a: Point2<i64> = Point2::new(10_000_000_000, -3);
+        let b = a * 2 + (1, 1);
+        assert_eq!(b, Point2::new(20_000_000_001, -5));
+        assert_eq!(Point2::manhattan_dist(a, b), 10_000_000_003u64);
+        assert_eq!(-a, Point2::new(-10_000_000_000, 3));
+
+        let range = Point2::bounding_range(&[a, b]).unwrap();
+        assert_eq!(range.width(), 10_000_000_002);
+        assert!(range.contains(Point2::new(15_000_000_000, -4)));
+    }
+
+    #[test]
+    fn test_checked_wrapping() {
+        let a = Point2 { x: i32::MAX, y: 0 };
+        let one = Point2 { x: 1, y: 1 };
+        assert_eq!(a.checked_add(&one), None);
+        assert_eq!(a.wrapping_add(&one), Point2 { x: i32::MIN, y: 1 });
+        assert_eq!(
+            one.checked_sub(&a),
+            Some(Point2 {
+                x: 1 - i32::MAX,
+                y: 1
+            })
+        );
+        assert_eq!(a.checked_mul(2), None);
+        assert_eq!(one.checked_mul(2), Some(Point2 { x: 2, y: 2 }));
+        assert_eq!(a.wrapping_mul(2), Point2 { x: -2, y: 0 });
+        assert_eq!(3 * one, one * 3);
+    }
 }
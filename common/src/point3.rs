@@ -1,4 +1,4 @@
-use crate::{signed, Range3};
+use crate::{signed, Coord, Range3};
 use auto_ops::{impl_op_ex, impl_op_ex_commutative};
 use nom::{
     character::complete::{char, space0},
@@ -9,16 +9,24 @@ use nom::{
 };
 use std::cmp::Ordering;
 
-#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
-pub struct Point3 {
-    pub x: i32,
-    pub y: i32,
-    pub z: i32,
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash)]
+pub struct Point3<T = i32> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
 }
 
-impl Point3 {
-    pub const fn origin() -> Self {
-        Self { x: 0, y: 0, z: 0 }
+impl<T: Coord> Point3<T> {
+    pub const fn new(x: T, y: T, z: T) -> Self {
+        Self { x, y, z }
+    }
+
+    pub fn zero() -> Self {
+        Self {
+            x: T::zero(),
+            y: T::zero(),
+            z: T::zero(),
+        }
     }
 
     pub fn parser(input: &str) -> IResult<&str, Self> {
@@ -42,10 +50,8 @@ impl Point3 {
         Ok((input, Self { x, y, z }))
     }
 
-    pub fn manhattan(a: Self, b: Self) -> u32 {
-        let delta = a - b;
-        let distance = delta.x.abs() + delta.y.abs() + delta.z.abs();
-        distance as u32
+    pub fn manhattan_dist(a: Self, b: Self) -> T::Unsigned {
+        (a.x - b.x).unsigned_abs() + (a.y - b.y).unsigned_abs() + (a.z - b.z).unsigned_abs()
     }
 
     pub fn cmp_xyz(a: &Self, b: &Self) -> Ordering {
@@ -76,9 +82,76 @@ impl Point3 {
         a.x.cmp(&b.x)
     }
 
-    pub fn get_range<'a, I>(values: I) -> Option<Range3>
+    const ORTHOGONALS: [(i8, i8, i8); 6] = [
+        (-1, 0, 0),
+        (1, 0, 0),
+        (0, -1, 0),
+        (0, 1, 0),
+        (0, 0, -1),
+        (0, 0, 1),
+    ];
+    pub fn orthogonals(&self) -> impl Iterator<Item = Self> {
+        let origin = *self;
+        Self::ORTHOGONALS.into_iter().map(move |(dx, dy, dz)| Self {
+            x: origin.x + T::from(dx).unwrap(),
+            y: origin.y + T::from(dy).unwrap(),
+            z: origin.z + T::from(dz).unwrap(),
+        })
+    }
+
+    // None if any coordinate would overflow
+    pub fn checked_add(&self, other: &Self) -> Option<Self> {
+        Some(Self {
+            x: self.x.checked_add(&other.x)?,
+            y: self.y.checked_add(&other.y)?,
+            z: self.z.checked_add(&other.z)?,
+        })
+    }
+
+    pub fn checked_sub(&self, other: &Self) -> Option<Self> {
+        Some(Self {
+            x: self.x.checked_sub(&other.x)?,
+            y: self.y.checked_sub(&other.y)?,
+            z: self.z.checked_sub(&other.z)?,
+        })
+    }
+
+    pub fn checked_mul(&self, scale: T) -> Option<Self> {
+        Some(Self {
+            x: self.x.checked_mul(&scale)?,
+            y: self.y.checked_mul(&scale)?,
+            z: self.z.checked_mul(&scale)?,
+        })
+    }
+
+    pub fn wrapping_add(&self, other: &Self) -> Self {
+        Self {
+            x: self.x.wrapping_add(&other.x),
+            y: self.y.wrapping_add(&other.y),
+            z: self.z.wrapping_add(&other.z),
+        }
+    }
+
+    pub fn wrapping_sub(&self, other: &Self) -> Self {
+        Self {
+            x: self.x.wrapping_sub(&other.x),
+            y: self.y.wrapping_sub(&other.y),
+            z: self.z.wrapping_sub(&other.z),
+        }
+    }
+
+    pub fn wrapping_mul(&self, scale: T) -> Self {
+        Self {
+            x: self.x.wrapping_mul(&scale),
+            y: self.y.wrapping_mul(&scale),
+            z: self.z.wrapping_mul(&scale),
+        }
+    }
+
+    pub fn bounding_range<'a, I>(values: I) -> Option<Range3<T>>
     where
         I: std::iter::IntoIterator<Item = &'a Self>, // Using IntoIterator instead of Iterator allows the user to pass either an iterator or something that can be turned into one
+        T: 'a,
     {
         let mut point_iter = values.into_iter(); // Note: if 'values' is an Iterator it will just return itself here
         if let Some(point) = point_iter.next() {
@@ -102,61 +175,107 @@ impl Point3 {
     }
 }
 
-impl From<(i32, i32, i32)> for Point3 {
-    fn from(tuple: (i32, i32, i32)) -> Self {
-        Self {
-            x: tuple.0,
-            y: tuple.1,
-            z: tuple.2,
-        }
+// The default i32 type keeps its concrete signatures so that integer literals still infer it, and so that origin
+// stays usable in const contexts. Other coordinate types use the generic versions above.
+impl Point3 {
+    pub const fn origin() -> Self {
+        Self { x: 0, y: 0, z: 0 }
     }
-}
 
-impl_op_ex!(+ |a: &Point3, b: &Point3| -> Point3 {
-    Point3 {
-        x: a.x + b.x,
-        y: a.y + b.y,
-        z: a.z + b.z,
+    pub fn manhattan(a: Self, b: Self) -> u32 {
+        Self::manhattan_dist(a, b)
     }
-});
 
-impl_op_ex_commutative!(+ |a: &Point3, b: &(i32, i32, i32)| -> Point3 {
-    Point3 {
-        x: a.x + b.0,
-        y: a.y + b.1,
-        z: a.z + b.2,
+    pub fn get_range<'a, I>(values: I) -> Option<Range3>
+    where
+        I: std::iter::IntoIterator<Item = &'a Self>,
+    {
+        Self::bounding_range(values)
     }
-});
-
-impl_op_ex!(+= |a: &mut Point3, b: &Point3| { *a = *a + b });
-impl_op_ex!(+= |a: &mut Point3, b: &(i32, i32, i32)| { *a = *a + b });
+}
 
-impl_op_ex!(-|a: &Point3, b: &Point3| -> Point3 {
-    Point3 {
-        x: a.x - b.x,
-        y: a.y - b.y,
-        z: a.z - b.z,
+impl<T> From<(T, T, T)> for Point3<T> {
+    fn from(tuple: (T, T, T)) -> Self {
+        Self {
+            x: tuple.0,
+            y: tuple.1,
+            z: tuple.2,
+        }
     }
-});
+}
 
-impl_op_ex!(-|a: &Point3, b: &(i32, i32, i32)| -> Point3 {
-    Point3 {
-        x: a.x - b.0,
-        y: a.y - b.1,
-        z: a.z - b.2,
-    }
-});
+// Written out for each coordinate type, see Point2
+macro_rules! impl_point3_ops {
+    ($($t:ty),*) => {
+        $(
+            impl_op_ex!(+ |a: &Point3<$t>, b: &Point3<$t>| -> Point3<$t> {
+                Point3 {
+                    x: a.x + b.x,
+                    y: a.y + b.y,
+                    z: a.z + b.z,
+                }
+            });
+
+            impl_op_ex_commutative!(+ |a: &Point3<$t>, b: &($t, $t, $t)| -> Point3<$t> {
+                Point3 {
+                    x: a.x + b.0,
+                    y: a.y + b.1,
+                    z: a.z + b.2,
+                }
+            });
+
+            impl_op_ex!(+= |a: &mut Point3<$t>, b: &Point3<$t>| { *a = *a + b });
+            impl_op_ex!(+= |a: &mut Point3<$t>, b: &($t, $t, $t)| { *a = *a + b });
+
+            impl_op_ex!(-|a: &Point3<$t>, b: &Point3<$t>| -> Point3<$t> {
+                Point3 {
+                    x: a.x - b.x,
+                    y: a.y - b.y,
+                    z: a.z - b.z,
+                }
+            });
+
+            impl_op_ex!(-|a: &Point3<$t>, b: &($t, $t, $t)| -> Point3<$t> {
+                Point3 {
+                    x: a.x - b.0,
+                    y: a.y - b.1,
+                    z: a.z - b.2,
+                }
+            });
+
+            impl_op_ex!(-= |a: &mut Point3<$t>, b: &Point3<$t>| { *a = *a - b });
+            impl_op_ex!(-= |a: &mut Point3<$t>, b: &($t, $t, $t)| { *a = *a - b });
+
+            impl_op_ex_commutative!(*|a: &Point3<$t>, b: &$t| -> Point3<$t> {
+                Point3 {
+                    x: a.x * b,
+                    y: a.y * b,
+                    z: a.z * b,
+                }
+            });
+
+            impl_op_ex!(*= |a: &mut Point3<$t>, b: &$t| { *a = *a * b });
+
+            impl_op_ex!(-|a: &Point3<$t>| -> Point3<$t> {
+                Point3 {
+                    x: -a.x,
+                    y: -a.y,
+                    z: -a.z,
+                }
+            });
+        )*
+    };
+}
 
-impl_op_ex!(-= |a: &mut Point3, b: &Point3| { *a = *a - b });
-impl_op_ex!(-= |a: &mut Point3, b: &(i32, i32, i32)| { *a = *a - b });
+impl_point3_ops!(i8, i16, i32, i64, i128, isize);
 
-impl std::fmt::Display for Point3 {
+impl<T: Coord> std::fmt::Display for Point3<T> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "({}, {}, {})", self.x, self.y, self.z)
     }
 }
 
-impl std::str::FromStr for Point3 {
+impl<T: Coord> std::str::FromStr for Point3<T> {
     type Err = Error<String>;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         match Point3::parser(s).finish() {
@@ -169,13 +288,13 @@ impl std::str::FromStr for Point3 {
     }
 }
 
-impl PartialOrd for Point3 {
+impl<T: Coord> PartialOrd for Point3<T> {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
         Some(self.cmp(other))
     }
 }
 
-impl Ord for Point3 {
+impl<T: Coord> Ord for Point3<T> {
     fn cmp(&self, other: &Self) -> Ordering {
         Self::cmp_zyx(self, other)
     }
@@ -0,0 +1,304 @@
+use crate::{signed, Point2, Point3, RangeN};
+use nom::{
+    character::complete::{char, space0},
+    combinator::{cond, opt, verify},
+    error::Error,
+    multi::separated_list1,
+    sequence::{delimited, pair, tuple},
+    Finish, IResult,
+};
+use std::ops::{Add, AddAssign, Index, IndexMut, Mul, Neg, Sub, SubAssign};
+
+// A point with any number of dimensions, for when Point2 and Point3 aren't enough or code should work the same no matter
+// how many there are. Orders by the first coordinate, then the second and so on.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct PointN<const D: usize>(pub [i32; D]);
+
+impl<const D: usize> PointN<D> {
+    pub const fn origin() -> Self {
+        Self([0; D])
+    }
+
+    // The x and y of a Point2 with every other coordinate 0, e.g. for a slice through a higher dimensional space
+    pub fn from_point2(p: Point2) -> Self {
+        assert!(D >= 2, "Not enough dimensions for a Point2");
+        let mut coords = [0; D];
+        coords[0] = p.x;
+        coords[1] = p.y;
+        Self(coords)
+    }
+
+    pub fn parser(input: &str) -> IResult<&str, Self> {
+        // Accepts the following for any number of coordinates, with whitespace allowed anywhere:
+        //  1,2,3,4
+        //  (1,2,3,4)
+        let (input, _) = space0(input)?;
+        let (input, open_paren) = opt(char('('))(input)?;
+        let (input, coords) = verify(
+            separated_list1(
+                tuple((space0, char(','), space0)),
+                delimited(space0, signed, space0),
+            ),
+            |coords: &Vec<i32>| coords.len() == D,
+        )(input)?;
+        let (input, _) = cond(open_paren.is_some(), pair(space0, char(')')))(input)?;
+
+        let mut point = Self::origin();
+        point.0.copy_from_slice(&coords);
+        Ok((input, point))
+    }
+
+    pub fn manhattan(a: Self, b: Self) -> u32 {
+        a.0.iter().zip(b.0).map(|(a, b)| a.abs_diff(b)).sum()
+    }
+
+    // The number of king's moves apart
+    pub fn chebyshev(a: Self, b: Self) -> u32 {
+        a.0.iter()
+            .zip(b.0)
+            .map(|(a, b)| a.abs_diff(b))
+            .max()
+            .unwrap_or(0)
+    }
+
+    // One step along a single axis, 2 * D of them
+    pub fn orthogonals(&self) -> impl Iterator<Item = Self> {
+        let origin = *self;
+        (0..D).flat_map(move |axis| {
+            [-1, 1].into_iter().map(move |delta| {
+                let mut p = origin;
+                p.0[axis] += delta;
+                p
+            })
+        })
+    }
+
+    // Every point touching this one, diagonals included, 3^D - 1 of them
+    pub fn adjacents(&self) -> impl Iterator<Item = Self> {
+        let origin = *self;
+        (0..3usize.pow(D as u32))
+            .map(move |mut n| {
+                let mut p = origin;
+                for coord in p.0.iter_mut() {
+                    *coord += (n % 3) as i32 - 1;
+                    n /= 3;
+                }
+                p
+            })
+            .filter(move |p| *p != origin)
+    }
+
+    pub fn get_range<'a, I>(values: I) -> Option<RangeN<D>>
+    where
+        I: std::iter::IntoIterator<Item = &'a Self>,
+    {
+        let mut point_iter = values.into_iter();
+        let first = point_iter.next()?;
+        let mut range = RangeN(first.0.map(|c| (c, c)));
+        for p in point_iter {
+            range.expand(p);
+        }
+        Some(range)
+    }
+}
+
+impl<const D: usize> From<[i32; D]> for PointN<D> {
+    fn from(coords: [i32; D]) -> Self {
+        Self(coords)
+    }
+}
+
+impl From<Point2> for PointN<2> {
+    fn from(p: Point2) -> Self {
+        Self([p.x, p.y])
+    }
+}
+
+impl From<PointN<2>> for Point2 {
+    fn from(p: PointN<2>) -> Self {
+        Self { x: p[0], y: p[1] }
+    }
+}
+
+impl From<Point3> for PointN<3> {
+    fn from(p: Point3) -> Self {
+        Self([p.x, p.y, p.z])
+    }
+}
+
+impl From<PointN<3>> for Point3 {
+    fn from(p: PointN<3>) -> Self {
+        Self {
+            x: p[0],
+            y: p[1],
+            z: p[2],
+        }
+    }
+}
+
+impl<const D: usize> Index<usize> for PointN<D> {
+    type Output = i32;
+    fn index(&self, axis: usize) -> &i32 {
+        &self.0[axis]
+    }
+}
+
+impl<const D: usize> IndexMut<usize> for PointN<D> {
+    fn index_mut(&mut self, axis: usize) -> &mut i32 {
+        &mut self.0[axis]
+    }
+}
+
+impl<const D: usize> Add for PointN<D> {
+    type Output = Self;
+    fn add(mut self, other: Self) -> Self {
+        self += other;
+        self
+    }
+}
+
+impl<const D: usize> Add<[i32; D]> for PointN<D> {
+    type Output = Self;
+    fn add(self, other: [i32; D]) -> Self {
+        self + Self(other)
+    }
+}
+
+impl<const D: usize> AddAssign for PointN<D> {
+    fn add_assign(&mut self, other: Self) {
+        for (a, b) in self.0.iter_mut().zip(other.0) {
+            *a += b;
+        }
+    }
+}
+
+impl<const D: usize> Sub for PointN<D> {
+    type Output = Self;
+    fn sub(mut self, other: Self) -> Self {
+        self -= other;
+        self
+    }
+}
+
+impl<const D: usize> Sub<[i32; D]> for PointN<D> {
+    type Output = Self;
+    fn sub(self, other: [i32; D]) -> Self {
+        self - Self(other)
+    }
+}
+
+impl<const D: usize> SubAssign for PointN<D> {
+    fn sub_assign(&mut self, other: Self) {
+        for (a, b) in self.0.iter_mut().zip(other.0) {
+            *a -= b;
+        }
+    }
+}
+
+impl<const D: usize> Neg for PointN<D> {
+    type Output = Self;
+    fn neg(self) -> Self {
+        Self(self.0.map(|c| -c))
+    }
+}
+
+impl<const D: usize> Mul<i32> for PointN<D> {
+    type Output = Self;
+    fn mul(self, scale: i32) -> Self {
+        Self(self.0.map(|c| c * scale))
+    }
+}
+
+impl<const D: usize> std::fmt::Display for PointN<D> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let coords: Vec<String> = self.0.iter().map(|c| c.to_string()).collect();
+        write!(f, "({})", coords.join(", "))
+    }
+}
+
+impl<const D: usize> std::str::FromStr for PointN<D> {
+    type Err = Error<String>;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match Self::parser(s).finish() {
+            Ok((_remaining, point)) => Ok(point),
+            Err(Error { input, code }) => Err(Error {
+                input: input.to_string(),
+                code,
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_from_string() {
+        assert_eq!("1,-2,3,4".parse(), Ok(PointN([1, -2, 3, 4])));
+        assert_eq!(" ( 1 , -2,3, 4 ) ".parse(), Ok(PointN([1, -2, 3, 4])));
+        assert_eq!("7".parse(), Ok(PointN([7])));
+        assert!("1,2,3".parse::<PointN<4>>().is_err());
+        assert!("1,2,3,4,5".parse::<PointN<4>>().is_err());
+        assert!("(1,2".parse::<PointN<2>>().is_err());
+    }
+
+    #[test]
+    fn test_distances() {
+        let a = PointN([0, 0, 0, 0]);
+        let b = PointN([3, -4, 0, 1]);
+        assert_eq!(PointN::manhattan(a, b), 8);
+        assert_eq!(PointN::chebyshev(a, b), 4);
+        assert_eq!(PointN::manhattan(b, b), 0);
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        let mut p = PointN([1, 2, 3]);
+        assert_eq!(p + PointN([1, 1, 1]), PointN([2, 3, 4]));
+        assert_eq!(p - [1, 2, 3], PointN::origin());
+        assert_eq!(-p * 2, PointN([-2, -4, -6]));
+        p += PointN([0, 0, 1]);
+        p -= PointN([1, 0, 0]);
+        p[1] = 5;
+        assert_eq!(p, PointN([0, 5, 4]));
+    }
+
+    #[test]
+    fn test_neighbors() {
+        let p = PointN([1, 1, 1, 1]);
+        assert_eq!(p.orthogonals().count(), 8);
+        assert!(p.orthogonals().all(|n| PointN::manhattan(p, n) == 1));
+        assert_eq!(p.adjacents().count(), 80);
+        assert!(p.adjacents().all(|n| PointN::chebyshev(p, n) == 1));
+
+        // The same neighbors as the fixed dimension points
+        let p2 = Point2 { x: 3, y: -1 };
+        let mut a: Vec<Point2> = PointN::from(p2).adjacents().map(Point2::from).collect();
+        let mut b: Vec<Point2> = p2.adjacents().collect();
+        a.sort();
+        b.sort();
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    fn test_conversions() {
+        let p3 = Point3 { x: 1, y: 2, z: 3 };
+        assert_eq!(Point3::from(PointN::from(p3)), p3);
+        assert_eq!(
+            PointN::<4>::from_point2(Point2 { x: 5, y: 6 }),
+            PointN([5, 6, 0, 0])
+        );
+        assert_eq!(PointN([1, -2]).to_string(), "(1, -2)");
+    }
+
+    #[test]
+    fn test_get_range() {
+        let points = [PointN([1, 5, -3]), PointN([-2, 0, 4]), PointN([0, 7, 0])];
+        assert_eq!(
+            PointN::get_range(&points),
+            Some(RangeN([(-2, 1), (0, 7), (-3, 4)]))
+        );
+        assert_eq!(PointN::<3>::get_range(&[]), None);
+    }
+}
@@ -1,34 +1,56 @@
-use std::cmp::{max, min};
+use crate::{Coord, Point2};
 
-use crate::Point2;
-
-#[derive(Clone, Copy, Debug, Eq, PartialEq)]
-pub struct Range2 {
-    pub x: (i32, i32),
-    pub y: (i32, i32),
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct Range2<T = i32> {
+    pub x: (T, T),
+    pub y: (T, T),
 }
 
-impl Range2 {
-    pub const fn origin() -> Self {
+impl<T: Coord> Range2<T> {
+    pub fn origin() -> Self {
         Self {
-            x: (0, 0),
-            y: (0, 0),
+            x: (T::zero(), T::zero()),
+            y: (T::zero(), T::zero()),
         }
     }
 
-    pub fn contains(&self, p: Point2) -> bool {
+    pub fn contains(&self, p: Point2<T>) -> bool {
         self.x.0 <= p.x && p.x <= self.x.1 && self.y.0 <= p.y && p.y <= self.y.1
     }
 
-    pub fn expand(&mut self, p: &Point2) {
-        self.x.0 = min(self.x.0, p.x);
-        self.x.1 = max(self.x.1, p.x);
-        self.y.0 = min(self.y.0, p.y);
-        self.y.1 = max(self.y.1, p.y);
+    pub fn expand(&mut self, p: &Point2<T>) {
+        self.x.0 = self.x.0.min(p.x);
+        self.x.1 = self.x.1.max(p.x);
+        self.y.0 = self.y.0.min(p.y);
+        self.y.1 = self.y.1.max(p.y);
+    }
+
+    pub fn width(&self) -> T {
+        self.x.1 - self.x.0 + T::one()
+    }
+
+    pub fn height(&self) -> T {
+        self.y.1 - self.y.0 + T::one()
+    }
+
+    // Brings p back inside as if the range were repeated endlessly in every direction
+    pub fn wrap(&self, p: Point2<T>) -> Point2<T> {
+        Point2 {
+            x: self.x.0 + (p.x - self.x.0).mod_floor(&self.width()),
+            y: self.y.0 + (p.y - self.y.0).mod_floor(&self.height()),
+        }
+    }
+
+    // Which repeat of the range p falls in, with the range itself being (0, 0)
+    pub fn copy_of(&self, p: Point2<T>) -> Point2<T> {
+        Point2 {
+            x: (p.x - self.x.0).div_floor(&self.width()),
+            y: (p.y - self.y.0).div_floor(&self.height()),
+        }
     }
 }
 
-impl std::fmt::Display for Range2 {
+impl<T: Coord> std::fmt::Display for Range2<T> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(
             f,
@@ -80,4 +102,22 @@ mod test {
         };
         assert_eq!(r.contains(Point2 { x: -100, y: 5000 }), false);
     }
+
+    #[test]
+    fn test_wrap() {
+        let r = Range2 {
+            x: (1, 3),
+            y: (-2, 2),
+        };
+        assert_eq!(r.width(), 3);
+        assert_eq!(r.height(), 5);
+        assert_eq!(r.wrap(Point2 { x: 2, y: 0 }), Point2 { x: 2, y: 0 });
+        assert_eq!(r.wrap(Point2 { x: 4, y: 3 }), Point2 { x: 1, y: -2 });
+        assert_eq!(r.wrap(Point2 { x: 0, y: -3 }), Point2 { x: 3, y: 2 });
+        assert_eq!(r.wrap(Point2 { x: -8, y: 13 }), Point2 { x: 1, y: -2 });
+        assert_eq!(r.copy_of(Point2 { x: 2, y: 0 }), Point2 { x: 0, y: 0 });
+        assert_eq!(r.copy_of(Point2 { x: 4, y: 3 }), Point2 { x: 1, y: 1 });
+        assert_eq!(r.copy_of(Point2 { x: 0, y: -3 }), Point2 { x: -1, y: -1 });
+        assert_eq!(r.copy_of(Point2 { x: -8, y: 13 }), Point2 { x: -3, y: 3 });
+    }
 }
@@ -1,14 +1,15 @@
-use crate::Point3;
+use crate::{Coord, Point3};
+use num::ToPrimitive;
 
-#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
-pub struct Range3 {
-    pub x: (i32, i32),
-    pub y: (i32, i32),
-    pub z: (i32, i32),
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct Range3<T = i32> {
+    pub x: (T, T),
+    pub y: (T, T),
+    pub z: (T, T),
 }
 
-impl Range3 {
-    pub fn contains(&self, p: Point3) -> bool {
+impl<T: Coord> Range3<T> {
+    pub fn contains(&self, p: Point3<T>) -> bool {
         self.x.0 <= p.x
             && p.x <= self.x.1
             && self.y.0 <= p.y
@@ -17,7 +18,7 @@ impl Range3 {
             && p.z <= self.z.1
     }
 
-    pub fn contains_exclusive(&self, p: &Point3) -> bool {
+    pub fn contains_exclusive(&self, p: &Point3<T>) -> bool {
         self.x.0 <= p.x
             && p.x < self.x.1
             && self.y.0 <= p.y
@@ -27,10 +28,8 @@ impl Range3 {
     }
 
     pub fn volume_exclusive(&self) -> u64 {
-        let dx = (self.x.1 - self.x.0).unsigned_abs() as u64;
-        let dy = (self.y.1 - self.y.0).unsigned_abs() as u64;
-        let dz = (self.z.1 - self.z.0).unsigned_abs() as u64;
-        dx * dy * dz
+        let size = |(lo, hi): (T, T)| (hi - lo).unsigned_abs().to_u64().unwrap();
+        size(self.x) * size(self.y) * size(self.z)
     }
 }
 
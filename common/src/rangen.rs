@@ -0,0 +1,82 @@
+use crate::PointN;
+
+// An inclusive range along each axis, for PointN
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct RangeN<const D: usize>(pub [(i32, i32); D]);
+
+impl<const D: usize> RangeN<D> {
+    pub fn contains(&self, p: &PointN<D>) -> bool {
+        self.0
+            .iter()
+            .zip(p.0)
+            .all(|((lo, hi), c)| *lo <= c && c <= *hi)
+    }
+
+    pub fn expand(&mut self, p: &PointN<D>) {
+        for ((lo, hi), c) in self.0.iter_mut().zip(p.0) {
+            *lo = (*lo).min(c);
+            *hi = (*hi).max(c);
+        }
+    }
+
+    // How many points fit along the axis
+    pub fn size(&self, axis: usize) -> i32 {
+        self.0[axis].1 - self.0[axis].0 + 1
+    }
+
+    // How many points are inside
+    pub fn volume(&self) -> u64 {
+        (0..D).map(|axis| self.size(axis).max(0) as u64).product()
+    }
+
+    // Every point inside, with the first axis changing fastest
+    pub fn points(&self) -> impl Iterator<Item = PointN<D>> + '_ {
+        (0..self.volume()).map(|mut n| {
+            let mut p = PointN(self.0.map(|(lo, _hi)| lo));
+            for axis in 0..D {
+                let size = self.size(axis) as u64;
+                p[axis] += (n % size) as i32;
+                n /= size;
+            }
+            p
+        })
+    }
+}
+
+impl<const D: usize> std::fmt::Display for RangeN<D> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let axes: Vec<String> = self
+            .0
+            .iter()
+            .map(|(lo, hi)| format!("{}..={}", lo, hi))
+            .collect();
+        write!(f, "({})", axes.join(", "))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_contains() {
+        let r = RangeN([(0, 5), (-2, 2), (3, 3)]);
+        assert!(r.contains(&PointN([0, 0, 3])));
+        assert!(r.contains(&PointN([5, -2, 3])));
+        assert_eq!(r.contains(&PointN([6, 0, 3])), false);
+        assert_eq!(r.contains(&PointN([0, 0, 4])), false);
+    }
+
+    #[test]
+    fn test_points() {
+        let r = RangeN([(0, 1), (5, 7)]);
+        assert_eq!(r.volume(), 6);
+        let points: Vec<PointN<2>> = r.points().collect();
+        assert_eq!(points.len(), 6);
+        assert_eq!(points[0], PointN([0, 5]));
+        assert_eq!(points[1], PointN([1, 5]));
+        assert_eq!(points[5], PointN([1, 7]));
+        assert!(points.iter().all(|p| r.contains(p)));
+        assert_eq!(r.to_string(), "(0..=1, 5..=7)");
+    }
+}
@@ -0,0 +1,285 @@
+use crate::{Point2, Point3, Range2, Range3};
+use std::collections::HashSet;
+use std::hash::Hash;
+
+// A point which regions can be built from, connected to its neighbors across each edge or face
+pub trait Cell: Copy + Eq + Hash {
+    type Range;
+
+    fn neighbors(&self) -> impl Iterator<Item = Self>;
+
+    fn range<'a>(points: impl IntoIterator<Item = &'a Self>) -> Option<Self::Range>
+    where
+        Self: 'a;
+
+    // Whether the point lies on the outermost layer of the range
+    fn on_boundary(&self, range: &Self::Range) -> bool;
+}
+
+impl Cell for Point2 {
+    type Range = Range2;
+
+    fn neighbors(&self) -> impl Iterator<Item = Self> {
+        self.orthogonals()
+    }
+
+    fn range<'a>(points: impl IntoIterator<Item = &'a Self>) -> Option<Range2> {
+        Point2::get_range(points)
+    }
+
+    fn on_boundary(&self, range: &Range2) -> bool {
+        self.x == range.x.0 || self.x == range.x.1 || self.y == range.y.0 || self.y == range.y.1
+    }
+}
+
+impl Cell for Point3 {
+    type Range = Range3;
+
+    fn neighbors(&self) -> impl Iterator<Item = Self> {
+        self.orthogonals()
+    }
+
+    fn range<'a>(points: impl IntoIterator<Item = &'a Self>) -> Option<Range3> {
+        Point3::get_range(points)
+    }
+
+    fn on_boundary(&self, range: &Range3) -> bool {
+        self.x == range.x.0
+            || self.x == range.x.1
+            || self.y == range.y.0
+            || self.y == range.y.1
+            || self.z == range.z.0
+            || self.z == range.z.1
+    }
+}
+
+// A set of points, usually but not necessarily all connected
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Region<P: Cell> {
+    points: HashSet<P>,
+}
+
+impl<P: Cell> Region<P> {
+    pub fn new(points: impl IntoIterator<Item = P>) -> Self {
+        Self {
+            points: points.into_iter().collect(),
+        }
+    }
+
+    pub fn points(&self) -> &HashSet<P> {
+        &self.points
+    }
+
+    pub fn contains(&self, p: &P) -> bool {
+        self.points.contains(p)
+    }
+
+    // The number of points, which is the volume for Point3
+    pub fn area(&self) -> usize {
+        self.points.len()
+    }
+
+    // The number of edges between a point inside and a point outside, which is the surface area for Point3
+    pub fn perimeter(&self) -> usize {
+        self.points
+            .iter()
+            .map(|p| p.neighbors().filter(|n| !self.contains(n)).count())
+            .sum()
+    }
+
+    pub fn range(&self) -> Option<P::Range> {
+        P::range(&self.points)
+    }
+
+    // Whether any of the region is on the edge of the range, e.g. to tell enclosed pockets from the outside
+    pub fn touches_boundary(&self, range: &P::Range) -> bool {
+        self.points.iter().any(|p| p.on_boundary(range))
+    }
+}
+
+impl Region<Point2> {
+    // The number of straight sides, which is the same as the number of corners. Holes count too.
+    pub fn sides(&self) -> usize {
+        let mut corners = 0;
+        for p in &self.points {
+            for (dx, dy) in [(-1, -1), (1, -1), (1, 1), (-1, 1)] {
+                let beside = self.contains(&(p + (dx, 0)));
+                let above_below = self.contains(&(p + (0, dy)));
+                let diagonal = self.contains(&(p + (dx, dy)));
+                let outer = !beside && !above_below;
+                let inner = beside && above_below && !diagonal;
+                if outer || inner {
+                    corners += 1;
+                }
+            }
+        }
+        corners
+    }
+}
+
+// Every point reachable from the starts by stepping to neighbors that pass the test. The starts are always included.
+pub fn flood_fill<P: Cell>(
+    starts: impl IntoIterator<Item = P>,
+    mut passable: impl FnMut(P) -> bool,
+) -> Region<P> {
+    let mut points: HashSet<P> = HashSet::new();
+    let mut frontier = Vec::new();
+    for start in starts {
+        if points.insert(start) {
+            frontier.push(start);
+        }
+    }
+    while let Some(curr) = frontier.pop() {
+        for next in curr.neighbors() {
+            if !points.contains(&next) && passable(next) {
+                points.insert(next);
+                frontier.push(next);
+            }
+        }
+    }
+    Region { points }
+}
+
+// Splits points up into connected regions, where same decides whether two neighboring points go together. The regions
+// come out in the order of their first point.
+pub fn components<P: Cell>(
+    points: impl IntoIterator<Item = P>,
+    mut same: impl FnMut(P, P) -> bool,
+) -> Vec<Region<P>> {
+    let points: Vec<P> = points.into_iter().collect();
+    let all: HashSet<P> = points.iter().copied().collect();
+    let mut seen: HashSet<P> = HashSet::new();
+    let mut regions = Vec::new();
+    for start in points {
+        if seen.contains(&start) {
+            continue;
+        }
+        let mut region = HashSet::from([start]);
+        let mut frontier = vec![start];
+        while let Some(curr) = frontier.pop() {
+            for next in curr.neighbors() {
+                if all.contains(&next) && !region.contains(&next) && same(curr, next) {
+                    region.insert(next);
+                    frontier.push(next);
+                }
+            }
+        }
+        seen.extend(region.iter().copied());
+        regions.push(Region { points: region });
+    }
+    regions
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn points(input: &str, c: char) -> Vec<Point2> {
+        let mut points = Vec::new();
+        for (y, line) in input.lines().enumerate() {
+            for (x, tile) in line.chars().enumerate() {
+                if tile == c {
+                    points.push((x as i32, y as i32).into());
+                }
+            }
+        }
+        points
+    }
+
+    #[test]
+    fn test_flood_fill() {
+        let input = "\
+#####
+#..##
+#.#.#
+#####";
+        let open: HashSet<Point2> = points(input, '.').into_iter().collect();
+        let region = flood_fill([Point2 { x: 1, y: 1 }], |p| open.contains(&p));
+        assert_eq!(region.area(), 3);
+        assert_eq!(region.contains(&(3, 2).into()), false);
+        assert_eq!(
+            region.range(),
+            Some(Range2 {
+                x: (1, 2),
+                y: (1, 2)
+            })
+        );
+    }
+
+    #[test]
+    fn test_components() {
+        let input = "\
+AAAA
+BBCD
+BBCC
+EEEC";
+        let all = points(input, 'A')
+            .into_iter()
+            .chain(points(input, 'B'))
+            .chain(points(input, 'C'))
+            .chain(points(input, 'D'))
+            .chain(points(input, 'E'));
+        let plots: Vec<char> = input.chars().filter(|c| *c != '\n').collect();
+        let plot = |p: Point2| plots[(p.y * 4 + p.x) as usize];
+        let regions = components(all, |a, b| plot(a) == plot(b));
+
+        let metrics: Vec<(usize, usize, usize)> = regions
+            .iter()
+            .map(|r| (r.area(), r.perimeter(), r.sides()))
+            .collect();
+        assert_eq!(
+            metrics,
+            [(4, 10, 4), (4, 8, 4), (4, 10, 8), (1, 4, 4), (3, 8, 4)]
+        );
+
+        let range = Range2 {
+            x: (0, 3),
+            y: (0, 3),
+        };
+        assert!(regions.iter().all(|r| r.touches_boundary(&range)));
+    }
+
+    #[test]
+    fn test_sides_with_hole() {
+        let input = "\
+OOOOO
+OXOXO
+OOOOO
+OXOXO
+OOOOO";
+        let regions = components(points(input, 'O'), |_, _| true);
+        assert_eq!(regions.len(), 1);
+        assert_eq!(regions[0].area(), 21);
+        assert_eq!(regions[0].perimeter(), 36);
+        assert_eq!(regions[0].sides(), 20);
+
+        let holes = components(points(input, 'X'), |_, _| true);
+        let range = Region::new(points(input, 'O')).range().unwrap();
+        assert_eq!(holes.len(), 4);
+        assert!(holes.iter().all(|h| !h.touches_boundary(&range)));
+    }
+
+    #[test]
+    fn test_point3() {
+        // A hollow 3x3x3 cube
+        let mut cube = Vec::new();
+        for x in 0..3 {
+            for y in 0..3 {
+                for z in 0..3 {
+                    if (x, y, z) != (1, 1, 1) {
+                        cube.push(Point3 { x, y, z });
+                    }
+                }
+            }
+        }
+        let region = Region::new(cube.clone());
+        assert_eq!(region.area(), 26);
+        assert_eq!(region.perimeter(), 54 + 6);
+        assert_eq!(components(cube, |_, _| true).len(), 1);
+
+        let range = region.range().unwrap();
+        let inside = flood_fill([Point3 { x: 1, y: 1, z: 1 }], |p| !region.contains(&p));
+        assert_eq!(inside.area(), 1);
+        assert!(!inside.touches_boundary(&range));
+    }
+}
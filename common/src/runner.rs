@@ -0,0 +1,217 @@
+use crate::answers::{Answers, Verdict};
+pub use aoc_runner::{ArcStr, Runner};
+use std::error::Error;
+use std::fs;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::Path;
+use std::process;
+use std::time::{Duration, Instant};
+
+type Factory = fn(ArcStr) -> Result<Box<dyn Runner>, Box<dyn Error>>;
+
+// One generator and solver pair, as registered by #[aoc(...)]
+pub struct Solution {
+    pub day: u32,
+    pub part: u32,
+    factory: Factory,
+}
+
+impl Solution {
+    // The name is the one aoc-runner gives the factory method, e.g. day6_part1
+    pub fn new(name: &str, factory: Factory) -> Self {
+        let parse = || {
+            let (day, part) = name.strip_prefix("day")?.split_once("_part")?;
+            Some((day.parse().ok()?, part.parse().ok()?))
+        };
+        let (day, part) = parse().unwrap_or_else(|| panic!("Invalid solution name: {}", name));
+        Self { day, part, factory }
+    }
+
+    fn run(&self, input: &str) -> Result<(String, Duration, Duration), String> {
+        let result = panic::catch_unwind(AssertUnwindSafe(|| {
+            let start = Instant::now();
+            let runner = (self.factory)(ArcStr::from(input))
+                .map_err(|e| format!("FAILED while generating: {:?}", e))?;
+            let generated = Instant::now();
+            let answer = runner
+                .try_run()
+                .map_err(|e| format!("FAILED while running: {:?}", e))?;
+            Ok((answer.to_string(), generated - start, generated.elapsed()))
+        }));
+        result.unwrap_or_else(|_| Err("PANICKED".to_owned()))
+    }
+}
+
+#[derive(Debug, Default)]
+struct Options {
+    verify: bool,
+    day: Option<u32>,
+    part: Option<u32>,
+}
+
+impl Options {
+    fn from_args() -> Self {
+        let mut options = Self::default();
+        let mut args = std::env::args().skip(1);
+        while let Some(arg) = args.next() {
+            let mut value = || args.next().and_then(|v| v.parse().ok());
+            match arg.as_str() {
+                "--verify" => options.verify = true,
+                "-d" | "--day" => options.day = Some(value().expect("Expected a day number")),
+                "-p" | "--part" => options.part = Some(value().expect("Expected a part number")),
+                _ => {
+                    eprintln!("Usage: [--verify] [--day D] [--part P]");
+                    process::exit(2);
+                }
+            }
+        }
+        options
+    }
+}
+
+// Runs every solution with the inputs in <dir>/input/<year>/. With --verify the answers are checked against
+// <dir>/answers.txt instead of being timed, and a solution which fails doesn't stop the others.
+pub fn run(year: u32, dir: &str, solutions: &[Solution]) {
+    let options = Options::from_args();
+    let dir = Path::new(dir);
+    let answers = Answers::load(&dir.join("answers.txt"));
+    let (mut matched, mut mismatched, mut unknown, mut failed) = (0, 0, 0, 0);
+
+    println!("Advent of code {}", year);
+    for solution in solutions {
+        if options.day.is_some_and(|day| day != solution.day)
+            || options.part.is_some_and(|part| part != solution.part)
+        {
+            continue;
+        }
+
+        let name = format!("Day {} - Part {}", solution.day, solution.part);
+        let path = dir.join(format!("input/{}/day{}.txt", year, solution.day));
+        let Ok(input) = fs::read_to_string(&path) else {
+            println!("{}: no input at {}\n", name, path.display());
+            continue;
+        };
+
+        let (answer, generator, runner) = match solution.run(&input) {
+            Ok(result) => result,
+            Err(e) => {
+                eprintln!("{}: {}\n", name, e);
+                failed += 1;
+                continue;
+            }
+        };
+        if !options.verify {
+            println!(
+                "{}: {}\n\tgenerator: {:?},\n\trunner: {:?}\n",
+                name, answer, generator, runner
+            );
+            continue;
+        }
+
+        match answers.verify(solution.day, solution.part, &input, &answer) {
+            Verdict::Match => {
+                println!("{}: {} (match)", name, answer);
+                matched += 1;
+            }
+            Verdict::Mismatch(expected) => {
+                println!("{}: {} (MISMATCH, expected {})", name, answer, expected);
+                mismatched += 1;
+            }
+            Verdict::Unknown => {
+                println!("{}: {} (unknown)", name, answer);
+                println!(
+                    "\tadd to answers.txt: {}",
+                    Answers::line(solution.day, solution.part, &input, &answer)
+                );
+                unknown += 1;
+            }
+        }
+    }
+
+    if options.verify {
+        println!(
+            "\n{} matched, {} mismatched, {} unknown, {} failed",
+            matched, mismatched, unknown, failed
+        );
+        if mismatched > 0 || failed > 0 {
+            process::exit(1);
+        }
+    }
+}
+
+// Replacement for aoc-runner's aoc_main! which adds --verify. Every solution needs listing by its factory method name:
+//
+//   common::aoc_main! { lib = advent_of_code_2024; day1_part1, day1_part2, ... }
+#[macro_export]
+macro_rules! aoc_main {
+    (lib = $lib:ident; $($solution:ident),* $(,)?) => {
+        use $lib::*;
+
+        fn main() {
+            $crate::runner::run(
+                YEAR,
+                env!("CARGO_MANIFEST_DIR"),
+                &[$($crate::runner::Solution::new(stringify!($solution), Factory::$solution)),*],
+            );
+        }
+    };
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use std::borrow::Borrow;
+    use std::fmt::Display;
+
+    // Answers with the length of its input
+    struct Length(usize);
+
+    impl Runner for Length {
+        fn gen(input: ArcStr) -> Self {
+            let input: &str = input.borrow();
+            Self(input.len())
+        }
+
+        fn run(&self) -> Box<dyn Display> {
+            Box::new(self.0)
+        }
+
+        fn bench(&self, _: fn(&dyn Display)) {}
+    }
+
+    fn factory(input: ArcStr) -> Result<Box<dyn Runner>, Box<dyn Error>> {
+        Ok(Box::new(Length::gen(input)))
+    }
+
+    fn failing_factory(_: ArcStr) -> Result<Box<dyn Runner>, Box<dyn Error>> {
+        Err("bad input".into())
+    }
+
+    #[test]
+    fn test_solution_name() {
+        let solution = Solution::new("day12_part2", factory);
+        assert_eq!((solution.day, solution.part), (12, 2));
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid solution name: part1")]
+    fn test_invalid_name() {
+        Solution::new("part1", factory);
+    }
+
+    #[test]
+    fn test_run() {
+        let solution = Solution::new("day1_part1", factory);
+        assert_eq!(
+            solution.run("abcd\n").map(|(answer, _, _)| answer),
+            Ok("4".to_owned())
+        );
+
+        let solution = Solution::new("day1_part1", failing_factory);
+        assert_eq!(
+            solution.run("abcd\n").map(|(answer, _, _)| answer),
+            Err("FAILED while generating: \"bad input\"".to_owned())
+        );
+    }
+}
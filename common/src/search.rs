@@ -0,0 +1,558 @@
+use num::Zero;
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
+use std::hash::Hash;
+
+// The outcome of a search. Every node which was reached has its distance and the node it was reached from, so paths
+// can be reconstructed to any of them and not just to the goal.
+#[derive(Clone, Debug)]
+pub struct Search<N, C> {
+    pub goal: Option<N>, // The first node found which satisfied the goal, if any
+    dist: HashMap<N, C>,
+    prev: HashMap<N, N>,
+}
+
+impl<N: Clone + Eq + Hash, C: Copy> Search<N, C> {
+    fn new() -> Self {
+        Self {
+            goal: None,
+            dist: HashMap::new(),
+            prev: HashMap::new(),
+        }
+    }
+
+    pub fn distance(&self, node: &N) -> Option<C> {
+        self.dist.get(node).copied()
+    }
+
+    pub fn goal_distance(&self) -> Option<C> {
+        self.distance(self.goal.as_ref()?)
+    }
+
+    pub fn distances(&self) -> &HashMap<N, C> {
+        &self.dist
+    }
+
+    pub fn predecessors(&self) -> &HashMap<N, N> {
+        &self.prev
+    }
+
+    // From a start node to the given node inclusive
+    pub fn path(&self, node: &N) -> Option<Vec<N>> {
+        if !self.dist.contains_key(node) {
+            return None;
+        }
+        let mut path = vec![node.clone()];
+        while let Some(prev) = self.prev.get(path.last().unwrap()) {
+            path.push(prev.clone());
+        }
+        path.reverse();
+        Some(path)
+    }
+
+    pub fn goal_path(&self) -> Option<Vec<N>> {
+        self.path(self.goal.as_ref()?)
+    }
+}
+
+// Breadth-first search where every step costs 1. Stops at the first node which satisfies the goal, use |_| false to
+// explore everything reachable.
+pub fn bfs<N, S, I>(
+    starts: S,
+    mut neighbors: impl FnMut(&N) -> I,
+    mut goal: impl FnMut(&N) -> bool,
+) -> Search<N, usize>
+where
+    N: Clone + Eq + Hash,
+    S: IntoIterator<Item = N>,
+    I: IntoIterator<Item = N>,
+{
+    let mut search = Search::new();
+    let mut frontier = VecDeque::new();
+    for start in starts {
+        if search.dist.insert(start.clone(), 0).is_none() {
+            frontier.push_back(start);
+        }
+    }
+
+    while let Some(curr) = frontier.pop_front() {
+        if goal(&curr) {
+            search.goal = Some(curr);
+            break;
+        }
+        let next_dist = search.dist[&curr] + 1;
+        for next in neighbors(&curr) {
+            if !search.dist.contains_key(&next) {
+                search.dist.insert(next.clone(), next_dist);
+                search.prev.insert(next.clone(), curr.clone());
+                frontier.push_back(next);
+            }
+        }
+    }
+    search
+}
+
+// Shortest paths where each step has a non-negative cost
+pub fn dijkstra<N, C, S, I>(
+    starts: S,
+    neighbors: impl FnMut(&N) -> I,
+    goal: impl FnMut(&N) -> bool,
+) -> Search<N, C>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Zero,
+    S: IntoIterator<Item = N>,
+    I: IntoIterator<Item = (N, C)>,
+{
+    astar(starts, neighbors, |_| C::zero(), goal)
+}
+
+// Dijkstra guided by a heuristic, which must never overestimate the remaining cost to the goal for the result to be a
+// shortest path
+pub fn astar<N, C, S, I>(
+    starts: S,
+    mut neighbors: impl FnMut(&N) -> I,
+    mut heuristic: impl FnMut(&N) -> C,
+    mut goal: impl FnMut(&N) -> bool,
+) -> Search<N, C>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Zero,
+    S: IntoIterator<Item = N>,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let mut search = Search::new();
+    let mut frontier = BinaryHeap::new();
+    for start in starts {
+        search.dist.insert(start.clone(), C::zero());
+        frontier.push(Entry {
+            priority: heuristic(&start),
+            cost: C::zero(),
+            node: start,
+        });
+    }
+
+    while let Some(Entry { cost, node, .. }) = frontier.pop() {
+        if cost > search.dist[&node] {
+            continue; // Already reached more cheaply since this was queued
+        }
+        if goal(&node) {
+            search.goal = Some(node);
+            break;
+        }
+        for (next, step) in neighbors(&node) {
+            let next_cost = cost + step;
+            if search.dist.get(&next).is_none_or(|&d| next_cost < d) {
+                search.dist.insert(next.clone(), next_cost);
+                search.prev.insert(next.clone(), node.clone());
+                frontier.push(Entry {
+                    priority: next_cost + heuristic(&next),
+                    cost: next_cost,
+                    node: next,
+                });
+            }
+        }
+    }
+    search
+}
+
+// Every shortest path at once, as a DAG where each node links back to all the nodes it can be reached from in the
+// fewest steps or lowest cost. All step costs must be positive or the DAG could end up with cycles.
+#[derive(Clone, Debug)]
+pub struct ShortestPaths<N, C> {
+    goals: Vec<N>, // Every node which satisfied the goal at the lowest distance any did
+    dist: HashMap<N, C>,
+    preds: HashMap<N, Vec<N>>,
+}
+
+impl<N: Clone + Eq + Hash, C: Copy + Ord> ShortestPaths<N, C> {
+    fn new() -> Self {
+        Self {
+            goals: Vec::new(),
+            dist: HashMap::new(),
+            preds: HashMap::new(),
+        }
+    }
+
+    pub fn goals(&self) -> &[N] {
+        &self.goals
+    }
+
+    pub fn distance(&self, node: &N) -> Option<C> {
+        self.dist.get(node).copied()
+    }
+
+    pub fn distances(&self) -> &HashMap<N, C> {
+        &self.dist
+    }
+
+    // Empty for the start nodes, as well as for nodes which weren't reached
+    pub fn predecessors(&self, node: &N) -> &[N] {
+        self.preds.get(node).map_or(&[], Vec::as_slice)
+    }
+
+    // The number of distinct shortest paths to every node reached. Counts can grow exponentially so they're kept
+    // modulo 2^64 rather than overflowing.
+    pub fn path_counts(&self) -> HashMap<N, u64> {
+        let mut nodes: Vec<(&N, &C)> = self.dist.iter().collect();
+        nodes.sort_by_key(|(_, dist)| **dist);
+
+        let mut counts: HashMap<N, u64> = HashMap::new();
+        for (node, _) in nodes {
+            let preds = self.predecessors(node);
+            let count = if preds.is_empty() {
+                1
+            } else {
+                preds
+                    .iter()
+                    .fold(0, |acc: u64, pred| acc.wrapping_add(counts[pred]))
+            };
+            counts.insert(node.clone(), count);
+        }
+        counts
+    }
+
+    pub fn count_paths(&self, to: &N) -> u64 {
+        self.path_counts().get(to).copied().unwrap_or(0)
+    }
+
+    // Lazily enumerates every shortest path from a start to the given node, in lexicographic order of the nodes along
+    // the path according to cmp. E.g. using Point2::cmp_yx, the first path is the one which takes the first step in
+    // reading order, breaking ties with the second step and so on.
+    pub fn paths_by(
+        &self,
+        to: &N,
+        mut cmp: impl FnMut(&N, &N) -> Ordering,
+    ) -> impl Iterator<Item = Vec<N>> {
+        // Turn the predecessors around, keeping only the part of the DAG which leads to the destination
+        let mut starts = Vec::new();
+        let mut succs: HashMap<N, Vec<N>> = HashMap::new();
+        if self.dist.contains_key(to) {
+            let mut seen = HashSet::from([to.clone()]);
+            let mut todo = vec![to.clone()];
+            while let Some(node) = todo.pop() {
+                let preds = self.predecessors(&node);
+                if preds.is_empty() {
+                    starts.push(node.clone());
+                }
+                for pred in preds {
+                    succs.entry(pred.clone()).or_default().push(node.clone());
+                    if seen.insert(pred.clone()) {
+                        todo.push(pred.clone());
+                    }
+                }
+            }
+        }
+
+        // Depth-first with the stack reversed so that the lowest choice at each step comes out first
+        let mut reversed = |nodes: &mut Vec<N>| {
+            nodes.sort_by(|a, b| cmp(b, a));
+        };
+        reversed(&mut starts);
+        succs.values_mut().for_each(reversed);
+
+        let to = to.clone();
+        let mut stack: Vec<(N, usize)> = starts.into_iter().map(|node| (node, 0)).collect();
+        let mut path = Vec::new();
+        std::iter::from_fn(move || {
+            while let Some((node, depth)) = stack.pop() {
+                path.truncate(depth);
+                path.push(node.clone());
+                if node == to {
+                    return Some(path.clone());
+                }
+                stack.extend(succs[&node].iter().map(|next| (next.clone(), depth + 1)));
+            }
+            None
+        })
+    }
+}
+
+// Like bfs, but finds every shortest path. The search finishes the whole step at which a goal is first found, so all
+// goals at that distance are reported.
+pub fn bfs_all<N, S, I>(
+    starts: S,
+    mut neighbors: impl FnMut(&N) -> I,
+    mut goal: impl FnMut(&N) -> bool,
+) -> ShortestPaths<N, usize>
+where
+    N: Clone + Eq + Hash,
+    S: IntoIterator<Item = N>,
+    I: IntoIterator<Item = N>,
+{
+    let mut paths = ShortestPaths::new();
+    let mut frontier = VecDeque::new();
+    for start in starts {
+        if paths.dist.insert(start.clone(), 0).is_none() {
+            frontier.push_back(start);
+        }
+    }
+
+    let mut goal_dist = None;
+    while let Some(curr) = frontier.pop_front() {
+        let curr_dist = paths.dist[&curr];
+        if goal_dist.is_some_and(|d| curr_dist > d) {
+            break;
+        }
+        if goal(&curr) {
+            paths.goals.push(curr.clone());
+            goal_dist = Some(curr_dist);
+        }
+        if goal_dist.is_some() {
+            continue; // Nothing further away is needed
+        }
+        for next in neighbors(&curr) {
+            match paths.dist.get(&next) {
+                None => {
+                    paths.dist.insert(next.clone(), curr_dist + 1);
+                    paths.preds.insert(next.clone(), vec![curr.clone()]);
+                    frontier.push_back(next);
+                }
+                Some(&d) if d == curr_dist + 1 => {
+                    paths.preds.get_mut(&next).unwrap().push(curr.clone());
+                }
+                Some(_) => (),
+            }
+        }
+    }
+    paths
+}
+
+// Like dijkstra, but finds every shortest path. Costs must be positive.
+pub fn dijkstra_all<N, C, S, I>(
+    starts: S,
+    mut neighbors: impl FnMut(&N) -> I,
+    mut goal: impl FnMut(&N) -> bool,
+) -> ShortestPaths<N, C>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Zero,
+    S: IntoIterator<Item = N>,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let mut paths = ShortestPaths::new();
+    let mut frontier = BinaryHeap::new();
+    for start in starts {
+        if paths.dist.insert(start.clone(), C::zero()).is_none() {
+            frontier.push(Entry {
+                priority: C::zero(),
+                cost: C::zero(),
+                node: start,
+            });
+        }
+    }
+
+    let mut goal_cost = None;
+    while let Some(Entry { cost, node, .. }) = frontier.pop() {
+        if cost > paths.dist[&node] {
+            continue; // Already reached more cheaply since this was queued
+        }
+        if goal_cost.is_some_and(|c| cost > c) {
+            break;
+        }
+        if goal(&node) {
+            paths.goals.push(node.clone());
+            goal_cost = Some(cost);
+        }
+        if goal_cost.is_some() {
+            continue;
+        }
+        for (next, step) in neighbors(&node) {
+            let next_cost = cost + step;
+            match paths.dist.get(&next) {
+                Some(&d) if next_cost > d => (),
+                Some(&d) if next_cost == d => {
+                    paths.preds.get_mut(&next).unwrap().push(node.clone());
+                }
+                _ => {
+                    paths.dist.insert(next.clone(), next_cost);
+                    paths.preds.insert(next.clone(), vec![node.clone()]);
+                    frontier.push(Entry {
+                        priority: next_cost,
+                        cost: next_cost,
+                        node: next,
+                    });
+                }
+            }
+        }
+    }
+    paths
+}
+
+// Orders the heap by lowest priority first, without needing the nodes themselves to be ordered
+struct Entry<N, C> {
+    priority: C,
+    cost: C,
+    node: N,
+}
+
+impl<N, C: Ord> Ord for Entry<N, C> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.priority.cmp(&self.priority)
+    }
+}
+
+impl<N, C: Ord> PartialOrd for Entry<N, C> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<N, C: Ord> PartialEq for Entry<N, C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.priority == other.priority
+    }
+}
+
+impl<N, C: Ord> Eq for Entry<N, C> {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Point2;
+
+    // Open squares of a small maze, with a wall down the middle that has a gap at the bottom
+    fn maze(p: &Point2) -> Vec<Point2> {
+        p.orthogonals()
+            .filter(|n| (0..5).contains(&n.x) && (0..5).contains(&n.y))
+            .filter(|n| n.x != 2 || n.y == 4)
+            .collect()
+    }
+
+    #[test]
+    fn test_bfs() {
+        let start = Point2 { x: 0, y: 0 };
+        let end = Point2 { x: 4, y: 0 };
+        let search = bfs([start], maze, |p| *p == end);
+        assert_eq!(search.goal, Some(end));
+        assert_eq!(search.goal_distance(), Some(12));
+        let path = search.goal_path().unwrap();
+        assert_eq!(path.len(), 13);
+        assert_eq!(path.first(), Some(&start));
+        assert_eq!(path.last(), Some(&end));
+        assert!(path.windows(2).all(|w| Point2::manhattan(w[0], w[1]) == 1));
+
+        let search = bfs([start], maze, |_| false);
+        assert_eq!(search.goal, None);
+        assert_eq!(search.distances().len(), 21);
+        assert_eq!(search.distance(&Point2 { x: 2, y: 0 }), None);
+        assert_eq!(search.path(&Point2 { x: 2, y: 0 }), None);
+        assert_eq!(search.path(&start), Some(vec![start]));
+    }
+
+    #[test]
+    fn test_dijkstra() {
+        // Two ways from 'a' to 'd', the one with more steps is cheaper
+        let graph = |n: &char| match n {
+            'a' => vec![('b', 1), ('d', 10)],
+            'b' => vec![('c', 2)],
+            'c' => vec![('d', 3)],
+            _ => vec![],
+        };
+        let search = dijkstra(['a'], graph, |n| *n == 'd');
+        assert_eq!(search.goal_distance(), Some(6));
+        assert_eq!(search.goal_path(), Some(vec!['a', 'b', 'c', 'd']));
+        assert_eq!(search.predecessors()[&'c'], 'b');
+
+        // Any start will do
+        let search = dijkstra(['a', 'c'], graph, |n| *n == 'd');
+        assert_eq!(search.goal_path(), Some(vec!['c', 'd']));
+    }
+
+    #[test]
+    fn test_astar() {
+        let start = Point2 { x: 0, y: 0 };
+        let end = Point2 { x: 4, y: 0 };
+        let neighbors = |p: &Point2| maze(p).into_iter().map(|n| (n, 1));
+        let search = astar(
+            [start],
+            neighbors,
+            |p| Point2::manhattan(*p, end),
+            |p| *p == end,
+        );
+        assert_eq!(search.goal_distance(), Some(12));
+        assert_eq!(search.goal_path().unwrap().len(), 13);
+
+        // The heuristic means less has to be explored than by Dijkstra
+        let plain = dijkstra([start], neighbors, |p| *p == end);
+        assert_eq!(plain.goal_distance(), Some(12));
+        assert!(search.distances().len() <= plain.distances().len());
+    }
+
+    #[test]
+    fn test_bfs_all() {
+        // Open 3x3 room, every monotonic path from corner to corner is shortest
+        let room = |p: &Point2| {
+            p.orthogonals()
+                .filter(|n| (0..3).contains(&n.x) && (0..3).contains(&n.y))
+                .collect::<Vec<_>>()
+        };
+        let start = Point2 { x: 0, y: 0 };
+        let end = Point2 { x: 2, y: 2 };
+        let paths = bfs_all([start], room, |p| *p == end);
+        assert_eq!(paths.goals(), [end]);
+        assert_eq!(paths.distance(&end), Some(4));
+        assert_eq!(paths.count_paths(&end), 6);
+        assert_eq!(paths.count_paths(&Point2 { x: 1, y: 1 }), 2);
+        assert_eq!(paths.predecessors(&start), []);
+
+        let all: Vec<Vec<Point2>> = paths.paths_by(&end, Point2::cmp_yx).collect();
+        assert_eq!(all.len(), 6);
+        assert!(all.windows(2).all(|w| w[0] < w[1]));
+        assert_eq!(
+            all[0],
+            [(0, 0), (1, 0), (2, 0), (2, 1), (2, 2)].map(Point2::from)
+        );
+        let all_xy: Vec<Vec<Point2>> = paths.paths_by(&end, Point2::cmp_xy).collect();
+        assert_eq!(
+            all_xy[0],
+            [(0, 0), (0, 1), (0, 2), (1, 2), (2, 2)].map(Point2::from)
+        );
+
+        // Every goal at the nearest distance is found
+        let paths = bfs_all([Point2 { x: 1, y: 1 }], room, |p| p.x != 1 && p.y != 1);
+        assert_eq!(paths.goals().len(), 4);
+        let paths = bfs_all([start], room, |p| p.x + p.y == 2);
+        assert_eq!(paths.goals().len(), 3);
+        assert_eq!(
+            paths
+                .paths_by(&Point2 { x: 2, y: 2 }, Point2::cmp_yx)
+                .count(),
+            0
+        );
+    }
+
+    #[test]
+    fn test_dijkstra_all() {
+        // Two routes of equal cost from 'a' to 'd' and a cheaper looking one which isn't
+        let graph = |n: &char| match n {
+            'a' => vec![('b', 1), ('c', 2), ('d', 10)],
+            'b' => vec![('d', 3)],
+            'c' => vec![('d', 2)],
+            _ => vec![],
+        };
+        let paths = dijkstra_all(['a'], graph, |n| *n == 'd');
+        assert_eq!(paths.distance(&'d'), Some(4));
+        assert_eq!(paths.count_paths(&'d'), 2);
+        let all: Vec<Vec<char>> = paths.paths_by(&'d', char::cmp).collect();
+        assert_eq!(all, [vec!['a', 'b', 'd'], vec!['a', 'c', 'd']]);
+        let all: Vec<Vec<char>> = paths.paths_by(&'d', |a, b| b.cmp(a)).collect();
+        assert_eq!(all, [vec!['a', 'c', 'd'], vec!['a', 'b', 'd']]);
+        assert_eq!(paths.paths_by(&'e', char::cmp).count(), 0);
+    }
+
+    #[test]
+    fn test_path_count_overflow() {
+        // A chain of diamonds doubles the number of paths each time
+        let diamonds = |&(i, side): &(u32, u32)| match (i, side) {
+            (200, _) => vec![],
+            (_, 0) => vec![(i + 1, 1), (i + 1, 2)],
+            _ => vec![(i + 1, 0)],
+        };
+        let paths = bfs_all([(0, 0)], diamonds, |_| false);
+        assert_eq!(paths.count_paths(&(2, 0)), 2);
+        assert_eq!(paths.count_paths(&(64, 0)), 1 << 32);
+        assert_eq!(paths.count_paths(&(126, 0)), 1 << 63);
+        assert_eq!(paths.count_paths(&(128, 0)), 0);
+    }
+}
@@ -0,0 +1,222 @@
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::hash::Hash;
+
+// A point in a search for the best outcome, e.g. a snapshot of a game partway through
+pub trait State: Clone + Eq + Hash {
+    type Score: Copy + Ord;
+
+    fn successors(&self) -> Vec<Self>;
+
+    // Whether this is as far as the search can go. Terminal states are scored but never expanded.
+    fn is_terminal(&self) -> bool;
+
+    // The outcome if the search were to stop here. This only needs to be accurate for terminal states but if it's a
+    // guaranteed minimum for the rest then good solutions get found sooner, which means more pruning.
+    fn score(&self) -> Self::Score;
+
+    // Nothing reachable from this state may score higher, or the best solution may be pruned away. The closer this is
+    // to the real best the more can be pruned.
+    fn upper_bound(&self) -> Self::Score;
+}
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct Stats {
+    pub visited: usize,
+    pub terminal: usize,
+    pub pruned: usize,
+    pub duplicates: usize,
+}
+
+impl fmt::Display for Stats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} visited, {} terminal, {} pruned, {} duplicates",
+            self.visited, self.terminal, self.pruned, self.duplicates
+        )
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Best<S: State> {
+    pub score: S::Score,
+    pub state: S,
+    pub stats: Stats,
+}
+
+// Depth-first branch and bound. A state is only expanded if its upper bound beats the best score found so far.
+#[derive(Clone, Debug, Default)]
+pub struct BranchAndBound {
+    dedup: bool,
+    report: bool,
+}
+
+impl BranchAndBound {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Skip states which have been seen before. Only worth it when there are many ways of reaching the same state, as
+    // every state is remembered.
+    pub fn with_dedup(self) -> Self {
+        Self {
+            dedup: true,
+            ..self
+        }
+    }
+
+    // Print the statistics once the search is done
+    pub fn with_report(self) -> Self {
+        Self {
+            report: true,
+            ..self
+        }
+    }
+
+    pub fn maximize<S: State>(&self, start: S) -> Best<S> {
+        let mut best = Best {
+            score: start.score(),
+            state: start.clone(),
+            stats: Stats::default(),
+        };
+        let mut visited = HashSet::new();
+        let mut stack = vec![start];
+        while let Some(state) = stack.pop() {
+            if self.dedup && !visited.insert(state.clone()) {
+                best.stats.duplicates += 1;
+                continue;
+            }
+            best.stats.visited += 1;
+
+            let score = state.score();
+            if score > best.score {
+                best.score = score;
+                best.state = state.clone();
+            }
+
+            if state.is_terminal() {
+                best.stats.terminal += 1;
+            } else if state.upper_bound() <= best.score {
+                best.stats.pruned += 1;
+            } else {
+                // Reversed so that the first successor is the first explored
+                stack.extend(state.successors().into_iter().rev());
+            }
+        }
+
+        if self.report {
+            println!("Branch and bound: {}", best.stats);
+        }
+        best
+    }
+}
+
+// Evaluates a recurrence over states, where f works out the value of a state from the values of the states it leads
+// to by calling back into the search. Each state is only evaluated once however many ways it's reached, which is what
+// makes counting problems tractable. The states mustn't form a cycle.
+pub fn memoize<S, V, F>(start: &S, f: F) -> V
+where
+    S: Clone + Eq + Hash,
+    V: Clone,
+    F: Fn(&S, &mut dyn FnMut(&S) -> V) -> V,
+{
+    fn evaluate<S, V, F>(state: &S, f: &F, cache: &mut HashMap<S, V>) -> V
+    where
+        S: Clone + Eq + Hash,
+        V: Clone,
+        F: Fn(&S, &mut dyn FnMut(&S) -> V) -> V,
+    {
+        if let Some(value) = cache.get(state) {
+            return value.clone();
+        }
+        let value = f(state, &mut |next| evaluate(next, f, cache));
+        cache.insert(state.clone(), value.clone());
+        value
+    }
+
+    evaluate(start, &f, &mut HashMap::new())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    // 0/1 knapsack, deciding on one item at a time
+    #[derive(Clone, Debug, Eq, Hash, PartialEq)]
+    struct Knapsack {
+        next_item: usize,
+        capacity: u32,
+        value: u32,
+    }
+
+    const ITEMS: [(u32, u32); 5] = [(12, 4), (2, 2), (1, 1), (1, 1), (4, 10)]; // Weight, value
+
+    impl State for Knapsack {
+        type Score = u32;
+
+        fn successors(&self) -> Vec<Self> {
+            let (weight, value) = ITEMS[self.next_item];
+            let skip = Self {
+                next_item: self.next_item + 1,
+                ..self.clone()
+            };
+            if weight <= self.capacity {
+                let take = Self {
+                    next_item: self.next_item + 1,
+                    capacity: self.capacity - weight,
+                    value: self.value + value,
+                };
+                vec![take, skip]
+            } else {
+                vec![skip]
+            }
+        }
+
+        fn is_terminal(&self) -> bool {
+            self.next_item == ITEMS.len()
+        }
+
+        fn score(&self) -> u32 {
+            self.value
+        }
+
+        fn upper_bound(&self) -> u32 {
+            self.value + ITEMS[self.next_item..].iter().map(|i| i.1).sum::<u32>()
+        }
+    }
+
+    #[test]
+    fn test_maximize() {
+        let start = Knapsack {
+            next_item: 0,
+            capacity: 15,
+            value: 0,
+        };
+        let best = BranchAndBound::new().maximize(start.clone());
+        assert_eq!(best.score, 14);
+        assert_eq!(best.state.capacity, 7);
+        assert!(best.stats.pruned > 0);
+        assert_eq!(best.stats.duplicates, 0);
+
+        // Taking only one of the two items that weigh 1 ends up in the same place either way
+        let dedup = BranchAndBound::new().with_dedup().maximize(start);
+        assert_eq!(dedup.score, 14);
+        assert!(dedup.stats.duplicates > 0);
+        assert!(dedup.stats.visited < best.stats.visited);
+    }
+
+    #[test]
+    fn test_memoize() {
+        // Paths through a grid only moving right or down
+        let paths = |&(x, y): &(u64, u64), recurse: &mut dyn FnMut(&(u64, u64)) -> u64| {
+            if x == 0 || y == 0 {
+                1
+            } else {
+                recurse(&(x - 1, y)) + recurse(&(x, y - 1))
+            }
+        };
+        assert_eq!(memoize(&(2, 2), paths), 6);
+        assert_eq!(memoize(&(30, 30), paths), 118264581564861424);
+    }
+}
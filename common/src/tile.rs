@@ -1,5 +1,8 @@
 use crate::{one_line, Point2, Range2};
-use nom::IResult;
+use nom::{
+    error::{Error, ErrorKind},
+    IResult,
+};
 use std::collections::{HashMap, HashSet};
 
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -95,6 +98,16 @@ impl std::fmt::Display for TileSet {
     }
 }
 
+// Hashed in reading order so that it doesn't depend on the order the tiles were added in
+impl std::hash::Hash for TileSet {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        let mut tiles: Vec<&Point2> = self.tiles.iter().collect();
+        tiles.sort();
+        tiles.hash(state);
+        self.active_char.hash(state);
+    }
+}
+
 impl std::ops::Deref for TileSet {
     type Target = HashSet<Point2>;
     fn deref(&self) -> &Self::Target {
@@ -119,15 +132,17 @@ where
 
 const DEFAULT_BACKGROUND_CHAR: char = '.';
 
-#[derive(Clone, Debug, Eq, PartialEq)]
+#[derive(Clone, Debug)]
 pub struct TileMap<T, const B: char = DEFAULT_BACKGROUND_CHAR> {
     tiles: HashMap<Point2, T>,
+    extent: Option<Range2>, // The rectangle it was parsed from, including any background around the tiles
 }
 
 impl<T: TileChar, const B: char> TileMap<T, B> {
     pub fn new() -> Self {
         Self {
             tiles: HashMap::new(),
+            extent: None,
         }
     }
 
@@ -137,6 +152,7 @@ impl<T: TileChar, const B: char> TileMap<T, B> {
     {
         Self {
             tiles: tiles.into_iter().collect(),
+            ..self
         }
     }
 
@@ -152,20 +168,41 @@ impl<T: TileChar, const B: char> TileMap<T, B> {
         };
 
         let mut tiles = HashMap::new();
+        let mut width = 0;
+        let mut height = 0;
         for (y, line) in pixels.lines().enumerate() {
             for (x, c) in line.chars().enumerate() {
                 if let Some(t) = T::from_char(c) {
                     let p = (x as i32, y as i32).into();
                     tiles.insert(p, t);
                 }
+                width = width.max(x as i32 + 1);
             }
+            height = y as i32 + 1;
         }
-        Ok((input, Self { tiles }))
+        let extent = (width > 0 && height > 0).then_some(Range2 {
+            x: (0, width - 1),
+            y: (0, height - 1),
+        });
+        Ok((input, Self { tiles, extent }))
     }
 
+    // The range covered by the tiles themselves, leaving out any background around them
     pub fn get_range(&self) -> Option<Range2> {
         Point2::get_range(self.tiles.keys())
     }
+
+    // The range it was parsed from, grown to cover any tiles added since. The same as get_range for a TileMap that
+    // wasn't parsed.
+    pub fn extent(&self) -> Option<Range2> {
+        let Some(mut extent) = self.extent else {
+            return self.get_range();
+        };
+        for p in self.tiles.keys() {
+            extent.expand(p);
+        }
+        Some(extent)
+    }
 }
 
 impl<T: TileChar, const B: char> Default for TileMap<T, B> {
@@ -191,6 +228,23 @@ impl<T: TileChar, const B: char> std::fmt::Display for TileMap<T, B> {
     }
 }
 
+// Equal when the tiles are, whatever size of input they were parsed from
+impl<T: PartialEq, const B: char> PartialEq for TileMap<T, B> {
+    fn eq(&self, other: &Self) -> bool {
+        self.tiles == other.tiles
+    }
+}
+
+impl<T: Eq, const B: char> Eq for TileMap<T, B> {}
+
+impl<T: std::hash::Hash, const B: char> std::hash::Hash for TileMap<T, B> {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        let mut tiles: Vec<(&Point2, &T)> = self.tiles.iter().collect();
+        tiles.sort_by_key(|(p, _)| *p);
+        tiles.hash(state);
+    }
+}
+
 impl<T: TileChar, const B: char> std::ops::Deref for TileMap<T, B> {
     type Target = HashMap<Point2, T>;
     fn deref(&self) -> &Self::Target {
@@ -204,6 +258,250 @@ impl<T: TileChar, const B: char> std::ops::DerefMut for TileMap<T, B> {
     }
 }
 
+// A rectangle of tiles with the top left at the origin, stored densely in reading order. Quicker than a TileMap when
+// every point has a tile, but there's no background so every character has to map to something.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Grid<T> {
+    tiles: Vec<T>,
+    width: usize,
+    height: usize,
+}
+
+impl<T> Grid<T> {
+    pub fn new(width: usize, height: usize, fill: T) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            tiles: vec![fill; width * height],
+            width,
+            height,
+        }
+    }
+
+    pub fn from_fn(width: usize, height: usize, mut f: impl FnMut(Point2) -> T) -> Self {
+        let tiles = (0..width * height)
+            .map(|idx| f(Self::point_of(idx, width)))
+            .collect();
+        Self {
+            tiles,
+            width,
+            height,
+        }
+    }
+
+    pub fn from_string_with(input: &str, f: impl FnMut(char) -> Option<T>) -> Self {
+        Self::parser_with(input, f).unwrap().1
+    }
+
+    // Parses up to the first blank line, which is where a TileMap would stop too. Fails on a character that f doesn't
+    // recognise or on rows of different lengths.
+    pub fn parser_with(input: &str, mut f: impl FnMut(char) -> Option<T>) -> IResult<&str, Self> {
+        let (pixels, rest) = if let Some(idx) = input.find("\n\n") {
+            input.split_at(idx)
+        } else {
+            (input, "")
+        };
+
+        let mut tiles = Vec::new();
+        let mut width = None;
+        let mut height = 0;
+        for line in pixels.lines() {
+            let row_start = tiles.len();
+            for c in line.chars() {
+                let Some(t) = f(c) else {
+                    return Err(nom::Err::Error(Error::new(line, ErrorKind::Char)));
+                };
+                tiles.push(t);
+            }
+            let row_width = tiles.len() - row_start;
+            if *width.get_or_insert(row_width) != row_width {
+                return Err(nom::Err::Error(Error::new(line, ErrorKind::Verify)));
+            }
+            height += 1;
+        }
+        let width = width.unwrap_or(0);
+        Ok((
+            rest,
+            Self {
+                tiles,
+                width,
+                height,
+            },
+        ))
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn get_range(&self) -> Range2 {
+        Range2 {
+            x: (0, self.width as i32 - 1),
+            y: (0, self.height as i32 - 1),
+        }
+    }
+
+    pub fn contains(&self, p: Point2) -> bool {
+        p.x >= 0 && p.y >= 0 && (p.x as usize) < self.width && (p.y as usize) < self.height
+    }
+
+    fn idx_of(&self, p: Point2) -> Option<usize> {
+        self.contains(p)
+            .then(|| p.y as usize * self.width + p.x as usize)
+    }
+
+    fn point_of(idx: usize, width: usize) -> Point2 {
+        Point2 {
+            x: (idx % width) as i32,
+            y: (idx / width) as i32,
+        }
+    }
+
+    pub fn get(&self, p: Point2) -> Option<&T> {
+        self.idx_of(p).map(|idx| &self.tiles[idx])
+    }
+
+    pub fn get_mut(&mut self, p: Point2) -> Option<&mut T> {
+        self.idx_of(p).map(|idx| &mut self.tiles[idx])
+    }
+
+    // Every point in reading order
+    pub fn points(&self) -> impl Iterator<Item = Point2> {
+        let width = self.width;
+        (0..self.tiles.len()).map(move |idx| Self::point_of(idx, width))
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = &T> {
+        self.tiles.iter()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Point2, &T)> {
+        self.points().zip(self.tiles.iter())
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (Point2, &mut T)> {
+        self.points().zip(self.tiles.iter_mut())
+    }
+
+    // The first point in reading order whose tile matches
+    pub fn position(&self, f: impl FnMut(&T) -> bool) -> Option<Point2> {
+        self.tiles
+            .iter()
+            .position(f)
+            .map(|idx| Self::point_of(idx, self.width))
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.tiles[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        (0..self.height).map(|y| self.row(y))
+    }
+
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        assert!(x < self.width);
+        self.tiles.iter().skip(x).step_by(self.width)
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(|x| self.column(x))
+    }
+
+    // Neighbors which are inside the grid
+    pub fn orthogonals(&self, p: Point2) -> impl Iterator<Item = Point2> + '_ {
+        p.orthogonals().filter(|n| self.contains(*n))
+    }
+
+    pub fn adjacents(&self, p: Point2) -> impl Iterator<Item = Point2> + '_ {
+        p.adjacents().filter(|n| self.contains(*n))
+    }
+
+    // Everything seen looking from start (not included) in steps of dir, until the edge of the grid
+    pub fn ray(&self, start: Point2, dir: Point2) -> impl Iterator<Item = (Point2, &T)> {
+        std::iter::successors(Some(start + dir), move |p| Some(*p + dir))
+            .map_while(|p| self.get(p).map(|t| (p, t)))
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            tiles: self.tiles.iter().map(f).collect(),
+            width: self.width,
+            height: self.height,
+        }
+    }
+}
+
+impl<T: TileChar> Grid<T> {
+    pub fn from_string(input: &str) -> Self {
+        Self::from_string_with(input, T::from_char)
+    }
+
+    pub fn parser(input: &str) -> IResult<&str, Self> {
+        Self::parser_with(input, T::from_char)
+    }
+
+    // Covers from the origin out to the furthest tile in the map, with the gaps filled in by background. The map
+    // mustn't have any tiles at negative coordinates.
+    pub fn from_tilemap<const B: char>(map: &TileMap<T, B>, background: T) -> Self
+    where
+        T: Clone,
+    {
+        let Some(range) = map.get_range() else {
+            return Self::new(0, 0, background);
+        };
+        assert!(range.x.0 >= 0 && range.y.0 >= 0);
+        Self::from_fn(range.x.1 as usize + 1, range.y.1 as usize + 1, |p| {
+            map.get(&p).unwrap_or(&background).clone()
+        })
+    }
+
+    // Every tile except the background ones
+    pub fn to_tilemap<const B: char>(&self, background: &T) -> TileMap<T, B>
+    where
+        T: Clone + PartialEq,
+    {
+        TileMap::new().with_tiles(
+            self.iter()
+                .filter(|(_, t)| *t != background)
+                .map(|(p, t)| (p, t.clone())),
+        )
+    }
+}
+
+impl<T: TileChar> std::fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for row in self.rows() {
+            for t in row {
+                write!(f, "{}", t.to_char())?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+impl<T> std::ops::Index<Point2> for Grid<T> {
+    type Output = T;
+    fn index(&self, p: Point2) -> &Self::Output {
+        self.get(p)
+            .unwrap_or_else(|| panic!("{} is outside the grid", p))
+    }
+}
+
+impl<T> std::ops::IndexMut<Point2> for Grid<T> {
+    fn index_mut(&mut self, p: Point2) -> &mut Self::Output {
+        let (width, height) = (self.width, self.height);
+        self.get_mut(p)
+            .unwrap_or_else(|| panic!("{} is outside the {}x{} grid", p, width, height))
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -339,7 +637,7 @@ X..
         );
     }
 
-    #[derive(Debug, PartialEq)]
+    #[derive(Clone, Debug, PartialEq)]
     enum MyTile {
         A,
         B,
@@ -373,12 +671,7 @@ X..
     fn test_tilemap_from_string() {
         let input = ".";
         let tilemap: TileMap<MyTile> = TileMap::from_string(input);
-        assert_eq!(
-            tilemap,
-            TileMap {
-                tiles: HashMap::new()
-            }
-        );
+        assert_eq!(tilemap, TileMap::new());
 
         let input = "\
 ABC
@@ -398,7 +691,7 @@ CBA";
         .into_iter()
         .map(|(p, t)| (p.into(), t))
         .collect();
-        assert_eq!(tilemap, TileMap { tiles: expected });
+        assert_eq!(tilemap, TileMap::new().with_tiles(expected));
 
         let input = "\
 AA
@@ -416,7 +709,7 @@ BB";
         .into_iter()
         .map(|(p, t)| (p.into(), t))
         .collect();
-        assert_eq!(tilemap, TileMap { tiles: expected });
+        assert_eq!(tilemap, TileMap::new().with_tiles(expected));
     }
 
     #[test]
@@ -479,4 +772,77 @@ CBA";
             })
         );
     }
+
+    #[test]
+    fn test_grid_from_string() {
+        let input = "\
+ABC
+CBA";
+        let grid: Grid<MyTile> = Grid::from_string(input);
+        assert_eq!(grid.width(), 3);
+        assert_eq!(grid.height(), 2);
+        assert_eq!(
+            grid.get_range(),
+            Range2 {
+                x: (0, 2),
+                y: (0, 1)
+            }
+        );
+        assert_eq!(grid[(1, 0).into()], MyTile::B);
+        assert_eq!(grid[(2, 1).into()], MyTile::A);
+        assert_eq!(grid.get((3, 0).into()), None);
+        assert_eq!(grid.get((0, -1).into()), None);
+        assert_eq!(grid.to_string().trim(), input);
+
+        // Stops at a blank line, and every character needs to be a tile
+        let (rest, grid) = Grid::<MyTile>::parser("AB\nBA\n\nrest").unwrap();
+        assert_eq!(grid.height(), 2);
+        assert_eq!(rest, "\n\nrest");
+        assert_eq!(Grid::<MyTile>::parser("AB\nB.").is_err(), true);
+        assert_eq!(Grid::<MyTile>::parser("AB\nB").is_err(), true);
+
+        let grid = Grid::from_string_with("12\n34", |c| c.to_digit(10));
+        assert_eq!(grid.values().sum::<u32>(), 10);
+    }
+
+    #[test]
+    fn test_grid_rows_columns() {
+        let grid = Grid::from_fn(3, 2, |p| p.x + 10 * p.y);
+        let rows: Vec<&[i32]> = grid.rows().collect();
+        assert_eq!(rows, [[0, 1, 2], [10, 11, 12]]);
+        let columns: Vec<Vec<i32>> = grid.columns().map(|c| c.copied().collect()).collect();
+        assert_eq!(columns, [[0, 10], [1, 11], [2, 12]]);
+        assert_eq!(grid.points().nth(4), Some((1, 1).into()));
+        assert_eq!(grid.position(|t| *t > 10), Some((1, 1).into()));
+    }
+
+    #[test]
+    fn test_grid_neighbors() {
+        let grid = Grid::new(3, 3, 0);
+        assert_eq!(grid.orthogonals((1, 1).into()).count(), 4);
+        assert_eq!(grid.adjacents((1, 1).into()).count(), 8);
+        assert_eq!(grid.orthogonals((0, 0).into()).count(), 2);
+        assert_eq!(grid.adjacents((0, 0).into()).count(), 3);
+        assert_eq!(grid.adjacents((2, 1).into()).count(), 5);
+
+        let ray: Vec<Point2> = grid
+            .ray((0, 0).into(), (1, 1).into())
+            .map(|(p, _)| p)
+            .collect();
+        assert_eq!(ray, [(1, 1).into(), (2, 2).into()]);
+        assert_eq!(grid.ray((0, 0).into(), (-1, 0).into()).count(), 0);
+    }
+
+    #[test]
+    fn test_grid_tilemap() {
+        let input = "\
+A..
+.B.
+..C";
+        let tilemap: TileMap<MyTile> = TileMap::from_string(input);
+        let grid = Grid::from_tilemap(&tilemap, MyTile::A);
+        assert_eq!(grid.to_string().trim(), "AAA\nABA\nAAC");
+        let tilemap: TileMap<MyTile> = grid.to_tilemap(&MyTile::A);
+        assert_eq!(tilemap.to_string().trim(), "B.\n.C");
+    }
 }
@@ -0,0 +1,238 @@
+use crate::{Grid, Point2, Range2, TileChar, TileMap, TileSet};
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+// One of the eight ways of turning a rectangle over and around: flipped left to right first if flipped is set, then
+// rotated clockwise by the given number of quarter turns.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Orientation {
+    pub rotation: u8,
+    pub flipped: bool,
+}
+
+impl Orientation {
+    pub const fn new(rotation: u8, flipped: bool) -> Self {
+        Self {
+            rotation: rotation % 4,
+            flipped,
+        }
+    }
+
+    // All eight, starting with leaving things as they are
+    pub fn all() -> impl Iterator<Item = Self> {
+        [false, true]
+            .into_iter()
+            .flat_map(|flipped| (0..4).map(move |rotation| Self { rotation, flipped }))
+    }
+
+    // The orientation which undoes this one
+    pub fn inverse(&self) -> Self {
+        if self.flipped {
+            // Flipping then rotating one way is the same as rotating the other way then flipping
+            *self
+        } else {
+            Self::new(4 - self.rotation, false)
+        }
+    }
+
+    // Where p ends up when the range it's in is turned. The top left corner of the range stays where it is, so the
+    // range itself only changes if it's rotated a quarter turn and isn't square.
+    pub fn apply(&self, p: Point2, range: Range2) -> Point2 {
+        let (mut width, mut height) = (range.width(), range.height());
+        let (mut x, mut y) = (p.x - range.x.0, p.y - range.y.0);
+        if self.flipped {
+            x = width - 1 - x;
+        }
+        for _ in 0..self.rotation {
+            (x, y) = (height - 1 - y, x);
+            (width, height) = (height, width);
+        }
+        Point2 {
+            x: range.x.0 + x,
+            y: range.y.0 + y,
+        }
+    }
+
+    pub fn apply_range(&self, range: Range2) -> Range2 {
+        if self.rotation.is_multiple_of(2) {
+            range
+        } else {
+            Range2 {
+                x: (range.x.0, range.x.0 + range.height() - 1),
+                y: (range.y.0, range.y.0 + range.width() - 1),
+            }
+        }
+    }
+}
+
+// Tiles that can be turned over and around, staying in place in the sense that the top left corner doesn't move
+pub trait Transform: Sized {
+    fn transform(&self, orientation: Orientation) -> Self;
+
+    fn rotate_cw(&self) -> Self {
+        self.transform(Orientation::new(1, false))
+    }
+
+    fn rotate_ccw(&self) -> Self {
+        self.transform(Orientation::new(3, false))
+    }
+
+    // Left to right
+    fn flip_horizontal(&self) -> Self {
+        self.transform(Orientation::new(0, true))
+    }
+
+    // Top to bottom
+    fn flip_vertical(&self) -> Self {
+        self.transform(Orientation::new(2, true))
+    }
+
+    // Swaps rows and columns
+    fn transpose(&self) -> Self {
+        self.transform(Orientation::new(3, true))
+    }
+
+    fn orientations(&self) -> impl Iterator<Item = (Orientation, Self)> {
+        Orientation::all().map(|o| (o, self.transform(o)))
+    }
+
+    // The same for every orientation of the same tiles, so that tiles can be compared or grouped however they're
+    // turned. Picked by hash rather than by order so only Hash is needed.
+    fn canonical(&self) -> (Orientation, Self)
+    where
+        Self: Hash,
+    {
+        self.orientations()
+            .min_by_key(|(o, t)| (hash_of(t), *o))
+            .unwrap()
+    }
+
+    fn canonical_hash(&self) -> u64
+    where
+        Self: Hash,
+    {
+        hash_of(&self.canonical().1)
+    }
+}
+
+fn hash_of<T: Hash>(t: &T) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    t.hash(&mut hasher);
+    hasher.finish()
+}
+
+impl Transform for TileSet {
+    // Turned within the range covered by its tiles
+    fn transform(&self, orientation: Orientation) -> Self {
+        let Some(range) = self.get_range() else {
+            return self.clone();
+        };
+        let mut output = self.clone();
+        output.clear();
+        output.extend(self.iter().map(|p| orientation.apply(*p, range)));
+        output
+    }
+}
+
+impl<T: TileChar + Clone, const B: char> Transform for TileMap<T, B> {
+    // Turned within the range covered by its tiles
+    fn transform(&self, orientation: Orientation) -> Self {
+        let Some(range) = self.get_range() else {
+            return self.clone();
+        };
+        TileMap::new().with_tiles(
+            self.iter()
+                .map(|(p, t)| (orientation.apply(*p, range), t.clone())),
+        )
+    }
+}
+
+impl<T: Clone> Transform for Grid<T> {
+    fn transform(&self, orientation: Orientation) -> Self {
+        let range = self.get_range();
+        let inverse = orientation.inverse();
+        let turned = orientation.apply_range(range);
+        Grid::from_fn(turned.width() as usize, turned.height() as usize, |p| {
+            self[inverse.apply(p, turned)].clone()
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn grid(input: &str) -> Grid<char> {
+        Grid::from_string_with(input, Some)
+    }
+
+    fn to_string(grid: &Grid<char>) -> String {
+        grid.rows()
+            .map(|row| row.iter().collect::<String>())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    #[test]
+    fn test_grid_transform() {
+        let g = grid("abc\ndef");
+        assert_eq!(to_string(&g.rotate_cw()), "da\neb\nfc");
+        assert_eq!(to_string(&g.rotate_ccw()), "cf\nbe\nad");
+        assert_eq!(to_string(&g.rotate_cw().rotate_cw()), "fed\ncba");
+        assert_eq!(to_string(&g.flip_horizontal()), "cba\nfed");
+        assert_eq!(to_string(&g.flip_vertical()), "def\nabc");
+        assert_eq!(to_string(&g.transpose()), "ad\nbe\ncf");
+
+        let all: Vec<Grid<char>> = g.orientations().map(|(_, t)| t).collect();
+        for (i, a) in all.iter().enumerate() {
+            for b in &all[i + 1..] {
+                assert_ne!(a, b);
+            }
+        }
+        for (o, t) in g.orientations() {
+            assert_eq!(t.transform(o.inverse()), g);
+        }
+    }
+
+    #[test]
+    fn test_orientation_apply() {
+        let range = Range2 {
+            x: (10, 12),
+            y: (20, 21),
+        };
+        for o in Orientation::all() {
+            let turned = o.apply_range(range);
+            assert_eq!((turned.x.0, turned.y.0), (10, 20));
+            for y in range.y.0..=range.y.1 {
+                for x in range.x.0..=range.x.1 {
+                    let p = o.apply(Point2 { x, y }, range);
+                    assert!(turned.contains(p));
+                    assert_eq!(o.inverse().apply(p, turned), Point2 { x, y });
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_tileset_transform() {
+        let tileset = TileSet::from_string::<'#'>("##.\n#..\n..#");
+        assert_eq!(tileset.rotate_cw().to_string().trim(), ".##\n..#\n#..");
+        assert_eq!(tileset.transpose(), tileset);
+        assert_eq!(
+            tileset.flip_horizontal().to_string().trim(),
+            ".##\n..#\n#.."
+        );
+    }
+
+    #[test]
+    fn test_canonical() {
+        let g = grid("ab.\n..c");
+        let canonical = g.canonical_hash();
+        for (o, t) in g.orientations() {
+            assert_eq!(t.canonical_hash(), canonical);
+            assert_eq!(t.canonical().1, g.canonical().1);
+            assert_eq!(t.transform(t.canonical().0), g.canonical().1, "{:?}", o);
+        }
+        assert_ne!(grid("ab.\n.c.").canonical_hash(), canonical);
+    }
+}
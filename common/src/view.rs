@@ -0,0 +1,256 @@
+use crate::{Grid, Point2, Range2, TileChar, TileMap};
+use std::collections::HashMap;
+
+// Anything that tiles can be looked up in, so that the views below work the same over a Grid, a TileMap or a plain map
+pub trait Tiles {
+    type Tile;
+
+    fn tile(&self, p: Point2) -> Option<&Self::Tile>;
+
+    // The smallest range covering every tile, or None if there aren't any
+    fn bounds(&self) -> Option<Range2>;
+
+    // The tiles repeated endlessly in every direction, so that walking off one edge comes back in on the other
+    fn wrapping(&self) -> Wrapping<'_, Self>
+    where
+        Self: Sized,
+    {
+        Wrapping {
+            tiles: self,
+            range: self.bounds().expect("Nothing to wrap"),
+        }
+    }
+
+    // A block of copies of the tiles, across by down, with the original at the top left
+    fn tiled(&self, across: usize, down: usize) -> Tiled<'_, Self>
+    where
+        Self: Sized,
+    {
+        let range = self.bounds().expect("Nothing to tile");
+        let outer = Range2 {
+            x: (range.x.0, range.x.0 + range.width() * across as i32 - 1),
+            y: (range.y.0, range.y.0 + range.height() * down as i32 - 1),
+        };
+        Tiled {
+            tiles: self,
+            range,
+            outer,
+        }
+    }
+
+    // The tiles surrounded by background forever. Gaps inside a TileMap are background too.
+    fn infinite(&self, background: Self::Tile) -> Infinite<'_, Self>
+    where
+        Self: Sized,
+    {
+        Infinite {
+            tiles: self,
+            background,
+        }
+    }
+}
+
+impl<T> Tiles for Grid<T> {
+    type Tile = T;
+
+    fn tile(&self, p: Point2) -> Option<&T> {
+        self.get(p)
+    }
+
+    fn bounds(&self) -> Option<Range2> {
+        (self.width() > 0 && self.height() > 0).then(|| self.get_range())
+    }
+}
+
+impl<T: TileChar, const B: char> Tiles for TileMap<T, B> {
+    type Tile = T;
+
+    fn tile(&self, p: Point2) -> Option<&T> {
+        self.get(&p)
+    }
+
+    // Includes any background around the tiles that the TileMap was parsed from, so that it wraps with the right period
+    fn bounds(&self) -> Option<Range2> {
+        self.extent()
+    }
+}
+
+impl<T> Tiles for HashMap<Point2, T> {
+    type Tile = T;
+
+    fn tile(&self, p: Point2) -> Option<&T> {
+        self.get(&p)
+    }
+
+    fn bounds(&self) -> Option<Range2> {
+        Point2::get_range(self.keys())
+    }
+}
+
+// The range is fixed when the view is made, so the tiles shouldn't grow while it's in use
+pub struct Wrapping<'a, S> {
+    tiles: &'a S,
+    range: Range2,
+}
+
+impl<S: Tiles> Wrapping<'_, S> {
+    // Only None for a TileMap with nothing at the wrapped point
+    pub fn get(&self, p: Point2) -> Option<&S::Tile> {
+        self.tiles.tile(self.wrap(p))
+    }
+
+    // The point in the original tiles that p is a copy of
+    pub fn wrap(&self, p: Point2) -> Point2 {
+        self.range.wrap(p)
+    }
+
+    pub fn copy_of(&self, p: Point2) -> Point2 {
+        self.range.copy_of(p)
+    }
+}
+
+pub struct Tiled<'a, S> {
+    tiles: &'a S,
+    range: Range2,
+    outer: Range2,
+}
+
+impl<S: Tiles> Tiled<'_, S> {
+    // None outside the block of copies
+    pub fn get(&self, p: Point2) -> Option<&S::Tile> {
+        if self.outer.contains(p) {
+            self.tiles.tile(self.range.wrap(p))
+        } else {
+            None
+        }
+    }
+
+    pub fn get_range(&self) -> Range2 {
+        self.outer
+    }
+
+    pub fn contains(&self, p: Point2) -> bool {
+        self.outer.contains(p)
+    }
+
+    pub fn wrap(&self, p: Point2) -> Point2 {
+        self.range.wrap(p)
+    }
+
+    // Which copy p falls in, e.g. for puzzles where each copy's tiles are changed a little
+    pub fn copy_of(&self, p: Point2) -> Point2 {
+        self.range.copy_of(p)
+    }
+}
+
+pub struct Infinite<'a, S: Tiles> {
+    tiles: &'a S,
+    background: S::Tile,
+}
+
+impl<S: Tiles> Infinite<'_, S> {
+    pub fn get(&self, p: Point2) -> &S::Tile {
+        self.tiles.tile(p).unwrap_or(&self.background)
+    }
+
+    pub fn background(&self) -> &S::Tile {
+        &self.background
+    }
+}
+
+impl<S: Tiles> std::ops::Index<Point2> for Infinite<'_, S> {
+    type Output = S::Tile;
+    fn index(&self, p: Point2) -> &Self::Output {
+        self.get(p)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[derive(Clone, Debug, PartialEq)]
+    struct Rock;
+
+    impl TileChar for Rock {
+        fn to_char(&self) -> char {
+            '#'
+        }
+
+        fn from_char(c: char) -> Option<Self> {
+            (c == '#').then_some(Rock)
+        }
+
+        fn all_chars() -> Vec<char> {
+            vec!['#']
+        }
+    }
+
+    #[test]
+    fn test_wrapping() {
+        let grid = Grid::from_fn(3, 2, |p| p.x + 10 * p.y);
+        let wrapping = grid.wrapping();
+        assert_eq!(wrapping.get((1, 1).into()), Some(&11));
+        assert_eq!(wrapping.get((4, 1).into()), Some(&11));
+        assert_eq!(wrapping.get((-1, -1).into()), Some(&12));
+        assert_eq!(wrapping.get((300, 201).into()), Some(&10));
+        assert_eq!(wrapping.copy_of((-1, -1).into()), (-1, -1).into());
+
+        // A TileMap wraps around the whole input it was parsed from, background included
+        let tilemap: TileMap<Rock> = TileMap::from_string("...\n.#.\n..#");
+        let wrapping = tilemap.wrapping();
+        assert_eq!(wrapping.get((4, 4).into()), Some(&Rock));
+        assert_eq!(wrapping.get((5, 5).into()), Some(&Rock));
+        assert_eq!(wrapping.get((3, 3).into()), None);
+        assert_eq!(wrapping.wrap((4, 3).into()), (1, 0).into());
+        assert_eq!(wrapping.get((4, 3).into()), None);
+
+        // One built from tiles only knows the range they cover
+        let tilemap: TileMap<Rock> =
+            TileMap::new().with_tiles([((1, 1).into(), Rock), ((2, 2).into(), Rock)]);
+        assert_eq!(tilemap.wrapping().wrap((4, 3).into()), (2, 1).into());
+    }
+
+    #[test]
+    fn test_tiled() {
+        let grid = Grid::from_fn(3, 2, |p| p.x + 10 * p.y);
+        let tiled = grid.tiled(2, 3);
+        assert_eq!(
+            tiled.get_range(),
+            Range2 {
+                x: (0, 5),
+                y: (0, 5)
+            }
+        );
+        assert_eq!(tiled.get((4, 5).into()), Some(&11));
+        assert_eq!(tiled.copy_of((4, 5).into()), (1, 2).into());
+        assert_eq!(tiled.get((6, 5).into()), None);
+        assert_eq!(tiled.get((4, -1).into()), None);
+
+        let tilemap: TileMap<Rock> = TileMap::from_string("#..\n...");
+        let tiled = tilemap.tiled(2, 2);
+        assert_eq!(
+            tiled.get_range(),
+            Range2 {
+                x: (0, 5),
+                y: (0, 3)
+            }
+        );
+        assert_eq!(tiled.get((3, 2).into()), Some(&Rock));
+        assert_eq!(tiled.get((1, 2).into()), None);
+    }
+
+    #[test]
+    fn test_infinite() {
+        let grid = Grid::from_fn(2, 2, |p| p.x + p.y);
+        let infinite = grid.infinite(-1);
+        assert_eq!(infinite[(1, 1).into()], 2);
+        assert_eq!(infinite[(2, 1).into()], -1);
+        assert_eq!(infinite[(-100, 100).into()], -1);
+
+        let tilemap: TileMap<Rock> = TileMap::from_string("#.\n.#");
+        let infinite = tilemap.infinite(Rock);
+        assert_eq!(infinite.get((1, 0).into()), &Rock);
+        assert_eq!(infinite.get((5, 5).into()), &Rock);
+    }
+}